@@ -21,7 +21,7 @@ use datafusion::error::DataFusionError;
 use datatypes::prelude::ConcreteDataType;
 use snafu::{Backtrace, ErrorCompat};
 
-use crate::DeregisterTableRequest;
+use crate::{DeregisterSchemaRequest, DeregisterTableRequest};
 
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub))]
@@ -152,6 +152,17 @@ pub enum Error {
         source: table::error::Error,
     },
 
+    #[snafu(display(
+        "Failed to deregister schema, request: {:?}, source: {}",
+        request,
+        source
+    ))]
+    DeregisterSchema {
+        request: DeregisterSchemaRequest,
+        #[snafu(backtrace)]
+        source: table::error::Error,
+    },
+
     #[snafu(display("Illegal catalog manager state: {}", msg))]
     IllegalManagerState { backtrace: Backtrace, msg: String },
 
@@ -204,6 +215,13 @@ pub enum Error {
 
     #[snafu(display("Illegal access to catalog: {} and schema: {}", catalog, schema))]
     QueryAccessDenied { catalog: String, schema: String },
+
+    #[snafu(display("Invalid options of schema {}, source: {}", schema, source))]
+    InvalidSchemaOptions {
+        schema: String,
+        #[snafu(backtrace)]
+        source: table::error::Error,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -238,12 +256,14 @@ impl ErrorExt for Error {
             | Error::InsertCatalogRecord { source, .. }
             | Error::OpenTable { source, .. }
             | Error::CreateTable { source, .. }
-            | Error::DeregisterTable { source, .. } => source.status_code(),
+            | Error::DeregisterTable { source, .. }
+            | Error::DeregisterSchema { source, .. } => source.status_code(),
 
             Error::MetaSrv { source, .. } => source.status_code(),
             Error::SystemCatalogTableScan { source } => source.status_code(),
             Error::SystemCatalogTableScanExec { source } => source.status_code(),
             Error::InvalidTableInfoInCatalog { source } => source.status_code(),
+            Error::InvalidSchemaOptions { source, .. } => source.status_code(),
             Error::SchemaProviderOperation { source } | Error::Internal { source } => {
                 source.status_code()
             }
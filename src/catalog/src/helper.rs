@@ -23,6 +23,7 @@ use regex::Regex;
 use serde::{Deserialize, Serialize, Serializer};
 use snafu::{ensure, OptionExt, ResultExt};
 use table::metadata::{RawTableInfo, TableId, TableVersion};
+use table::requests::TableOptions;
 
 pub const CATALOG_KEY_PREFIX: &str = "__c";
 pub const SCHEMA_KEY_PREFIX: &str = "__s";
@@ -250,8 +251,31 @@ impl SchemaKey {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct SchemaValue;
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SchemaValue {
+    /// Default options of tables created in the schema.
+    #[serde(default)]
+    pub options: TableOptions,
+}
+
+impl SchemaValue {
+    pub fn parse(s: impl AsRef<str>) -> Result<Self, Error> {
+        // Schema values written by older versions are unit structs, i.e. `null`.
+        let value: Option<Self> = serde_json::from_str(s.as_ref())
+            .context(DeserializeCatalogEntryValueSnafu { raw: s.as_ref() })?;
+        Ok(value.unwrap_or_default())
+    }
+
+    pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, Error> {
+        Self::parse(&String::from_utf8_lossy(bytes.as_ref()))
+    }
+
+    pub fn as_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(serde_json::to_string(self)
+            .context(SerializeCatalogEntryValueSnafu)?
+            .into_bytes())
+    }
+}
 
 macro_rules! define_catalog_value {
     ( $($val_ty: ty), *) => {
@@ -276,12 +300,7 @@ macro_rules! define_catalog_value {
         }
 }
 
-define_catalog_value!(
-    TableRegionalValue,
-    TableGlobalValue,
-    CatalogValue,
-    SchemaValue
-);
+define_catalog_value!(TableRegionalValue, TableGlobalValue, CatalogValue);
 
 #[cfg(test)]
 mod tests {
@@ -376,4 +395,20 @@ mod tests {
         let s = r#"{"node_id":1,"regions_id_map":{"1":[0]},"table_info":{"ident":{"table_id":1098,"version":1},"name":"container_cpu_limit","desc":"Created on insertion","catalog_name":"greptime","schema_name":"dd","meta":{"schema":{"column_schemas":[{"name":"container_id","data_type":{"String":null},"is_nullable":true,"is_time_index":false,"default_constraint":null,"metadata":{}},{"name":"container_name","data_type":{"String":null},"is_nullable":true,"is_time_index":false,"default_constraint":null,"metadata":{}},{"name":"docker_image","data_type":{"String":null},"is_nullable":true,"is_time_index":false,"default_constraint":null,"metadata":{}},{"name":"host","data_type":{"String":null},"is_nullable":true,"is_time_index":false,"default_constraint":null,"metadata":{}},{"name":"image_name","data_type":{"String":null},"is_nullable":true,"is_time_index":false,"default_constraint":null,"metadata":{}},{"name":"image_tag","data_type":{"String":null},"is_nullable":true,"is_time_index":false,"default_constraint":null,"metadata":{}},{"name":"interval","data_type":{"String":null},"is_nullable":true,"is_time_index":false,"default_constraint":null,"metadata":{}},{"name":"runtime","data_type":{"String":null},"is_nullable":true,"is_time_index":false,"default_constraint":null,"metadata":{}},{"name":"short_image","data_type":{"String":null},"is_nullable":true,"is_time_index":false,"default_constraint":null,"metadata":{}},{"name":"type","data_type":{"String":null},"is_nullable":true,"is_time_index":false,"default_constraint":null,"metadata":{}},{"name":"dd_value","data_type":{"Float64":{}},"is_nullable":true,"is_time_index":false,"default_constraint":null,"metadata":{}},{"name":"ts","data_type":{"Timestamp":{"Millisecond":null}},"is_nullable":false,"is_time_index":true,"default_constraint":null,"metadata":{"greptime:time_index":"true"}},{"name":"git.repository_url","data_type":{"String":null},"is_nullable":true,"is_time_index":false,"default_constraint":null,"metadata":{}}],"timestamp_index":11,"version":1},"primary_key_indices":[0,1,2,3,4,5,6,7,8,9,12],"value_indices":[10,11],"engine":"mito","next_column_id":12,"region_numbers":[],"engine_options":{},"options":{},"created_on":"1970-01-01T00:00:00Z"},"table_type":"Base"}}"#;
         TableGlobalValue::parse(s).unwrap();
     }
+
+    #[test]
+    fn test_schema_value_compatibility() {
+        let value = SchemaValue::parse("null").unwrap();
+        assert_eq!(TableOptions::default(), value.options);
+
+        let options = TableOptions {
+            ttl: Some(std::time::Duration::from_secs(3600)),
+            ..Default::default()
+        };
+        let value = SchemaValue {
+            options: options.clone(),
+        };
+        let deserialized = SchemaValue::from_bytes(value.as_bytes().unwrap()).unwrap();
+        assert_eq!(options, deserialized.options);
+    }
 }
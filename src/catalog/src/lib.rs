@@ -22,7 +22,7 @@ use common_telemetry::info;
 use snafu::{OptionExt, ResultExt};
 use table::engine::{EngineContext, TableEngineRef};
 use table::metadata::TableId;
-use table::requests::CreateTableRequest;
+use table::requests::{CreateTableRequest, TableOptions};
 use table::TableRef;
 
 use crate::error::{CreateTableSnafu, Result};
@@ -76,6 +76,9 @@ pub trait CatalogProvider: Sync + Send {
 
     /// Retrieves a specific schema from the catalog by name, provided it exists.
     fn schema(&self, name: &str) -> Result<Option<SchemaProviderRef>>;
+
+    /// Removes schema from this catalog, returns the removed schema if it exists.
+    fn deregister_schema(&self, name: &str) -> Result<Option<SchemaProviderRef>>;
 }
 
 pub type CatalogListRef = Arc<dyn CatalogList>;
@@ -98,6 +101,10 @@ pub trait CatalogManager: CatalogList {
     /// schema registered.
     async fn register_schema(&self, request: RegisterSchemaRequest) -> Result<bool>;
 
+    /// Deregisters a schema within given catalog from catalog manager, returns whether
+    /// the schema deregistered. Tables in the schema should be deregistered before.
+    async fn deregister_schema(&self, request: DeregisterSchemaRequest) -> Result<bool>;
+
     /// Rename a table to [RenameTableRequest::new_table_name], returns whether the table is renamed.
     async fn rename_table(&self, request: RenameTableRequest) -> Result<bool>;
 
@@ -171,6 +178,14 @@ pub struct DeregisterTableRequest {
 pub struct RegisterSchemaRequest {
     pub catalog: String,
     pub schema: String,
+    /// Default options of tables created in the schema.
+    pub options: TableOptions,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeregisterSchemaRequest {
+    pub catalog: String,
+    pub schema: String,
 }
 
 pub trait CatalogProviderFactory {
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 
@@ -28,27 +29,29 @@ use datatypes::vectors::{BinaryVector, UInt8Vector};
 use futures_util::lock::Mutex;
 use snafu::{ensure, OptionExt, ResultExt};
 use table::engine::{EngineContext, TableEngineRef};
-use table::metadata::TableId;
-use table::requests::OpenTableRequest;
+use table::metadata::{TableId, TableInfo, TableType};
+use table::requests::{OpenTableRequest, TableOptions};
 use table::table::numbers::NumbersTable;
+use table::table::view::ViewTable;
 use table::table::TableIdProvider;
 use table::TableRef;
 
 use crate::error::{
-    self, CatalogNotFoundSnafu, IllegalManagerStateSnafu, OpenTableSnafu, ReadSystemCatalogSnafu,
-    Result, SchemaExistsSnafu, SchemaNotFoundSnafu, SystemCatalogSnafu,
-    SystemCatalogTypeMismatchSnafu, TableExistsSnafu, TableNotFoundSnafu,
+    self, CatalogNotFoundSnafu, IllegalManagerStateSnafu, InvalidSchemaOptionsSnafu,
+    OpenTableSnafu, ReadSystemCatalogSnafu, Result, SchemaExistsSnafu, SchemaNotFoundSnafu,
+    SystemCatalogSnafu, SystemCatalogTypeMismatchSnafu, TableExistsSnafu, TableNotFoundSnafu,
 };
 use crate::local::memory::{MemoryCatalogManager, MemoryCatalogProvider, MemorySchemaProvider};
 use crate::system::{
-    decode_system_catalog, Entry, SystemCatalogTable, TableEntry, ENTRY_TYPE_INDEX, KEY_INDEX,
-    VALUE_INDEX,
+    decode_system_catalog, Entry, SystemCatalogTable, TableEntry, ViewEntry, ENTRY_TYPE_INDEX,
+    KEY_INDEX, VALUE_INDEX,
 };
 use crate::tables::SystemCatalog;
 use crate::{
     handle_system_table_request, CatalogList, CatalogManager, CatalogProvider, CatalogProviderRef,
-    DeregisterTableRequest, RegisterSchemaRequest, RegisterSystemTableRequest,
-    RegisterTableRequest, RenameTableRequest, SchemaProvider, SchemaProviderRef,
+    DeregisterSchemaRequest, DeregisterTableRequest, RegisterSchemaRequest,
+    RegisterSystemTableRequest, RegisterTableRequest, RenameTableRequest, SchemaProvider,
+    SchemaProviderRef,
 };
 
 /// A `CatalogManager` consists of a system catalog and a bunch of user catalogs.
@@ -209,9 +212,14 @@ impl LocalCatalogManager {
                             .context(CatalogNotFoundSnafu {
                                 catalog_name: &s.catalog_name,
                             })?;
+                    let options = s.options.clone().into_iter().collect::<HashMap<_, _>>();
+                    let table_options =
+                        TableOptions::try_from(&options).context(InvalidSchemaOptionsSnafu {
+                            schema: &s.schema_name,
+                        })?;
                     catalog.register_schema(
                         s.schema_name.clone(),
-                        Arc::new(MemorySchemaProvider::new()),
+                        Arc::new(MemorySchemaProvider::with_table_options(table_options)),
                     )?;
                     info!("Registered schema: {:?}", s);
                 }
@@ -220,13 +228,18 @@ impl LocalCatalogManager {
                     info!("Registered table: {:?}", t);
                     max_table_id = max_table_id.max(t.table_id);
                 }
+                Entry::View(v) => {
+                    let table_id = v.table_id;
+                    self.register_view_entry(v)?;
+                    max_table_id = max_table_id.max(table_id);
+                }
             }
         }
         Ok(max_table_id)
     }
 
     /// Sort catalog entries to ensure catalog entries comes first, then schema entries,
-    /// and table entries is the last.
+    /// and table entries and view entries are the last.
     fn sort_entries(mut entries: Vec<Entry>) -> Vec<Entry> {
         entries.sort();
         entries
@@ -274,6 +287,31 @@ impl LocalCatalogManager {
         schema.register_table(t.table_name.clone(), option)?;
         Ok(())
     }
+
+    fn register_view_entry(&self, v: ViewEntry) -> Result<()> {
+        let schema = self
+            .catalogs
+            .catalog(&v.catalog_name)?
+            .context(CatalogNotFoundSnafu {
+                catalog_name: &v.catalog_name,
+            })?
+            .schema(&v.schema_name)?
+            .context(SchemaNotFoundSnafu {
+                catalog: &v.catalog_name,
+                schema: &v.schema_name,
+            })?;
+
+        let table_info =
+            TableInfo::try_from(v.table_info).context(error::InvalidTableInfoInCatalogSnafu)?;
+        let view_name = table_info.name.clone();
+        let view = ViewTable::new(Arc::new(table_info), v.definition);
+        schema.register_table(view_name.clone(), Arc::new(view))?;
+        info!(
+            "Registered view: {}.{}.{}",
+            v.catalog_name, v.schema_name, view_name
+        );
+        Ok(())
+    }
 }
 
 impl CatalogList for LocalCatalogManager {
@@ -365,14 +403,18 @@ impl CatalogManager for LocalCatalogManager {
                 Ok(false)
             } else {
                 // table does not exist
-                self.system
-                    .register_table(
-                        catalog_name.clone(),
-                        schema_name.clone(),
-                        request.table_name.clone(),
-                        request.table_id,
-                    )
-                    .await?;
+                if let Some(view) = request.table.as_any().downcast_ref::<ViewTable>() {
+                    self.system.register_view(view).await?;
+                } else {
+                    self.system
+                        .register_table(
+                            catalog_name.clone(),
+                            schema_name.clone(),
+                            request.table_name.clone(),
+                            request.table_id,
+                        )
+                        .await?;
+                }
                 schema.register_table(request.table_name, request.table)?;
                 Ok(true)
             }
@@ -432,18 +474,21 @@ impl CatalogManager for LocalCatalogManager {
                 schema,
                 table_name,
             } = &request;
-            let table_id = self
+            let table = self
                 .catalogs
                 .table(catalog, schema, table_name)
                 .await?
                 .with_context(|| error::TableNotExistSnafu {
                     table: format_full_table_name(catalog, schema, table_name),
-                })?
-                .table_info()
-                .ident
-                .table_id;
+                })?;
+            let table_id = table.table_info().ident.table_id;
 
-            if !self.system.deregister_table(&request, table_id).await? {
+            let deregistered = if table.table_type() == TableType::View {
+                self.system.deregister_view(&request, table_id).await?
+            } else {
+                self.system.deregister_table(&request, table_id).await?
+            };
+            if !deregistered {
                 return Ok(false);
             }
 
@@ -476,13 +521,41 @@ impl CatalogManager for LocalCatalogManager {
                 }
             );
             self.system
-                .register_schema(request.catalog, schema_name.clone())
+                .register_schema(request.catalog, schema_name.clone(), &request.options)
                 .await?;
-            catalog.register_schema(request.schema, Arc::new(MemorySchemaProvider::new()))?;
+            catalog.register_schema(
+                request.schema,
+                Arc::new(MemorySchemaProvider::with_table_options(request.options)),
+            )?;
             Ok(true)
         }
     }
 
+    async fn deregister_schema(&self, request: DeregisterSchemaRequest) -> Result<bool> {
+        let started = self.init_lock.lock().await;
+        ensure!(
+            *started,
+            IllegalManagerStateSnafu {
+                msg: "Catalog manager not started",
+            }
+        );
+        let catalog_name = &request.catalog;
+
+        let catalog = self
+            .catalogs
+            .catalog(catalog_name)?
+            .context(CatalogNotFoundSnafu { catalog_name })?;
+
+        {
+            let _lock = self.register_lock.lock().await;
+            if catalog.schema(&request.schema)?.is_none() {
+                return Ok(false);
+            }
+            self.system.deregister_schema(&request).await?;
+            Ok(catalog.deregister_schema(&request.schema)?.is_some())
+        }
+    }
+
     async fn register_system_table(&self, request: RegisterSystemTableRequest) -> Result<()> {
         ensure!(
             !*self.init_lock.lock().await,
@@ -548,10 +621,12 @@ mod tests {
             Entry::Schema(SchemaEntry {
                 catalog_name: "C1".to_string(),
                 schema_name: "S1".to_string(),
+                options: Default::default(),
             }),
             Entry::Schema(SchemaEntry {
                 catalog_name: "C2".to_string(),
                 schema_name: "S2".to_string(),
+                options: Default::default(),
             }),
             Entry::Catalog(CatalogEntry {
                 catalog_name: "".to_string(),
@@ -23,6 +23,7 @@ use common_catalog::consts::MIN_USER_TABLE_ID;
 use common_telemetry::error;
 use snafu::{ensure, OptionExt};
 use table::metadata::TableId;
+use table::requests::TableOptions;
 use table::table::TableIdProvider;
 use table::TableRef;
 
@@ -31,9 +32,9 @@ use crate::error::{
 };
 use crate::schema::SchemaProvider;
 use crate::{
-    CatalogList, CatalogManager, CatalogProvider, CatalogProviderRef, DeregisterTableRequest,
-    RegisterSchemaRequest, RegisterSystemTableRequest, RegisterTableRequest, RenameTableRequest,
-    SchemaProviderRef,
+    CatalogList, CatalogManager, CatalogProvider, CatalogProviderRef, DeregisterSchemaRequest,
+    DeregisterTableRequest, RegisterSchemaRequest, RegisterSystemTableRequest,
+    RegisterTableRequest, RenameTableRequest, SchemaProviderRef,
 };
 
 /// Simple in-memory list of catalogs
@@ -138,10 +139,23 @@ impl CatalogManager for MemoryCatalogManager {
             .context(CatalogNotFoundSnafu {
                 catalog_name: &request.catalog,
             })?;
-        catalog.register_schema(request.schema, Arc::new(MemorySchemaProvider::new()))?;
+        catalog.register_schema(
+            request.schema,
+            Arc::new(MemorySchemaProvider::with_table_options(request.options)),
+        )?;
         Ok(true)
     }
 
+    async fn deregister_schema(&self, request: DeregisterSchemaRequest) -> Result<bool> {
+        let catalogs = self.catalogs.write().unwrap();
+        let catalog = catalogs
+            .get(&request.catalog)
+            .context(CatalogNotFoundSnafu {
+                catalog_name: &request.catalog,
+            })?;
+        Ok(catalog.deregister_schema(&request.schema)?.is_some())
+    }
+
     async fn register_system_table(&self, _request: RegisterSystemTableRequest) -> Result<()> {
         // TODO(ruihang): support register system table request
         Ok(())
@@ -266,18 +280,31 @@ impl CatalogProvider for MemoryCatalogProvider {
         let schemas = self.schemas.read().unwrap();
         Ok(schemas.get(name).cloned())
     }
+
+    fn deregister_schema(&self, name: &str) -> Result<Option<SchemaProviderRef>> {
+        let mut schemas = self.schemas.write().unwrap();
+        Ok(schemas.remove(name))
+    }
 }
 
 /// Simple in-memory implementation of a schema.
 pub struct MemorySchemaProvider {
     tables: RwLock<HashMap<String, TableRef>>,
+    table_options: TableOptions,
 }
 
 impl MemorySchemaProvider {
     /// Instantiates a new MemorySchemaProvider with an empty collection of tables.
     pub fn new() -> Self {
+        Self::with_table_options(TableOptions::default())
+    }
+
+    /// Instantiates a new MemorySchemaProvider with an empty collection of tables, tables
+    /// created in the schema use `table_options` as their default options.
+    pub fn with_table_options(table_options: TableOptions) -> Self {
         Self {
             tables: RwLock::new(HashMap::new()),
+            table_options,
         }
     }
 }
@@ -345,6 +372,10 @@ impl SchemaProvider for MemorySchemaProvider {
         let tables = self.tables.read().unwrap();
         Ok(tables.contains_key(name))
     }
+
+    fn table_options(&self) -> Result<TableOptions> {
+        Ok(self.table_options.clone())
+    }
 }
 
 /// Create a memory catalog list contains a numbers table for test
@@ -528,4 +559,37 @@ mod tests {
             .unwrap();
         assert!(!schema.table_exist("numbers").unwrap());
     }
+
+    #[tokio::test]
+    pub async fn test_catalog_deregister_schema() {
+        let catalog = MemoryCatalogManager::default();
+        let register_schema_req = RegisterSchemaRequest {
+            catalog: DEFAULT_CATALOG_NAME.to_string(),
+            schema: "test_schema".to_string(),
+            options: TableOptions::default(),
+        };
+        assert!(catalog.register_schema(register_schema_req).await.unwrap());
+        assert!(catalog
+            .schema(DEFAULT_CATALOG_NAME, "test_schema")
+            .unwrap()
+            .is_some());
+
+        let deregister_schema_req = DeregisterSchemaRequest {
+            catalog: DEFAULT_CATALOG_NAME.to_string(),
+            schema: "test_schema".to_string(),
+        };
+        assert!(catalog
+            .deregister_schema(deregister_schema_req.clone())
+            .await
+            .unwrap());
+        assert!(catalog
+            .schema(DEFAULT_CATALOG_NAME, "test_schema")
+            .unwrap()
+            .is_none());
+        // deregister again
+        assert!(!catalog
+            .deregister_schema(deregister_schema_req)
+            .await
+            .unwrap());
+    }
 }
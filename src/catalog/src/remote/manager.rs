@@ -43,8 +43,9 @@ use crate::helper::{
 use crate::remote::{Kv, KvBackendRef};
 use crate::{
     handle_system_table_request, CatalogList, CatalogManager, CatalogProvider, CatalogProviderRef,
-    DeregisterTableRequest, RegisterSchemaRequest, RegisterSystemTableRequest,
-    RegisterTableRequest, RenameTableRequest, SchemaProvider, SchemaProviderRef,
+    DeregisterSchemaRequest, DeregisterTableRequest, RegisterSchemaRequest,
+    RegisterSystemTableRequest, RegisterTableRequest, RenameTableRequest, SchemaProvider,
+    SchemaProviderRef,
 };
 
 /// Catalog manager based on metasrv.
@@ -278,7 +279,7 @@ impl RemoteCatalogManager {
         self.backend
             .set(
                 schema_key.as_bytes(),
-                &SchemaValue {}
+                &SchemaValue::default()
                     .as_bytes()
                     .context(InvalidCatalogValueSnafu)?,
             )
@@ -453,6 +454,13 @@ impl CatalogManager for RemoteCatalogManager {
         Ok(true)
     }
 
+    async fn deregister_schema(&self, _request: DeregisterSchemaRequest) -> Result<bool> {
+        UnimplementedSnafu {
+            operation: "deregister schema",
+        }
+        .fail()
+    }
+
     async fn rename_table(&self, _request: RenameTableRequest) -> Result<bool> {
         UnimplementedSnafu {
             operation: "rename table",
@@ -637,7 +645,7 @@ impl CatalogProvider for RemoteCatalogProvider {
                 backend
                     .set(
                         key.as_bytes(),
-                        &SchemaValue {}
+                        &SchemaValue::default()
                             .as_bytes()
                             .context(InvalidCatalogValueSnafu)?,
                     )
@@ -660,6 +668,13 @@ impl CatalogProvider for RemoteCatalogProvider {
         self.refresh_schemas()?;
         Ok(self.schemas.load().get(name).cloned())
     }
+
+    fn deregister_schema(&self, _name: &str) -> Result<Option<SchemaProviderRef>> {
+        UnimplementedSnafu {
+            operation: "deregister schema",
+        }
+        .fail()
+    }
 }
 
 pub struct RemoteSchemaProvider {
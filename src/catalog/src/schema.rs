@@ -16,6 +16,7 @@ use std::any::Any;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use table::requests::TableOptions;
 use table::TableRef;
 
 use crate::error::Result;
@@ -49,6 +50,11 @@ pub trait SchemaProvider: Sync + Send {
     /// If no matched table in the schema provider, return false.
     /// Otherwise, return true.
     fn table_exist(&self, name: &str) -> Result<bool>;
+
+    /// Returns the default options of tables created in this schema.
+    fn table_options(&self) -> Result<TableOptions> {
+        Ok(TableOptions::default())
+    }
 }
 
 pub type SchemaProviderRef = Arc<dyn SchemaProvider>;
@@ -13,7 +13,8 @@
 // limitations under the License.
 
 use std::any::Any;
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use common_catalog::consts::{
@@ -31,17 +32,18 @@ use datatypes::vectors::{BinaryVector, TimestampMillisecondVector, UInt8Vector};
 use serde::{Deserialize, Serialize};
 use snafu::{ensure, OptionExt, ResultExt};
 use table::engine::{EngineContext, TableEngineRef};
-use table::metadata::{TableId, TableInfoRef};
+use table::metadata::{RawTableInfo, TableId, TableInfoRef};
 use table::requests::{
     CreateTableRequest, DeleteRequest, InsertRequest, OpenTableRequest, TableOptions,
 };
+use table::table::view::ViewTable;
 use table::{Table, TableRef};
 
 use crate::error::{
     self, CreateSystemCatalogSnafu, EmptyValueSnafu, Error, InvalidEntryTypeSnafu, InvalidKeySnafu,
     OpenSystemCatalogSnafu, Result, ValueDeserializeSnafu,
 };
-use crate::DeregisterTableRequest;
+use crate::{DeregisterSchemaRequest, DeregisterTableRequest};
 
 pub const ENTRY_TYPE_INDEX: usize = 0;
 pub const KEY_INDEX: usize = 1;
@@ -215,6 +217,36 @@ pub(crate) fn build_table_deletion_request(
     }
 }
 
+/// Builds the request to persist `view`. Views are stored with their table info, as the
+/// schema of a view can't be recovered from storage like tables.
+pub fn build_view_insert_request(view: &ViewTable) -> InsertRequest {
+    let table_info = view.table_info();
+    let entry_key = format_table_entry_key(
+        &table_info.catalog_name,
+        &table_info.schema_name,
+        table_info.ident.table_id,
+    );
+    let value = ViewEntryValue {
+        table_info: RawTableInfo::from(table_info.as_ref().clone()),
+        definition: view.definition().to_string(),
+    };
+    build_insert_request(
+        EntryType::View,
+        entry_key.as_bytes(),
+        serde_json::to_string(&value).unwrap().as_bytes(),
+    )
+}
+
+pub(crate) fn build_view_deletion_request(
+    request: &DeregisterTableRequest,
+    table_id: TableId,
+) -> DeleteRequest {
+    let view_key = format_table_entry_key(&request.catalog, &request.schema, table_id);
+    DeleteRequest {
+        key_column_values: build_primary_key_columns(EntryType::View, view_key.as_bytes()),
+    }
+}
+
 fn build_primary_key_columns(entry_type: EntryType, key: &[u8]) -> HashMap<String, VectorRef> {
     let mut m = HashMap::with_capacity(3);
     m.insert(
@@ -233,17 +265,32 @@ fn build_primary_key_columns(entry_type: EntryType, key: &[u8]) -> HashMap<Strin
     m
 }
 
-pub fn build_schema_insert_request(catalog_name: String, schema_name: String) -> InsertRequest {
+pub fn build_schema_insert_request(
+    catalog_name: String,
+    schema_name: String,
+    options: &TableOptions,
+) -> InsertRequest {
     let full_schema_name = format!("{catalog_name}.{schema_name}");
+    let options = HashMap::from(options).into_iter().collect();
     build_insert_request(
         EntryType::Schema,
         full_schema_name.as_bytes(),
-        serde_json::to_string(&SchemaEntryValue {})
+        serde_json::to_string(&SchemaEntryValue { options })
             .unwrap()
             .as_bytes(),
     )
 }
 
+pub(crate) fn build_schema_deletion_request(request: &DeregisterSchemaRequest) -> DeleteRequest {
+    let full_schema_name = format!("{}.{}", request.catalog, request.schema);
+    DeleteRequest {
+        key_column_values: build_primary_key_columns(
+            EntryType::Schema,
+            full_schema_name.as_bytes(),
+        ),
+    }
+}
+
 pub fn build_insert_request(entry_type: EntryType, key: &[u8], value: &[u8]) -> InsertRequest {
     let primary_key_columns = build_primary_key_columns(entry_type, key);
 
@@ -296,7 +343,8 @@ pub fn decode_system_catalog(
         }
         EntryType::Schema => {
             // As for schema entry, the key is a string with format: `<catalog_name>.<schema_name>`
-            // and the value is current not used.
+            // and the value is a JSON string with format: `{"options": {<key>: <value>}}`, or
+            // `null` if the schema is created without options.
             let schema_parts = key.split('.').collect::<Vec<_>>();
             ensure!(
                 schema_parts.len() == 2,
@@ -304,9 +352,16 @@ pub fn decode_system_catalog(
                     key: Some(key.to_string())
                 }
             );
+            let schema_value = match value {
+                Some(value) => serde_json::from_slice::<Option<SchemaEntryValue>>(value)
+                    .context(ValueDeserializeSnafu)?
+                    .unwrap_or_default(),
+                None => SchemaEntryValue::default(),
+            };
             Ok(Entry::Schema(SchemaEntry {
                 catalog_name: schema_parts[0].to_string(),
                 schema_name: schema_parts[1].to_string(),
+                options: schema_value.options,
             }))
         }
 
@@ -332,6 +387,30 @@ pub fn decode_system_catalog(
                 table_id,
             }))
         }
+
+        EntryType::View => {
+            // As for view entry, the key is a string with format: `<catalog_name>.<schema_name>.<table_id>`
+            // and the value is a JSON string with format: `{"table_info": <table_info>, "definition": <sql>}`
+            let view_parts = key.split('.').collect::<Vec<_>>();
+            ensure!(
+                view_parts.len() >= 3,
+                InvalidKeySnafu {
+                    key: Some(key.to_string())
+                }
+            );
+            let value = value.context(EmptyValueSnafu)?;
+            debug!("View meta value: {}", String::from_utf8_lossy(value));
+            let view_value: ViewEntryValue =
+                serde_json::from_slice(value).context(ValueDeserializeSnafu)?;
+            let table_id = view_parts[2].parse::<TableId>().unwrap();
+            Ok(Entry::View(ViewEntry {
+                catalog_name: view_parts[0].to_string(),
+                schema_name: view_parts[1].to_string(),
+                table_id,
+                table_info: view_value.table_info,
+                definition: view_value.definition,
+            }))
+        }
     }
 }
 
@@ -340,6 +419,7 @@ pub enum EntryType {
     Catalog = 1,
     Schema = 2,
     Table = 3,
+    View = 4,
 }
 
 impl TryFrom<u8> for EntryType {
@@ -350,6 +430,7 @@ impl TryFrom<u8> for EntryType {
             b if b == Self::Catalog as u8 => Ok(Self::Catalog),
             b if b == Self::Schema as u8 => Ok(Self::Schema),
             b if b == Self::Table as u8 => Ok(Self::Table),
+            b if b == Self::View as u8 => Ok(Self::View),
             b => InvalidEntryTypeSnafu {
                 entry_type: Some(b),
             }
@@ -363,6 +444,7 @@ pub enum Entry {
     Catalog(CatalogEntry),
     Schema(SchemaEntry),
     Table(TableEntry),
+    View(ViewEntry),
 }
 
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd)]
@@ -374,10 +456,15 @@ pub struct CatalogEntry {
 pub struct SchemaEntry {
     pub catalog_name: String,
     pub schema_name: String,
+    /// Default options of tables created in the schema.
+    pub options: BTreeMap<String, String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
-pub struct SchemaEntryValue;
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SchemaEntryValue {
+    #[serde(default)]
+    pub options: BTreeMap<String, String>,
+}
 
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd)]
 pub struct TableEntry {
@@ -392,9 +479,43 @@ pub struct TableEntryValue {
     pub table_name: String,
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub struct ViewEntry {
+    pub catalog_name: String,
+    pub schema_name: String,
+    pub table_id: TableId,
+    pub table_info: RawTableInfo,
+    /// SQL of the query of the view.
+    pub definition: String,
+}
+
+// Table infos are not comparable, and the id identifies a view anyway.
+impl PartialOrd for ViewEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ViewEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.catalog_name, &self.schema_name, self.table_id).cmp(&(
+            &other.catalog_name,
+            &other.schema_name,
+            other.table_id,
+        ))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ViewEntryValue {
+    pub table_info: RawTableInfo,
+    pub definition: String,
+}
+
 #[cfg(test)]
 mod tests {
     use common_recordbatch::RecordBatches;
+    use datatypes::schema::Schema;
     use datatypes::value::Value;
     use log_store::NoopLogStore;
     use mito::config::EngineConfig;
@@ -403,8 +524,8 @@ mod tests {
     use storage::compaction::noop::NoopCompactionScheduler;
     use storage::config::EngineConfig as StorageEngineConfig;
     use storage::EngineImpl;
-    use table::metadata::TableType;
     use table::metadata::TableType::Base;
+    use table::metadata::{TableInfoBuilder, TableMetaBuilder, TableType};
     use tempdir::TempDir;
 
     use super::*;
@@ -436,9 +557,41 @@ mod tests {
         if let Entry::Schema(e) = entry {
             assert_eq!("some_catalog", e.catalog_name);
             assert_eq!("some_schema", e.schema_name);
+            assert!(e.options.is_empty());
         } else {
             panic!("Unexpected type: {entry:?}");
         }
+
+        // Schema created before supporting options.
+        let entry = decode_system_catalog(
+            Some(EntryType::Schema as u8),
+            Some("some_catalog.some_schema".as_bytes()),
+            Some("null".as_bytes()),
+        )
+        .unwrap();
+        assert_eq!(
+            Entry::Schema(SchemaEntry {
+                catalog_name: "some_catalog".to_string(),
+                schema_name: "some_schema".to_string(),
+                options: BTreeMap::new(),
+            }),
+            entry
+        );
+
+        let entry = decode_system_catalog(
+            Some(EntryType::Schema as u8),
+            Some("some_catalog.some_schema".as_bytes()),
+            Some("{\"options\":{\"ttl\":\"7days\"}}".as_bytes()),
+        )
+        .unwrap();
+        assert_eq!(
+            Entry::Schema(SchemaEntry {
+                catalog_name: "some_catalog".to_string(),
+                schema_name: "some_schema".to_string(),
+                options: BTreeMap::from([("ttl".to_string(), "7days".to_string())]),
+            }),
+            entry
+        );
     }
 
     #[test]
@@ -460,6 +613,59 @@ mod tests {
         }
     }
 
+    #[test]
+    pub fn test_decode_view() {
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "host",
+            ConcreteDataType::string_datatype(),
+            true,
+        )]));
+        let meta = TableMetaBuilder::default()
+            .schema(schema)
+            .primary_key_indices(vec![])
+            .next_column_id(1)
+            .build()
+            .unwrap();
+        let table_info = TableInfoBuilder::new("some_view", meta)
+            .table_id(42)
+            .catalog_name("some_catalog")
+            .schema_name("some_schema")
+            .table_type(TableType::View)
+            .build()
+            .unwrap();
+        let view = ViewTable::new(
+            Arc::new(table_info.clone()),
+            "SELECT host FROM some_table".to_string(),
+        );
+
+        let request = build_view_insert_request(&view);
+        let column_data = |name: &str| {
+            request.columns_values[name]
+                .as_any()
+                .downcast_ref::<BinaryVector>()
+                .unwrap()
+                .get_data(0)
+                .unwrap()
+                .to_vec()
+        };
+        let entry = decode_system_catalog(
+            Some(EntryType::View as u8),
+            Some(&column_data("key")),
+            Some(&column_data("value")),
+        )
+        .unwrap();
+        assert_eq!(
+            Entry::View(ViewEntry {
+                catalog_name: "some_catalog".to_string(),
+                schema_name: "some_schema".to_string(),
+                table_id: 42,
+                table_info: RawTableInfo::from(table_info),
+                definition: "SELECT host FROM some_table".to_string(),
+            }),
+            entry
+        );
+    }
+
     #[test]
     #[should_panic]
     pub fn test_decode_mismatch() {
@@ -36,16 +36,20 @@ use snafu::ResultExt;
 use table::engine::TableEngineRef;
 use table::error::TablesRecordBatchSnafu;
 use table::metadata::{TableId, TableInfoRef};
+use table::requests::TableOptions;
 use table::table::scan::SimpleTableScan;
+use table::table::view::ViewTable;
 use table::{Table, TableRef};
 
 use crate::error::{self, Error, InsertCatalogRecordSnafu, Result as CatalogResult};
 use crate::system::{
-    build_schema_insert_request, build_table_deletion_request, build_table_insert_request,
+    build_schema_deletion_request, build_schema_insert_request, build_table_deletion_request,
+    build_table_insert_request, build_view_deletion_request, build_view_insert_request,
     SystemCatalogTable,
 };
 use crate::{
-    CatalogListRef, CatalogProvider, DeregisterTableRequest, SchemaProvider, SchemaProviderRef,
+    CatalogListRef, CatalogProvider, DeregisterSchemaRequest, DeregisterTableRequest,
+    SchemaProvider, SchemaProviderRef,
 };
 
 /// Tables holds all tables created by user.
@@ -295,18 +299,57 @@ impl SystemCatalog {
             })
     }
 
+    pub async fn register_view(&self, view: &ViewTable) -> crate::error::Result<usize> {
+        let request = build_view_insert_request(view);
+        self.information_schema
+            .system
+            .insert(request)
+            .await
+            .context(InsertCatalogRecordSnafu)
+    }
+
+    pub(crate) async fn deregister_view(
+        &self,
+        request: &DeregisterTableRequest,
+        table_id: TableId,
+    ) -> CatalogResult<bool> {
+        self.information_schema
+            .system
+            .delete(build_view_deletion_request(request, table_id))
+            .await
+            .map(|x| x == 1)
+            .with_context(|_| error::DeregisterTableSnafu {
+                request: request.clone(),
+            })
+    }
+
     pub async fn register_schema(
         &self,
         catalog: String,
         schema: String,
+        options: &TableOptions,
     ) -> crate::error::Result<usize> {
-        let request = build_schema_insert_request(catalog, schema);
+        let request = build_schema_insert_request(catalog, schema, options);
         self.information_schema
             .system
             .insert(request)
             .await
             .context(InsertCatalogRecordSnafu)
     }
+
+    pub(crate) async fn deregister_schema(
+        &self,
+        request: &DeregisterSchemaRequest,
+    ) -> CatalogResult<bool> {
+        self.information_schema
+            .system
+            .delete(build_schema_deletion_request(request))
+            .await
+            .map(|x| x == 1)
+            .with_context(|_| error::DeregisterSchemaSnafu {
+                request: request.clone(),
+            })
+    }
 }
 
 impl CatalogProvider for SystemCatalog {
@@ -333,6 +376,13 @@ impl CatalogProvider for SystemCatalog {
             Ok(None)
         }
     }
+
+    fn deregister_schema(&self, _name: &str) -> Result<Option<SchemaProviderRef>, Error> {
+        error::UnimplementedSnafu {
+            operation: "deregister schema in system catalog",
+        }
+        .fail()
+    }
 }
 
 fn build_schema_for_tables() -> Schema {
@@ -59,7 +59,10 @@ mod tests {
         }
         .to_string();
         backend
-            .set(schema_key.as_bytes(), &SchemaValue {}.as_bytes().unwrap())
+            .set(
+                schema_key.as_bytes(),
+                &SchemaValue::default().as_bytes().unwrap(),
+            )
             .await
             .unwrap();
 
@@ -22,6 +22,7 @@ use datafusion::arrow::datatypes::SchemaRef as DfSchemaRef;
 use datafusion::error::Result as DfResult;
 pub use datafusion::execution::context::{SessionContext, TaskContext};
 use datafusion::physical_plan::expressions::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::MetricsSet;
 pub use datafusion::physical_plan::Partitioning;
 use datafusion::physical_plan::Statistics;
 use datatypes::schema::SchemaRef;
@@ -64,6 +65,12 @@ pub trait PhysicalPlan: Debug + Send + Sync {
         partition: usize,
         context: Arc<TaskContext>,
     ) -> Result<SendableRecordBatchStream>;
+
+    /// Returns the metrics of this plan, which are shown by `EXPLAIN ANALYZE`. Returns
+    /// `None` if the plan doesn't record any metrics.
+    fn metrics(&self) -> Option<MetricsSet> {
+        None
+    }
 }
 
 #[derive(Debug)]
@@ -130,6 +137,10 @@ impl PhysicalPlan for PhysicalPlanAdapter {
 
         Ok(Box::pin(adapter))
     }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        self.df_plan.metrics()
+    }
 }
 
 #[derive(Debug)]
@@ -191,6 +202,10 @@ impl DfPhysicalPlan for DfPhysicalPlanAdapter {
         // TODO(LFC): impl statistics
         Statistics::default()
     }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        self.0.metrics()
+    }
 }
 
 #[cfg(test)]
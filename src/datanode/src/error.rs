@@ -83,6 +83,27 @@ pub enum Error {
         source: BoxedError,
     },
 
+    #[snafu(display("Failed to flush table {}, source: {}", table_name, source))]
+    FlushTable {
+        table_name: String,
+        #[snafu(backtrace)]
+        source: TableError,
+    },
+
+    #[snafu(display("Failed to compact table {}, source: {}", table_name, source))]
+    CompactTable {
+        table_name: String,
+        #[snafu(backtrace)]
+        source: TableError,
+    },
+
+    #[snafu(display("Failed to truncate table {}, source: {}", table_name, source))]
+    TruncateTable {
+        table_name: String,
+        #[snafu(backtrace)]
+        source: TableError,
+    },
+
     #[snafu(display("Table not found: {}", table_name))]
     TableNotFound {
         table_name: String,
@@ -190,12 +211,6 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
-    #[snafu(display("Failed to build backend, source: {}", source))]
-    BuildBackend {
-        source: object_store::Error,
-        backtrace: Backtrace,
-    },
-
     #[snafu(display("Runtime resource error, source: {}", source))]
     RuntimeResource {
         #[snafu(backtrace)]
@@ -247,6 +262,13 @@ pub enum Error {
         source: catalog::error::Error,
     },
 
+    #[snafu(display("Failed to deregister schema {}, source: {}", name, source))]
+    DeregisterSchema {
+        name: String,
+        #[snafu(backtrace)]
+        source: catalog::error::Error,
+    },
+
     #[snafu(display("Schema {} already exists", name))]
     SchemaExists { name: String, backtrace: Backtrace },
 
@@ -396,6 +418,53 @@ pub enum Error {
         source: object_store::Error,
     },
 
+    #[snafu(display("Failed to list objects in path: {}, source: {}", path, source))]
+    ListObjects {
+        path: String,
+        backtrace: Backtrace,
+        source: object_store::Error,
+    },
+
+    #[snafu(display("Failed to read object from path: {}, source: {}", path, source))]
+    ReadObject {
+        path: String,
+        backtrace: Backtrace,
+        source: object_store::Error,
+    },
+
+    #[snafu(display("Failed to read parquet file: {}, source: {}", path, source))]
+    ReadParquet {
+        path: String,
+        source: parquet::errors::ParquetError,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Failed to parse file: {}, source: {}", path, source))]
+    ParseFile {
+        path: String,
+        source: datatypes::arrow::error::ArrowError,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("No file matches path: {}", path))]
+    NoMatchingFile { path: String, backtrace: Backtrace },
+
+    #[snafu(display("Invalid path to copy: {}, it can't contain '..'", path))]
+    InvalidCopyPath { path: String, backtrace: Backtrace },
+
+    #[snafu(display(
+        "Failed to cast column {} into type {:?}, source: {}",
+        column,
+        data_type,
+        source
+    ))]
+    CastColumn {
+        column: String,
+        data_type: ConcreteDataType,
+        source: datatypes::arrow::error::ArrowError,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display("Unrecognized table option: {}", source))]
     UnrecognizedTableOption {
         #[snafu(backtrace)]
@@ -421,6 +490,39 @@ pub enum Error {
         #[snafu(backtrace)]
         source: common_procedure::error::Error,
     },
+
+    #[snafu(display("Table or view {} already exists", name))]
+    TableExists { name: String, backtrace: Backtrace },
+
+    #[snafu(display("{} is not a view", name))]
+    NotView { name: String, backtrace: Backtrace },
+
+    #[snafu(display("Failed to build table meta for view {}, source: {}", name, source))]
+    BuildViewMeta {
+        name: String,
+        source: table::metadata::TableMetaBuilderError,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Failed to build table info for view {}, source: {}", name, source))]
+    BuildViewInfo {
+        name: String,
+        source: table::metadata::TableInfoBuilderError,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Flow {} already exists", name))]
+    FlowExists { name: String, backtrace: Backtrace },
+
+    #[snafu(display("Flow {} not found", name))]
+    FlowNotFound { name: String, backtrace: Backtrace },
+
+    #[snafu(display("Invalid flow {}, {}", name, msg))]
+    InvalidFlow {
+        name: String,
+        msg: String,
+        backtrace: Backtrace,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -432,17 +534,22 @@ impl ErrorExt for Error {
             ExecuteSql { source } | DescribeStatement { source } => source.status_code(),
             DecodeLogicalPlan { source } => source.status_code(),
             NewCatalog { source } | RegisterSchema { source } => source.status_code(),
+            DeregisterSchema { source, .. } => source.status_code(),
             FindTable { source, .. } => source.status_code(),
             CreateTable { source, .. } | GetTable { source, .. } | AlterTable { source, .. } => {
                 source.status_code()
             }
             DropTable { source, .. } => source.status_code(),
+            FlushTable { source, .. }
+            | CompactTable { source, .. }
+            | TruncateTable { source, .. } => source.status_code(),
 
             Insert { source, .. } => source.status_code(),
             Delete { source, .. } => source.status_code(),
             CollectRecords { source, .. } => source.status_code(),
 
             TableNotFound { .. } => StatusCode::TableNotFound,
+            TableExists { .. } => StatusCode::TableAlreadyExists,
             ColumnNotFound { .. } => StatusCode::TableColumnNotFound,
 
             ParseSqlValue { source, .. } | ParseSql { source, .. } => source.status_code(),
@@ -469,7 +576,15 @@ impl ErrorExt for Error {
             | DatabaseNotFound { .. }
             | MissingNodeId { .. }
             | MissingMetasrvOpts { .. }
-            | ColumnNoneDefaultValue { .. } => StatusCode::InvalidArguments,
+            | ColumnNoneDefaultValue { .. }
+            | ParseFile { .. }
+            | NoMatchingFile { .. }
+            | InvalidCopyPath { .. }
+            | CastColumn { .. }
+            | NotView { .. }
+            | FlowExists { .. }
+            | FlowNotFound { .. }
+            | InvalidFlow { .. } => StatusCode::InvalidArguments,
 
             // TODO(yingwen): Further categorize http error.
             StartServer { .. }
@@ -481,13 +596,17 @@ impl ErrorExt for Error {
             | RenameTable { .. }
             | Catalog { .. }
             | MissingRequiredField { .. }
-            | IncorrectInternalState { .. } => StatusCode::Internal,
+            | IncorrectInternalState { .. }
+            | BuildViewMeta { .. }
+            | BuildViewInfo { .. } => StatusCode::Internal,
 
-            BuildBackend { .. }
-            | InitBackend { .. }
+            InitBackend { .. }
             | WriteParquet { .. }
             | PollStream { .. }
-            | WriteObject { .. } => StatusCode::StorageUnavailable,
+            | WriteObject { .. }
+            | ListObjects { .. }
+            | ReadObject { .. }
+            | ReadParquet { .. } => StatusCode::StorageUnavailable,
             OpenLogStore { source } => source.status_code(),
             StartScriptManager { source } => source.status_code(),
             OpenStorageEngine { source } => source.status_code(),
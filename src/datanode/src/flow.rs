@@ -0,0 +1,788 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Flows, i.e. continuous aggregations that downsample a source table into a sink table.
+//!
+//! A flow is defined by a query over a single source table, which groups rows into time
+//! windows by `date_bin(<interval>, <time index>)`. Inserting into the source table marks
+//! the written time range as pending, and the flow periodically recomputes the windows
+//! overlapping the pending range and writes them into the sink table, overwriting the
+//! previous results of these windows. Only rows written after the flow is created are
+//! aggregated.
+//!
+//! Flows are only supported in standalone mode, where the datanode holds all the regions of
+//! the source table.
+//!
+//! Deleting rows from the source table also marks their time range as pending. Windows
+//! whose rows are all deleted are not recomputed, so the sink table keeps their previous
+//! results.
+//!
+//! The pending range is persisted into the flows table before the rows are written, so the
+//! flows won't miss them even if the datanode crashes right after writing them.
+
+mod table;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::Duration;
+
+use catalog::CatalogManagerRef;
+use common_catalog::format_full_table_name;
+use common_query::Output;
+use common_recordbatch::{util as record_util, RecordBatch};
+use common_telemetry::logging::{error, info};
+use common_time::timestamp::TimeUnit;
+use common_time::util;
+use datatypes::prelude::{Value, VectorRef};
+use query::parser::QueryStatement;
+use query::QueryEngineRef;
+use session::context::QueryContext;
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::ast::{
+    BinaryOperator, Expr, Function, FunctionArg, FunctionArgExpr, Ident, ObjectName,
+    Query as SpQuery, SelectItem, SetExpr, TableFactor, Value as SqlValue,
+};
+use sql::dialect::GenericDialect;
+use sql::parser::ParserContext;
+use sql::statements::query::Query;
+use sql::statements::statement::Statement;
+use table::engine::{TableEngineRef, TableReference};
+use table::requests::{DeleteRequest, InsertRequest};
+use table::TableRef;
+use tokio::sync::Mutex;
+
+use crate::error::{
+    CatalogSnafu, CollectRecordsSnafu, ColumnNotFoundSnafu, ExecuteSqlSnafu, FlowExistsSnafu,
+    FlowNotFoundSnafu, InsertSnafu, InvalidFlowSnafu, ParseSqlSnafu, Result, TableNotFoundSnafu,
+    VectorComputationSnafu,
+};
+use crate::flow::table::FlowsTable;
+use crate::instance::sql::table_idents_to_full_name;
+
+/// Interval between two runs of the flows.
+const FLOW_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The function grouping rows of a flow into time windows.
+const WINDOW_FUNCTION: &str = "date_bin";
+
+/// Inclusive range of the time index, in milliseconds, written into the source table of
+/// a flow but not aggregated yet.
+pub(crate) type PendingRange = (i64, i64);
+
+#[derive(Debug)]
+pub struct CreateFlowRequest {
+    pub catalog_name: String,
+    pub schema_name: String,
+    pub flow_name: String,
+    pub sink_catalog: String,
+    pub sink_schema: String,
+    pub sink_table: String,
+    pub definition: String,
+    pub create_if_not_exists: bool,
+}
+
+#[derive(Debug)]
+pub struct DropFlowRequest {
+    pub catalog_name: String,
+    pub schema_name: String,
+    pub flow_name: String,
+    pub drop_if_exists: bool,
+}
+
+/// Persisted definition of a flow. The sink table is in the catalog of the flow.
+#[derive(Debug, Clone)]
+pub(crate) struct FlowInfo {
+    pub(crate) catalog_name: String,
+    pub(crate) schema_name: String,
+    pub(crate) flow_name: String,
+    pub(crate) sink_schema: String,
+    pub(crate) sink_table: String,
+    pub(crate) definition: String,
+}
+
+impl FlowInfo {
+    fn full_name(&self) -> String {
+        format_full_table_name(&self.catalog_name, &self.schema_name, &self.flow_name)
+    }
+
+    fn sink_full_name(&self) -> String {
+        format_full_table_name(&self.catalog_name, &self.sink_schema, &self.sink_table)
+    }
+}
+
+struct Flow {
+    info: FlowInfo,
+    /// Full name of the source table.
+    source: String,
+    /// Name of the time index column of the source table.
+    time_index: String,
+    query: SpQuery,
+    /// The `date_bin` call grouping rows into time windows.
+    window: Function,
+    pending: Mutex<PendingState>,
+}
+
+/// Pending range of a flow.
+#[derive(Default)]
+struct PendingState {
+    /// The range to aggregate in the next run.
+    range: Option<PendingRange>,
+    /// Ranges of the writes in progress by their ids. A run may not see the rows of these
+    /// writes, so their ranges are still pending after the run.
+    writing: HashMap<u64, PendingRange>,
+    /// The range persisted in the flows table, which covers all the ranges above and the
+    /// range being aggregated.
+    persisted: Option<PendingRange>,
+}
+
+impl Flow {
+    async fn try_new(
+        info: FlowInfo,
+        pending: Option<PendingRange>,
+        catalog_manager: &CatalogManagerRef,
+    ) -> Result<Self> {
+        let name = info.full_name();
+        let invalid = |msg: &str| InvalidFlowSnafu { name: &name, msg }.build();
+
+        let mut stmts = ParserContext::create_with_dialect(&info.definition, &GenericDialect {})
+            .context(ParseSqlSnafu)?;
+        let query = match stmts.pop() {
+            Some(Statement::Query(query)) if stmts.is_empty() => query.inner,
+            _ => return Err(invalid("the definition must be a query")),
+        };
+        let select = match &*query.body {
+            SetExpr::Select(select) => select,
+            _ => return Err(invalid("the query must be a simple SELECT")),
+        };
+        let source_name = match &select.from[..] {
+            [from] if from.joins.is_empty() => match &from.relation {
+                TableFactor::Table { name, .. } => name,
+                _ => return Err(invalid("the query must select from a table")),
+            },
+            _ => return Err(invalid("the query must select from exactly one table")),
+        };
+
+        let query_ctx = Arc::new(QueryContext::with(&info.catalog_name, &info.schema_name));
+        let (catalog_name, schema_name, table_name) =
+            table_idents_to_full_name(source_name, query_ctx)?;
+        let source = format_full_table_name(&catalog_name, &schema_name, &table_name);
+        let source_table = catalog_manager
+            .table(&catalog_name, &schema_name, &table_name)
+            .await
+            .context(CatalogSnafu)?
+            .with_context(|| TableNotFoundSnafu {
+                table_name: &source,
+            })?;
+        if source == info.sink_full_name() {
+            return Err(invalid("the sink table can't be the source table"));
+        }
+        let source_schema = source_table.schema();
+        let time_index = match source_schema.timestamp_column() {
+            Some(column) => column.name.clone(),
+            None => return Err(invalid("the source table has no time index")),
+        };
+
+        let window = select.projection.iter().find_map(|item| match item {
+            SelectItem::UnnamedExpr(Expr::Function(function))
+            | SelectItem::ExprWithAlias {
+                expr: Expr::Function(function),
+                ..
+            } if is_window_of(function, &time_index) => Some(function.clone()),
+            _ => None,
+        });
+        let window = match window {
+            Some(window) => window,
+            None => {
+                return Err(invalid(&format!(
+                    "the query must select {WINDOW_FUNCTION}(<interval>, {time_index})"
+                )))
+            }
+        };
+
+        Ok(Self {
+            info,
+            source,
+            time_index,
+            query,
+            window,
+            pending: Mutex::new(PendingState {
+                range: pending,
+                writing: HashMap::new(),
+                persisted: pending,
+            }),
+        })
+    }
+
+    /// Returns the window containing the timestamp `millis`.
+    fn window_of(&self, millis: i64) -> Function {
+        let timestamp = Function {
+            name: ObjectName(vec![Ident::new("to_timestamp_millis")]),
+            args: vec![FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(
+                SqlValue::Number(millis.to_string(), false),
+            )))],
+            ..self.window.clone()
+        };
+        let mut window = self.window.clone();
+        window.args[1] = FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Function(timestamp)));
+        window
+    }
+
+    /// Builds the query aggregating the windows overlapping `range`.
+    ///
+    /// The windows can't be pushed down to the scan of the source table, so the time index
+    /// is also filtered by the bounds of these windows.
+    fn build_query(&self, (start, end): PendingRange) -> SpQuery {
+        let window = || Expr::Function(self.window.clone());
+        let time_index = || Expr::Identifier(Ident::with_quote('"', &self.time_index));
+        let first_window = || Expr::Function(self.window_of(start));
+        let last_window = || Expr::Function(self.window_of(end));
+        let mut filters = vec![
+            binary_op(window(), BinaryOperator::GtEq, first_window()),
+            binary_op(window(), BinaryOperator::LtEq, last_window()),
+            binary_op(time_index(), BinaryOperator::GtEq, first_window()),
+        ];
+        // The end of the last window is only known if the interval is an interval literal,
+        // as strings can't be added to timestamps.
+        if let Some(FunctionArg::Unnamed(FunctionArgExpr::Expr(interval @ Expr::Interval { .. }))) =
+            self.window.args.first()
+        {
+            let window_end = binary_op(last_window(), BinaryOperator::Plus, interval.clone());
+            filters.push(binary_op(time_index(), BinaryOperator::Lt, window_end));
+        }
+        let in_range = filters
+            .into_iter()
+            .reduce(|left, right| binary_op(left, BinaryOperator::And, right))
+            .expect("filters are not empty");
+
+        let mut query = self.query.clone();
+        if let SetExpr::Select(select) = &mut *query.body {
+            select.selection = Some(match select.selection.take() {
+                Some(selection) => Expr::BinaryOp {
+                    left: Box::new(Expr::Nested(Box::new(selection))),
+                    op: BinaryOperator::And,
+                    right: Box::new(in_range),
+                },
+                None => in_range,
+            });
+        }
+        query
+    }
+}
+
+/// Returns the smallest range covering both `range` and `other`.
+fn merge_range(range: Option<PendingRange>, other: PendingRange) -> PendingRange {
+    match range {
+        Some((start, end)) => (start.min(other.0), end.max(other.1)),
+        None => other,
+    }
+}
+
+/// Returns whether `range` covers `other`.
+fn covers(range: Option<PendingRange>, other: PendingRange) -> bool {
+    matches!(range, Some((start, end)) if start <= other.0 && other.1 <= end)
+}
+
+fn binary_op(left: Expr, op: BinaryOperator, right: Expr) -> Expr {
+    Expr::BinaryOp {
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+    }
+}
+
+/// Returns whether `function` is `date_bin(<interval>, <time index>, ...)`.
+fn is_window_of(function: &Function, time_index: &str) -> bool {
+    if !function
+        .name
+        .to_string()
+        .eq_ignore_ascii_case(WINDOW_FUNCTION)
+    {
+        return false;
+    }
+    match function.args.get(1) {
+        Some(FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Identifier(ident)))) => {
+            // Unquoted identifiers are normalized to lowercase by the planner.
+            match ident.quote_style {
+                Some(_) => ident.value == time_index,
+                None => ident.value.to_lowercase() == time_index,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Time range written into or deleted from the source table of some flows.
+pub(crate) struct WrittenRange {
+    source: String,
+    range: PendingRange,
+}
+
+pub type FlowManagerRef = Arc<FlowManager>;
+
+pub struct FlowManager {
+    catalog_manager: CatalogManagerRef,
+    query_engine: QueryEngineRef,
+    flows_table: FlowsTable,
+    /// Flows by their full names.
+    flows: RwLock<HashMap<String, Arc<Flow>>>,
+    /// Id of the next write into the source tables.
+    next_write_id: AtomicU64,
+}
+
+impl FlowManager {
+    pub fn new(
+        table_engine: TableEngineRef,
+        catalog_manager: CatalogManagerRef,
+        query_engine: QueryEngineRef,
+    ) -> Self {
+        Self {
+            flows_table: FlowsTable::new(
+                table_engine,
+                catalog_manager.clone(),
+                query_engine.clone(),
+            ),
+            catalog_manager,
+            query_engine,
+            flows: RwLock::new(HashMap::new()),
+            next_write_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Loads the flows from the flows table and starts running them in background.
+    pub async fn start(self: &Arc<Self>) -> Result<()> {
+        for row in self.flows_table.load().await? {
+            let name = row.info.full_name();
+            match Flow::try_new(row.info, row.pending, &self.catalog_manager).await {
+                Ok(flow) => {
+                    self.write_flows().insert(name, Arc::new(flow));
+                }
+                Err(e) => error!(e; "Failed to load flow {}", name),
+            }
+        }
+
+        let manager = Arc::downgrade(self);
+        common_runtime::spawn_bg(async move {
+            let mut interval = tokio::time::interval(FLOW_INTERVAL);
+            loop {
+                interval.tick().await;
+                match manager.upgrade() {
+                    Some(manager) => manager.run_flows().await,
+                    None => break,
+                }
+            }
+        });
+        Ok(())
+    }
+
+    pub(crate) async fn create_flow(&self, req: CreateFlowRequest) -> Result<Output> {
+        let info = FlowInfo {
+            catalog_name: req.catalog_name,
+            schema_name: req.schema_name,
+            flow_name: req.flow_name,
+            sink_schema: req.sink_schema,
+            sink_table: req.sink_table,
+            definition: req.definition,
+        };
+        let name = info.full_name();
+        if self.read_flows().contains_key(&name) {
+            return if req.create_if_not_exists {
+                Ok(Output::AffectedRows(0))
+            } else {
+                FlowExistsSnafu { name }.fail()
+            };
+        }
+        ensure!(
+            req.sink_catalog == info.catalog_name,
+            InvalidFlowSnafu {
+                name: &name,
+                msg: "the sink table must be in the catalog of the flow",
+            }
+        );
+
+        let flow = Flow::try_new(info, None, &self.catalog_manager).await?;
+        self.validate_sink(&flow).await?;
+
+        self.flows_table.save(&flow.info, None).await?;
+        self.write_flows().insert(name.clone(), Arc::new(flow));
+        info!("Successfully created flow: {}", name);
+        Ok(Output::AffectedRows(0))
+    }
+
+    /// Checks that all output columns of the flow exist in its sink table.
+    async fn validate_sink(&self, flow: &Flow) -> Result<()> {
+        let sink = self.sink_table(flow).await?;
+        let query_ctx = Arc::new(QueryContext::with(
+            &flow.info.catalog_name,
+            &flow.info.schema_name,
+        ));
+        let query = Query::try_from(flow.query.clone()).context(ParseSqlSnafu)?;
+        let stmt = Statement::Query(Box::new(query));
+        let plan = self
+            .query_engine
+            .statement_to_plan(QueryStatement::Sql(stmt), query_ctx)
+            .await
+            .context(ExecuteSqlSnafu)?;
+        let schema = plan.schema().context(ExecuteSqlSnafu)?;
+
+        let sink_schema = sink.schema();
+        for column in schema.column_schemas() {
+            ensure!(
+                sink_schema.column_schema_by_name(&column.name).is_some(),
+                InvalidFlowSnafu {
+                    name: flow.info.full_name(),
+                    msg: format!(
+                        "column {} not found in sink table {}",
+                        column.name,
+                        flow.info.sink_full_name()
+                    ),
+                }
+            );
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn drop_flow(&self, req: DropFlowRequest) -> Result<Output> {
+        let name = format_full_table_name(&req.catalog_name, &req.schema_name, &req.flow_name);
+        let flow = self.write_flows().remove(&name);
+        match flow {
+            Some(flow) => {
+                // Holds the lock so a concurrent run won't save the flow again.
+                let _pending = flow.pending.lock().await;
+                self.flows_table.delete(&flow.info).await?;
+                info!("Successfully dropped flow: {}", name);
+                Ok(Output::AffectedRows(1))
+            }
+            None => {
+                ensure!(req.drop_if_exists, FlowNotFoundSnafu { name });
+                Ok(Output::AffectedRows(0))
+            }
+        }
+    }
+
+    /// Locks the flows for reading.
+    ///
+    /// Every critical section of the flows is a single operation on the map, which leaves
+    /// the map consistent even if it panics, so poisoned locks are recovered.
+    fn read_flows(&self) -> RwLockReadGuard<'_, HashMap<String, Arc<Flow>>> {
+        self.flows.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the flows for writing, recovering poisoned locks like [FlowManager::read_flows].
+    fn write_flows(&self) -> RwLockWriteGuard<'_, HashMap<String, Arc<Flow>>> {
+        self.flows.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_registered(&self, flow: &Arc<Flow>) -> bool {
+        self.read_flows()
+            .get(&flow.info.full_name())
+            .map(|registered| Arc::ptr_eq(registered, flow))
+            .unwrap_or(false)
+    }
+
+    fn flows_of_source(&self, source: &str) -> Vec<Arc<Flow>> {
+        self.read_flows()
+            .values()
+            .filter(|flow| flow.source == source)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns whether any flow reads from the table `source`.
+    pub(crate) fn has_flows_of(&self, source: &str) -> bool {
+        self.read_flows().values().any(|flow| flow.source == source)
+    }
+
+    /// Returns the time range written by `request` if any flow reads from its table.
+    ///
+    /// Must be called before the request is inserted, as inserting consumes the request.
+    pub(crate) fn written_range(&self, request: &InsertRequest) -> Option<WrittenRange> {
+        let source = format_full_table_name(
+            &request.catalog_name,
+            &request.schema_name,
+            &request.table_name,
+        );
+        let flows = self.flows_of_source(&source);
+        let flow = flows.first()?;
+
+        let range = match request.columns_values.get(&flow.time_index) {
+            Some(vector) => time_range_of(vector)?,
+            // The time index is filled by its default value.
+            None => {
+                let now = util::current_time_millis();
+                (now, now)
+            }
+        };
+        Some(WrittenRange { source, range })
+    }
+
+    /// Returns the time range deleted by `request` from the table `table_ref` if any flow
+    /// reads from the table.
+    pub(crate) fn deleted_range(
+        &self,
+        table_ref: &TableReference,
+        request: &DeleteRequest,
+    ) -> Option<WrittenRange> {
+        let source = table_ref.to_string();
+        let flows = self.flows_of_source(&source);
+        let flow = flows.first()?;
+
+        let range = time_range_of(request.key_column_values.get(&flow.time_index)?)?;
+        Some(WrittenRange { source, range })
+    }
+
+    /// Runs `write`, which writes the `written` range of the source table of some flows, and
+    /// marks the range as pending in these flows.
+    ///
+    /// The range is persisted before running `write`, and kept pending until `write` is done,
+    /// so the flows never miss the written rows.
+    pub(crate) async fn write<T>(
+        &self,
+        written: Option<WrittenRange>,
+        write: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let Some(written) = written else { return write.await; };
+
+        let id = self.next_write_id.fetch_add(1, Ordering::Relaxed);
+        let flows = self.flows_of_source(&written.source);
+        let result = match self.mark_pending(&flows, id, written.range).await {
+            Ok(()) => write.await,
+            Err(e) => Err(e),
+        };
+        for flow in flows {
+            let _ = flow.pending.lock().await.writing.remove(&id);
+        }
+        result
+    }
+
+    /// Marks `range` as pending in `flows` for the write `id`, persists it if the persisted
+    /// range doesn't cover it.
+    async fn mark_pending(&self, flows: &[Arc<Flow>], id: u64, range: PendingRange) -> Result<()> {
+        for flow in flows {
+            let mut pending = flow.pending.lock().await;
+            pending.range = Some(merge_range(pending.range, range));
+            let _ = pending.writing.insert(id, range);
+            if covers(pending.persisted, range) || !self.is_registered(flow) {
+                continue;
+            }
+
+            // The persisted range also covers the range being aggregated, which has been
+            // taken from the pending state.
+            let persisted = merge_range(pending.persisted, range);
+            self.flows_table.save(&flow.info, Some(persisted)).await?;
+            pending.persisted = Some(persisted);
+        }
+        Ok(())
+    }
+
+    /// Runs all flows once.
+    pub async fn run_flows(&self) {
+        let flows = self.read_flows().values().cloned().collect::<Vec<_>>();
+        for flow in flows {
+            if let Err(e) = self.run_flow(&flow).await {
+                error!(e; "Failed to run flow {}", flow.info.full_name());
+            }
+        }
+    }
+
+    /// Aggregates the windows overlapping the pending range of `flow` into its sink table,
+    /// then persists the range left pending.
+    async fn run_flow(&self, flow: &Arc<Flow>) -> Result<()> {
+        // Ranges marked while running are left pending, and aggregated in the next run.
+        let range = match flow.pending.lock().await.range.take() {
+            Some(range) => range,
+            None => return Ok(()),
+        };
+        let result = self.aggregate(flow, range).await;
+
+        let mut guard = flow.pending.lock().await;
+        let pending = &mut *guard;
+        if result.is_err() {
+            pending.range = Some(merge_range(pending.range, range));
+        }
+        for written in pending.writing.values() {
+            pending.range = Some(merge_range(pending.range, *written));
+        }
+        if pending.range == pending.persisted || !self.is_registered(flow) {
+            return result;
+        }
+        let saved = self.flows_table.save(&flow.info, pending.range).await;
+        if saved.is_ok() {
+            pending.persisted = pending.range;
+        }
+        result.and(saved)
+    }
+
+    async fn aggregate(&self, flow: &Flow, range: PendingRange) -> Result<()> {
+        let query_ctx = Arc::new(QueryContext::with(
+            &flow.info.catalog_name,
+            &flow.info.schema_name,
+        ));
+        let query = Query::try_from(flow.build_query(range)).context(ParseSqlSnafu)?;
+        let plan = self
+            .query_engine
+            .statement_to_plan(
+                QueryStatement::Sql(Statement::Query(Box::new(query))),
+                query_ctx,
+            )
+            .await
+            .context(ExecuteSqlSnafu)?;
+        let batches = match self
+            .query_engine
+            .execute(&plan)
+            .await
+            .context(ExecuteSqlSnafu)?
+        {
+            Output::RecordBatches(batches) => batches.take(),
+            Output::Stream(stream) => record_util::collect(stream)
+                .await
+                .context(CollectRecordsSnafu)?,
+            Output::AffectedRows(_) => unreachable!("a query always outputs record batches"),
+        };
+
+        let sink = self.sink_table(flow).await?;
+        for batch in batches {
+            insert_batch(&sink, &flow.info, batch).await?;
+        }
+        Ok(())
+    }
+
+    async fn sink_table(&self, flow: &Flow) -> Result<TableRef> {
+        self.catalog_manager
+            .table(
+                &flow.info.catalog_name,
+                &flow.info.sink_schema,
+                &flow.info.sink_table,
+            )
+            .await
+            .context(CatalogSnafu)?
+            .with_context(|| TableNotFoundSnafu {
+                table_name: flow.info.sink_full_name(),
+            })
+    }
+}
+
+/// Returns the range of the timestamps in `vector`, in milliseconds.
+fn time_range_of(vector: &VectorRef) -> Option<PendingRange> {
+    let mut range = None;
+    for i in 0..vector.len() {
+        if let Value::Timestamp(ts) = vector.get(i) {
+            let start = ts.convert_to(TimeUnit::Millisecond)?.value();
+            let end = ts.convert_to_ceil(TimeUnit::Millisecond)?.value();
+            range = Some(merge_range(range, (start, end)));
+        }
+    }
+    range
+}
+
+/// Inserts `batch` into the sink table. Columns are matched with the sink table's by name
+/// and cast into the sink table's column types.
+async fn insert_batch(sink: &TableRef, info: &FlowInfo, batch: RecordBatch) -> Result<usize> {
+    if batch.num_rows() == 0 {
+        return Ok(0);
+    }
+
+    let sink_schema = sink.schema();
+    let mut columns_values = HashMap::with_capacity(batch.num_columns());
+    for (column, vector) in batch.schema.column_schemas().iter().zip(batch.columns()) {
+        let column_schema = sink_schema
+            .column_schema_by_name(&column.name)
+            .with_context(|| ColumnNotFoundSnafu {
+                column_name: &column.name,
+                table_name: &info.sink_table,
+            })?;
+        let vector = if vector.data_type() == column_schema.data_type {
+            vector.clone()
+        } else {
+            vector
+                .cast(&column_schema.data_type)
+                .context(VectorComputationSnafu)?
+        };
+        let _ = columns_values.insert(column.name.clone(), vector);
+    }
+
+    let insert_request = InsertRequest {
+        catalog_name: info.catalog_name.clone(),
+        schema_name: info.sink_schema.clone(),
+        table_name: info.sink_table.clone(),
+        columns_values,
+        region_number: 0,
+    };
+    sink.insert(insert_request)
+        .await
+        .with_context(|_| InsertSnafu {
+            table_name: info.sink_full_name(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_flow(definition: &str) -> Flow {
+        let mut stmts = ParserContext::create_with_dialect(definition, &GenericDialect {}).unwrap();
+        let query = match stmts.pop() {
+            Some(Statement::Query(query)) => query.inner,
+            _ => unreachable!(),
+        };
+        let window = match &*query.body {
+            SetExpr::Select(select) => match &select.projection[0] {
+                SelectItem::ExprWithAlias {
+                    expr: Expr::Function(function),
+                    ..
+                } => function.clone(),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        Flow {
+            info: FlowInfo {
+                catalog_name: "greptime".to_string(),
+                schema_name: "public".to_string(),
+                flow_name: "demo_flow".to_string(),
+                sink_schema: "public".to_string(),
+                sink_table: "demo_10s".to_string(),
+                definition: definition.to_string(),
+            },
+            source: "greptime.public.demo".to_string(),
+            time_index: "ts".to_string(),
+            query,
+            window,
+            pending: Mutex::new(PendingState::default()),
+        }
+    }
+
+    #[test]
+    fn test_build_query() {
+        let flow = new_flow(
+            "SELECT date_bin(INTERVAL '10 seconds', ts) AS ts, avg(cpu) AS cpu FROM demo GROUP BY 1",
+        );
+        let query = flow.build_query((1000, 12000)).to_string();
+        assert!(
+            query.contains(r#""ts" >= date_bin(INTERVAL '10 seconds', to_timestamp_millis(1000))"#)
+        );
+        assert!(query.contains(
+            r#""ts" < date_bin(INTERVAL '10 seconds', to_timestamp_millis(12000)) + INTERVAL '10 seconds'"#
+        ));
+
+        // The end of the last window is unknown.
+        let flow = new_flow(
+            "SELECT date_bin('10 seconds', ts) AS ts, avg(cpu) AS cpu FROM demo GROUP BY 1",
+        );
+        let query = flow.build_query((1000, 12000)).to_string();
+        assert!(query.contains(r#""ts" >= date_bin('10 seconds', to_timestamp_millis(1000))"#));
+        assert!(!query.contains(r#""ts" <"#));
+    }
+}
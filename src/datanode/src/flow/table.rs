@@ -0,0 +1,349 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Flows table, which persists the definitions of flows and their pending time ranges.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use catalog::{CatalogManagerRef, RegisterTableRequest};
+use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, FLOWS_TABLE_ID};
+use common_catalog::format_full_table_name;
+use common_query::Output;
+use common_recordbatch::util as record_util;
+use common_telemetry::logging;
+use common_time::util;
+use datatypes::prelude::{ConcreteDataType, Value};
+use datatypes::schema::{ColumnSchema, RawSchema};
+use datatypes::vectors::{Int64Vector, StringVector, TimestampMillisecondVector, VectorRef};
+use query::parser::QueryLanguageParser;
+use query::QueryEngineRef;
+use session::context::QueryContext;
+use snafu::{ensure, ResultExt};
+use table::engine::{EngineContext, TableEngineRef};
+use table::requests::{CreateTableRequest, DeleteRequest, InsertRequest, TableOptions};
+use table::TableRef;
+
+use crate::error::{
+    CatalogSnafu, CollectRecordsSnafu, CreateTableSnafu, DeleteSnafu, ExecuteSqlSnafu, InsertSnafu,
+    InsertSystemCatalogSnafu, Result, TableExistsSnafu,
+};
+use crate::flow::{FlowInfo, PendingRange};
+
+pub const FLOWS_TABLE_NAME: &str = "flows";
+
+/// A flow loaded from the flows table.
+pub(crate) struct FlowRow {
+    pub(crate) info: FlowInfo,
+    pub(crate) pending: Option<PendingRange>,
+}
+
+pub(crate) struct FlowsTable {
+    table_engine: TableEngineRef,
+    catalog_manager: CatalogManagerRef,
+    query_engine: QueryEngineRef,
+}
+
+impl FlowsTable {
+    pub(crate) fn new(
+        table_engine: TableEngineRef,
+        catalog_manager: CatalogManagerRef,
+        query_engine: QueryEngineRef,
+    ) -> Self {
+        Self {
+            table_engine,
+            catalog_manager,
+            query_engine,
+        }
+    }
+
+    /// Returns the flows table if it has been created.
+    async fn table(&self) -> Result<Option<TableRef>> {
+        let table = self
+            .catalog_manager
+            .table(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, FLOWS_TABLE_NAME)
+            .await
+            .context(CatalogSnafu)?;
+        match table {
+            Some(table) => {
+                ensure!(
+                    table.table_info().ident.table_id == FLOWS_TABLE_ID,
+                    TableExistsSnafu {
+                        name: full_table_name()
+                    }
+                );
+                Ok(Some(table))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the flows table, creates it if absent.
+    ///
+    /// Unlike the scripts table, the flows table is created on the first `CREATE FLOW`,
+    /// so databases without flows never see it.
+    async fn get_or_create_table(&self) -> Result<TableRef> {
+        if let Some(table) = self.table().await? {
+            return Ok(table);
+        }
+
+        let request = CreateTableRequest {
+            id: FLOWS_TABLE_ID,
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: FLOWS_TABLE_NAME.to_string(),
+            desc: Some("Flows table".to_string()),
+            schema: build_flows_schema(),
+            region_numbers: vec![0],
+            // catalog, schema and name as primary key
+            primary_key_indices: vec![0, 1, 2],
+            create_if_not_exists: true,
+            table_options: TableOptions::default(),
+        };
+        let table = self
+            .table_engine
+            .create_table(&EngineContext::default(), request)
+            .await
+            .context(CreateTableSnafu {
+                table_name: FLOWS_TABLE_NAME,
+            })?;
+        let register_req = RegisterTableRequest {
+            catalog: DEFAULT_CATALOG_NAME.to_string(),
+            schema: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: FLOWS_TABLE_NAME.to_string(),
+            table_id: FLOWS_TABLE_ID,
+            table: table.clone(),
+        };
+        self.catalog_manager
+            .register_table(register_req)
+            .await
+            .context(InsertSystemCatalogSnafu)?;
+        logging::info!("Created flows table: {}", full_table_name());
+
+        Ok(table)
+    }
+
+    /// Saves the flow and its pending range, overwriting the previous row of the flow.
+    pub(crate) async fn save(&self, info: &FlowInfo, pending: Option<PendingRange>) -> Result<()> {
+        let table = self.get_or_create_table().await?;
+
+        let mut columns_values: HashMap<String, VectorRef> = HashMap::with_capacity(10);
+        columns_values.insert(
+            "catalog".to_string(),
+            Arc::new(StringVector::from(vec![info.catalog_name.as_str()])) as _,
+        );
+        columns_values.insert(
+            "schema".to_string(),
+            Arc::new(StringVector::from(vec![info.schema_name.as_str()])) as _,
+        );
+        columns_values.insert(
+            "name".to_string(),
+            Arc::new(StringVector::from(vec![info.flow_name.as_str()])) as _,
+        );
+        // Timestamp in key part is intentionally left to 0
+        columns_values.insert(
+            "timestamp".to_string(),
+            Arc::new(TimestampMillisecondVector::from_slice([0])) as _,
+        );
+        columns_values.insert(
+            "sink_schema".to_string(),
+            Arc::new(StringVector::from(vec![info.sink_schema.as_str()])) as _,
+        );
+        columns_values.insert(
+            "sink_table".to_string(),
+            Arc::new(StringVector::from(vec![info.sink_table.as_str()])) as _,
+        );
+        columns_values.insert(
+            "definition".to_string(),
+            Arc::new(StringVector::from(vec![info.definition.as_str()])) as _,
+        );
+        columns_values.insert(
+            "pending_start".to_string(),
+            Arc::new(Int64Vector::from(vec![pending.map(|(start, _)| start)])) as _,
+        );
+        columns_values.insert(
+            "pending_end".to_string(),
+            Arc::new(Int64Vector::from(vec![pending.map(|(_, end)| end)])) as _,
+        );
+        columns_values.insert(
+            "gmt_modified".to_string(),
+            Arc::new(TimestampMillisecondVector::from_slice([
+                util::current_time_millis(),
+            ])) as _,
+        );
+
+        let _ = table
+            .insert(InsertRequest {
+                catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+                schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+                table_name: FLOWS_TABLE_NAME.to_string(),
+                columns_values,
+                region_number: 0,
+            })
+            .await
+            .context(InsertSnafu {
+                table_name: full_table_name(),
+            })?;
+        Ok(())
+    }
+
+    /// Deletes the row of the flow.
+    pub(crate) async fn delete(&self, info: &FlowInfo) -> Result<()> {
+        let Some(table) = self.table().await? else { return Ok(()); };
+
+        let mut key_column_values: HashMap<String, VectorRef> = HashMap::with_capacity(4);
+        key_column_values.insert(
+            "catalog".to_string(),
+            Arc::new(StringVector::from(vec![info.catalog_name.as_str()])) as _,
+        );
+        key_column_values.insert(
+            "schema".to_string(),
+            Arc::new(StringVector::from(vec![info.schema_name.as_str()])) as _,
+        );
+        key_column_values.insert(
+            "name".to_string(),
+            Arc::new(StringVector::from(vec![info.flow_name.as_str()])) as _,
+        );
+        key_column_values.insert(
+            "timestamp".to_string(),
+            Arc::new(TimestampMillisecondVector::from_slice([0])) as _,
+        );
+
+        let _ = table
+            .delete(DeleteRequest { key_column_values })
+            .await
+            .context(DeleteSnafu {
+                table_name: full_table_name(),
+            })?;
+        Ok(())
+    }
+
+    /// Loads all flows from the flows table.
+    pub(crate) async fn load(&self) -> Result<Vec<FlowRow>> {
+        if self.table().await?.is_none() {
+            return Ok(vec![]);
+        }
+
+        let sql = format!(
+            "select catalog, schema, name, sink_schema, sink_table, definition, pending_start, pending_end from {}",
+            full_table_name()
+        );
+        let stmt = QueryLanguageParser::parse_sql(&sql).context(ExecuteSqlSnafu)?;
+        let plan = self
+            .query_engine
+            .statement_to_plan(stmt, Arc::new(QueryContext::new()))
+            .await
+            .context(ExecuteSqlSnafu)?;
+        let batches = match self
+            .query_engine
+            .execute(&plan)
+            .await
+            .context(ExecuteSqlSnafu)?
+        {
+            Output::RecordBatches(batches) => batches.take(),
+            Output::Stream(stream) => record_util::collect(stream)
+                .await
+                .context(CollectRecordsSnafu)?,
+            Output::AffectedRows(_) => unreachable!("a query always outputs record batches"),
+        };
+
+        let mut rows = Vec::new();
+        for batch in batches {
+            for i in 0..batch.num_rows() {
+                let string_at = |column: usize| match batch.column(column).get(i) {
+                    Value::String(s) => s.as_utf8().to_string(),
+                    _ => String::new(),
+                };
+                let int_at = |column: usize| match batch.column(column).get(i) {
+                    Value::Int64(v) => Some(v),
+                    _ => None,
+                };
+
+                let info = FlowInfo {
+                    catalog_name: string_at(0),
+                    schema_name: string_at(1),
+                    flow_name: string_at(2),
+                    sink_schema: string_at(3),
+                    sink_table: string_at(4),
+                    definition: string_at(5),
+                };
+                let pending = int_at(6).zip(int_at(7));
+                rows.push(FlowRow { info, pending });
+            }
+        }
+        Ok(rows)
+    }
+}
+
+fn full_table_name() -> String {
+    format_full_table_name(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, FLOWS_TABLE_NAME)
+}
+
+/// Build flows table
+fn build_flows_schema() -> RawSchema {
+    let cols = vec![
+        ColumnSchema::new(
+            "catalog".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "schema".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "name".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "timestamp".to_string(),
+            ConcreteDataType::timestamp_millisecond_datatype(),
+            false,
+        )
+        .with_time_index(true),
+        ColumnSchema::new(
+            "sink_schema".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "sink_table".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "definition".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "pending_start".to_string(),
+            ConcreteDataType::int64_datatype(),
+            true,
+        ),
+        ColumnSchema::new(
+            "pending_end".to_string(),
+            ConcreteDataType::int64_datatype(),
+            true,
+        ),
+        ColumnSchema::new(
+            "gmt_modified".to_string(),
+            ConcreteDataType::timestamp_millisecond_datatype(),
+            false,
+        ),
+    ];
+
+    RawSchema::new(cols)
+}
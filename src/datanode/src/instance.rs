@@ -104,7 +104,7 @@ impl Instance {
                 object_store.clone(),
                 compaction_scheduler,
             ),
-            object_store,
+            object_store.clone(),
         ));
 
         // create remote catalog manager
@@ -201,6 +201,7 @@ impl Instance {
                 query_engine.clone(),
                 table_engine,
                 procedure_manager,
+                object_store,
             ),
             catalog_manager,
             script_executor,
@@ -214,6 +215,7 @@ impl Instance {
             .start()
             .await
             .context(NewCatalogSnafu)?;
+        self.sql_handler.flow_manager().start().await?;
         if let Some(task) = &self.heartbeat_task {
             task.start().await?;
         }
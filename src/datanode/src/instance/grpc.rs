@@ -24,7 +24,7 @@ use servers::query_handler::grpc::GrpcQueryHandler;
 use session::context::QueryContextRef;
 use snafu::prelude::*;
 use substrait::{DFLogicalSubstraitConvertor, SubstraitPlan};
-use table::requests::CreateDatabaseRequest;
+use table::requests::{CreateDatabaseRequest, TableOptions};
 
 use crate::error::{self, DecodeLogicalPlanSnafu, ExecuteSqlSnafu, Result};
 use crate::instance::Instance;
@@ -38,6 +38,7 @@ impl Instance {
         let req = CreateDatabaseRequest {
             db_name: expr.database_name,
             create_if_not_exists: expr.create_if_not_exists,
+            options: TableOptions::default(),
         };
         self.sql_handler.create_database(req, query_ctx).await
     }
@@ -82,10 +83,16 @@ impl Instance {
         let request = common_grpc_expr::insert::to_table_insert_request(catalog, schema, request)
             .context(error::InsertDataSnafu)?;
 
-        let affected_rows = table
-            .insert(request)
-            .await
-            .context(error::InsertSnafu { table_name })?;
+        let flow_manager = self.sql_handler.flow_manager();
+        let written = flow_manager.written_range(&request);
+        let affected_rows = flow_manager
+            .write(written, async {
+                table
+                    .insert(request)
+                    .await
+                    .context(error::InsertSnafu { table_name })
+            })
+            .await?;
         Ok(Output::AffectedRows(affected_rows))
     }
 
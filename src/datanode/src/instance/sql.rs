@@ -26,15 +26,21 @@ use query::parser::{PromQuery, QueryLanguageParser, QueryStatement};
 use servers::error as server_error;
 use servers::promql::PromqlHandler;
 use servers::query_handler::sql::SqlQueryHandler;
-use session::context::{QueryContext, QueryContextRef};
+use session::context::QueryContextRef;
 use snafu::prelude::*;
 use sql::ast::ObjectName;
+use sql::statements::copy::{CopyDirection, Format};
 use sql::statements::statement::Statement;
 use sql::statements::tql::Tql;
 use table::engine::TableReference;
-use table::requests::{CopyTableRequest, CreateDatabaseRequest, DropTableRequest};
+use table::requests::{
+    CompactTableRequest, CopyDirection as RequestCopyDirection, CopyFormat, CopyTableRequest,
+    CreateViewRequest, DropDatabaseRequest, DropTableRequest, DropViewRequest, FlushTableRequest,
+    TruncateTableRequest,
+};
 
 use crate::error::{self, BumpTableIdSnafu, ExecuteSqlSnafu, Result, TableIdProviderNotFoundSnafu};
+use crate::flow::{CreateFlowRequest, DropFlowRequest};
 use crate::instance::Instance;
 use crate::metric;
 use crate::sql::insert::InsertRequests;
@@ -93,10 +99,9 @@ impl Instance {
                 self.sql_handler.execute(request, query_ctx).await
             }
             QueryStatement::Sql(Statement::CreateDatabase(create_database)) => {
-                let request = CreateDatabaseRequest {
-                    db_name: create_database.name.to_string(),
-                    create_if_not_exists: create_database.if_not_exists,
-                };
+                let request = self
+                    .sql_handler
+                    .create_database_to_request(create_database)?;
 
                 info!("Creating a new database: {}", request.db_name);
 
@@ -105,6 +110,19 @@ impl Instance {
                     .await
             }
 
+            QueryStatement::Sql(Statement::DropDatabase(drop_database)) => {
+                let request = DropDatabaseRequest {
+                    db_name: drop_database.name().to_string(),
+                    drop_if_exists: drop_database.if_exists(),
+                };
+
+                info!("Dropping database: {}", request.db_name);
+
+                self.sql_handler
+                    .execute(SqlRequest::DropDatabase(request), query_ctx)
+                    .await
+            }
+
             QueryStatement::Sql(Statement::CreateTable(create_table)) => {
                 let table_id = self
                     .table_id_provider
@@ -150,6 +168,75 @@ impl Instance {
                     .execute(SqlRequest::DropTable(req), query_ctx)
                     .await
             }
+            QueryStatement::Sql(Statement::CreateView(create_view)) => {
+                let view_id = self
+                    .table_id_provider
+                    .as_ref()
+                    .context(TableIdProviderNotFoundSnafu)?
+                    .next_table_id()
+                    .await
+                    .context(BumpTableIdSnafu)?;
+                let (catalog_name, schema_name, view_name) =
+                    table_idents_to_full_name(&create_view.name, query_ctx.clone())?;
+                let req = CreateViewRequest {
+                    id: view_id,
+                    catalog_name,
+                    schema_name,
+                    view_name,
+                    definition: create_view.query.inner.to_string(),
+                    or_replace: create_view.or_replace,
+                };
+                info!("Creating view: {}, view id = {}", req.view_name, view_id);
+
+                self.sql_handler
+                    .execute(SqlRequest::CreateView(req), query_ctx)
+                    .await
+            }
+            QueryStatement::Sql(Statement::DropView(drop_view)) => {
+                let (catalog_name, schema_name, view_name) =
+                    table_idents_to_full_name(drop_view.name(), query_ctx.clone())?;
+                let req = DropViewRequest {
+                    catalog_name,
+                    schema_name,
+                    view_name,
+                    drop_if_exists: drop_view.if_exists(),
+                };
+                self.sql_handler
+                    .execute(SqlRequest::DropView(req), query_ctx)
+                    .await
+            }
+            QueryStatement::Sql(Statement::CreateFlow(create_flow)) => {
+                let (catalog_name, schema_name, flow_name) =
+                    table_idents_to_full_name(&create_flow.name, query_ctx.clone())?;
+                let (sink_catalog, sink_schema, sink_table) =
+                    table_idents_to_full_name(&create_flow.sink_table, query_ctx.clone())?;
+                let req = CreateFlowRequest {
+                    catalog_name,
+                    schema_name,
+                    flow_name,
+                    sink_catalog,
+                    sink_schema,
+                    sink_table,
+                    definition: create_flow.query.inner.to_string(),
+                    create_if_not_exists: create_flow.if_not_exists,
+                };
+                self.sql_handler
+                    .execute(SqlRequest::CreateFlow(req), query_ctx)
+                    .await
+            }
+            QueryStatement::Sql(Statement::DropFlow(drop_flow)) => {
+                let (catalog_name, schema_name, flow_name) =
+                    table_idents_to_full_name(drop_flow.name(), query_ctx.clone())?;
+                let req = DropFlowRequest {
+                    catalog_name,
+                    schema_name,
+                    flow_name,
+                    drop_if_exists: drop_flow.if_exists(),
+                };
+                self.sql_handler
+                    .execute(SqlRequest::DropFlow(req), query_ctx)
+                    .await
+            }
             QueryStatement::Sql(Statement::ShowDatabases(show_databases)) => {
                 self.sql_handler
                     .execute(SqlRequest::ShowDatabases(show_databases), query_ctx)
@@ -188,12 +275,23 @@ impl Instance {
                 let (catalog_name, schema_name, table_name) =
                     table_idents_to_full_name(copy_table.table_name(), query_ctx.clone())?;
                 let file_name = copy_table.file_name().to_string();
+                let direction = match copy_table.direction() {
+                    CopyDirection::To => RequestCopyDirection::Export,
+                    CopyDirection::From => RequestCopyDirection::Import,
+                };
+                let format = match copy_table.format() {
+                    Format::Parquet => CopyFormat::Parquet,
+                    Format::Csv => CopyFormat::Csv,
+                    Format::Json => CopyFormat::Json,
+                };
 
                 let req = CopyTableRequest {
                     catalog_name,
                     schema_name,
                     table_name,
                     file_name,
+                    direction,
+                    format,
                 };
 
                 self.sql_handler
@@ -201,6 +299,44 @@ impl Instance {
                     .await
             }
             QueryStatement::Sql(Statement::Tql(tql)) => self.execute_tql(tql, query_ctx).await,
+            QueryStatement::Sql(Statement::FlushTable(flush_table)) => {
+                let (catalog_name, schema_name, table_name) =
+                    table_idents_to_full_name(flush_table.table_name(), query_ctx.clone())?;
+                let req = FlushTableRequest {
+                    catalog_name,
+                    schema_name,
+                    table_name,
+                    region_number: flush_table.region_number(),
+                };
+                self.sql_handler
+                    .execute(SqlRequest::FlushTable(req), query_ctx)
+                    .await
+            }
+            QueryStatement::Sql(Statement::CompactTable(compact_table)) => {
+                let (catalog_name, schema_name, table_name) =
+                    table_idents_to_full_name(compact_table.table_name(), query_ctx.clone())?;
+                let req = CompactTableRequest {
+                    catalog_name,
+                    schema_name,
+                    table_name,
+                    region_number: compact_table.region_number(),
+                };
+                self.sql_handler
+                    .execute(SqlRequest::CompactTable(req), query_ctx)
+                    .await
+            }
+            QueryStatement::Sql(Statement::TruncateTable(truncate_table)) => {
+                let (catalog_name, schema_name, table_name) =
+                    table_idents_to_full_name(truncate_table.table_name(), query_ctx.clone())?;
+                let req = TruncateTableRequest {
+                    catalog_name,
+                    schema_name,
+                    table_name,
+                };
+                self.sql_handler
+                    .execute(SqlRequest::TruncateTable(req), query_ctx)
+                    .await
+            }
         }
     }
 
@@ -363,10 +499,14 @@ impl SqlQueryHandler for Instance {
 
 #[async_trait]
 impl PromqlHandler for Instance {
-    async fn do_query(&self, query: &PromQuery) -> server_error::Result<Output> {
+    async fn do_query(
+        &self,
+        query: &PromQuery,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<Output> {
         let _timer = timer!(metric::METRIC_HANDLE_PROMQL_ELAPSED);
 
-        self.execute_promql(query, QueryContext::arc())
+        self.execute_promql(query, query_ctx)
             .await
             .map_err(BoxedError::new)
             .with_context(|_| {
@@ -376,6 +516,41 @@ impl PromqlHandler for Instance {
                 }
             })
     }
+
+    async fn metric_names(&self, query_ctx: QueryContextRef) -> server_error::Result<Vec<String>> {
+        let schema = self
+            .catalog_manager
+            .schema(&query_ctx.current_catalog(), &query_ctx.current_schema())
+            .context(server_error::CatalogSnafu)?;
+        match schema {
+            Some(schema) => schema.table_names().context(server_error::CatalogSnafu),
+            None => Ok(vec![]),
+        }
+    }
+
+    async fn label_names(
+        &self,
+        metric: &str,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<Option<Vec<String>>> {
+        let table = self
+            .catalog_manager
+            .table(
+                &query_ctx.current_catalog(),
+                &query_ctx.current_schema(),
+                metric,
+            )
+            .await
+            .context(server_error::CatalogSnafu)?;
+        Ok(table.map(|table| {
+            table
+                .table_info()
+                .meta
+                .row_key_column_names()
+                .cloned()
+                .collect()
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -16,6 +16,7 @@
 
 pub mod datanode;
 pub mod error;
+pub mod flow;
 mod heartbeat;
 pub mod instance;
 mod metric;
@@ -58,7 +58,7 @@ impl Instance {
                 object_store.clone(),
                 compaction_scheduler,
             ),
-            object_store,
+            object_store.clone(),
         ));
 
         // By default, catalog manager and factory are created in standalone mode
@@ -113,6 +113,7 @@ impl Instance {
                 query_engine.clone(),
                 table_engine,
                 procedure_manager,
+                object_store,
             ),
             catalog_manager,
             script_executor,
@@ -12,14 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use catalog::CatalogManagerRef;
 use common_procedure::ProcedureManagerRef;
 use common_query::Output;
 use common_telemetry::error;
+use object_store::ObjectStore;
 use query::query_engine::QueryEngineRef;
 use query::sql::{describe_table, explain, show_databases, show_tables};
 use session::context::QueryContextRef;
-use snafu::{OptionExt, ResultExt};
+use snafu::{ensure, OptionExt, ResultExt};
 use sql::statements::delete::Delete;
 use sql::statements::describe::DescribeTable;
 use sql::statements::explain::Explain;
@@ -29,28 +32,40 @@ use table::requests::*;
 use table::TableRef;
 
 use crate::error::{self, ExecuteSqlSnafu, GetTableSnafu, Result, TableNotFoundSnafu};
+use crate::flow::{CreateFlowRequest, DropFlowRequest, FlowManager, FlowManagerRef};
 use crate::instance::sql::table_idents_to_full_name;
 
 mod alter;
-mod copy_table;
+mod copy_table_from;
+mod copy_table_to;
 mod create;
 mod delete;
 mod drop_table;
+mod flush_table;
 pub(crate) mod insert;
+mod truncate_table;
 
 #[derive(Debug)]
 pub enum SqlRequest {
     Insert(InsertRequest),
     CreateTable(CreateTableRequest),
     CreateDatabase(CreateDatabaseRequest),
+    DropDatabase(DropDatabaseRequest),
     Alter(AlterTableRequest),
     DropTable(DropTableRequest),
+    CreateView(CreateViewRequest),
+    DropView(DropViewRequest),
+    CreateFlow(CreateFlowRequest),
+    DropFlow(DropFlowRequest),
     ShowDatabases(ShowDatabases),
     ShowTables(ShowTables),
     DescribeTable(DescribeTable),
     Explain(Box<Explain>),
     Delete(Delete),
     CopyTable(CopyTableRequest),
+    FlushTable(FlushTableRequest),
+    CompactTable(CompactTableRequest),
+    TruncateTable(TruncateTableRequest),
 }
 
 // Handler to execute SQL except query
@@ -60,6 +75,9 @@ pub struct SqlHandler {
     query_engine: QueryEngineRef,
     engine_procedure: TableEngineProcedureRef,
     procedure_manager: Option<ProcedureManagerRef>,
+    flow_manager: FlowManagerRef,
+    /// Object store of the datanode, where COPY reads files from and writes files into.
+    object_store: ObjectStore,
 }
 
 impl SqlHandler {
@@ -69,13 +87,21 @@ impl SqlHandler {
         query_engine: QueryEngineRef,
         engine_procedure: TableEngineProcedureRef,
         procedure_manager: Option<ProcedureManagerRef>,
+        object_store: ObjectStore,
     ) -> Self {
+        let flow_manager = Arc::new(FlowManager::new(
+            table_engine.clone(),
+            catalog_manager.clone(),
+            query_engine.clone(),
+        ));
         Self {
             table_engine,
             catalog_manager,
             query_engine,
             engine_procedure,
             procedure_manager,
+            flow_manager,
+            object_store,
         }
     }
 
@@ -88,10 +114,21 @@ impl SqlHandler {
             SqlRequest::Insert(req) => self.insert(req).await,
             SqlRequest::CreateTable(req) => self.create_table(req).await,
             SqlRequest::CreateDatabase(req) => self.create_database(req, query_ctx.clone()).await,
+            SqlRequest::DropDatabase(req) => self.drop_database(req, query_ctx.clone()).await,
             SqlRequest::Alter(req) => self.alter(req).await,
             SqlRequest::DropTable(req) => self.drop_table(req).await,
+            SqlRequest::CreateView(req) => self.create_view(req).await,
+            SqlRequest::DropView(req) => self.drop_view(req).await,
+            SqlRequest::CreateFlow(req) => self.flow_manager.create_flow(req).await,
+            SqlRequest::DropFlow(req) => self.flow_manager.drop_flow(req).await,
             SqlRequest::Delete(req) => self.delete(query_ctx.clone(), req).await,
-            SqlRequest::CopyTable(req) => self.copy_table(req).await,
+            SqlRequest::CopyTable(req) => match req.direction {
+                CopyDirection::Export => self.copy_table_to(req).await,
+                CopyDirection::Import => self.copy_table_from(req).await,
+            },
+            SqlRequest::FlushTable(req) => self.flush_table(req).await,
+            SqlRequest::CompactTable(req) => self.compact_table(req).await,
+            SqlRequest::TruncateTable(req) => self.truncate_table(req).await,
             SqlRequest::ShowDatabases(req) => {
                 show_databases(req, self.catalog_manager.clone()).context(ExecuteSqlSnafu)
             }
@@ -136,6 +173,20 @@ impl SqlHandler {
     pub fn table_engine(&self) -> TableEngineRef {
         self.table_engine.clone()
     }
+
+    pub fn flow_manager(&self) -> &FlowManagerRef {
+        &self.flow_manager
+    }
+}
+
+/// Files of COPY are in the object store of the datanode, so their paths are relative to the
+/// root of the store and mustn't escape from it.
+fn ensure_copy_path(path: &str) -> Result<()> {
+    ensure!(
+        !path.split('/').any(|component| component == ".."),
+        error::InvalidCopyPathSnafu { path }
+    );
+    Ok(())
 }
 
 #[cfg(test)]
@@ -236,7 +287,7 @@ mod tests {
                 object_store.clone(),
                 compaction_scheduler,
             ),
-            object_store,
+            object_store.clone(),
         ));
 
         let catalog_list = Arc::new(
@@ -264,6 +315,7 @@ mod tests {
             query_engine.clone(),
             table_engine,
             None,
+            object_store,
         );
 
         let stmt = match QueryLanguageParser::parse_sql(sql).unwrap() {
@@ -16,11 +16,14 @@ use catalog::RenameTableRequest;
 use common_query::Output;
 use snafu::prelude::*;
 use sql::statements::alter::{AlterTable, AlterTableOperation};
-use sql::statements::column_def_to_schema;
+use sql::statements::{
+    column_def_to_schema, sql_data_type_to_concrete_data_type, sql_default_to_constraint,
+};
 use table::engine::{EngineContext, TableReference};
 use table::requests::{AddColumnRequest, AlterKind, AlterTableRequest};
 
 use crate::error::{self, Result};
+use crate::sql::create::stmt_options_to_map;
 use crate::sql::SqlHandler;
 
 impl SqlHandler {
@@ -93,6 +96,45 @@ impl SqlHandler {
             AlterTableOperation::RenameTable { new_table_name } => AlterKind::RenameTable {
                 new_table_name: new_table_name.clone(),
             },
+            AlterTableOperation::ModifyColumn { name, target_type } => {
+                AlterKind::ModifyColumnType {
+                    column_name: name.value.clone(),
+                    data_type: sql_data_type_to_concrete_data_type(target_type)
+                        .context(error::ParseSqlSnafu)?,
+                }
+            }
+            AlterTableOperation::RenameColumn { name, new_name } => AlterKind::RenameColumn {
+                column_name: name.value.clone(),
+                new_column_name: new_name.value.clone(),
+            },
+            AlterTableOperation::SetColumnDefault { name, default } => {
+                // The default value is parsed according to the type of the column.
+                let table = self.get_table(&table_ref)?;
+                let schema = table.schema();
+                let column_schema = schema.column_schema_by_name(&name.value).context(
+                    error::ColumnNotFoundSnafu {
+                        column_name: &name.value,
+                        table_name: table_ref.to_string(),
+                    },
+                )?;
+                AlterKind::SetColumnDefault {
+                    column_name: name.value.clone(),
+                    default_constraint: Some(
+                        sql_default_to_constraint(&name.value, &column_schema.data_type, default)
+                            .context(error::ParseSqlSnafu)?,
+                    ),
+                }
+            }
+            AlterTableOperation::DropColumnDefault { name } => AlterKind::SetColumnDefault {
+                column_name: name.value.clone(),
+                default_constraint: None,
+            },
+            AlterTableOperation::SetTableOptions { options } => AlterKind::SetTableOptions {
+                options: stmt_options_to_map(options),
+            },
+            AlterTableOperation::UnsetTableOptions { keys } => AlterKind::UnsetTableOptions {
+                keys: keys.iter().map(|key| key.value.clone()).collect(),
+            },
         };
         Ok(AlterTableRequest {
             catalog_name: table_ref.catalog.to_string(),
@@ -178,4 +220,72 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[tokio::test]
+    async fn test_alter_to_request_with_modifying_column() {
+        let handler = create_mock_sql_handler().await;
+        let alter_table = parse_sql("ALTER TABLE my_metric_1 MODIFY COLUMN v BIGINT;");
+        let req = handler
+            .alter_to_request(
+                alter_table,
+                TableReference::full("greptime", "public", "my_metric_1"),
+            )
+            .unwrap();
+
+        match req.alter_kind {
+            AlterKind::ModifyColumnType {
+                column_name,
+                data_type,
+            } => {
+                assert_eq!("v", column_name);
+                assert_eq!(ConcreteDataType::int64_datatype(), data_type);
+            }
+            _ => unreachable!(),
+        }
+
+        let alter_table = parse_sql("ALTER TABLE my_metric_1 ALTER COLUMN v DROP DEFAULT;");
+        let req = handler
+            .alter_to_request(
+                alter_table,
+                TableReference::full("greptime", "public", "my_metric_1"),
+            )
+            .unwrap();
+        assert_matches!(
+            req.alter_kind,
+            AlterKind::SetColumnDefault {
+                default_constraint: None,
+                ..
+            }
+        );
+    }
+    #[tokio::test]
+    async fn test_alter_to_request_with_table_options() {
+        let handler = create_mock_sql_handler().await;
+        let alter_table = parse_sql("ALTER TABLE my_metric_1 SET ttl = '30d';");
+        let req = handler
+            .alter_to_request(
+                alter_table,
+                TableReference::full("greptime", "public", "my_metric_1"),
+            )
+            .unwrap();
+        match req.alter_kind {
+            AlterKind::SetTableOptions { options } => {
+                assert_eq!(1, options.len());
+                assert_eq!("30d", options["ttl"]);
+            }
+            _ => unreachable!(),
+        }
+
+        let alter_table = parse_sql("ALTER TABLE my_metric_1 UNSET ttl;");
+        let req = handler
+            .alter_to_request(
+                alter_table,
+                TableReference::full("greptime", "public", "my_metric_1"),
+            )
+            .unwrap();
+        match req.alter_kind {
+            AlterKind::UnsetTableOptions { keys } => assert_eq!(vec!["ttl"], keys),
+            _ => unreachable!(),
+        }
+    }
 }
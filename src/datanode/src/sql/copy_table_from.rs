@@ -0,0 +1,375 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::io::{BufReader, Read};
+use std::sync::Arc;
+
+use async_compat::CompatExt;
+use common_query::Output;
+use datafusion::parquet::arrow::ParquetRecordBatchStreamBuilder;
+use datatypes::arrow::datatypes::SchemaRef;
+use datatypes::arrow::error::ArrowError;
+use datatypes::arrow::record_batch::RecordBatch;
+use datatypes::arrow::{compute, csv, json};
+use datatypes::data_type::DataType;
+use datatypes::vectors::Helper;
+use futures::TryStreamExt;
+use object_store::ObjectStore;
+use snafu::{ensure, OptionExt, ResultExt};
+use store_api::storage::RegionNumber;
+use table::engine::TableReference;
+use table::requests::{CopyFormat, CopyTableRequest, InsertRequest};
+use table::TableRef;
+use tokio::sync::mpsc;
+use tokio_util::io::SyncIoBridge;
+
+use crate::error::{self, Result};
+use crate::flow::FlowManagerRef;
+use crate::sql::{ensure_copy_path, SqlHandler};
+
+/// Number of rows in each record batch decoded from the files.
+const READ_BATCH_SIZE: usize = 4096;
+/// Max number of records read to infer the schema of CSV and JSON files.
+const INFER_SCHEMA_MAX_RECORDS: usize = 1000;
+
+type ArrowResult<T> = std::result::Result<T, ArrowError>;
+
+impl SqlHandler {
+    pub(crate) async fn copy_table_from(&self, req: CopyTableRequest) -> Result<Output> {
+        let table_ref = TableReference {
+            catalog: &req.catalog_name,
+            schema: &req.schema_name,
+            table: &req.table_name,
+        };
+        let table = self.get_table(&table_ref)?;
+
+        // The datanode doesn't know how rows are partitioned, so only tables with a single
+        // region are supported.
+        let region_number = match table.table_info().meta.region_numbers[..] {
+            [region_number] => region_number,
+            _ => {
+                return error::NotSupportSqlSnafu {
+                    msg: format!(
+                        "COPY FROM into table {} with multiple regions",
+                        req.table_name
+                    ),
+                }
+                .fail()
+            }
+        };
+
+        ensure_copy_path(&req.file_name)?;
+        let paths = list_files(&self.object_store, &req.file_name).await?;
+        ensure!(
+            !paths.is_empty(),
+            error::NoMatchingFileSnafu {
+                path: &req.file_name,
+            }
+        );
+
+        // Like COPY TO, files are imported synchronously. Batches are inserted once they are
+        // decoded, so a file is never read into memory as a whole.
+        let inserter = BatchInserter {
+            table: &table,
+            req: &req,
+            region_number,
+            flow_manager: &self.flow_manager,
+        };
+        let mut rows = 0;
+        for path in paths {
+            rows += match req.format {
+                CopyFormat::Parquet => import_parquet(&self.object_store, &path, &inserter).await?,
+                CopyFormat::Csv | CopyFormat::Json => {
+                    import_text(&self.object_store, &path, req.format, &inserter).await?
+                }
+            };
+        }
+
+        Ok(Output::AffectedRows(rows))
+    }
+}
+
+/// Lists the files to import. The last component of `path` may contain wildcards (`*`
+/// and `?`), in which case all files under its parent directory whose names match the
+/// pattern are returned in lexicographic order.
+async fn list_files(object_store: &ObjectStore, path: &str) -> Result<Vec<String>> {
+    let (dir, pattern) = match path.rfind('/') {
+        Some(idx) => (&path[..=idx], &path[idx + 1..]),
+        None => ("/", path),
+    };
+    if !pattern.contains(['*', '?']) {
+        return Ok(vec![path.to_string()]);
+    }
+
+    let lister = object_store
+        .object(dir)
+        .list()
+        .await
+        .context(error::ListObjectsSnafu { path: dir })?;
+    let mut paths = lister
+        .try_filter_map(|object| async move {
+            let name = object.name();
+            // directories are ended with '/'
+            if !name.ends_with('/') && wildcard_match(pattern, name) {
+                Ok(Some(format!("{}{}", dir, name)))
+            } else {
+                Ok(None)
+            }
+        })
+        .try_collect::<Vec<_>>()
+        .await
+        .context(error::ListObjectsSnafu { path: dir })?;
+    paths.sort_unstable();
+
+    Ok(paths)
+}
+
+/// Returns whether `name` matches `pattern`, in which `*` matches any sequence of
+/// characters (including an empty one) and `?` matches exactly one character.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+
+    let (mut p, mut n) = (0, 0);
+    // position of the last seen '*' in pattern, and the position in name it's matched up to
+    let mut backtrack = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // let the last '*' consume one more character
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+async fn import_parquet(
+    object_store: &ObjectStore,
+    path: &str,
+    inserter: &BatchInserter<'_>,
+) -> Result<usize> {
+    let reader = object_store
+        .object(path)
+        .reader()
+        .await
+        .context(error::ReadObjectSnafu { path })?;
+    let reader = tokio::io::BufReader::new(reader.compat());
+    let mut stream = ParquetRecordBatchStreamBuilder::new(reader)
+        .await
+        .context(error::ReadParquetSnafu { path })?
+        .with_batch_size(READ_BATCH_SIZE)
+        .build()
+        .context(error::ReadParquetSnafu { path })?;
+
+    let mut rows = 0;
+    while let Some(batch) = stream
+        .try_next()
+        .await
+        .context(error::ReadParquetSnafu { path })?
+    {
+        rows += inserter.insert(batch).await?;
+    }
+    Ok(rows)
+}
+
+/// Imports the CSV or JSON file, whose decoders are blocking, so they run on a blocking
+/// thread and send the decoded batches back one by one.
+async fn import_text(
+    object_store: &ObjectStore,
+    path: &str,
+    format: CopyFormat,
+    inserter: &BatchInserter<'_>,
+) -> Result<usize> {
+    // The decoders can't seek back to the start of the file after inferring the schema, so
+    // the schema is inferred by another reader.
+    let object = object_store.object(path);
+    let schema_reader = object
+        .reader()
+        .await
+        .context(error::ReadObjectSnafu { path })?;
+    let reader = object
+        .reader()
+        .await
+        .context(error::ReadObjectSnafu { path })?;
+    let schema_reader = SyncIoBridge::new(schema_reader.compat());
+    let reader = SyncIoBridge::new(reader.compat());
+
+    // The decoding stops once the receiver is dropped, e.g. on failing to insert.
+    let (tx, mut rx) = mpsc::channel(1);
+    let _handle = tokio::task::spawn_blocking(move || {
+        decode_text(schema_reader, reader, format, tx);
+    });
+
+    let mut rows = 0;
+    while let Some(batch) = rx.recv().await {
+        let batch = batch.context(error::ParseFileSnafu { path })?;
+        rows += inserter.insert(batch).await?;
+    }
+    Ok(rows)
+}
+
+/// Decodes the CSV or JSON file from `reader` and sends the batches to `tx`, with the
+/// schema inferred from at most [INFER_SCHEMA_MAX_RECORDS] records of `schema_reader`.
+fn decode_text(
+    schema_reader: impl Read,
+    reader: impl Read + 'static,
+    format: CopyFormat,
+    tx: mpsc::Sender<ArrowResult<RecordBatch>>,
+) {
+    let batches = match infer_text_schema(schema_reader, format) {
+        Ok(schema) => new_text_decoder(reader, schema, format),
+        Err(e) => {
+            let _ = tx.blocking_send(Err(e));
+            return;
+        }
+    };
+    for batch in batches {
+        if tx.blocking_send(batch).is_err() {
+            break;
+        }
+    }
+}
+
+fn infer_text_schema(reader: impl Read, format: CopyFormat) -> ArrowResult<SchemaRef> {
+    let schema = match format {
+        CopyFormat::Csv => {
+            let max_records = Some(INFER_SCHEMA_MAX_RECORDS);
+            csv::reader::infer_reader_schema(reader, b',', max_records, true)?.0
+        }
+        CopyFormat::Json => json::reader::infer_json_schema(
+            &mut BufReader::new(reader),
+            Some(INFER_SCHEMA_MAX_RECORDS),
+        )?,
+        CopyFormat::Parquet => unreachable!("parquet files have their own schema"),
+    };
+    Ok(Arc::new(schema))
+}
+
+fn new_text_decoder(
+    reader: impl Read + 'static,
+    schema: SchemaRef,
+    format: CopyFormat,
+) -> Box<dyn Iterator<Item = ArrowResult<RecordBatch>>> {
+    match format {
+        CopyFormat::Csv => Box::new(csv::Reader::from_reader(
+            reader,
+            schema,
+            true,
+            None,
+            READ_BATCH_SIZE,
+            None,
+            None,
+            None,
+        )),
+        CopyFormat::Json => Box::new(json::Reader::new(
+            reader,
+            schema,
+            json::reader::DecoderOptions::new().with_batch_size(READ_BATCH_SIZE),
+        )),
+        CopyFormat::Parquet => unreachable!("parquet files have their own schema"),
+    }
+}
+
+/// Inserts the batches decoded from files into the table.
+struct BatchInserter<'a> {
+    table: &'a TableRef,
+    req: &'a CopyTableRequest,
+    region_number: RegionNumber,
+    flow_manager: &'a FlowManagerRef,
+}
+
+impl BatchInserter<'_> {
+    /// Inserts `batch` into the table. Columns are matched with the table's by name and
+    /// cast into the table's column types, and the table's columns absent in `batch` are
+    /// filled by their default values.
+    async fn insert(&self, batch: RecordBatch) -> Result<usize> {
+        if batch.num_rows() == 0 {
+            return Ok(0);
+        }
+
+        let (table, req) = (self.table, self.req);
+        let table_schema = table.schema();
+        let mut columns_values = HashMap::with_capacity(batch.num_columns());
+        for (field, array) in batch.schema().fields().iter().zip(batch.columns()) {
+            let column_schema = table_schema
+                .column_schema_by_name(field.name())
+                .with_context(|| error::ColumnNotFoundSnafu {
+                    column_name: field.name(),
+                    table_name: &req.table_name,
+                })?;
+
+            let target_type = column_schema.data_type.as_arrow_type();
+            let array = if array.data_type() == &target_type {
+                array.clone()
+            } else {
+                compute::cast(array, &target_type).with_context(|_| error::CastColumnSnafu {
+                    column: field.name(),
+                    data_type: column_schema.data_type.clone(),
+                })?
+            };
+            let vector = Helper::try_into_vector(array).context(error::VectorComputationSnafu)?;
+            let _ = columns_values.insert(field.name().clone(), vector);
+        }
+
+        let insert_request = InsertRequest {
+            catalog_name: req.catalog_name.clone(),
+            schema_name: req.schema_name.clone(),
+            table_name: req.table_name.clone(),
+            columns_values,
+            region_number: self.region_number,
+        };
+        let written = self.flow_manager.written_range(&insert_request);
+        self.flow_manager
+            .write(written, async {
+                table
+                    .insert(insert_request)
+                    .await
+                    .with_context(|_| error::InsertSnafu {
+                        table_name: &req.table_name,
+                    })
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("demo.csv", "demo.csv"));
+        assert!(!wildcard_match("demo.csv", "demo.json"));
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("*", "demo.csv"));
+        assert!(wildcard_match("*.csv", "demo.csv"));
+        assert!(!wildcard_match("*.csv", "demo.csv.bak"));
+        assert!(wildcard_match("demo_?.csv", "demo_1.csv"));
+        assert!(!wildcard_match("demo_?.csv", "demo_10.csv"));
+        assert!(wildcard_match("demo.parquet_*_*", "demo.parquet_1_2"));
+        assert!(wildcard_match("a*b*c", "aXbYbZc"));
+        assert!(!wildcard_match("a*b*c", "aXbYbZ"));
+    }
+}
@@ -22,23 +22,30 @@ use datafusion::parquet::basic::{Compression, Encoding};
 use datafusion::parquet::file::properties::WriterProperties;
 use datafusion::physical_plan::RecordBatchStream;
 use futures::TryStreamExt;
-use object_store::services::Fs as Builder;
-use object_store::{ObjectStore, ObjectStoreBuilder};
-use snafu::ResultExt;
+use object_store::ObjectStore;
+use snafu::{ensure, ResultExt};
 use table::engine::TableReference;
-use table::requests::CopyTableRequest;
+use table::requests::{CopyFormat, CopyTableRequest};
 
 use crate::error::{self, Result};
-use crate::sql::SqlHandler;
+use crate::sql::{ensure_copy_path, SqlHandler};
 
 impl SqlHandler {
-    pub(crate) async fn copy_table(&self, req: CopyTableRequest) -> Result<Output> {
+    pub(crate) async fn copy_table_to(&self, req: CopyTableRequest) -> Result<Output> {
+        ensure!(
+            req.format == CopyFormat::Parquet,
+            error::NotSupportSqlSnafu {
+                msg: format!("COPY TO with format {:?}", req.format),
+            }
+        );
+
         let table_ref = TableReference {
             catalog: &req.catalog_name,
             schema: &req.schema_name,
             table: &req.table_name,
         };
         let table = self.get_table(&table_ref)?;
+        ensure_copy_path(&req.file_name)?;
 
         let stream = table
             .scan(None, &[], None)
@@ -52,13 +59,8 @@ impl SqlHandler {
             .context(error::TableScanExecSnafu)?;
         let stream = Box::pin(DfRecordBatchStreamAdapter::new(stream));
 
-        let accessor = Builder::default()
-            .root("/")
-            .build()
-            .context(error::BuildBackendSnafu)?;
-        let object_store = ObjectStore::new(accessor).finish();
-
-        let mut parquet_writer = ParquetWriter::new(req.file_name, stream, object_store);
+        let mut parquet_writer =
+            ParquetWriter::new(req.file_name, stream, self.object_store.clone());
         // TODO(jiachun):
         // For now, COPY is implemented synchronously.
         // When copying large table, it will be blocked for a long time.
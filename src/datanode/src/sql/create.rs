@@ -13,27 +13,34 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
-use catalog::{RegisterSchemaRequest, RegisterTableRequest};
+use catalog::{DeregisterTableRequest, RegisterSchemaRequest, RegisterTableRequest};
 use common_procedure::{watcher, ProcedureManagerRef, ProcedureWithId};
 use common_query::Output;
 use common_telemetry::tracing::{error, info};
 use datatypes::schema::RawSchema;
-use session::context::QueryContextRef;
+use query::parser::QueryStatement;
+use session::context::{QueryContext, QueryContextRef};
 use snafu::{ensure, OptionExt, ResultExt};
 use sql::ast::{ColumnOption, SqlOption, TableConstraint, Value};
+use sql::dialect::GenericDialect;
+use sql::parser::ParserContext;
 use sql::statements::column_def_to_schema;
-use sql::statements::create::CreateTable;
+use sql::statements::create::{CreateDatabase, CreateTable};
+use sql::statements::statement::Statement;
 use store_api::storage::consts::TIME_INDEX_NAME;
 use table::engine::{EngineContext, TableReference};
-use table::metadata::TableId;
+use table::metadata::{TableId, TableInfoBuilder, TableMetaBuilder, TableType};
 use table::requests::*;
+use table::table::view::ViewTable;
 use table_procedure::CreateTableProcedure;
 
 use crate::error::{
-    self, CatalogNotFoundSnafu, CatalogSnafu, ConstraintNotSupportedSnafu, CreateTableSnafu,
-    IllegalPrimaryKeysDefSnafu, InsertSystemCatalogSnafu, KeyColumnNotFoundSnafu,
-    RegisterSchemaSnafu, Result, SchemaExistsSnafu, SchemaNotFoundSnafu, SubmitProcedureSnafu,
+    self, BuildViewInfoSnafu, BuildViewMetaSnafu, CatalogNotFoundSnafu, CatalogSnafu,
+    ConstraintNotSupportedSnafu, CreateTableSnafu, ExecuteSqlSnafu, IllegalPrimaryKeysDefSnafu,
+    InsertSystemCatalogSnafu, KeyColumnNotFoundSnafu, ParseSqlSnafu, RegisterSchemaSnafu, Result,
+    SchemaExistsSnafu, SchemaNotFoundSnafu, SubmitProcedureSnafu, TableExistsSnafu,
     UnrecognizedTableOptionSnafu, WaitProcedureSnafu,
 };
 use crate::sql::SqlHandler;
@@ -46,6 +53,18 @@ impl SqlHandler {
     ) -> Result<Output> {
         let catalog = query_ctx.current_catalog();
         let schema = req.db_name;
+        // Bloom filter indexes are built on specific columns, so they can't be defaults of
+        // all tables in the database.
+        ensure!(
+            !req.options
+                .extra_options
+                .contains_key(INDEX_BLOOM_FILTER_COLUMNS_KEY),
+            error::InvalidSqlSnafu {
+                msg: format!(
+                    "option {INDEX_BLOOM_FILTER_COLUMNS_KEY} is not allowed in database options"
+                ),
+            }
+        );
         if self
             .catalog_manager
             .schema(&catalog, &schema)
@@ -62,6 +81,7 @@ impl SqlHandler {
         let reg_req = RegisterSchemaRequest {
             catalog,
             schema: schema.clone(),
+            options: req.options,
         };
         self.catalog_manager
             .register_schema(reg_req)
@@ -72,7 +92,8 @@ impl SqlHandler {
         Ok(Output::AffectedRows(1))
     }
 
-    pub(crate) async fn create_table(&self, req: CreateTableRequest) -> Result<Output> {
+    pub(crate) async fn create_table(&self, mut req: CreateTableRequest) -> Result<Output> {
+        self.fill_default_table_options(&mut req)?;
         if let Some(procedure_manager) = &self.procedure_manager {
             return self.create_table_by_procedure(procedure_manager, req).await;
         }
@@ -132,6 +153,103 @@ impl SqlHandler {
         Ok(Output::AffectedRows(0))
     }
 
+    pub(crate) async fn create_view(&self, req: CreateViewRequest) -> Result<Output> {
+        let view_name = TableReference {
+            catalog: &req.catalog_name,
+            schema: &req.schema_name,
+            table: &req.view_name,
+        }
+        .to_string();
+
+        let existing = self
+            .catalog_manager
+            .table(&req.catalog_name, &req.schema_name, &req.view_name)
+            .await
+            .context(CatalogSnafu)?;
+        if let Some(table) = &existing {
+            ensure!(
+                req.or_replace && table.table_type() == TableType::View,
+                TableExistsSnafu { name: &view_name }
+            );
+        }
+
+        // Plans the definition to validate it and to get the schema of the view. Tables in
+        // the definition are resolved in the schema of the view, as they are when the view
+        // is expanded in queries.
+        let mut stmts = ParserContext::create_with_dialect(&req.definition, &GenericDialect {})
+            .context(ParseSqlSnafu)?;
+        let stmt = match stmts.pop() {
+            Some(stmt @ Statement::Query(_)) if stmts.is_empty() => stmt,
+            _ => {
+                return error::InvalidSqlSnafu {
+                    msg: format!("invalid definition of view: {}", req.definition),
+                }
+                .fail()
+            }
+        };
+        let query_ctx = Arc::new(QueryContext::with(&req.catalog_name, &req.schema_name));
+        let plan = self
+            .query_engine
+            .statement_to_plan(QueryStatement::Sql(stmt), query_ctx)
+            .await
+            .context(ExecuteSqlSnafu)?;
+        let schema = plan.schema().context(ExecuteSqlSnafu)?;
+
+        let meta = TableMetaBuilder::default()
+            .schema(Arc::new(schema))
+            .primary_key_indices(vec![])
+            .next_column_id(0)
+            .build()
+            .context(BuildViewMetaSnafu { name: &view_name })?;
+        let table_info = TableInfoBuilder::new(&req.view_name, meta)
+            .table_id(req.id)
+            .catalog_name(&req.catalog_name)
+            .schema_name(&req.schema_name)
+            .table_type(TableType::View)
+            .build()
+            .context(BuildViewInfoSnafu { name: &view_name })?;
+        let view = ViewTable::new(Arc::new(table_info), req.definition);
+
+        if existing.is_some() {
+            let deregister_req = DeregisterTableRequest {
+                catalog: req.catalog_name.clone(),
+                schema: req.schema_name.clone(),
+                table_name: req.view_name.clone(),
+            };
+            self.catalog_manager
+                .deregister_table(deregister_req)
+                .await
+                .context(InsertSystemCatalogSnafu)?;
+        }
+        let register_req = RegisterTableRequest {
+            catalog: req.catalog_name,
+            schema: req.schema_name,
+            table_name: req.view_name,
+            table_id: req.id,
+            table: Arc::new(view),
+        };
+        self.catalog_manager
+            .register_table(register_req)
+            .await
+            .context(InsertSystemCatalogSnafu)?;
+
+        info!("Successfully created view: {}", view_name);
+        Ok(Output::AffectedRows(0))
+    }
+
+    /// Fills the options absent in `req` by the default table options of its schema.
+    fn fill_default_table_options(&self, req: &mut CreateTableRequest) -> Result<()> {
+        let schema = self
+            .catalog_manager
+            .schema(&req.catalog_name, &req.schema_name)
+            .context(CatalogSnafu)?;
+        if let Some(schema) = schema {
+            let defaults = schema.table_options().context(CatalogSnafu)?;
+            req.table_options.fill_defaults(&defaults);
+        }
+        Ok(())
+    }
+
     pub(crate) async fn create_table_by_procedure(
         &self,
         procedure_manager: &ProcedureManagerRef,
@@ -161,6 +279,18 @@ impl SqlHandler {
         Ok(Output::AffectedRows(0))
     }
 
+    /// Converts [CreateDatabase] to [SqlRequest::CreateDatabase].
+    pub(crate) fn create_database_to_request(
+        &self,
+        stmt: CreateDatabase,
+    ) -> Result<CreateDatabaseRequest> {
+        Ok(CreateDatabaseRequest {
+            db_name: stmt.name.to_string(),
+            create_if_not_exists: stmt.if_not_exists,
+            options: stmt_options_to_table_options(&stmt.options)?,
+        })
+    }
+
     /// Converts [CreateTable] to [SqlRequest::CreateTable].
     pub(crate) fn create_to_request(
         &self,
@@ -292,6 +422,14 @@ impl SqlHandler {
 }
 
 fn stmt_options_to_table_options(opts: &[SqlOption]) -> error::Result<TableOptions> {
+    let map = stmt_options_to_map(opts);
+    let options = TableOptions::try_from(&map).context(UnrecognizedTableOptionSnafu)?;
+    Ok(options)
+}
+
+/// Converts options in a statement to a map from option names to values, quoted
+/// values are unquoted.
+pub(crate) fn stmt_options_to_map(opts: &[SqlOption]) -> HashMap<String, String> {
     let mut map = HashMap::with_capacity(opts.len());
     for SqlOption { name, value } in opts {
         let value_str = match value {
@@ -301,8 +439,7 @@ fn stmt_options_to_table_options(opts: &[SqlOption]) -> error::Result<TableOptio
         };
         map.insert(name.value.clone(), value_str);
     }
-    let options = TableOptions::try_from(&map).context(UnrecognizedTableOptionSnafu)?;
-    Ok(options)
+    map
 }
 
 #[cfg(test)]
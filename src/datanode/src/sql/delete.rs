@@ -13,29 +13,38 @@
 // limitations under the License.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::str::FromStr;
 
 use common_query::Output;
-use datatypes::data_type::DataType;
-use datatypes::prelude::VectorRef;
-use datatypes::vectors::StringVector;
+use common_recordbatch::RecordBatch;
+use common_time::range::TimestampRange;
+use common_time::timestamp::TimeUnit;
+use common_time::Timestamp;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::ColumnSchema;
+use futures::StreamExt;
+use query::parser::QueryStatement;
 use session::context::QueryContextRef;
-use snafu::{OptionExt, ResultExt};
-use sql::ast::{BinaryOperator, Expr, Value};
+use snafu::ResultExt;
+use sql::ast::{BinaryOperator, Expr, Value as SqlValue};
+use sql::dialect::GenericDialect;
+use sql::parser::ParserContext;
 use sql::statements::delete::Delete;
-use sql::statements::sql_value_to_value;
 use table::engine::TableReference;
 use table::requests::DeleteRequest;
 use table::TableRef;
 
-use crate::error::{ColumnNotFoundSnafu, DeleteSnafu, InvalidSqlSnafu, NotSupportSqlSnafu, Result};
+use crate::error::{
+    CollectRecordsSnafu, DeleteSnafu, ExecuteSqlSnafu, InvalidSqlSnafu, ParseSqlSnafu, Result,
+};
+use crate::flow::FlowManager;
 use crate::instance::sql::table_idents_to_full_name;
 use crate::sql::SqlHandler;
 
 impl SqlHandler {
     pub(crate) async fn delete(&self, query_ctx: QueryContextRef, stmt: Delete) -> Result<Output> {
         let (catalog_name, schema_name, table_name) =
-            table_idents_to_full_name(stmt.table_name(), query_ctx)?;
+            table_idents_to_full_name(stmt.table_name(), query_ctx.clone())?;
         let table_ref = TableReference {
             catalog: &catalog_name.to_string(),
             schema: &schema_name.to_string(),
@@ -44,99 +53,323 @@ impl SqlHandler {
 
         let table = self.get_table(&table_ref)?;
 
-        let req = DeleteRequest {
-            key_column_values: parse_selection(stmt.selection(), &table)?,
-        };
+        let mut affected_rows = 0;
+        // If the predicate only filters the time index, files whose rows all match the
+        // predicate are dropped as a whole first, so the scan below only finds rows in
+        // memtables and files partially matching the predicate. All rows in the dropped
+        // files are counted as affected, even if some of them were already overwritten
+        // or deleted. Flows need the time range of the deleted rows, so tables read by
+        // flows always delete the scanned rows.
+        let time_range = time_range_of_selection(stmt.selection(), &table)
+            .filter(|_| !self.flow_manager.has_flows_of(&table_ref.to_string()));
+        if let Some(time_range) = time_range {
+            affected_rows += table
+                .delete_files_in_range(time_range)
+                .await
+                .with_context(|_| DeleteSnafu {
+                    table_name: table_ref.to_string(),
+                })?;
+        }
 
-        let affected_rows = table.delete(req).await.with_context(|_| DeleteSnafu {
-            table_name: table_ref.to_string(),
-        })?;
+        let logical_plan = self
+            .query_engine
+            .statement_to_plan(
+                QueryStatement::Sql(build_scan_statement(&stmt, &table)?),
+                query_ctx,
+            )
+            .await
+            .context(ExecuteSqlSnafu)?;
+        let output = self
+            .query_engine
+            .execute(&logical_plan)
+            .await
+            .context(ExecuteSqlSnafu)?;
+        // Rows are deleted batch by batch while scanning. The scan reads a snapshot of the
+        // table, so it doesn't see the deletions, and it returns each key at most once.
+        let mut stream = match output {
+            Output::RecordBatches(batches) => batches.as_stream(),
+            Output::Stream(stream) => stream,
+            Output::AffectedRows(_) => unreachable!("a query always outputs record batches"),
+        };
+        while let Some(batch) = stream.next().await {
+            let batch = batch.context(CollectRecordsSnafu)?;
+            affected_rows += delete_batch(&table, &table_ref, batch, &self.flow_manager).await?;
+        }
 
         Ok(Output::AffectedRows(affected_rows))
     }
 }
 
-/// parse selection, currently supported format is `tagkey1 = 'tagvalue1' and 'ts' = 'value'`.
-/// (only uses =, and in the where clause and provides all columns needed by the key.)
-fn parse_selection(
-    selection: &Option<Expr>,
+/// Builds a statement that scans the row keys of the rows to delete, i.e. the primary
+/// key columns and the time index column of rows matching the selection of `stmt`.
+fn build_scan_statement(
+    stmt: &Delete,
     table: &TableRef,
-) -> Result<HashMap<String, VectorRef>> {
-    let mut key_column_values = HashMap::new();
-    if let Some(expr) = selection {
-        parse_expr(expr, &mut key_column_values, table)?;
+) -> Result<sql::statements::statement::Statement> {
+    let table_info = table.table_info();
+    let schema = table.schema();
+    let key_columns = table_info
+        .meta
+        .row_key_column_names()
+        .chain(schema.timestamp_column().map(|column| &column.name))
+        .map(|name| format!("\"{}\"", name.replace('"', "\"\"")))
+        .collect::<Vec<_>>();
+
+    let mut sql = format!(
+        "SELECT {} FROM {}",
+        key_columns.join(", "),
+        stmt.table_name()
+    );
+    if let Some(selection) = stmt.selection() {
+        sql.push_str(&format!(" WHERE {selection}"));
+    }
+
+    let mut stmts =
+        ParserContext::create_with_dialect(&sql, &GenericDialect {}).context(ParseSqlSnafu)?;
+    match stmts.pop() {
+        Some(stmt) if stmts.is_empty() => Ok(stmt),
+        _ => InvalidSqlSnafu {
+            msg: format!("invalid selection of delete: {sql}"),
+        }
+        .fail(),
     }
-    Ok(key_column_values)
 }
 
-fn parse_expr(
-    expr: &Expr,
-    key_column_values: &mut HashMap<String, VectorRef>,
+/// Deletes rows whose keys are in `batch` from `table`, returns the number of deleted rows.
+async fn delete_batch(
     table: &TableRef,
-) -> Result<()> {
-    // match BinaryOp
-    if let Expr::BinaryOp { left, op, right } = expr {
-        match (&**left, op, &**right) {
-            // match And operator
-            (Expr::BinaryOp { .. }, BinaryOperator::And, Expr::BinaryOp { .. }) => {
-                parse_expr(left, key_column_values, table)?;
-                parse_expr(right, key_column_values, table)?;
-                return Ok(());
+    table_ref: &TableReference<'_>,
+    batch: RecordBatch,
+    flow_manager: &FlowManager,
+) -> Result<usize> {
+    if batch.num_rows() == 0 {
+        return Ok(0);
+    }
+
+    let key_column_values = batch
+        .schema
+        .column_schemas()
+        .iter()
+        .zip(batch.columns())
+        .map(|(column_schema, vector)| (column_schema.name.clone(), vector.clone()))
+        .collect::<HashMap<_, _>>();
+
+    let request = DeleteRequest { key_column_values };
+    let deleted = flow_manager.deleted_range(table_ref, &request);
+    flow_manager
+        .write(deleted, async {
+            table.delete(request).await.with_context(|_| DeleteSnafu {
+                table_name: table_ref.to_string(),
+            })
+        })
+        .await
+}
+
+/// Returns the time range of the rows selected by `selection` if it only filters the time
+/// index column of the table.
+///
+/// The returned range never contains timestamps not selected by `selection`, so the files
+/// inside it could be deleted as a whole. Returns `None` if the selection contains other
+/// predicates, or the range can't be computed exactly.
+fn time_range_of_selection(selection: &Option<Expr>, table: &TableRef) -> Option<TimestampRange> {
+    let schema = table.schema();
+    let ts_column = schema.timestamp_column()?;
+    match selection {
+        Some(expr) => time_range_of_expr(expr, ts_column),
+        None => Some(TimestampRange::min_to_max()),
+    }
+}
+
+fn time_range_of_expr(expr: &Expr, ts_column: &ColumnSchema) -> Option<TimestampRange> {
+    match expr {
+        Expr::Nested(expr) => time_range_of_expr(expr, ts_column),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            let left = time_range_of_expr(left, ts_column)?;
+            let right = time_range_of_expr(right, ts_column)?;
+            Some(left.and(&right))
+        }
+        Expr::BinaryOp { left, op, right } => match (&**left, &**right) {
+            (Expr::Identifier(ident), Expr::Value(value)) if ident.value == ts_column.name => {
+                let ts = literal_to_timestamp(value, ts_column)?;
+                time_range_of_comparison(op, ts)
             }
-            // match Eq operator
-            (Expr::Identifier(column_name), BinaryOperator::Eq, Expr::Value(value)) => {
-                key_column_values.insert(
-                    column_name.to_string(),
-                    value_to_vector(&column_name.to_string(), value, table)?,
-                );
-                return Ok(());
+            (Expr::Value(value), Expr::Identifier(ident)) if ident.value == ts_column.name => {
+                let ts = literal_to_timestamp(value, ts_column)?;
+                // Swaps the operands so the time index is on the left.
+                let op = match op {
+                    BinaryOperator::Lt => BinaryOperator::Gt,
+                    BinaryOperator::LtEq => BinaryOperator::GtEq,
+                    BinaryOperator::Gt => BinaryOperator::Lt,
+                    BinaryOperator::GtEq => BinaryOperator::LtEq,
+                    BinaryOperator::Eq => BinaryOperator::Eq,
+                    _ => return None,
+                };
+                time_range_of_comparison(&op, ts)
             }
-            (Expr::Identifier(column_name), BinaryOperator::Eq, Expr::Identifier(value)) => {
-                key_column_values.insert(
-                    column_name.to_string(),
-                    Arc::new(StringVector::from(vec![value.to_string()])),
-                );
-                return Ok(());
+            _ => None,
+        },
+        Expr::Between {
+            expr,
+            negated: false,
+            low,
+            high,
+        } => match (&**expr, &**low, &**high) {
+            (Expr::Identifier(ident), Expr::Value(low), Expr::Value(high))
+                if ident.value == ts_column.name =>
+            {
+                let low = literal_to_timestamp(low, ts_column)?;
+                let high = literal_to_timestamp(high, ts_column)?;
+                Some(TimestampRange::new_inclusive(Some(low), Some(high)))
             }
-            _ => {}
-        }
+            _ => None,
+        },
+        _ => None,
     }
-    NotSupportSqlSnafu {
-        msg: format!(
-            "Not support sql expr:{expr},correct format is tagkey1 = tagvalue1 and ts = value"
-        ),
+}
+
+/// Returns the range of `ts_column <op> ts`.
+fn time_range_of_comparison(op: &BinaryOperator, ts: Timestamp) -> Option<TimestampRange> {
+    match op {
+        BinaryOperator::Eq => Some(TimestampRange::single(ts)),
+        BinaryOperator::Lt => Some(TimestampRange::until_end(ts, false)),
+        BinaryOperator::LtEq => Some(TimestampRange::until_end(ts, true)),
+        BinaryOperator::Gt => {
+            // `ts` is in the unit of the time index, so there is no timestamp between
+            // `ts` and `ts + 1`.
+            let start = ts.value().checked_add(1)?;
+            Some(TimestampRange::from_start(Timestamp::new(start, ts.unit())))
+        }
+        BinaryOperator::GtEq => Some(TimestampRange::from_start(ts)),
+        _ => None,
     }
-    .fail()
 }
 
-/// parse value to vector
-fn value_to_vector(column_name: &String, sql_value: &Value, table: &TableRef) -> Result<VectorRef> {
-    let schema = table.schema();
-    let column_schema =
-        schema
-            .column_schema_by_name(column_name)
-            .with_context(|| ColumnNotFoundSnafu {
-                table_name: table.table_info().name.clone(),
-                column_name: column_name.to_string(),
-            })?;
-    let data_type = &column_schema.data_type;
-    let value = sql_value_to_value(column_name, data_type, sql_value);
-    match value {
-        Ok(value) => {
-            let mut vec = data_type.create_mutable_vector(1);
-            if vec.try_push_value_ref(value.as_value_ref()).is_err() {
-                return InvalidSqlSnafu {
-                    msg: format!(
-                        "invalid sql, column name is {column_name}, value is {sql_value}",
-                    ),
-                }
-                    .fail();
-            }
-            Ok(vec.to_vector())
+/// Converts the literal compared with the time index column to a timestamp in the unit of
+/// the column. Returns `None` if the literal can't be converted exactly.
+///
+/// Strings without an explicit time zone are not converted, as their interpretation
+/// depends on the time zone the filter of the scan uses, and a wrong range would drop
+/// files containing rows not selected.
+fn literal_to_timestamp(value: &SqlValue, ts_column: &ColumnSchema) -> Option<Timestamp> {
+    let ConcreteDataType::Timestamp(ts_type) = &ts_column.data_type else { return None; };
+    let unit = ts_type.unit();
+    let ts = match value {
+        // Integers are compared with the time index as milliseconds.
+        SqlValue::Number(n, _) if unit == TimeUnit::Millisecond => {
+            Timestamp::new_millisecond(n.parse().ok()?)
         }
-        _ => InvalidSqlSnafu {
-            msg: format!("invalid sql, column name is {column_name}, value is {sql_value}",),
+        SqlValue::SingleQuotedString(s) | SqlValue::DoubleQuotedString(s)
+            if has_explicit_time_zone(s) =>
+        {
+            Timestamp::from_str(s).ok()?
         }
-        .fail(),
+        _ => return None,
+    };
+
+    // Rounds down a timestamp with a smaller unit would change the range.
+    let converted = ts.convert_to(unit)?;
+    (converted == ts).then_some(converted)
+}
+
+/// Returns whether the timestamp string `s`, like `2023-01-01 00:00:00+08:00`, ends with
+/// an explicit time zone `Z` or an offset.
+fn has_explicit_time_zone(s: &str) -> bool {
+    // Skips the date part, which contains `-`.
+    match s.get("YYYY-MM-DD".len()..) {
+        Some(time) => time.ends_with(['Z', 'z']) || time.contains(['+', '-']),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sql::statements::statement::Statement;
+
+    use super::*;
+
+    fn ts_column() -> ColumnSchema {
+        ColumnSchema::new(
+            "ts",
+            ConcreteDataType::timestamp_millisecond_datatype(),
+            false,
+        )
+        .with_time_index(true)
+    }
+
+    fn time_range_of_sql(sql: &str) -> Option<TimestampRange> {
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        let Statement::Delete(delete) = stmts.remove(0) else { unreachable!() };
+        match delete.selection() {
+            Some(expr) => time_range_of_expr(expr, &ts_column()),
+            None => Some(TimestampRange::min_to_max()),
+        }
+    }
+
+    #[test]
+    fn test_time_range_of_selection() {
+        let millis = Timestamp::new_millisecond;
+
+        assert_eq!(
+            Some(TimestampRange::min_to_max()),
+            time_range_of_sql("DELETE FROM t")
+        );
+        assert_eq!(
+            Some(TimestampRange::until_end(millis(1000), false)),
+            time_range_of_sql("DELETE FROM t WHERE ts < 1000")
+        );
+        assert_eq!(
+            Some(TimestampRange::until_end(millis(1000), true)),
+            time_range_of_sql("DELETE FROM t WHERE 1000 >= ts")
+        );
+        assert_eq!(
+            Some(TimestampRange::new(millis(1001), millis(2000)).unwrap()),
+            time_range_of_sql("DELETE FROM t WHERE ts > 1000 AND (ts < 2000)")
+        );
+        assert_eq!(
+            Some(TimestampRange::new_inclusive(
+                Some(millis(1000)),
+                Some(millis(2000))
+            )),
+            time_range_of_sql("DELETE FROM t WHERE ts BETWEEN 1000 AND 2000")
+        );
+        assert_eq!(
+            Some(TimestampRange::until_end(millis(1672531200000), false)),
+            time_range_of_sql("DELETE FROM t WHERE ts < '2023-01-01 00:00:00Z'")
+        );
+
+        // Other predicates.
+        assert_eq!(
+            None,
+            time_range_of_sql("DELETE FROM t WHERE ts < 1000 AND host = 'host1'")
+        );
+        assert_eq!(
+            None,
+            time_range_of_sql("DELETE FROM t WHERE ts < 1000 OR ts > 2000")
+        );
+        assert_eq!(None, time_range_of_sql("DELETE FROM t WHERE ts <> 1000"));
+        assert_eq!(None, time_range_of_sql("DELETE FROM t WHERE ts < now()"));
+        assert_eq!(
+            Some(TimestampRange::from_start(millis(1672502400000))),
+            time_range_of_sql("DELETE FROM t WHERE ts >= '2023-01-01 00:00:00+08:00'")
+        );
+
+        // Strings without time zone.
+        assert_eq!(
+            None,
+            time_range_of_sql("DELETE FROM t WHERE ts < '2023-01-01 00:00:00'")
+        );
+        assert_eq!(
+            None,
+            time_range_of_sql("DELETE FROM t WHERE ts < '2023-01-01T00:00:00'")
+        );
+        // Literal finer than the unit of the time index.
+        assert_eq!(
+            None,
+            time_range_of_sql("DELETE FROM t WHERE ts >= '2023-01-01 00:00:00.000001Z'")
+        );
     }
 }
@@ -12,15 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use catalog::DeregisterTableRequest;
+use catalog::{DeregisterSchemaRequest, DeregisterTableRequest};
+use common_catalog::consts::{DEFAULT_SCHEMA_NAME, INFORMATION_SCHEMA_NAME};
 use common_error::prelude::BoxedError;
 use common_query::Output;
 use common_telemetry::info;
-use snafu::ResultExt;
+use session::context::QueryContextRef;
+use snafu::{ensure, OptionExt, ResultExt};
 use table::engine::{EngineContext, TableReference};
-use table::requests::DropTableRequest;
+use table::metadata::TableType;
+use table::requests::{DropDatabaseRequest, DropTableRequest, DropViewRequest};
 
-use crate::error::{self, Result};
+use crate::error::{self, CatalogSnafu, Result};
 use crate::sql::SqlHandler;
 
 impl SqlHandler {
@@ -59,4 +62,138 @@ impl SqlHandler {
 
         Ok(Output::AffectedRows(1))
     }
+
+    pub(crate) async fn drop_database(
+        &self,
+        req: DropDatabaseRequest,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let catalog = query_ctx.current_catalog();
+        let schema = req.db_name;
+        ensure!(
+            schema != DEFAULT_SCHEMA_NAME && schema != INFORMATION_SCHEMA_NAME,
+            error::InvalidSqlSnafu {
+                msg: format!("database {schema} can't be dropped"),
+            }
+        );
+
+        let schema_provider = match self
+            .catalog_manager
+            .schema(&catalog, &schema)
+            .context(CatalogSnafu)?
+        {
+            Some(schema_provider) => schema_provider,
+            None if req.drop_if_exists => return Ok(Output::AffectedRows(0)),
+            None => return error::DatabaseNotFoundSnafu { catalog, schema }.fail(),
+        };
+
+        // Checks every table before dropping any of them, so a database with a table that
+        // can't be dropped is left untouched.
+        let ctx = EngineContext {};
+        let mut tables = Vec::new();
+        for table_name in schema_provider.table_names().context(CatalogSnafu)? {
+            let table_ref = TableReference {
+                catalog: &catalog,
+                schema: &schema,
+                table: &table_name,
+            };
+            let table_full_name = table_ref.to_string();
+            let table = schema_provider
+                .table(&table_name)
+                .await
+                .context(CatalogSnafu)?
+                .context(error::TableNotFoundSnafu {
+                    table_name: &table_full_name,
+                })?;
+            let table_type = table.table_type();
+            if table_type == TableType::Base {
+                ensure!(
+                    self.table_engine().table_exists(&ctx, &table_ref),
+                    error::TableNotFoundSnafu {
+                        table_name: &table_full_name,
+                    }
+                );
+            }
+            tables.push((table_name, table_type));
+        }
+
+        // The database is removed after all its tables. If dropping a table still fails, the
+        // database keeps the remaining tables and the statement could be retried.
+        for (table_name, table_type) in tables {
+            if table_type == TableType::View {
+                let req = DropViewRequest {
+                    catalog_name: catalog.clone(),
+                    schema_name: schema.clone(),
+                    view_name: table_name,
+                    drop_if_exists: true,
+                };
+                self.drop_view(req).await?;
+            } else {
+                let req = DropTableRequest {
+                    catalog_name: catalog.clone(),
+                    schema_name: schema.clone(),
+                    table_name,
+                };
+                self.drop_table(req).await?;
+            }
+        }
+
+        let deregister_schema_req = DeregisterSchemaRequest {
+            catalog,
+            schema: schema.clone(),
+        };
+        self.catalog_manager
+            .deregister_schema(deregister_schema_req)
+            .await
+            .context(error::DeregisterSchemaSnafu { name: &schema })?;
+
+        info!("Successfully dropped database: {}", schema);
+
+        Ok(Output::AffectedRows(1))
+    }
+
+    pub(crate) async fn drop_view(&self, req: DropViewRequest) -> Result<Output> {
+        let view_name = TableReference {
+            catalog: &req.catalog_name,
+            schema: &req.schema_name,
+            table: &req.view_name,
+        }
+        .to_string();
+
+        let view = self
+            .catalog_manager
+            .table(&req.catalog_name, &req.schema_name, &req.view_name)
+            .await
+            .context(CatalogSnafu)?;
+        match view {
+            Some(view) => ensure!(
+                view.table_type() == TableType::View,
+                error::NotViewSnafu { name: &view_name }
+            ),
+            None if req.drop_if_exists => return Ok(Output::AffectedRows(0)),
+            None => {
+                return error::TableNotFoundSnafu {
+                    table_name: view_name,
+                }
+                .fail()
+            }
+        }
+
+        let deregister_req = DeregisterTableRequest {
+            catalog: req.catalog_name,
+            schema: req.schema_name,
+            table_name: req.view_name,
+        };
+        self.catalog_manager
+            .deregister_table(deregister_req)
+            .await
+            .map_err(BoxedError::new)
+            .context(error::DropTableSnafu {
+                table_name: &view_name,
+            })?;
+
+        info!("Successfully dropped view: {}", view_name);
+
+        Ok(Output::AffectedRows(1))
+    }
 }
@@ -66,9 +66,15 @@ impl SqlHandler {
 
         let table = self.get_table(&table_ref)?;
 
-        let affected_rows = table.insert(req).await.with_context(|_| InsertSnafu {
-            table_name: table_ref.to_string(),
-        })?;
+        let written = self.flow_manager.written_range(&req);
+        let affected_rows = self
+            .flow_manager
+            .write(written, async {
+                table.insert(req).await.with_context(|_| InsertSnafu {
+                    table_name: table_ref.to_string(),
+                })
+            })
+            .await?;
 
         Ok(Output::AffectedRows(affected_rows))
     }
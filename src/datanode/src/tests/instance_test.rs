@@ -13,7 +13,9 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
 
+use common_base::readable_size::ReadableSize;
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
 use common_query::Output;
 use common_recordbatch::util;
@@ -607,6 +609,581 @@ async fn test_alter_table() {
     check_output_stream(output, expected).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_alter_table_modify_column() {
+    let instance = setup_test_instance("test_alter_table_modify_column").await;
+
+    execute_sql(
+        &instance,
+        "create table demo(host string, v int, ts timestamp time index, primary key(host))",
+    )
+    .await;
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, v, ts) values ('host1', 1, 1000), ('host2', 2, 2000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+    // Rows in the SST are read with the altered schema.
+    let output = execute_sql(&instance, "admin flush table demo").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(&instance, "alter table demo modify column v bigint").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = execute_sql(&instance, "alter table demo rename column v to v2").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = execute_sql(&instance, "alter table demo alter column v2 set default 10").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, ts) values ('host3', 3000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    let output = execute_sql(&instance, "select * from demo where v2 > 1 order by ts").await;
+    let expected = "\
++-------+----+---------------------+
+| host  | v2 | ts                  |
++-------+----+---------------------+
+| host2 | 2  | 1970-01-01T00:00:02 |
+| host3 | 10 | 1970-01-01T00:00:03 |
++-------+----+---------------------+\
+    "
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    // Only widening the type is allowed.
+    let result = try_execute_sql(&instance, "alter table demo modify column v2 int").await;
+    assert!(result.is_err());
+    // Primary key and time index can't be modified.
+    let result = try_execute_sql(&instance, "alter table demo rename column host to host2").await;
+    assert!(result.is_err());
+    let result = try_execute_sql(&instance, "alter table demo modify column ts bigint").await;
+    assert!(result.is_err());
+
+    let output = execute_sql(&instance, "alter table demo alter column v2 drop default").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, ts) values ('host4', 4000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let output = execute_sql(&instance, "select v2 from demo where host = 'host4'").await;
+    let expected = "\
++----+
+| v2 |
++----+
+|    |
++----+\
+    "
+    .to_string();
+    check_output_stream(output, expected).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_alter_table_options() {
+    let instance = setup_test_instance("test_alter_table_options").await;
+
+    execute_sql(
+        &instance,
+        "create table demo(host string, v int, ts timestamp time index, primary key(host)) \
+         with(ttl='1d')",
+    )
+    .await;
+
+    let output = execute_sql(
+        &instance,
+        "alter table demo set ttl = '30d', write_buffer_size = '1MB'",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let table = instance
+        .inner()
+        .catalog_manager()
+        .table(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, "demo")
+        .await
+        .unwrap()
+        .unwrap();
+    let options = &table.table_info().meta.options;
+    assert_eq!(Some(Duration::from_secs(30 * 24 * 3600)), options.ttl);
+    assert_eq!(Some(ReadableSize::mb(1)), options.write_buffer_size);
+
+    // The table is still writable after altering options.
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, v, ts) values ('host1', 1, 1000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    let output = execute_sql(&instance, "alter table demo unset ttl").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let table = instance
+        .inner()
+        .catalog_manager()
+        .table(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, "demo")
+        .await
+        .unwrap()
+        .unwrap();
+    let options = &table.table_info().meta.options;
+    assert_eq!(None, options.ttl);
+    assert_eq!(Some(ReadableSize::mb(1)), options.write_buffer_size);
+
+    // Invalid values and options that can't be altered.
+    let result = try_execute_sql(&instance, "alter table demo set ttl = 'abc'").await;
+    assert!(result.is_err());
+    let result = try_execute_sql(
+        &instance,
+        "alter table demo set \"memtable.type\" = 'series'",
+    )
+    .await;
+    assert!(result.is_err());
+    let result = try_execute_sql(&instance, "alter table demo unset regions").await;
+    assert!(result.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_create_database_with_options() {
+    let instance = setup_test_instance("test_create_database_with_options").await;
+
+    let output = execute_sql(
+        &instance,
+        "create database db with(ttl='7d', write_buffer_size='1MB')",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    // Options of the table override the defaults of the database.
+    execute_sql(
+        &instance,
+        "create table db.demo(host string, ts timestamp time index) with(ttl='1d')",
+    )
+    .await;
+    let table = instance
+        .inner()
+        .catalog_manager()
+        .table(DEFAULT_CATALOG_NAME, "db", "demo")
+        .await
+        .unwrap()
+        .unwrap();
+    let options = &table.table_info().meta.options;
+    assert_eq!(Some(Duration::from_secs(24 * 3600)), options.ttl);
+    assert_eq!(Some(ReadableSize::mb(1)), options.write_buffer_size);
+
+    // Tables in other databases are not affected.
+    execute_sql(
+        &instance,
+        "create table demo(host string, ts timestamp time index)",
+    )
+    .await;
+    let table = instance
+        .inner()
+        .catalog_manager()
+        .table(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, "demo")
+        .await
+        .unwrap()
+        .unwrap();
+    let options = &table.table_info().meta.options;
+    assert_eq!(None, options.ttl);
+    assert_eq!(None, options.write_buffer_size);
+
+    let result = try_execute_sql(&instance, "create database db2 with(ttl='abc')").await;
+    assert!(result.is_err());
+    let result = try_execute_sql(
+        &instance,
+        "create database db2 with(\"index.bloom_filter_columns\"='host')",
+    )
+    .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_drop_database() {
+    let instance = setup_test_instance("test_drop_database").await;
+
+    execute_sql(&instance, "create database db").await;
+    execute_sql(
+        &instance,
+        "create table db.demo(host string, cpu double, ts timestamp time index)",
+    )
+    .await;
+    execute_sql(
+        &instance,
+        "insert into db.demo(host, cpu, ts) values ('host1', 1.1, 1000)",
+    )
+    .await;
+    execute_sql(
+        &instance,
+        "create table db.demo2(host string, ts timestamp time index)",
+    )
+    .await;
+
+    let output = execute_sql(&instance, "drop database db").await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let catalog_manager = instance.inner().catalog_manager();
+    assert!(catalog_manager
+        .schema(DEFAULT_CATALOG_NAME, "db")
+        .unwrap()
+        .is_none());
+    let result = try_execute_sql(&instance, "select * from db.demo").await;
+    assert!(result.is_err());
+
+    let result = try_execute_sql(&instance, "drop database db").await;
+    assert!(result.is_err());
+    let output = execute_sql(&instance, "drop database if exists db").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let result = try_execute_sql(&instance, "drop database public").await;
+    assert!(result.is_err());
+
+    // The database could be created again, without data of the dropped one.
+    execute_sql(&instance, "create database db").await;
+    execute_sql(
+        &instance,
+        "create table db.demo(host string, cpu double, ts timestamp time index)",
+    )
+    .await;
+    let output = execute_sql(&instance, "select * from db.demo").await;
+    let expected = "\
+++
+++"
+    .to_string();
+    check_output_stream(output, expected).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_truncate_table() {
+    let instance = setup_test_instance("test_truncate_table").await;
+
+    execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, ts timestamp time index, primary key(host))",
+    )
+    .await;
+    execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host1', 1.1, 1000), ('host2', 2.2, 2000)",
+    )
+    .await;
+    execute_sql(&instance, "admin flush table demo").await;
+    execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host3', 3.3, 3000)",
+    )
+    .await;
+    let table_id = instance
+        .inner()
+        .catalog_manager()
+        .table(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, "demo")
+        .await
+        .unwrap()
+        .unwrap()
+        .table_info()
+        .ident
+        .table_id;
+
+    let output = execute_sql(&instance, "truncate table demo").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(&instance, "select * from demo").await;
+    let expected = "\
+++
+++"
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    // The table is still writable, and keeps its ID.
+    execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host4', 4.4, 4000)",
+    )
+    .await;
+    let output = execute_sql(&instance, "select host, cpu, ts from demo").await;
+    let expected = "\
++-------+-----+---------------------+
+| host  | cpu | ts                  |
++-------+-----+---------------------+
+| host4 | 4.4 | 1970-01-01T00:00:04 |
++-------+-----+---------------------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+    let table = instance
+        .inner()
+        .catalog_manager()
+        .table(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, "demo")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(table_id, table.table_info().ident.table_id);
+
+    let result = try_execute_sql(&instance, "truncate table not_exist").await;
+    assert!(result.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_create_view() {
+    let instance = setup_test_instance("test_create_view").await;
+
+    execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, ts timestamp time index, primary key(host))",
+    )
+    .await;
+    execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host1', 1.1, 1000), ('host2', 2.2, 2000)",
+    )
+    .await;
+
+    let output = execute_sql(
+        &instance,
+        "create view busy_hosts as select host, cpu from demo where cpu > 2",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = execute_sql(&instance, "select * from busy_hosts").await;
+    let expected = "\
++-------+-----+
+| host  | cpu |
++-------+-----+
+| host2 | 2.2 |
++-------+-----+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    // The view always reflects the latest data of its tables.
+    execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host3', 3.3, 3000)",
+    )
+    .await;
+    // Views are expanded in views.
+    execute_sql(
+        &instance,
+        "create view busy_host_names as select host from busy_hosts",
+    )
+    .await;
+    let output = execute_sql(&instance, "select * from busy_host_names order by host").await;
+    let expected = "\
++-------+
+| host  |
++-------+
+| host2 |
+| host3 |
++-------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    let result = try_execute_sql(&instance, "create view busy_hosts as select * from demo").await;
+    assert!(result.is_err());
+    let result = try_execute_sql(&instance, "create or replace view demo as select 1").await;
+    assert!(result.is_err());
+    let result = try_execute_sql(&instance, "create view v as select * from not_exist").await;
+    assert!(result.is_err());
+
+    execute_sql(
+        &instance,
+        "create or replace view busy_hosts as select host, cpu from demo where cpu > 3",
+    )
+    .await;
+    let output = execute_sql(&instance, "select * from busy_hosts").await;
+    let expected = "\
++-------+-----+
+| host  | cpu |
++-------+-----+
+| host3 | 3.3 |
++-------+-----+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    // A view referring to itself can't be expanded.
+    execute_sql(
+        &instance,
+        "create or replace view busy_hosts as select * from busy_host_names",
+    )
+    .await;
+    let result = try_execute_sql(&instance, "select * from busy_hosts").await;
+    assert!(result.is_err());
+
+    let output = execute_sql(&instance, "drop view busy_host_names").await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let result = try_execute_sql(&instance, "select * from busy_host_names").await;
+    assert!(result.is_err());
+    let output = execute_sql(&instance, "drop view if exists busy_host_names").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let result = try_execute_sql(&instance, "drop view busy_host_names").await;
+    assert!(result.is_err());
+    let result = try_execute_sql(&instance, "drop view demo").await;
+    assert!(result.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_create_flow() {
+    let instance = setup_test_instance("test_create_flow").await;
+
+    execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, ts timestamp time index, primary key(host))",
+    )
+    .await;
+    execute_sql(
+        &instance,
+        "create table demo_10s(host string, cpu double, ts timestamp time index, primary key(host))",
+    )
+    .await;
+    // Windows only written before the flow is created are not aggregated.
+    execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host1', 9.0, 35000)",
+    )
+    .await;
+
+    let create_flow = r"create flow demo_flow sink to demo_10s as
+select date_bin(interval '10 seconds', ts) as ts, host, avg(cpu) as cpu from demo
+group by date_bin(interval '10 seconds', ts), host";
+    let output = execute_sql(&instance, create_flow).await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let result = try_execute_sql(&instance, create_flow).await;
+    assert!(result.is_err());
+    let output = execute_sql(
+        &instance,
+        &create_flow.replace("create flow", "create flow if not exists"),
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host1', 1.0, 1000), ('host1', 3.0, 2000), ('host2', 5.0, 11000)",
+    )
+    .await;
+    // The pending range is persisted once the rows are written.
+    let select_pending = "select pending_start, pending_end from greptime.public.flows";
+    let output = execute_sql(&instance, select_pending).await;
+    let expected = "\
++---------------+-------------+
+| pending_start | pending_end |
++---------------+-------------+
+| 1000          | 11000       |
++---------------+-------------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    let flow_manager = instance.inner().sql_handler().flow_manager().clone();
+    flow_manager.run_flows().await;
+    let output = execute_sql(&instance, "select host, cpu, ts from demo_10s order by ts").await;
+    let expected = "\
++-------+-----+---------------------+
+| host  | cpu | ts                  |
++-------+-----+---------------------+
+| host1 | 2.0 | 1970-01-01T00:00:00 |
+| host2 | 5.0 | 1970-01-01T00:00:10 |
++-------+-----+---------------------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    // Writing into an aggregated window recomputes the whole window.
+    execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host2', 7.0, 12000)",
+    )
+    .await;
+    flow_manager.run_flows().await;
+    let output = execute_sql(&instance, "select host, cpu, ts from demo_10s order by ts").await;
+    let expected = "\
++-------+-----+---------------------+
+| host  | cpu | ts                  |
++-------+-----+---------------------+
+| host1 | 2.0 | 1970-01-01T00:00:00 |
+| host2 | 6.0 | 1970-01-01T00:00:10 |
++-------+-----+---------------------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    // Deleting from an aggregated window recomputes the window, too.
+    execute_sql(
+        &instance,
+        "delete from demo where host = 'host2' and ts = 12000",
+    )
+    .await;
+    flow_manager.run_flows().await;
+    let output = execute_sql(&instance, "select host, cpu, ts from demo_10s order by ts").await;
+    let expected = "\
++-------+-----+---------------------+
+| host  | cpu | ts                  |
++-------+-----+---------------------+
+| host1 | 2.0 | 1970-01-01T00:00:00 |
+| host2 | 5.0 | 1970-01-01T00:00:10 |
++-------+-----+---------------------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+    let output = execute_sql(&instance, select_pending).await;
+    let expected = "\
++---------------+-------------+
+| pending_start | pending_end |
++---------------+-------------+
+|               |             |
++---------------+-------------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    // Invalid flows.
+    let result = try_execute_sql(
+        &instance,
+        "create flow f sink to not_exist as select date_bin(interval '10 seconds', ts) as ts from demo group by date_bin(interval '10 seconds', ts)",
+    )
+    .await;
+    assert!(result.is_err());
+    let result = try_execute_sql(
+        &instance,
+        "create flow f sink to demo_10s as select host, avg(cpu) as cpu from demo group by host",
+    )
+    .await;
+    assert!(result.is_err());
+    let result = try_execute_sql(
+        &instance,
+        "create flow f sink to demo_10s as select date_bin(interval '10 seconds', ts) as ts, max(cpu) as max_cpu from demo group by date_bin(interval '10 seconds', ts)",
+    )
+    .await;
+    assert!(result.is_err());
+
+    let output = execute_sql(&instance, "drop flow demo_flow").await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let output = execute_sql(&instance, "drop flow if exists demo_flow").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let result = try_execute_sql(&instance, "drop flow demo_flow").await;
+    assert!(result.is_err());
+
+    // Dropped flows no longer aggregate new rows.
+    execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host1', 10.0, 21000)",
+    )
+    .await;
+    flow_manager.run_flows().await;
+    let output = execute_sql(&instance, "select count(*) from demo_10s").await;
+    let expected = "\
++-----------------+
+| COUNT(UInt8(1)) |
++-----------------+
+| 2               |
++-----------------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+}
+
 async fn test_insert_with_default_value_for_type(type_name: &str) {
     let instance = MockInstance::new("execute_create").await;
 
@@ -747,7 +1324,7 @@ async fn test_delete() {
 
     let output = execute_sql(
         &instance,
-        "delete from test_table where host = host1 and ts = 1655276557000 ",
+        "delete from test_table where host = 'host1' and ts = 1655276557000 ",
     )
     .await;
     assert!(matches!(output, Output::AffectedRows(1)));
@@ -765,6 +1342,89 @@ async fn test_delete() {
     check_output_stream(output, expect).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_delete_with_predicates() {
+    let instance = MockInstance::new("test_delete_with_predicates").await;
+
+    execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, ts timestamp time index, primary key(host));",
+    )
+    .await;
+
+    let output = execute_sql(
+        &instance,
+        r#"insert into demo(host, cpu, ts) values
+                            ('old-1', 1.0, 1000),
+                            ('old-2', 2.0, 2000),
+                            ('new-1', 3.0, 2000),
+                            ('old-1', 4.0, 5000),
+                            ('new-1', 5.0, 6000)
+                            "#,
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(5)));
+
+    let output = execute_sql(
+        &instance,
+        "delete from demo where ts < 3000 and host like 'old-%'",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+
+    let output = execute_sql(
+        &instance,
+        "select host, cpu, ts from demo order by ts, host",
+    )
+    .await;
+    let expected = "\
++-------+-----+---------------------+
+| host  | cpu | ts                  |
++-------+-----+---------------------+
+| new-1 | 3.0 | 1970-01-01T00:00:02 |
+| old-1 | 4.0 | 1970-01-01T00:00:05 |
+| new-1 | 5.0 | 1970-01-01T00:00:06 |
++-------+-----+---------------------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    // Rows in the SST are all inside the deleted range, so the SST is dropped as a whole,
+    // then the row in the memtable is deleted by the scan.
+    execute_sql(
+        &instance,
+        "create table metrics(host string, cpu double, ts timestamp time index, primary key(host));",
+    )
+    .await;
+    let output = execute_sql(
+        &instance,
+        "insert into metrics(host, cpu, ts) values ('host1', 1.0, 1000), ('host2', 2.0, 2000), ('host1', 3.0, 3000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(3)));
+    let output = execute_sql(&instance, "admin flush table metrics").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    execute_sql(
+        &instance,
+        "insert into metrics(host, cpu, ts) values ('host2', 4.0, 6000), ('host3', 5.0, 2000)",
+    )
+    .await;
+
+    let output = execute_sql(&instance, "delete from metrics where ts < 5000").await;
+    assert!(matches!(output, Output::AffectedRows(4)));
+
+    let output = execute_sql(&instance, "select host, cpu, ts from metrics").await;
+    let expected = "\
++-------+-----+---------------------+
+| host  | cpu | ts                  |
++-------+-----+---------------------+
+| host2 | 4.0 | 1970-01-01T00:00:06 |
++-------+-----+---------------------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_execute_copy_to() {
     let instance = setup_test_instance("test_execute_copy_to").await;
@@ -786,13 +1446,359 @@ async fn test_execute_copy_to() {
     .await;
     assert!(matches!(output, Output::AffectedRows(2)));
 
-    // exports
+    // exports, paths are relative to the data home
+    let output = execute_sql(&instance, "Copy demo TO 'export/demo.parquet'").await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+    let data_dir = instance.data_tmp_dir().path();
+    assert!(data_dir.join("export/demo.parquet_1_2").exists());
+
+    // paths can't escape from the data home
+    let result = try_execute_sql(&instance, "Copy demo TO '../demo.parquet'").await;
+    assert!(matches!(result, Err(Error::InvalidCopyPath { .. })));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_execute_copy_from() {
+    let instance = setup_test_instance("test_execute_copy_from").await;
+
+    // setups
+    execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, memory double, ts timestamp time index);",
+    )
+    .await;
+    execute_sql(
+        &instance,
+        "create table demo_copy(host string, cpu double, memory double, ts timestamp time index);",
+    )
+    .await;
+
+    let output = execute_sql(
+        &instance,
+        r#"insert into demo(host, cpu, memory, ts) values
+                            ('host1', 66.6, 1024, 1655276557000),
+                            ('host2', 88.8,  333.3, 1655276558000)
+                            "#,
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+
+    // both exports and imports use paths relative to the data home
+    let path = "export/demo.parquet";
+    let output = execute_sql(&instance, &format!("Copy demo TO '{path}'")).await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+
+    // imports from parquet files, which are suffixed by their row numbers on export
+    let output = execute_sql(&instance, &format!("Copy demo_copy FROM '{path}_*'")).await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+
     let data_dir = instance.data_tmp_dir().path();
 
-    let copy_to_stmt = format!("Copy demo TO '{}/export/demo.parquet'", data_dir.display());
+    // imports from csv file, columns are casted to the table's types
+    let csv_path = data_dir.join("demo.csv");
+    std::fs::write(
+        csv_path,
+        "ts,host,cpu,memory\n1655276559000,host3,77.7,2048\n",
+    )
+    .unwrap();
+    let copy_from_stmt = "Copy demo_copy FROM 'demo.csv' WITH (FORMAT = 'csv')";
+    let output = execute_sql(&instance, copy_from_stmt).await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    // imports from json file, the missing column is filled with null
+    let json_path = data_dir.join("demo.json");
+    std::fs::write(
+        json_path,
+        "{\"host\": \"host4\", \"cpu\": 55.5, \"ts\": 1655276560000}\n",
+    )
+    .unwrap();
+    let copy_from_stmt = "Copy demo_copy FROM 'demo.json' WITH (FORMAT = 'json')";
+    let output = execute_sql(&instance, copy_from_stmt).await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    let output = execute_sql(&instance, "select * from demo_copy order by ts").await;
+    let expect = "\
++-------+------+--------+---------------------+
+| host  | cpu  | memory | ts                  |
++-------+------+--------+---------------------+
+| host1 | 66.6 | 1024.0 | 2022-06-15T07:02:37 |
+| host2 | 88.8 | 333.3  | 2022-06-15T07:02:38 |
+| host3 | 77.7 | 2048.0 | 2022-06-15T07:02:39 |
+| host4 | 55.5 |        | 2022-06-15T07:02:40 |
++-------+------+--------+---------------------+\
+"
+    .to_string();
+    check_output_stream(output, expect).await;
+
+    // unknown column in file
+    let csv_path = data_dir.join("unknown.csv");
+    std::fs::write(csv_path, "ts,region\n1655276561000,us\n").unwrap();
+    let copy_from_stmt = "Copy demo_copy FROM 'unknown.csv' WITH (FORMAT = 'csv')";
+    let result = try_execute_sql(&instance, copy_from_stmt).await;
+    assert!(matches!(result, Err(Error::ColumnNotFound { .. })));
 
-    let output = execute_sql(&instance, &copy_to_stmt).await;
+    // no file matches
+    let result = try_execute_sql(&instance, "Copy demo_copy FROM 'none_*.parquet'").await;
+    assert!(matches!(result, Err(Error::NoMatchingFile { .. })));
+
+    // paths can't escape from the data home
+    let result = try_execute_sql(&instance, "Copy demo_copy FROM '../demo.csv'").await;
+    assert!(matches!(result, Err(Error::InvalidCopyPath { .. })));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_execute_flush_and_compact() {
+    let instance = setup_test_instance("test_execute_flush_and_compact").await;
+
+    execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, memory double, ts timestamp time index);",
+    )
+    .await;
+
+    let output = execute_sql(
+        &instance,
+        r#"insert into demo(host, cpu, memory, ts) values
+                            ('host1', 66.6, 1024, 1655276557000),
+                            ('host2', 88.8,  333.3, 1655276558000)
+                            "#,
+    )
+    .await;
     assert!(matches!(output, Output::AffectedRows(2)));
+
+    let output = execute_sql(&instance, "admin flush table demo").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, cpu, memory, ts) values ('host3', 99.9, 512, 1655276559000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    let output = execute_sql(&instance, "admin flush table demo region 0").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(&instance, "admin compact table demo").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(
+        &instance,
+        "select host, cpu, memory, ts from demo order by ts",
+    )
+    .await;
+    let expected = "\
++-------+------+--------+---------------------+
+| host  | cpu  | memory | ts                  |
++-------+------+--------+---------------------+
+| host1 | 66.6 | 1024.0 | 2022-06-15T07:02:37 |
+| host2 | 88.8 | 333.3  | 2022-06-15T07:02:38 |
+| host3 | 99.9 | 512.0  | 2022-06-15T07:02:39 |
++-------+------+--------+---------------------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    // region not found
+    let result = try_execute_sql(&instance, "admin compact table demo region 1").await;
+    assert!(matches!(result, Err(Error::CompactTable { .. })));
+
+    // table not found
+    let result = try_execute_sql(&instance, "admin flush table not_exist").await;
+    assert!(matches!(result, Err(Error::FlushTable { .. })));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_query_with_bloom_filter_index() {
+    let instance = setup_test_instance("test_query_with_bloom_filter_index").await;
+
+    execute_sql(
+        &instance,
+        r#"create table demo(host string, cpu double, ts timestamp time index, primary key(host))
+            engine=mito with("index.bloom_filter_columns"='host');"#,
+    )
+    .await;
+
+    let output = execute_sql(
+        &instance,
+        r#"insert into demo(host, cpu, ts) values
+                            ('host1', 66.6, 1655276557000),
+                            ('host2', 88.8, 1655276558000),
+                            ('host3', 99.9, 1655276559000)
+                            "#,
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(3)));
+    let output = execute_sql(&instance, "admin flush table demo").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(
+        &instance,
+        "select host, cpu, ts from demo where host = 'host2' or host in ('host3', 'host4') order by ts",
+    )
+    .await;
+    let expected = "\
++-------+------+---------------------+
+| host  | cpu  | ts                  |
++-------+------+---------------------+
+| host2 | 88.8 | 2022-06-15T07:02:38 |
+| host3 | 99.9 | 2022-06-15T07:02:39 |
++-------+------+---------------------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    let output = execute_sql(&instance, "select host from demo where host = 'host4'").await;
+    match output {
+        Output::Stream(s) => {
+            let batches = util::collect(s).await.unwrap();
+            assert!(batches.iter().all(|batch| batch.num_rows() == 0));
+        }
+        _ => unreachable!(),
+    }
+
+    // only primary key columns can be indexed
+    let result = try_execute_sql(
+        &instance,
+        r#"create table demo2(host string, cpu double, ts timestamp time index)
+            engine=mito with("index.bloom_filter_columns"='cpu');"#,
+    )
+    .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_explain_analyze() {
+    let instance = setup_test_instance("test_explain_analyze").await;
+
+    execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, ts timestamp time index, primary key(host))",
+    )
+    .await;
+    execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host1', 66.6, 1655276557000)",
+    )
+    .await;
+    let output = execute_sql(&instance, "admin flush table demo").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host2', 88.8, 1655276558000)",
+    )
+    .await;
+
+    let output = execute_sql(&instance, "explain analyze select * from demo").await;
+    let recordbatches = match output {
+        Output::Stream(stream) => util::collect_batches(stream).await.unwrap(),
+        Output::RecordBatches(recordbatches) => recordbatches,
+        _ => unreachable!(),
+    };
+    let pretty_print = recordbatches.pretty_print().unwrap();
+    for metric in [
+        "output_rows=2",
+        "memtables_read=1",
+        "ssts_opened=1",
+        "ssts_pruned=0",
+        "row_groups_skipped=0",
+        "bytes_fetched=",
+        "merge_time=",
+        "dedup_time=",
+    ] {
+        assert!(pretty_print.contains(metric), "{}", pretty_print);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_range_query() {
+    let instance = setup_test_instance("test_range_query").await;
+
+    execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, ts timestamp time index, primary key(host))",
+    )
+    .await;
+    execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host1', 1.0, 0), ('host1', 2.0, 10000), ('host1', 5.0, 40000), ('host2', 10.0, 0)",
+    )
+    .await;
+
+    // Groups by the primary key by default, and skips aligned timestamps without data.
+    let output = execute_sql(
+        &instance,
+        "select ts, host, avg(cpu) range '10s' from demo align '10s' order by host, ts",
+    )
+    .await;
+    let expected = "\
++---------------------+-------+---------------+
+| ts                  | host  | AVG(demo.cpu) |
++---------------------+-------+---------------+
+| 1970-01-01T00:00:00 | host1 | 1.0           |
+| 1970-01-01T00:00:10 | host1 | 2.0           |
+| 1970-01-01T00:00:40 | host1 | 5.0           |
+| 1970-01-01T00:00:00 | host2 | 10.0          |
++---------------------+-------+---------------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    let output = execute_sql(
+        &instance,
+        "select ts, host, avg(cpu) range '10s' fill linear, max(cpu) range '10s' from demo align '10s' by (host) fill prev order by host, ts",
+    )
+    .await;
+    let expected = "\
++---------------------+-------+---------------+---------------+
+| ts                  | host  | AVG(demo.cpu) | MAX(demo.cpu) |
++---------------------+-------+---------------+---------------+
+| 1970-01-01T00:00:00 | host1 | 1.0           | 1.0           |
+| 1970-01-01T00:00:10 | host1 | 2.0           | 2.0           |
+| 1970-01-01T00:00:20 | host1 | 3.0           | 2.0           |
+| 1970-01-01T00:00:30 | host1 | 4.0           | 2.0           |
+| 1970-01-01T00:00:40 | host1 | 5.0           | 5.0           |
+| 1970-01-01T00:00:00 | host2 | 10.0          | 10.0          |
++---------------------+-------+---------------+---------------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    // Ranges of different lengths, without grouping.
+    let output = execute_sql(
+        &instance,
+        "select ts, sum(cpu) range '20s' fill null, min(cpu) range '10s' fill 0 from demo align '10s' by () order by ts",
+    )
+    .await;
+    let expected = "\
++---------------------+---------------+---------------+
+| ts                  | SUM(demo.cpu) | MIN(demo.cpu) |
++---------------------+---------------+---------------+
+| 1970-01-01T00:00:00 | 11.0          | 1.0           |
+| 1970-01-01T00:00:10 | 13.0          | 2.0           |
+| 1970-01-01T00:00:20 | 2.0           | 0.0           |
+| 1970-01-01T00:00:30 |               | 0.0           |
+| 1970-01-01T00:00:40 | 5.0           | 5.0           |
+| 1970-01-01T00:00:50 | 5.0           | 0.0           |
++---------------------+---------------+---------------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    for sql in [
+        // GROUP BY is replaced by BY.
+        "select avg(cpu) range '10s' from demo group by host align '10s'",
+        // Aggregations must have ranges.
+        "select avg(cpu) range '10s', max(cpu) from demo align '10s'",
+        "select avg(cpu) range 'foo' from demo align '10s'",
+        "select avg(cpu) range '10s' from demo align '0s'",
+        "select max(host) range '10s' fill linear from demo align '10s'",
+        "select avg(cpu) range '10s' fill 'foo' from demo align '10s'",
+        "select avg(cpu) range '10s' from demo",
+    ] {
+        let result = try_execute_sql(&instance, sql).await;
+        assert!(result.is_err(), "{sql}");
+    }
 }
 
 #[tokio::test(flavor = "multi_thread")]
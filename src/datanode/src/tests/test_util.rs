@@ -157,7 +157,7 @@ pub async fn create_mock_sql_handler() -> SqlHandler {
     let mock_engine = Arc::new(MockMitoEngine::new(
         EngineConfig::default(),
         MockEngine::default(),
-        object_store,
+        object_store.clone(),
     ));
     let catalog_manager = Arc::new(
         catalog::local::LocalCatalogManager::try_new(mock_engine.clone())
@@ -174,6 +174,7 @@ pub async fn create_mock_sql_handler() -> SqlHandler {
         factory.query_engine(),
         mock_engine,
         None,
+        object_store,
     )
 }
 
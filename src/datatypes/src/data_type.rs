@@ -143,6 +143,43 @@ impl ConcreteDataType {
             _ => None,
         }
     }
+
+    /// Returns true if all values of this type could be represented by `target` without
+    /// losing precision, e.g. from int32 to int64, so a column of this type could be
+    /// widened to `target`.
+    pub fn can_widen_to(&self, target: &ConcreteDataType) -> bool {
+        use ConcreteDataType::*;
+
+        if self == target {
+            return true;
+        }
+        match self {
+            Int8(_) => matches!(
+                target,
+                Int16(_) | Int32(_) | Int64(_) | Float32(_) | Float64(_)
+            ),
+            Int16(_) => matches!(target, Int32(_) | Int64(_) | Float32(_) | Float64(_)),
+            Int32(_) => matches!(target, Int64(_) | Float64(_)),
+            UInt8(_) => matches!(
+                target,
+                UInt16(_)
+                    | UInt32(_)
+                    | UInt64(_)
+                    | Int16(_)
+                    | Int32(_)
+                    | Int64(_)
+                    | Float32(_)
+                    | Float64(_)
+            ),
+            UInt16(_) => matches!(
+                target,
+                UInt32(_) | UInt64(_) | Int32(_) | Int64(_) | Float32(_) | Float64(_)
+            ),
+            UInt32(_) => matches!(target, UInt64(_) | Int64(_) | Float64(_)),
+            Float32(_) => matches!(target, Float64(_)),
+            _ => false,
+        }
+    }
 }
 
 impl TryFrom<&ArrowDataType> for ConcreteDataType {
@@ -514,4 +551,27 @@ mod tests {
         );
         assert!(ConcreteDataType::int32_datatype().as_list().is_none());
     }
+
+    #[test]
+    fn test_can_widen_to() {
+        let int32 = ConcreteDataType::int32_datatype();
+        assert!(int32.can_widen_to(&int32));
+        assert!(int32.can_widen_to(&ConcreteDataType::int64_datatype()));
+        assert!(int32.can_widen_to(&ConcreteDataType::float64_datatype()));
+        assert!(!int32.can_widen_to(&ConcreteDataType::int16_datatype()));
+        assert!(!int32.can_widen_to(&ConcreteDataType::float32_datatype()));
+        assert!(!int32.can_widen_to(&ConcreteDataType::uint32_datatype()));
+        assert!(!int32.can_widen_to(&ConcreteDataType::string_datatype()));
+
+        assert!(
+            ConcreteDataType::uint32_datatype().can_widen_to(&ConcreteDataType::int64_datatype())
+        );
+        assert!(ConcreteDataType::float32_datatype()
+            .can_widen_to(&ConcreteDataType::float64_datatype()));
+        assert!(!ConcreteDataType::float64_datatype()
+            .can_widen_to(&ConcreteDataType::float32_datatype()));
+        assert!(
+            !ConcreteDataType::string_datatype().can_widen_to(&ConcreteDataType::binary_datatype())
+        );
+    }
 }
@@ -150,6 +150,19 @@ impl ColumnDefaultConstraint {
         }
     }
 
+    /// Converts the default value of this constraint to `data_type`, which is used when
+    /// the type of the column changes. Function constraints are returned unchanged.
+    pub fn cast_to(&self, data_type: &ConcreteDataType) -> Result<ColumnDefaultConstraint> {
+        match self {
+            ColumnDefaultConstraint::Value(v) if !v.is_null() => {
+                let vector = self.create_default_vector(&v.data_type(), false, 1)?;
+                let vector = vector.cast(data_type)?;
+                Ok(ColumnDefaultConstraint::Value(vector.get(0)))
+            }
+            _ => Ok(self.clone()),
+        }
+    }
+
     /// Returns true if this constraint might creates NULL.
     fn maybe_null(&self) -> bool {
         // Once we support more functions, we may return true if given function
@@ -300,4 +313,30 @@ mod tests {
             .unwrap_err();
         assert!(matches!(err, Error::DefaultValueType { .. }), "{err:?}");
     }
+
+    #[test]
+    fn test_cast_default_value() {
+        let constraint = ColumnDefaultConstraint::Value(Value::Int32(10));
+        let constraint = constraint
+            .cast_to(&ConcreteDataType::int64_datatype())
+            .unwrap();
+        assert_eq!(ColumnDefaultConstraint::Value(Value::Int64(10)), constraint);
+
+        let constraint = ColumnDefaultConstraint::Value(Value::Float32(1.5f32.into()));
+        let constraint = constraint
+            .cast_to(&ConcreteDataType::float64_datatype())
+            .unwrap();
+        assert_eq!(
+            ColumnDefaultConstraint::Value(Value::Float64(1.5f64.into())),
+            constraint
+        );
+
+        let constraint = ColumnDefaultConstraint::null_value();
+        assert_eq!(
+            constraint,
+            constraint
+                .cast_to(&ConcreteDataType::int64_datatype())
+                .unwrap()
+        );
+    }
 }
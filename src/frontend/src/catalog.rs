@@ -20,19 +20,20 @@ use async_trait::async_trait;
 use catalog::error::{self as catalog_err, InvalidCatalogValueSnafu, Result as CatalogResult};
 use catalog::helper::{
     build_catalog_prefix, build_schema_prefix, build_table_global_prefix, CatalogKey, SchemaKey,
-    TableGlobalKey, TableGlobalValue,
+    SchemaValue, TableGlobalKey, TableGlobalValue,
 };
 use catalog::remote::{Kv, KvBackendRef};
 use catalog::{
-    CatalogList, CatalogManager, CatalogProvider, CatalogProviderRef, DeregisterTableRequest,
-    RegisterSchemaRequest, RegisterSystemTableRequest, RegisterTableRequest, RenameTableRequest,
-    SchemaProvider, SchemaProviderRef,
+    CatalogList, CatalogManager, CatalogProvider, CatalogProviderRef, DeregisterSchemaRequest,
+    DeregisterTableRequest, RegisterSchemaRequest, RegisterSystemTableRequest,
+    RegisterTableRequest, RenameTableRequest, SchemaProvider, SchemaProviderRef,
 };
 use common_telemetry::error;
 use futures::StreamExt;
 use meta_client::rpc::TableName;
 use partition::manager::PartitionRuleManagerRef;
 use snafu::prelude::*;
+use table::requests::TableOptions;
 use table::TableRef;
 
 use crate::datanode::DatanodeClients;
@@ -100,6 +101,16 @@ impl CatalogManager for FrontendCatalogManager {
         unimplemented!()
     }
 
+    async fn deregister_schema(
+        &self,
+        _request: DeregisterSchemaRequest,
+    ) -> catalog::error::Result<bool> {
+        catalog_err::UnimplementedSnafu {
+            operation: "deregister schema in frontend catalog manager",
+        }
+        .fail()
+    }
+
     async fn rename_table(&self, _request: RenameTableRequest) -> catalog_err::Result<bool> {
         unimplemented!()
     }
@@ -247,6 +258,13 @@ impl CatalogProvider for FrontendCatalogProvider {
             Ok(None)
         }
     }
+
+    fn deregister_schema(&self, _name: &str) -> catalog::error::Result<Option<SchemaProviderRef>> {
+        catalog_err::UnimplementedSnafu {
+            operation: "deregister schema in frontend catalog provider",
+        }
+        .fail()
+    }
 }
 
 pub struct FrontendSchemaProvider {
@@ -329,4 +347,25 @@ impl SchemaProvider for FrontendSchemaProvider {
     fn table_exist(&self, name: &str) -> catalog::error::Result<bool> {
         Ok(self.table_names()?.contains(&name.to_string()))
     }
+
+    fn table_options(&self) -> catalog::error::Result<TableOptions> {
+        let backend = self.backend.clone();
+        let schema_key = SchemaKey {
+            catalog_name: self.catalog_name.clone(),
+            schema_name: self.schema_name.clone(),
+        }
+        .to_string();
+
+        std::thread::spawn(|| {
+            common_runtime::block_on_read(async move {
+                let Some(Kv(_, value)) = backend.get(schema_key.as_bytes()).await? else {
+                    return Ok(TableOptions::default());
+                };
+                let value = SchemaValue::from_bytes(value).context(InvalidCatalogValueSnafu)?;
+                Ok(value.options)
+            })
+        })
+        .join()
+        .unwrap()
+    }
 }
@@ -24,8 +24,8 @@ use session::context::QueryContextRef;
 use snafu::{ensure, ResultExt};
 use sql::ast::{ColumnDef, ColumnOption, SqlOption, TableConstraint, Value};
 use sql::statements::column_def_to_schema;
-use sql::statements::create::{CreateTable, TIME_INDEX};
-use table::requests::TableOptions;
+use sql::statements::create::{CreateDatabase, CreateTable, TIME_INDEX};
+use table::requests::{TableOptions, INDEX_BLOOM_FILTER_COLUMNS_KEY};
 
 use crate::error::{
     self, BuildCreateExprOnInsertionSnafu, ColumnDataTypeSnafu,
@@ -100,6 +100,25 @@ pub(crate) fn create_to_expr(
     Ok(expr)
 }
 
+/// Converts the options of `CreateDatabase` statement to the default table options of
+/// the database.
+pub(crate) fn create_database_options(create: &CreateDatabase) -> Result<TableOptions> {
+    let options = stmt_options_to_table_options(&create.options)?;
+    // Bloom filter indexes are built on specific columns, so they can't be defaults of
+    // all tables in the database.
+    ensure!(
+        !options
+            .extra_options
+            .contains_key(INDEX_BLOOM_FILTER_COLUMNS_KEY),
+        InvalidSqlSnafu {
+            err_msg: format!(
+                "option {INDEX_BLOOM_FILTER_COLUMNS_KEY} is not allowed in database options"
+            ),
+        }
+    );
+    Ok(options)
+}
+
 fn find_primary_keys(
     columns: &[ColumnDef],
     constraints: &[TableConstraint],
@@ -399,7 +399,15 @@ impl Instance {
             | Statement::Alter(_)
             | Statement::DropTable(_)
             | Statement::Tql(_)
-            | Statement::Copy(_) => self.sql_handler.do_statement_query(stmt, query_ctx).await,
+            | Statement::Copy(_)
+            | Statement::FlushTable(_)
+            | Statement::CompactTable(_)
+            | Statement::DropDatabase(_)
+            | Statement::TruncateTable(_)
+            | Statement::CreateView(_)
+            | Statement::DropView(_)
+            | Statement::CreateFlow(_)
+            | Statement::DropFlow(_) => self.sql_handler.do_statement_query(stmt, query_ctx).await,
             Statement::Use(db) => self.handle_use(db, query_ctx),
             Statement::ShowCreateTable(_) => NotSupportedSnafu {
                 feat: format!("{stmt:?}"),
@@ -453,9 +461,13 @@ impl SqlQueryHandler for Instance {
         }
     }
 
-    async fn do_promql_query(&self, query: &PromQuery, _: QueryContextRef) -> Vec<Result<Output>> {
+    async fn do_promql_query(
+        &self,
+        query: &PromQuery,
+        query_ctx: QueryContextRef,
+    ) -> Vec<Result<Output>> {
         if let Some(handler) = &self.promql_handler {
-            let result = handler.do_query(query).await.with_context(|_| {
+            let result = handler.do_query(query, query_ctx).await.with_context(|_| {
                 let query_literal = format!("{query:?}");
                 ExecutePromqlSnafu {
                     query: query_literal,
@@ -539,9 +551,13 @@ impl ScriptHandler for Instance {
 
 #[async_trait]
 impl PromqlHandler for Instance {
-    async fn do_query(&self, query: &PromQuery) -> server_error::Result<Output> {
+    async fn do_query(
+        &self,
+        query: &PromQuery,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<Output> {
         if let Some(promql_handler) = &self.promql_handler {
-            promql_handler.do_query(query).await
+            promql_handler.do_query(query, query_ctx).await
         } else {
             server_error::NotSupportedSnafu {
                 feat: "PromQL query in Frontend",
@@ -549,6 +565,32 @@ impl PromqlHandler for Instance {
             .fail()
         }
     }
+
+    async fn metric_names(&self, query_ctx: QueryContextRef) -> server_error::Result<Vec<String>> {
+        if let Some(promql_handler) = &self.promql_handler {
+            promql_handler.metric_names(query_ctx).await
+        } else {
+            server_error::NotSupportedSnafu {
+                feat: "PromQL metadata query in Frontend",
+            }
+            .fail()
+        }
+    }
+
+    async fn label_names(
+        &self,
+        metric: &str,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<Option<Vec<String>>> {
+        if let Some(promql_handler) = &self.promql_handler {
+            promql_handler.label_names(metric, query_ctx).await
+        } else {
+            server_error::NotSupportedSnafu {
+                feat: "PromQL metadata query in Frontend",
+            }
+            .fail()
+        }
+    }
 }
 
 pub fn check_permission(
@@ -582,6 +624,12 @@ pub fn check_permission(
         Statement::DropTable(drop_stmt) => {
             validate_param(drop_stmt.table_name(), query_ctx)?;
         }
+        Statement::DropDatabase(stmt) => {
+            let database = stmt.name().to_string();
+            validate_catalog_and_schema(&query_ctx.current_catalog(), &database, query_ctx)
+                .map_err(BoxedError::new)
+                .context(SqlExecInterceptedSnafu)?;
+        }
         Statement::ShowTables(stmt) => {
             if let Some(database) = &stmt.database {
                 validate_catalog_and_schema(&query_ctx.current_catalog(), database, query_ctx)
@@ -598,6 +646,28 @@ pub fn check_permission(
         Statement::Copy(stmd) => {
             validate_param(stmd.table_name(), query_ctx)?;
         }
+        Statement::FlushTable(stmt) => {
+            validate_param(stmt.table_name(), query_ctx)?;
+        }
+        Statement::CompactTable(stmt) => {
+            validate_param(stmt.table_name(), query_ctx)?;
+        }
+        Statement::TruncateTable(stmt) => {
+            validate_param(stmt.table_name(), query_ctx)?;
+        }
+        Statement::CreateView(stmt) => {
+            validate_param(&stmt.name, query_ctx)?;
+        }
+        Statement::DropView(stmt) => {
+            validate_param(stmt.name(), query_ctx)?;
+        }
+        Statement::CreateFlow(stmt) => {
+            validate_param(&stmt.name, query_ctx)?;
+            validate_param(&stmt.sink_table, query_ctx)?;
+        }
+        Statement::DropFlow(stmt) => {
+            validate_param(stmt.name(), query_ctx)?;
+        }
     }
     Ok(())
 }
@@ -732,6 +802,17 @@ mod tests {
         let re = check_permission(plugins.clone(), &stmt[0], &query_ctx);
         assert!(re.is_err());
 
+        // test drop database
+        let sql = "DROP DATABASE public";
+        let stmt = parse_stmt(sql).unwrap();
+        let re = check_permission(plugins.clone(), &stmt[0], &query_ctx);
+        assert!(re.is_ok());
+
+        let sql = "DROP DATABASE wrongschema";
+        let stmt = parse_stmt(sql).unwrap();
+        let re = check_permission(plugins.clone(), &stmt[0], &query_ctx);
+        assert!(re.is_err());
+
         // test describe table
         let sql = "DESC TABLE {catalog}{schema}demo;";
         replace_test(sql, plugins.clone(), &query_ctx);
@@ -28,7 +28,7 @@ use catalog::{CatalogManager, DeregisterTableRequest, RegisterTableRequest};
 use chrono::DateTime;
 use client::Database;
 use common_base::Plugins;
-use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, INFORMATION_SCHEMA_NAME};
 use common_catalog::format_full_table_name;
 use common_error::prelude::BoxedError;
 use common_query::Output;
@@ -39,8 +39,8 @@ use datatypes::schema::{RawSchema, Schema};
 use meta_client::client::MetaClient;
 use meta_client::rpc::router::DeleteRequest as MetaDeleteRequest;
 use meta_client::rpc::{
-    CompareAndPutRequest, CreateRequest as MetaCreateRequest, Partition as MetaPartition,
-    RouteResponse, TableName,
+    CompareAndPutRequest, CreateRequest as MetaCreateRequest, DeleteRangeRequest,
+    Partition as MetaPartition, RouteResponse, TableName,
 };
 use partition::partition::{PartitionBound, PartitionDef};
 use query::parser::{PromQuery, QueryStatement};
@@ -49,8 +49,9 @@ use query::{QueryEngineFactory, QueryEngineRef};
 use servers::query_handler::sql::SqlQueryHandler;
 use session::context::QueryContextRef;
 use snafu::{ensure, OptionExt, ResultExt};
-use sql::ast::Value as SqlValue;
+use sql::ast::{ObjectName, Value as SqlValue};
 use sql::statements::create::Partitions;
+use sql::statements::drop::DropDatabase;
 use sql::statements::sql_value_to_value;
 use sql::statements::statement::Statement;
 use table::metadata::{RawTableInfo, RawTableMeta, TableIdent, TableType};
@@ -128,6 +129,7 @@ impl DistInstance {
             };
         }
 
+        self.fill_default_table_options(create_table)?;
         let mut table_info = create_table_info(create_table)?;
 
         let response = self
@@ -266,6 +268,105 @@ impl DistInstance {
         Ok(Output::AffectedRows(1))
     }
 
+    /// Drops all tables of the database before removing the database itself, so a failed drop
+    /// leaves the database in place and the statement could be retried.
+    async fn drop_database(
+        &self,
+        stmt: DropDatabase,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let catalog = query_ctx.current_catalog();
+        let schema = stmt.name().to_string();
+        ensure!(
+            schema != DEFAULT_SCHEMA_NAME && schema != INFORMATION_SCHEMA_NAME,
+            error::InvalidSqlSnafu {
+                err_msg: format!("database {schema} can't be dropped"),
+            }
+        );
+
+        let schema_provider = match self
+            .catalog_manager
+            .schema(&catalog, &schema)
+            .context(CatalogSnafu)?
+        {
+            Some(schema_provider) => schema_provider,
+            None if stmt.if_exists() => return Ok(Output::AffectedRows(0)),
+            None => {
+                return error::SchemaNotFoundSnafu {
+                    schema_info: format!("{catalog}.{schema}"),
+                }
+                .fail()
+            }
+        };
+
+        for table_name in schema_provider.table_names().context(CatalogSnafu)? {
+            let table_name = TableName::new(&catalog, &schema, table_name);
+            self.drop_table(table_name).await?;
+        }
+
+        let key = SchemaKey {
+            catalog_name: catalog,
+            schema_name: schema,
+        };
+        self.meta_client
+            .delete_range(DeleteRangeRequest::new().with_key(key.to_string()))
+            .await
+            .context(RequestMetaSnafu)?;
+
+        info!("Successfully dropped database: {}", key.schema_name);
+
+        Ok(Output::AffectedRows(1))
+    }
+
+    /// Sends `sql` to the Datanodes leading the regions of the table, or only to the leader of
+    /// the region if `region_number` is given.
+    async fn forward_to_region_leaders(
+        &self,
+        table_name: &TableName,
+        region_number: Option<u32>,
+        sql: &str,
+    ) -> Result<Output> {
+        let table_route = self
+            .catalog_manager
+            .partition_manager()
+            .find_table_route(table_name)
+            .await
+            .with_context(|_| error::FindTableRouteSnafu {
+                table_name: table_name.to_string(),
+            })?;
+        let leaders = if let Some(region_number) = region_number {
+            let leader = table_route
+                .region_routes
+                .iter()
+                .find(|x| x.region.id == region_number as u64)
+                .and_then(|x| x.leader_peer.clone())
+                .context(error::FindDatanodeSnafu {
+                    region: region_number,
+                })?;
+            vec![leader]
+        } else {
+            table_route.find_leaders().into_iter().collect()
+        };
+        ensure!(
+            !leaders.is_empty(),
+            error::LeaderNotFoundSnafu {
+                table: table_name.to_string()
+            }
+        );
+
+        let mut affected_rows = 0;
+        for datanode in leaders {
+            debug!("Sending {sql} of table {table_name} to Datanode {datanode:?}");
+
+            let client = self.datanode_clients.get_client(&datanode).await;
+            let db = Database::new(&table_name.catalog_name, &table_name.schema_name, client);
+            if let Output::AffectedRows(rows) = db.sql(sql).await.context(RequestDatanodeSnafu)? {
+                affected_rows += rows;
+            }
+        }
+        Ok(Output::AffectedRows(affected_rows))
+    }
+
     async fn handle_statement(
         &self,
         stmt: Statement,
@@ -281,11 +382,12 @@ impl DistInstance {
                 self.query_engine.execute(&plan).await
             }
             Statement::CreateDatabase(stmt) => {
+                let options = expr_factory::create_database_options(&stmt)?;
                 let expr = CreateDatabaseExpr {
                     database_name: stmt.name.to_string(),
                     create_if_not_exists: stmt.if_not_exists,
                 };
-                return self.handle_create_database(expr, query_ctx).await;
+                return self.handle_create_database(expr, options, query_ctx).await;
             }
             Statement::CreateTable(stmt) => {
                 let create_expr = &mut expr_factory::create_to_expr(&stmt, query_ctx)?;
@@ -343,6 +445,43 @@ impl DistInstance {
                     table.insert(insert_request).await.context(TableSnafu)?,
                 ));
             }
+            Statement::FlushTable(stmt) => {
+                let table_name = resolve_table_name(stmt.table_name(), query_ctx)?;
+                let sql = admin_sql("FLUSH", stmt.table_name(), stmt.region_number());
+                return self
+                    .forward_to_region_leaders(&table_name, stmt.region_number(), &sql)
+                    .await;
+            }
+            Statement::CompactTable(stmt) => {
+                let table_name = resolve_table_name(stmt.table_name(), query_ctx)?;
+                let sql = admin_sql("COMPACT", stmt.table_name(), stmt.region_number());
+                return self
+                    .forward_to_region_leaders(&table_name, stmt.region_number(), &sql)
+                    .await;
+            }
+            Statement::TruncateTable(stmt) => {
+                let table_name = resolve_table_name(stmt.table_name(), query_ctx)?;
+                let sql = format!("TRUNCATE TABLE {}", stmt.table_name());
+                return self
+                    .forward_to_region_leaders(&table_name, None, &sql)
+                    .await;
+            }
+            Statement::DropDatabase(stmt) => return self.drop_database(stmt, query_ctx).await,
+            // Views are only persisted in the local catalog of standalone mode.
+            Statement::CreateView(_) | Statement::DropView(_) => {
+                return error::NotSupportedSnafu {
+                    feat: "views in distributed mode",
+                }
+                .fail()
+            }
+            // Flows are run by the Datanode over its local regions, which only hold a part of
+            // a distributed table.
+            Statement::CreateFlow(_) | Statement::DropFlow(_) => {
+                return error::NotSupportedSnafu {
+                    feat: "flows in distributed mode",
+                }
+                .fail()
+            }
             _ => {
                 return error::NotSupportedSnafu {
                     feat: format!("{stmt:?}"),
@@ -377,9 +516,12 @@ impl DistInstance {
     }
 
     /// Handles distributed database creation
+    /// Creates the database in meta, `options` are the default options of tables created
+    /// in the database.
     async fn handle_create_database(
         &self,
         expr: CreateDatabaseExpr,
+        options: TableOptions,
         query_ctx: QueryContextRef,
     ) -> Result<Output> {
         let catalog = query_ctx.current_catalog();
@@ -403,7 +545,7 @@ impl DistInstance {
             catalog_name: catalog,
             schema_name: expr.database_name,
         };
-        let value = SchemaValue {};
+        let value = SchemaValue { options };
         let client = self
             .meta_client
             .store_client()
@@ -458,6 +600,21 @@ impl DistInstance {
         Ok(Output::AffectedRows(0))
     }
 
+    /// Fills the options absent in `create_table` by the default table options of its schema.
+    fn fill_default_table_options(&self, create_table: &mut CreateTableExpr) -> Result<()> {
+        let schema = self
+            .catalog_manager
+            .schema(&create_table.catalog_name, &create_table.schema_name)
+            .context(CatalogSnafu)?;
+        let Some(schema) = schema else { return Ok(()) };
+
+        let mut options = TableOptions::try_from(&create_table.table_options)
+            .context(UnrecognizedTableOptionSnafu)?;
+        options.fill_defaults(&schema.table_options().context(CatalogSnafu)?);
+        create_table.table_options = HashMap::from(&options);
+        Ok(())
+    }
+
     async fn create_table_in_meta(
         &self,
         create_table: &CreateTableExpr,
@@ -566,6 +723,21 @@ impl SqlQueryHandler for DistInstance {
     }
 }
 
+fn resolve_table_name(name: &ObjectName, query_ctx: QueryContextRef) -> Result<TableName> {
+    let (catalog, schema, table) = table_idents_to_full_name(name, query_ctx)
+        .map_err(BoxedError::new)
+        .context(error::ExternalSnafu)?;
+    Ok(TableName::new(catalog, schema, table))
+}
+
+/// Builds the `ADMIN <command> TABLE` statement to forward to the Datanodes.
+fn admin_sql(command: &str, table_name: &ObjectName, region_number: Option<u32>) -> String {
+    match region_number {
+        Some(region_number) => format!("ADMIN {command} TABLE {table_name} REGION {region_number}"),
+        None => format!("ADMIN {command} TABLE {table_name}"),
+    }
+}
+
 fn create_table_info(create_table: &CreateTableExpr) -> Result<RawTableInfo> {
     let mut column_schemas = Vec::with_capacity(create_table.column_defs.len());
     let mut column_name_to_index_map = HashMap::new();
@@ -720,6 +892,10 @@ fn find_partition_columns(
 
 #[cfg(test)]
 mod test {
+    use std::time::Duration;
+
+    use common_base::readable_size::ReadableSize;
+    use common_recordbatch::RecordBatches;
     use itertools::Itertools;
     use servers::query_handler::sql::SqlQueryHandlerRef;
     use session::context::QueryContext;
@@ -881,4 +1057,297 @@ ENGINE=mito",
             assert_show_tables(StandaloneSqlQueryHandler::arc(x.clone())).await
         }
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_flush_and_compact_table() {
+        let instance =
+            crate::tests::create_distributed_instance("test_flush_and_compact_table").await;
+        let dist_instance = &instance.dist_instance;
+
+        let sql = "
+            CREATE TABLE dist_numbers (
+                ts BIGINT,
+                n INT,
+                TIME INDEX (ts),
+            )
+            PARTITION BY RANGE COLUMNS (n) (
+                PARTITION r0 VALUES LESS THAN (10),
+                PARTITION r1 VALUES LESS THAN (20),
+                PARTITION r2 VALUES LESS THAN (50),
+                PARTITION r3 VALUES LESS THAN (MAXVALUE),
+            )
+            ENGINE=mito";
+        dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+
+        let sql = "INSERT INTO dist_numbers (ts, n) VALUES (1, 1), (2, 11), (3, 21), (4, 51)";
+        let output = dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+        assert!(matches!(output, Output::AffectedRows(4)));
+
+        for sql in [
+            "admin flush table dist_numbers",
+            "admin flush table dist_numbers region 2",
+            "admin compact table dist_numbers",
+        ] {
+            let output = dist_instance
+                .handle_sql(sql, QueryContext::arc())
+                .await
+                .remove(0)
+                .unwrap();
+            assert!(matches!(output, Output::AffectedRows(0)));
+        }
+
+        let sql = "SELECT ts, n FROM dist_numbers ORDER BY ts";
+        let output = dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+        let Output::Stream(stream) = output else { unreachable!() };
+        let recordbatches = RecordBatches::try_collect(stream).await.unwrap();
+        let expected = "\
++----+----+
+| ts | n  |
++----+----+
+| 1  | 1  |
+| 2  | 11 |
+| 3  | 21 |
+| 4  | 51 |
++----+----+";
+        assert_eq!(recordbatches.pretty_print().unwrap(), expected);
+
+        let sql = "admin flush table dist_numbers region 4";
+        let result = dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0);
+        assert!(matches!(result, Err(error::Error::FindDatanode { .. })));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_truncate_table() {
+        let instance = crate::tests::create_distributed_instance("test_truncate_table").await;
+        let dist_instance = &instance.dist_instance;
+
+        let sql = "
+            CREATE TABLE dist_numbers (
+                ts BIGINT,
+                n INT,
+                TIME INDEX (ts),
+            )
+            PARTITION BY RANGE COLUMNS (n) (
+                PARTITION r0 VALUES LESS THAN (10),
+                PARTITION r1 VALUES LESS THAN (20),
+                PARTITION r2 VALUES LESS THAN (50),
+                PARTITION r3 VALUES LESS THAN (MAXVALUE),
+            )
+            ENGINE=mito";
+        dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+
+        let sql = "INSERT INTO dist_numbers (ts, n) VALUES (1, 1), (2, 11), (3, 21), (4, 51)";
+        dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+
+        let sql = "TRUNCATE TABLE dist_numbers";
+        dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+
+        let sql = "SELECT ts, n FROM dist_numbers";
+        let output = dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+        let Output::Stream(stream) = output else { unreachable!() };
+        let recordbatches = RecordBatches::try_collect(stream).await.unwrap();
+        assert_eq!(0, recordbatches.iter().map(|x| x.num_rows()).sum::<usize>());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_drop_database() {
+        let instance = crate::tests::create_distributed_instance("test_drop_database").await;
+        let dist_instance = &instance.dist_instance;
+
+        let sql = "create database test_drop_database";
+        dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+
+        let sql = "
+            CREATE TABLE greptime.test_drop_database.dist_numbers (
+                ts BIGINT,
+                n INT,
+                TIME INDEX (ts),
+            )
+            PARTITION BY RANGE COLUMNS (n) (
+                PARTITION r0 VALUES LESS THAN (10),
+                PARTITION r1 VALUES LESS THAN (MAXVALUE),
+            )
+            ENGINE=mito";
+        dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+
+        let sql = "drop database test_drop_database";
+        let output = dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+        assert!(matches!(output, Output::AffectedRows(1)));
+        assert!(!dist_instance
+            .is_valid_schema(DEFAULT_CATALOG_NAME, "test_drop_database")
+            .unwrap());
+        let table_name = TableName::new(DEFAULT_CATALOG_NAME, "test_drop_database", "dist_numbers");
+        assert!(dist_instance
+            .catalog_manager
+            .partition_manager()
+            .find_table_route(&table_name)
+            .await
+            .is_err());
+
+        let result = dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0);
+        assert!(matches!(result, Err(error::Error::SchemaNotFound { .. })));
+
+        let sql = "drop database if exists test_drop_database";
+        let output = dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+        assert!(matches!(output, Output::AffectedRows(0)));
+
+        let sql = "drop database public";
+        let result = dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0);
+        assert!(matches!(result, Err(error::Error::InvalidSql { .. })));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_view_not_supported() {
+        let instance = crate::tests::create_distributed_instance("test_view_not_supported").await;
+        let dist_instance = &instance.dist_instance;
+
+        for sql in ["create view v as select 1", "drop view if exists v"] {
+            let result = dist_instance
+                .handle_sql(sql, QueryContext::arc())
+                .await
+                .remove(0);
+            assert!(matches!(result, Err(error::Error::NotSupported { .. })));
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_flow_not_supported() {
+        let instance = crate::tests::create_distributed_instance("test_flow_not_supported").await;
+        let dist_instance = &instance.dist_instance;
+
+        let sql = "
+            CREATE TABLE demo (
+                host STRING,
+                cpu DOUBLE,
+                ts TIMESTAMP TIME INDEX,
+            )
+            ENGINE=mito";
+        dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+
+        for sql in [
+            "create flow demo_1m sink to demo_sink as select date_bin(interval '1 minute', ts) as ts, avg(cpu) as cpu from demo group by date_bin(interval '1 minute', ts)",
+            "drop flow if exists demo_1m",
+        ] {
+            let result = dist_instance
+                .handle_sql(sql, QueryContext::arc())
+                .await
+                .remove(0);
+            assert!(matches!(result, Err(error::Error::NotSupported { .. })));
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_create_database_with_options() {
+        let instance =
+            crate::tests::create_distributed_instance("test_create_database_with_options").await;
+        let dist_instance = &instance.dist_instance;
+        let datanode_instances = instance.datanodes;
+
+        let sql = "create database test_db_options with (ttl='7d', write_buffer_size='32MB')";
+        dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+
+        // Options of the table take precedence over the defaults of the database.
+        let sql = "
+            CREATE TABLE greptime.test_db_options.demo (
+                ts TIMESTAMP TIME INDEX,
+                n INT,
+            )
+            ENGINE=mito
+            WITH (ttl='1d')";
+        dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0)
+            .unwrap();
+
+        let assert_options = |options: &TableOptions| {
+            assert_eq!(Some(Duration::from_secs(24 * 3600)), options.ttl);
+            assert_eq!(Some(ReadableSize::mb(32)), options.write_buffer_size);
+        };
+        let table = dist_instance
+            .catalog_manager
+            .table("greptime", "test_db_options", "demo")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_options(&table.table_info().meta.options);
+        // Asserts that the table is created in Datanode with the filled options as well.
+        for x in datanode_instances.values() {
+            let table = x
+                .catalog_manager()
+                .table("greptime", "test_db_options", "demo")
+                .await
+                .unwrap()
+                .unwrap();
+            assert_options(&table.table_info().meta.options);
+        }
+
+        let sql = "create database test_db_bloom_filter with (\"index.bloom_filter_columns\"='n')";
+        let result = dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .remove(0);
+        assert!(matches!(result, Err(error::Error::InvalidSql { .. })));
+    }
 }
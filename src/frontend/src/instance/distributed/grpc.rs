@@ -26,6 +26,7 @@ use session::context::QueryContextRef;
 use snafu::{OptionExt, ResultExt};
 use sql::statements::alter::{AlterTable, AlterTableOperation};
 use sql::statements::sql_column_def_to_grpc_column_def;
+use table::requests::TableOptions;
 
 use crate::error::{self, ExternalSnafu, Result};
 use crate::instance::distributed::DistInstance;
@@ -45,7 +46,11 @@ impl GrpcQueryHandler for DistInstance {
                     err_msg: "Missing 'expr' in DDL request",
                 })?;
                 match expr {
-                    DdlExpr::CreateDatabase(expr) => self.handle_create_database(expr, ctx).await,
+                    DdlExpr::CreateDatabase(expr) => {
+                        // `CreateDatabaseExpr` doesn't carry options.
+                        self.handle_create_database(expr, TableOptions::default(), ctx)
+                            .await
+                    }
                     DdlExpr::CreateTable(mut expr) => {
                         // TODO(LFC): Support creating distributed table through GRPC interface.
                         // Currently only SQL supports it; how to design the fields in CreateTableExpr?
@@ -97,6 +102,32 @@ pub(crate) fn to_alter_expr(
         AlterTableOperation::RenameTable { new_table_name } => Kind::RenameTable(RenameTable {
             new_table_name: new_table_name.to_string(),
         }),
+        AlterTableOperation::ModifyColumn { .. } => {
+            return error::NotSupportedSnafu {
+                feat: "MODIFY COLUMN",
+            }
+            .fail();
+        }
+        AlterTableOperation::RenameColumn { .. } => {
+            return error::NotSupportedSnafu {
+                feat: "RENAME COLUMN",
+            }
+            .fail();
+        }
+        AlterTableOperation::SetColumnDefault { .. }
+        | AlterTableOperation::DropColumnDefault { .. } => {
+            return error::NotSupportedSnafu {
+                feat: "ALTER COLUMN DEFAULT",
+            }
+            .fail();
+        }
+        AlterTableOperation::SetTableOptions { .. }
+        | AlterTableOperation::UnsetTableOptions { .. } => {
+            return error::NotSupportedSnafu {
+                feat: "ALTER TABLE SET/UNSET options",
+            }
+            .fail();
+        }
     };
 
     Ok(AlterExpr {
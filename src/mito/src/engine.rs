@@ -47,8 +47,9 @@ use crate::config::EngineConfig;
 use crate::engine::procedure::CreateMitoTable;
 use crate::error::{
     self, BuildColumnDescriptorSnafu, BuildColumnFamilyDescriptorSnafu, BuildRegionDescriptorSnafu,
-    BuildRowKeyDescriptorSnafu, InvalidPrimaryKeySnafu, InvalidRawSchemaSnafu,
-    MissingTimestampIndexSnafu, RegionNotFoundSnafu, Result, TableExistsSnafu,
+    BuildRowKeyDescriptorSnafu, InvalidIndexColumnSnafu, InvalidPrimaryKeySnafu,
+    InvalidRawSchemaSnafu, InvalidTableOptionsSnafu, MissingTimestampIndexSnafu,
+    RegionNotFoundSnafu, Result, TableExistsSnafu,
 };
 use crate::manifest::TableManifest;
 use crate::table::MitoTable;
@@ -317,6 +318,30 @@ fn validate_create_table_request(request: &CreateTableRequest) -> Result<()> {
         }
     );
 
+    let index_options =
+        request
+            .table_options
+            .index_options()
+            .context(InvalidTableOptionsSnafu {
+                table_name: &request.table_name,
+            })?;
+    for column_name in &index_options.bloom_filter_columns {
+        let is_primary_key = request
+            .schema
+            .column_schemas
+            .iter()
+            .position(|column_schema| &column_schema.name == column_name)
+            .map(|index| request.primary_key_indices.contains(&index))
+            .unwrap_or(false);
+        ensure!(
+            is_primary_key,
+            InvalidIndexColumnSnafu {
+                table_name: &request.table_name,
+                column_name,
+            }
+        );
+    }
+
     Ok(())
 }
 
@@ -364,6 +389,19 @@ impl<S: StorageEngine> MitoEngineInner<S> {
             primary_key_indices,
         )?;
 
+        let compaction_options = request
+            .table_options
+            .compaction_options()
+            .context(InvalidTableOptionsSnafu { table_name })?;
+        let index_options = request
+            .table_options
+            .index_options()
+            .context(InvalidTableOptionsSnafu { table_name })?;
+        let memtable_type = request
+            .table_options
+            .memtable_type()
+            .context(InvalidTableOptionsSnafu { table_name })?;
+
         let table_id = request.id;
         let table_dir = table_dir(catalog_name, schema_name, table_id);
         let mut regions = HashMap::with_capacity(request.region_numbers.len());
@@ -399,6 +437,9 @@ impl<S: StorageEngine> MitoEngineInner<S> {
                     .write_buffer_size
                     .map(|size| size.0 as usize),
                 ttl: request.table_options.ttl,
+                compaction_options: compaction_options.clone(),
+                index_options: index_options.clone(),
+                memtable_type,
             };
 
             let region = self
@@ -496,6 +537,27 @@ impl<S: StorageEngine> MitoEngineInner<S> {
                     .write_buffer_size
                     .map(|s| s.0 as usize),
                 ttl: table_info.meta.options.ttl,
+                compaction_options: table_info
+                    .meta
+                    .options
+                    .compaction_options()
+                    .context(InvalidTableOptionsSnafu { table_name })
+                    .map_err(BoxedError::new)
+                    .context(TableOperationSnafu)?,
+                index_options: table_info
+                    .meta
+                    .options
+                    .index_options()
+                    .context(InvalidTableOptionsSnafu { table_name })
+                    .map_err(BoxedError::new)
+                    .context(TableOperationSnafu)?,
+                memtable_type: table_info
+                    .meta
+                    .options
+                    .memtable_type()
+                    .context(InvalidTableOptionsSnafu { table_name })
+                    .map_err(BoxedError::new)
+                    .context(TableOperationSnafu)?,
             };
 
             debug!(
@@ -613,13 +675,60 @@ impl<S: StorageEngine> MitoEngineInner<S> {
             schema: &req.schema_name,
             table: &req.table_name,
         };
-        // todo(ruihang): reclaim persisted data
-        Ok(self
+        // Acquires the mutex so the table won't be opened again while we are dropping it.
+        let _lock = self.table_mutex.lock().await;
+        let removed = self
             .tables
             .write()
             .unwrap()
-            .remove(&table_reference.to_string())
-            .is_some())
+            .remove(&table_reference.to_string());
+        let Some(table) = removed else { return Ok(false) };
+
+        let table_info = table.table_info();
+        let table_id = table_info.ident.table_id;
+        let engine_ctx = StorageEngineContext::default();
+        for region_number in &table_info.meta.region_numbers {
+            let region_name = region_name(table_id, *region_number);
+            let region = self
+                .storage_engine
+                .get_region(&engine_ctx, &region_name)
+                .map_err(BoxedError::new)
+                .context(error::DropRegionSnafu {
+                    region_name: &region_name,
+                })?;
+            if let Some(region) = region {
+                self.storage_engine
+                    .drop_region(&engine_ctx, region)
+                    .await
+                    .map_err(BoxedError::new)
+                    .context(error::DropRegionSnafu {
+                        region_name: &region_name,
+                    })?;
+            }
+        }
+
+        // Deletes the table manifest, which is the only thing left in the table directory.
+        let table_dir = table_dir(&req.catalog_name, &req.schema_name, table_id);
+        let exists = self
+            .object_store
+            .object(&table_dir)
+            .is_exist()
+            .await
+            .context(error::DeleteTableDirSnafu {
+                table_dir: &table_dir,
+            })?;
+        if exists {
+            self.object_store
+                .batch()
+                .remove_all(&table_dir)
+                .await
+                .context(error::DeleteTableDirSnafu {
+                    table_dir: &table_dir,
+                })?;
+        }
+
+        logging::info!("Mito engine dropped table {}", table_reference);
+        Ok(true)
     }
 }
 
@@ -636,7 +745,10 @@ impl<S: StorageEngine> MitoEngineInner<S> {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+    use common_error::prelude::{ErrorExt, StatusCode};
     use common_query::physical_plan::SessionContext;
     use common_recordbatch::util;
     use datatypes::prelude::ConcreteDataType;
@@ -651,14 +763,18 @@ mod tests {
     use storage::region::RegionImpl;
     use storage::EngineImpl;
     use store_api::manifest::Manifest;
-    use store_api::storage::ReadContext;
-    use table::requests::{AddColumnRequest, AlterKind, DeleteRequest, TableOptions};
+    use store_api::storage::{AlterOptions, ReadContext};
+    use table::requests::{
+        AddColumnRequest, AlterKind, CompactTableRequest, DeleteRequest, FlushTableRequest,
+        TableOptions, INDEX_BLOOM_FILTER_COLUMNS_KEY, MEMTABLE_TYPE_KEY, TTL_KEY,
+        WRITE_BUFFER_SIZE_KEY,
+    };
     use tempdir::TempDir;
 
     use super::*;
     use crate::table::test_util;
     use crate::table::test_util::{
-        new_insert_request, schema_for_test, TestEngineComponents, TABLE_NAME,
+        new_insert_request, schema_for_test, MockRegion, TestEngineComponents, TABLE_NAME,
     };
 
     async fn setup_table_with_column_default_constraint() -> (TempDir, String, TableRef) {
@@ -829,6 +945,20 @@ mod tests {
 
         request.primary_key_indices = vec![0];
         assert!(validate_create_table_request(&request).is_ok());
+
+        let _ = request.table_options.extra_options.insert(
+            INDEX_BLOOM_FILTER_COLUMNS_KEY.to_string(),
+            "name".to_string(),
+        );
+        assert!(validate_create_table_request(&request).is_ok());
+
+        // Only primary key columns can be indexed.
+        let _ = request
+            .table_options
+            .extra_options
+            .insert(INDEX_BLOOM_FILTER_COLUMNS_KEY.to_string(), "ts".to_string());
+        let err = validate_create_table_request(&request).unwrap_err();
+        assert!(err.to_string().contains("Invalid index column ts"));
     }
 
     #[tokio::test]
@@ -1182,6 +1312,156 @@ mod tests {
         assert_eq!(new_schema.version(), old_schema.version() + 1);
     }
 
+    #[tokio::test]
+    async fn test_alter_table_modify_column() {
+        let (_engine, table_engine, _table, _object_store, _dir) =
+            test_util::setup_mock_engine_and_table().await;
+
+        let new_tag = ColumnSchema::new("my_tag", ConcreteDataType::string_datatype(), true);
+        let new_field = ColumnSchema::new("my_field", ConcreteDataType::int32_datatype(), true);
+        let req = new_add_columns_req(&new_tag, &new_field);
+        let table = table_engine
+            .alter_table(&EngineContext::default(), req)
+            .await
+            .unwrap();
+        let old_schema = table.schema();
+
+        let alter_kinds = [
+            AlterKind::ModifyColumnType {
+                column_name: String::from("my_field"),
+                data_type: ConcreteDataType::int64_datatype(),
+            },
+            AlterKind::RenameColumn {
+                column_name: String::from("my_field"),
+                new_column_name: String::from("my_field2"),
+            },
+            AlterKind::SetColumnDefault {
+                column_name: String::from("memory"),
+                default_constraint: Some(ColumnDefaultConstraint::Value(Value::Float64(
+                    1.0f64.into(),
+                ))),
+            },
+        ];
+        let mut table = table;
+        for alter_kind in alter_kinds {
+            let req = AlterTableRequest {
+                catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+                schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+                table_name: TABLE_NAME.to_string(),
+                alter_kind,
+            };
+            table = table_engine
+                .alter_table(&EngineContext::default(), req)
+                .await
+                .unwrap();
+        }
+
+        let new_schema = table.schema();
+        assert_eq!(new_schema.version(), old_schema.version() + 3);
+        assert!(new_schema.column_schema_by_name("my_field").is_none());
+        let column_schema = new_schema.column_schema_by_name("my_field2").unwrap();
+        assert_eq!(ConcreteDataType::int64_datatype(), column_schema.data_type);
+        let column_schema = new_schema.column_schema_by_name("memory").unwrap();
+        assert_eq!(
+            Some(&ColumnDefaultConstraint::Value(Value::Float64(
+                1.0f64.into()
+            ))),
+            column_schema.default_constraint()
+        );
+
+        // Modifying the primary key is not allowed.
+        let req = AlterTableRequest {
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: TABLE_NAME.to_string(),
+            alter_kind: AlterKind::RenameColumn {
+                column_name: String::from("host"),
+                new_column_name: String::from("host2"),
+            },
+        };
+        let err = table_engine
+            .alter_table(&EngineContext::default(), req)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+    }
+
+    #[tokio::test]
+    async fn test_alter_table_options() {
+        let (_engine, table_engine, _table, _object_store, _dir) =
+            test_util::setup_mock_engine_and_table().await;
+
+        let new_alter_req = |alter_kind| AlterTableRequest {
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: TABLE_NAME.to_string(),
+            alter_kind,
+        };
+        let assert_region_options = |table: &TableRef, expect: AlterOptions| {
+            let table = table
+                .as_any()
+                .downcast_ref::<MitoTable<MockRegion>>()
+                .unwrap();
+            for region in table.regions().values() {
+                assert_eq!(expect, **region.inner.options.load());
+            }
+        };
+
+        let req = new_alter_req(AlterKind::SetTableOptions {
+            options: HashMap::from([
+                (TTL_KEY.to_string(), "1h".to_string()),
+                (WRITE_BUFFER_SIZE_KEY.to_string(), "1MB".to_string()),
+            ]),
+        });
+        let table = table_engine
+            .alter_table(&EngineContext::default(), req)
+            .await
+            .unwrap();
+        let options = &table.table_info().meta.options;
+        assert_eq!(Some(Duration::from_secs(3600)), options.ttl);
+        assert_eq!(
+            Some(1024 * 1024),
+            options.write_buffer_size.map(|size| size.0)
+        );
+        assert_region_options(
+            &table,
+            AlterOptions {
+                write_buffer_size: Some(1024 * 1024),
+                ttl: Some(Duration::from_secs(3600)),
+            },
+        );
+
+        let req = new_alter_req(AlterKind::UnsetTableOptions {
+            keys: vec![WRITE_BUFFER_SIZE_KEY.to_string()],
+        });
+        let table = table_engine
+            .alter_table(&EngineContext::default(), req)
+            .await
+            .unwrap();
+        let options = &table.table_info().meta.options;
+        assert_eq!(Some(Duration::from_secs(3600)), options.ttl);
+        assert!(options.write_buffer_size.is_none());
+        assert_region_options(
+            &table,
+            AlterOptions {
+                write_buffer_size: None,
+                ttl: Some(Duration::from_secs(3600)),
+            },
+        );
+
+        // Options other than ttl and write_buffer_size can't be altered.
+        let req = new_alter_req(AlterKind::SetTableOptions {
+            options: HashMap::from([(MEMTABLE_TYPE_KEY.to_string(), "series".to_string())]),
+        });
+        let err = table_engine
+            .alter_table(&EngineContext::default(), req)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+    }
+
     #[tokio::test]
     async fn test_alter_rename_table() {
         let TestEngineComponents {
@@ -1268,7 +1548,7 @@ mod tests {
         common_telemetry::init_default_ut_logging();
         let ctx = EngineContext::default();
 
-        let (_engine, table_engine, table, _object_store, _dir) =
+        let (_engine, table_engine, table, object_store, _dir) =
             test_util::setup_mock_engine_and_table().await;
         let engine_ctx = EngineContext {};
 
@@ -1310,6 +1590,13 @@ mod tests {
             .unwrap();
         assert!(table_dropped);
         assert!(!table_engine.table_exists(&engine_ctx, &table_reference));
+        // the table manifest should be deleted
+        let dir = table_dir(
+            DEFAULT_CATALOG_NAME,
+            DEFAULT_SCHEMA_NAME,
+            table_info.ident.table_id,
+        );
+        assert!(!object_store.object(&dir).is_exist().await.unwrap());
 
         // should be able to re-create
         let request = CreateTableRequest {
@@ -1375,4 +1662,72 @@ mod tests {
 +-------+-----+--------+-------------------------+"
         );
     }
+
+    #[tokio::test]
+    async fn test_flush_and_compact_table() {
+        let TestEngineComponents {
+            table_engine,
+            table_ref: table,
+            dir: _dir,
+            ..
+        } = test_util::setup_test_engine_and_table().await;
+        let ctx = EngineContext::default();
+
+        let mut columns_values: HashMap<String, VectorRef> = HashMap::with_capacity(4);
+        let hosts: VectorRef = Arc::new(StringVector::from(vec!["host1", "host2"]));
+        let cpus: VectorRef = Arc::new(Float64Vector::from_vec(vec![1.0, 2.0]));
+        let memories: VectorRef = Arc::new(Float64Vector::from_vec(vec![1.0, 2.0]));
+        let tss: VectorRef = Arc::new(TimestampMillisecondVector::from_vec(vec![1, 2]));
+        columns_values.insert("host".to_string(), hosts);
+        columns_values.insert("cpu".to_string(), cpus);
+        columns_values.insert("memory".to_string(), memories);
+        columns_values.insert("ts".to_string(), tss);
+        let insert_req = new_insert_request("demo".to_string(), columns_values);
+        assert_eq!(2, table.insert(insert_req).await.unwrap());
+
+        let flush_req = FlushTableRequest {
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: TABLE_NAME.to_string(),
+            region_number: None,
+        };
+        table_engine.flush_table(&ctx, flush_req).await.unwrap();
+
+        let compact_req = CompactTableRequest {
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: TABLE_NAME.to_string(),
+            region_number: Some(0),
+        };
+        table_engine.compact_table(&ctx, compact_req).await.unwrap();
+
+        // Region not found.
+        let flush_req = FlushTableRequest {
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: TABLE_NAME.to_string(),
+            region_number: Some(1),
+        };
+        assert!(table_engine.flush_table(&ctx, flush_req).await.is_err());
+
+        // Table not found.
+        let compact_req = CompactTableRequest {
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: "not_exists".to_string(),
+            region_number: None,
+        };
+        let err = table_engine
+            .compact_table(&ctx, compact_req)
+            .await
+            .unwrap_err();
+        assert_eq!(StatusCode::TableNotFound, err.status_code());
+
+        // Rows are still readable after flush.
+        let session_ctx = SessionContext::new();
+        let stream = table.scan(None, &[], None).await.unwrap();
+        let stream = stream.execute(0, session_ctx.task_ctx()).unwrap();
+        let batches = util::collect_batches(stream).await.unwrap();
+        assert_eq!(2, batches.iter().map(|b| b.num_rows()).sum::<usize>());
+    }
 }
@@ -31,7 +31,7 @@ use table::requests::CreateTableRequest;
 use crate::engine::{self, MitoEngineInner, TableReference};
 use crate::error::{
     BuildRegionDescriptorSnafu, BuildTableInfoSnafu, BuildTableMetaSnafu, InvalidRawSchemaSnafu,
-    TableExistsSnafu,
+    InvalidTableOptionsSnafu, TableExistsSnafu,
 };
 use crate::table::MitoTable;
 
@@ -151,15 +151,45 @@ impl<S: StorageEngine> CreateMitoTable<S> {
             &self.data.request.schema_name,
             self.data.request.id,
         );
+        let compaction_options = self
+            .data
+            .request
+            .table_options
+            .compaction_options()
+            .context(InvalidTableOptionsSnafu {
+                table_name: &self.data.request.table_name,
+            })?;
+        let index_options =
+            self.data
+                .request
+                .table_options
+                .index_options()
+                .context(InvalidTableOptionsSnafu {
+                    table_name: &self.data.request.table_name,
+                })?;
+        let memtable_type =
+            self.data
+                .request
+                .table_options
+                .memtable_type()
+                .context(InvalidTableOptionsSnafu {
+                    table_name: &self.data.request.table_name,
+                })?;
         let open_opts = OpenOptions {
             parent_dir: table_dir.clone(),
             write_buffer_size: None,
             ttl: None,
+            compaction_options: compaction_options.clone(),
+            index_options: index_options.clone(),
+            memtable_type,
         };
         let create_opts = CreateOptions {
             parent_dir: table_dir,
             write_buffer_size: None,
             ttl: None,
+            compaction_options,
+            index_options,
+            memtable_type,
         };
 
         let table_schema =
@@ -53,6 +53,17 @@ pub enum Error {
     #[snafu(display("Invalid primary key: {}", msg))]
     InvalidPrimaryKey { msg: String, backtrace: Backtrace },
 
+    #[snafu(display(
+        "Invalid index column {} of table {}, only primary key columns can be indexed",
+        column_name,
+        table_name
+    ))]
+    InvalidIndexColumn {
+        table_name: String,
+        column_name: String,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display("Missing timestamp index for table: {}", table_name))]
     MissingTimestampIndex {
         table_name: String,
@@ -154,6 +165,13 @@ pub enum Error {
         source: table::error::Error,
     },
 
+    #[snafu(display("Invalid options of table {}, source: {}", table_name, source))]
+    InvalidTableOptions {
+        table_name: String,
+        #[snafu(backtrace)]
+        source: table::error::Error,
+    },
+
     #[snafu(display(
         "Projected column not found in region, column: {}",
         column_qualified_name
@@ -187,6 +205,20 @@ pub enum Error {
 
     #[snafu(display("Invalid schema, source: {}", source))]
     InvalidRawSchema { source: datatypes::error::Error },
+
+    #[snafu(display("Failed to drop region {}, source: {}", region_name, source))]
+    DropRegion {
+        region_name: String,
+        #[snafu(backtrace)]
+        source: BoxedError,
+    },
+
+    #[snafu(display("Failed to delete table directory {}, source: {}", table_dir, source))]
+    DeleteTableDir {
+        table_dir: String,
+        source: object_store::Error,
+        backtrace: Backtrace,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -196,9 +228,9 @@ impl ErrorExt for Error {
         use Error::*;
 
         match self {
-            CreateRegion { source, .. } => source.status_code(),
+            CreateRegion { source, .. } | DropRegion { source, .. } => source.status_code(),
 
-            AlterTable { source, .. } => source.status_code(),
+            AlterTable { source, .. } | InvalidTableOptions { source, .. } => source.status_code(),
 
             BuildRowKeyDescriptor { .. }
             | BuildColumnDescriptor { .. }
@@ -209,13 +241,16 @@ impl ErrorExt for Error {
             | TableExists { .. }
             | ProjectedColumnNotFound { .. }
             | InvalidPrimaryKey { .. }
+            | InvalidIndexColumn { .. }
             | MissingTimestampIndex { .. }
             | TableNotFound { .. }
             | InvalidRawSchema { .. } => StatusCode::InvalidArguments,
 
             TableInfoNotFound { .. } | ConvertRaw { .. } => StatusCode::Unexpected,
 
-            ScanTableManifest { .. } | UpdateTableManifest { .. } => StatusCode::StorageUnavailable,
+            ScanTableManifest { .. } | UpdateTableManifest { .. } | DeleteTableDir { .. } => {
+                StatusCode::StorageUnavailable
+            }
             RegionNotFound { .. } => StatusCode::Internal,
             InvalidRegionName { .. } => StatusCode::Internal,
         }
@@ -18,7 +18,9 @@ pub mod test_util;
 use std::any::Any;
 use std::collections::HashMap;
 use std::pin::Pin;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
@@ -28,6 +30,8 @@ use common_query::physical_plan::PhysicalPlanRef;
 use common_recordbatch::error::{ExternalSnafu, Result as RecordBatchResult};
 use common_recordbatch::{RecordBatch, RecordBatchStream};
 use common_telemetry::logging;
+use common_time::range::TimestampRange;
+use datafusion::physical_plan::metrics::{Count, ExecutionPlanMetricsSet, MetricBuilder, Time};
 use datatypes::schema::Schema;
 use futures::task::{Context, Poll};
 use futures::Stream;
@@ -35,8 +39,9 @@ use object_store::ObjectStore;
 use snafu::{ensure, OptionExt, ResultExt};
 use store_api::manifest::{self, Manifest, ManifestVersion, MetaActionIterator};
 use store_api::storage::{
-    AddColumn, AlterOperation, AlterRequest, ChunkReader, ReadContext, Region, RegionMeta,
-    RegionNumber, ScanRequest, SchemaRef, Snapshot, WriteContext, WriteRequest,
+    AddColumn, AlterOperation, AlterOptions, AlterRequest, ChunkReader, CompactContext,
+    FlushContext, ReadContext, Region, RegionMeta, RegionNumber, ScanMetricsRef, ScanRequest,
+    SchemaRef, Snapshot, WriteContext, WriteRequest,
 };
 use table::error as table_error;
 use table::error::{RegionSchemaMismatchSnafu, Result as TableResult, TableOperationSnafu};
@@ -143,6 +148,7 @@ impl<R: Region> Table for MitoTable<R> {
     ) -> TableResult<PhysicalPlanRef> {
         let read_ctx = ReadContext::default();
         let mut readers = Vec::with_capacity(self.regions.len());
+        let mut scan_metrics = Vec::with_capacity(self.regions.len());
         let mut first_schema: Option<Arc<Schema>> = None;
 
         let table_info = self.table_info.load();
@@ -164,12 +170,13 @@ impl<R: Region> Table for MitoTable<R> {
                 filters,
                 ..Default::default()
             };
-            let reader = snapshot
+            let response = snapshot
                 .scan(&read_ctx, scan_request)
                 .await
                 .map_err(BoxedError::new)
-                .context(table_error::TableOperationSnafu)?
-                .reader;
+                .context(table_error::TableOperationSnafu)?;
+            let reader = response.reader;
+            scan_metrics.push(response.metrics);
 
             let schema = reader.user_schema().clone();
             if let Some(first_schema) = &first_schema {
@@ -195,7 +202,11 @@ impl<R: Region> Table for MitoTable<R> {
         // assumption may become invalid.
         let stream_schema = first_schema.unwrap();
         let schema = stream_schema.clone();
+        let metrics = ExecutionPlanMetricsSet::new();
+        let storage_metrics = StorageScanMetrics::new(&metrics, scan_metrics);
         let stream = Box::pin(async_stream::try_stream! {
+            // Records the storage metrics once the stream is finished or dropped.
+            let _storage_metrics = storage_metrics;
             for mut reader in readers {
                 while let Some(chunk) = reader.next_chunk().await.map_err(BoxedError::new).context(ExternalSnafu)? {
                     let chunk = reader.project_chunk(chunk);
@@ -205,7 +216,7 @@ impl<R: Region> Table for MitoTable<R> {
         });
 
         let stream = Box::pin(ChunkStream { schema, stream });
-        Ok(Arc::new(SimpleTableScan::new(stream)))
+        Ok(Arc::new(SimpleTableScan::new(stream).with_metrics(metrics)))
     }
 
     fn supports_filter_pushdown(&self, _filter: &Expr) -> table::error::Result<FilterPushDownType> {
@@ -225,7 +236,13 @@ impl<R: Region> Table for MitoTable<R> {
             AlterKind::RenameTable { new_table_name } => {
                 new_info.name = new_table_name.clone();
             }
-            AlterKind::AddColumns { .. } | AlterKind::DropColumns { .. } => {
+            AlterKind::AddColumns { .. }
+            | AlterKind::DropColumns { .. }
+            | AlterKind::ModifyColumnType { .. }
+            | AlterKind::RenameColumn { .. }
+            | AlterKind::SetColumnDefault { .. }
+            | AlterKind::SetTableOptions { .. }
+            | AlterKind::UnsetTableOptions { .. } => {
                 let table_meta = &table_info.meta;
                 let new_meta = table_meta
                     .builder_with_alter_kind(table_name, &req.alter_kind)?
@@ -284,6 +301,29 @@ impl<R: Region> Table for MitoTable<R> {
                     .context(TableOperationSnafu)?;
             }
         }
+        if matches!(
+            req.alter_kind,
+            AlterKind::SetTableOptions { .. } | AlterKind::UnsetTableOptions { .. }
+        ) {
+            let table_options = &new_info.meta.options;
+            let alter_opts = AlterOptions {
+                write_buffer_size: table_options.write_buffer_size.map(|size| size.0 as usize),
+                ttl: table_options.ttl,
+            };
+            for region in self.regions().values() {
+                logging::debug!(
+                    "start altering options of region {} of table {}, with options {:?}",
+                    region.name(),
+                    table_name,
+                    alter_opts,
+                );
+                region
+                    .alter_options(&alter_opts)
+                    .await
+                    .map_err(BoxedError::new)
+                    .context(TableOperationSnafu)?;
+            }
+        }
         // Update in memory metadata of the table.
         self.set_table_info(new_info);
 
@@ -322,6 +362,52 @@ impl<R: Region> Table for MitoTable<R> {
         }
         Ok(rows_deleted)
     }
+
+    async fn delete_files_in_range(&self, time_range: TimestampRange) -> TableResult<usize> {
+        let mut rows_deleted = 0;
+        for region in self.regions.values() {
+            rows_deleted += region
+                .drop_files_in_range(time_range)
+                .await
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+        }
+        Ok(rows_deleted)
+    }
+
+    async fn flush(&self, region_number: Option<RegionNumber>) -> TableResult<()> {
+        let flush_ctx = FlushContext::default();
+        for region in self.regions_to_operate(region_number)? {
+            region
+                .flush(&flush_ctx)
+                .await
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+        }
+        Ok(())
+    }
+
+    async fn compact(&self, region_number: Option<RegionNumber>) -> TableResult<()> {
+        let compact_ctx = CompactContext::default();
+        for region in self.regions_to_operate(region_number)? {
+            region
+                .compact(&compact_ctx)
+                .await
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+        }
+        Ok(())
+    }
+    async fn truncate(&self) -> TableResult<()> {
+        for region in self.regions.values() {
+            region
+                .truncate()
+                .await
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+        }
+        Ok(())
+    }
 }
 
 struct ChunkStream {
@@ -343,6 +429,60 @@ impl Stream for ChunkStream {
     }
 }
 
+/// Metrics of the storage layer in a table scan, which are added to the metrics of the scan
+/// plan when dropped.
+struct StorageScanMetrics {
+    /// Metrics of the scans of regions.
+    regions: Vec<ScanMetricsRef>,
+    memtables_read: Count,
+    ssts_opened: Count,
+    ssts_pruned: Count,
+    row_groups_skipped: Count,
+    bytes_fetched: Count,
+    merge_time: Time,
+    dedup_time: Time,
+}
+
+impl StorageScanMetrics {
+    fn new(metrics: &ExecutionPlanMetricsSet, regions: Vec<ScanMetricsRef>) -> Self {
+        let counter = |name: &'static str| MetricBuilder::new(metrics).counter(name, 0);
+        let time = |name: &'static str| MetricBuilder::new(metrics).subset_time(name, 0);
+        Self {
+            regions,
+            memtables_read: counter("memtables_read"),
+            ssts_opened: counter("ssts_opened"),
+            ssts_pruned: counter("ssts_pruned"),
+            row_groups_skipped: counter("row_groups_skipped"),
+            bytes_fetched: counter("bytes_fetched"),
+            merge_time: time("merge_time"),
+            dedup_time: time("dedup_time"),
+        }
+    }
+}
+
+impl Drop for StorageScanMetrics {
+    fn drop(&mut self) {
+        for region in &self.regions {
+            self.memtables_read
+                .add(region.memtables_read.load(Ordering::Relaxed));
+            self.ssts_opened
+                .add(region.ssts_opened.load(Ordering::Relaxed));
+            self.ssts_pruned
+                .add(region.ssts_pruned.load(Ordering::Relaxed));
+            self.row_groups_skipped
+                .add(region.row_groups_skipped.load(Ordering::Relaxed));
+            self.bytes_fetched
+                .add(region.bytes_fetched.load(Ordering::Relaxed));
+            self.merge_time.add_duration(Duration::from_nanos(
+                region.merge_nanos.load(Ordering::Relaxed),
+            ));
+            self.dedup_time.add_duration(Duration::from_nanos(
+                region.dedup_nanos.load(Ordering::Relaxed),
+            ));
+        }
+    }
+}
+
 #[inline]
 fn column_qualified_name(table_name: &str, region_name: &str, column_name: &str) -> String {
     format!("{table_name}.{region_name}.{column_name}")
@@ -492,6 +632,31 @@ impl<R: Region> MitoTable<R> {
     }
 
     #[inline]
+    /// Returns the region `region_number` if it's given, otherwise returns all regions
+    /// of the table.
+    fn regions_to_operate(&self, region_number: Option<RegionNumber>) -> TableResult<Vec<&R>> {
+        match region_number {
+            Some(region_number) => {
+                let table_info = self.table_info();
+                let region = self
+                    .regions
+                    .get(&region_number)
+                    .with_context(|| RegionNotFoundSnafu {
+                        table: common_catalog::format_full_table_name(
+                            &table_info.catalog_name,
+                            &table_info.schema_name,
+                            &table_info.name,
+                        ),
+                        region: region_number,
+                    })
+                    .map_err(BoxedError::new)
+                    .context(table_error::TableOperationSnafu)?;
+                Ok(vec![region])
+            }
+            None => Ok(self.regions.values().collect()),
+        }
+    }
+
     pub fn regions(&self) -> &HashMap<RegionNumber, R> {
         &self.regions
     }
@@ -526,6 +691,29 @@ fn create_alter_operation(
         })),
         // No need to build alter operation when reaming tables.
         AlterKind::RenameTable { .. } => Ok(None),
+        // Table options are applied to regions by altering their options.
+        AlterKind::SetTableOptions { .. } | AlterKind::UnsetTableOptions { .. } => Ok(None),
+        AlterKind::ModifyColumnType {
+            column_name,
+            data_type,
+        } => Ok(Some(AlterOperation::ModifyColumnType {
+            name: column_name.clone(),
+            data_type: data_type.clone(),
+        })),
+        AlterKind::RenameColumn {
+            column_name,
+            new_column_name,
+        } => Ok(Some(AlterOperation::RenameColumn {
+            name: column_name.clone(),
+            new_name: new_column_name.clone(),
+        })),
+        AlterKind::SetColumnDefault {
+            column_name,
+            default_constraint,
+        } => Ok(Some(AlterOperation::SetColumnDefault {
+            name: column_name.clone(),
+            default_constraint: default_constraint.clone(),
+        })),
     }
 }
 
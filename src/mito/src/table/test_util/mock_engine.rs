@@ -21,14 +21,16 @@ use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use common_error::mock::MockError;
 use common_telemetry::logging;
+use common_time::range::TimestampRange;
 use datatypes::prelude::{DataType, Value, VectorRef};
 use datatypes::schema::{ColumnSchema, Schema};
 use storage::metadata::{RegionMetaImpl, RegionMetadata};
 use storage::write_batch::WriteBatch;
 use store_api::storage::{
-    AlterRequest, Chunk, ChunkReader, CreateOptions, EngineContext, GetRequest, GetResponse,
-    OpenOptions, ReadContext, Region, RegionDescriptor, RegionId, ScanRequest, ScanResponse,
-    SchemaRef, Snapshot, StorageEngine, WriteContext, WriteResponse,
+    AlterOptions, AlterRequest, Chunk, ChunkReader, CompactContext, CreateOptions, EngineContext,
+    FlushContext, GetRequest, GetResponse, OpenOptions, ReadContext, Region, RegionDescriptor,
+    RegionId, ScanRequest, ScanResponse, SchemaRef, Snapshot, StorageEngine, WriteContext,
+    WriteResponse,
 };
 
 pub type Result<T> = std::result::Result<T, MockError>;
@@ -120,7 +122,10 @@ impl Snapshot for MockSnapshot {
             memtable,
             read: false,
         };
-        Ok(ScanResponse { reader })
+        Ok(ScanResponse {
+            reader,
+            metrics: Default::default(),
+        })
     }
 
     async fn get(&self, _ctx: &ReadContext, _request: GetRequest) -> Result<GetResponse> {
@@ -139,6 +144,7 @@ pub struct MockRegion {
 pub struct MockRegionInner {
     name: String,
     pub metadata: ArcSwap<RegionMetadata>,
+    pub options: ArcSwap<AlterOptions>,
     memtable: Arc<RwLock<MockMemtable>>,
 }
 
@@ -196,6 +202,29 @@ impl Region for MockRegion {
     async fn close(&self) -> Result<()> {
         Ok(())
     }
+
+    async fn flush(&self, _ctx: &FlushContext) -> Result<()> {
+        Ok(())
+    }
+
+    async fn compact(&self, _ctx: &CompactContext) -> Result<()> {
+        Ok(())
+    }
+
+    async fn drop_files_in_range(&self, _time_range: TimestampRange) -> Result<usize> {
+        // The mock region has no SST files.
+        Ok(0)
+    }
+
+    async fn alter_options(&self, opts: &AlterOptions) -> Result<()> {
+        self.inner.options.store(Arc::new(opts.clone()));
+        Ok(())
+    }
+
+    async fn truncate(&self) -> Result<()> {
+        self.inner.truncate();
+        Ok(())
+    }
 }
 
 impl MockRegionInner {
@@ -207,6 +236,7 @@ impl MockRegionInner {
         Self {
             name: metadata.name().to_string(),
             metadata: ArcSwap::new(Arc::new(metadata)),
+            options: ArcSwap::new(Arc::new(AlterOptions::default())),
             memtable: Arc::new(RwLock::new(memtable)),
         }
     }
@@ -226,6 +256,11 @@ impl MockRegionInner {
         self.metadata.swap(Arc::new(metadata));
     }
 
+    fn truncate(&self) {
+        let mut memtable = self.memtable.write().unwrap();
+        memtable.values_mut().for_each(|column| column.clear());
+    }
+
     fn write(&self, request: WriteBatch) {
         let metadata = self.metadata.load();
 
@@ -310,8 +345,13 @@ impl StorageEngine for MockEngine {
         Ok(region)
     }
 
-    async fn drop_region(&self, _ctx: &EngineContext, _region: Self::Region) -> Result<()> {
-        unimplemented!()
+    async fn drop_region(&self, _ctx: &EngineContext, region: Self::Region) -> Result<()> {
+        logging::info!("Mock engine drop region, name: {}", region.name());
+
+        let mut regions = self.regions.lock().unwrap();
+        regions.opened_regions.remove(region.name());
+        regions.closed_regions.remove(region.name());
+        Ok(())
     }
 
     fn get_region(&self, _ctx: &EngineContext, name: &str) -> Result<Option<MockRegion>> {
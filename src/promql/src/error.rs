@@ -84,6 +84,21 @@ pub enum Error {
     #[snafu(display("Empty range is not expected"))]
     EmptyRange { backtrace: Backtrace },
 
+    #[snafu(display("Cannot find the le column of histogram in tags {:?}", tags))]
+    LeNotFound {
+        tags: Vec<String>,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display(
+        "Expect a bucket series of classic histogram whose name ends with _bucket, found: {}",
+        metric
+    ))]
+    NotBucketSeries {
+        metric: String,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display(
         "Table (metric) name not found, this indicates a procedure error in PromQL planner"
     ))]
@@ -105,7 +120,9 @@ impl ErrorExt for Error {
             | UnsupportedExpr { .. }
             | UnexpectedToken { .. }
             | MultipleVector { .. }
-            | ExpectExpr { .. } => StatusCode::InvalidArguments,
+            | ExpectExpr { .. }
+            | LeNotFound { .. }
+            | NotBucketSeries { .. } => StatusCode::InvalidArguments,
 
             UnknownTable { .. }
             | DataFusionPlanning { .. }
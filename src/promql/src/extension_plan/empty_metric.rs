@@ -0,0 +1,335 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use datafusion::arrow::array::TimestampMillisecondArray;
+use datafusion::arrow::datatypes::{DataType, SchemaRef, TimeUnit};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::common::{DFField, DFSchema, DFSchemaRef};
+use datafusion::error::Result as DataFusionResult;
+use datafusion::execution::context::TaskContext;
+use datafusion::logical_expr::{Expr, LogicalPlan, UserDefinedLogicalNode};
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::{
+    DisplayFormatType, ExecutionPlan, Partitioning, RecordBatchStream, SendableRecordBatchStream,
+    Statistics,
+};
+use futures::Stream;
+
+use crate::extension_plan::Millisecond;
+
+/// Generates a time index column that contains every evaluation timestamp from
+/// `start` to `end` (both inclusive) with step `interval`.
+///
+/// It has no input, and is used to plan expressions that don't select any metric,
+/// like number literals and `time()`. Value columns are expected to be added by
+/// a projection on it.
+#[derive(Debug)]
+pub struct EmptyMetric {
+    start: Millisecond,
+    end: Millisecond,
+    interval: Millisecond,
+    time_index_column: String,
+    output_schema: DFSchemaRef,
+}
+
+impl EmptyMetric {
+    pub fn new(
+        start: Millisecond,
+        end: Millisecond,
+        interval: Millisecond,
+        time_index_column: String,
+    ) -> DataFusionResult<Self> {
+        let output_schema = Arc::new(DFSchema::new_with_metadata(
+            vec![DFField::new(
+                None,
+                &time_index_column,
+                DataType::Timestamp(TimeUnit::Millisecond, None),
+                false,
+            )],
+            HashMap::new(),
+        )?);
+        Ok(Self {
+            start,
+            end,
+            interval,
+            time_index_column,
+            output_schema,
+        })
+    }
+
+    pub fn to_execution_plan(&self) -> Arc<dyn ExecutionPlan> {
+        Arc::new(EmptyMetricExec {
+            start: self.start,
+            end: self.end,
+            interval: self.interval,
+            output_schema: Arc::new(self.output_schema.as_ref().into()),
+            metric: ExecutionPlanMetricsSet::new(),
+        })
+    }
+}
+
+impl UserDefinedLogicalNode for EmptyMetric {
+    fn as_any(&self) -> &dyn Any {
+        self as _
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        &self.output_schema
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        vec![]
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "PromEmptyMetric: range=[{}..{}], interval=[{}], time index=[{}]",
+            self.start, self.end, self.interval, self.time_index_column
+        )
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[Expr],
+        _inputs: &[LogicalPlan],
+    ) -> Arc<dyn UserDefinedLogicalNode> {
+        Arc::new(Self {
+            start: self.start,
+            end: self.end,
+            interval: self.interval,
+            time_index_column: self.time_index_column.clone(),
+            output_schema: self.output_schema.clone(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct EmptyMetricExec {
+    start: Millisecond,
+    end: Millisecond,
+    interval: Millisecond,
+    output_schema: SchemaRef,
+    metric: ExecutionPlanMetricsSet,
+}
+
+impl EmptyMetricExec {
+    fn num_rows(&self) -> usize {
+        if self.start > self.end {
+            0
+        } else if self.interval <= 0 {
+            1
+        } else {
+            ((self.end - self.start) / self.interval + 1) as usize
+        }
+    }
+}
+
+impl ExecutionPlan for EmptyMetricExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.output_schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(Self {
+            start: self.start,
+            end: self.end,
+            interval: self.interval,
+            output_schema: self.output_schema.clone(),
+            metric: self.metric.clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        let baseline_metric = BaselineMetrics::new(&self.metric, partition);
+        let batch_size = context.session_config().batch_size();
+        Ok(Box::pin(EmptyMetricStream {
+            next_ts: self.start,
+            end: self.end,
+            interval: self.interval,
+            batch_size,
+            schema: self.output_schema.clone(),
+            metric: baseline_metric,
+        }))
+    }
+
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => write!(
+                f,
+                "PromEmptyMetricExec: range=[{}..{}], interval=[{}]",
+                self.start, self.end, self.interval
+            ),
+        }
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metric.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        let num_rows = self.num_rows();
+        Statistics {
+            num_rows: Some(num_rows),
+            total_byte_size: Some(num_rows * std::mem::size_of::<Millisecond>()),
+            column_statistics: None,
+            is_exact: true,
+        }
+    }
+}
+
+pub struct EmptyMetricStream {
+    /// The next timestamp to generate. Generation is finished when it's greater than `end`.
+    next_ts: Millisecond,
+    end: Millisecond,
+    interval: Millisecond,
+    batch_size: usize,
+    schema: SchemaRef,
+    metric: BaselineMetrics,
+}
+
+impl RecordBatchStream for EmptyMetricStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl Stream for EmptyMetricStream {
+    type Item = DataFusionResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.next_ts > self.end {
+            return Poll::Ready(None);
+        }
+
+        let elapsed_compute = self.metric.elapsed_compute().clone();
+        let timer = elapsed_compute.timer();
+        let mut timestamps = Vec::with_capacity(self.batch_size);
+        while self.next_ts <= self.end && timestamps.len() < self.batch_size {
+            timestamps.push(self.next_ts);
+            if self.interval <= 0 {
+                // only one timestamp can be generated
+                self.next_ts = self.end + 1;
+            } else {
+                self.next_ts += self.interval;
+            }
+        }
+        let result = RecordBatch::try_new(
+            self.schema.clone(),
+            vec![Arc::new(TimestampMillisecondArray::from(timestamps))],
+        )
+        .map_err(Into::into);
+        timer.done();
+
+        let poll = Poll::Ready(Some(result));
+        self.metric.record_poll(poll)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use datafusion::prelude::SessionContext;
+
+    use super::*;
+
+    async fn do_empty_metric_test(
+        start: Millisecond,
+        end: Millisecond,
+        interval: Millisecond,
+        expected: Vec<Millisecond>,
+    ) {
+        let empty_metric = EmptyMetric::new(start, end, interval, "time".to_string()).unwrap();
+        let exec = empty_metric.to_execution_plan();
+        let session_context = SessionContext::default();
+        let result = datafusion::physical_plan::collect(exec, session_context.task_ctx())
+            .await
+            .unwrap();
+        let timestamps = result
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<TimestampMillisecondArray>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(timestamps, expected);
+    }
+
+    #[tokio::test]
+    async fn range_timestamps() {
+        do_empty_metric_test(0, 10_000, 3_000, vec![0, 3_000, 6_000, 9_000]).await;
+    }
+
+    #[tokio::test]
+    async fn instant_timestamp() {
+        do_empty_metric_test(5_000, 5_000, 0, vec![5_000]).await;
+        do_empty_metric_test(5_000, 5_000, 1_000, vec![5_000]).await;
+    }
+
+    #[tokio::test]
+    async fn empty_range() {
+        do_empty_metric_test(10_000, 0, 1_000, vec![]).await;
+    }
+
+    #[test]
+    fn empty_metric_schema() {
+        let empty_metric = EmptyMetric::new(0, 10, 1, "time".to_string()).unwrap();
+        assert_eq!(
+            empty_metric.schema().field_names(),
+            vec!["time".to_string()]
+        );
+        assert_eq!(
+            empty_metric.to_execution_plan().statistics().num_rows,
+            Some(11)
+        );
+    }
+}
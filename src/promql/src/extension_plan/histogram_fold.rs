@@ -0,0 +1,651 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use datafusion::arrow::array::{Array, ArrayRef, Float64Array, StringArray};
+use datafusion::arrow::compute;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::common::{DFSchema, DFSchemaRef};
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::context::TaskContext;
+use datafusion::logical_expr::{Expr, LogicalPlan, UserDefinedLogicalNode};
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::{
+    DisplayFormatType, ExecutionPlan, Partitioning, RecordBatchStream, SendableRecordBatchStream,
+    Statistics,
+};
+use datafusion::scalar::ScalarValue;
+use futures::{ready, Stream, StreamExt};
+
+/// Fold buckets of classic histograms into the φ-quantile, i.e., `histogram_quantile`.
+///
+/// All rows with the same values on columns other than `le` and the field column are
+/// treated as buckets of one histogram sample. They are folded into one row, whose `le`
+/// column is removed and field column is replaced by the estimated quantile.
+///
+/// The input is expected to be sorted on those "other" columns, so buckets of one
+/// histogram sample are consecutive.
+#[derive(Debug)]
+pub struct HistogramFold {
+    le_column: String,
+    field_column: String,
+    quantile: f64,
+    input: LogicalPlan,
+    output_schema: DFSchemaRef,
+}
+
+impl HistogramFold {
+    pub fn new(
+        le_column: String,
+        field_column: String,
+        quantile: f64,
+        input: LogicalPlan,
+    ) -> DataFusionResult<Self> {
+        let output_schema =
+            Self::calculate_output_schema(input.schema(), &le_column, &field_column)?;
+        Ok(Self {
+            le_column,
+            field_column,
+            quantile,
+            input,
+            output_schema,
+        })
+    }
+
+    fn calculate_output_schema(
+        input_schema: &DFSchemaRef,
+        le_column: &str,
+        field_column: &str,
+    ) -> DataFusionResult<DFSchemaRef> {
+        for column in [le_column, field_column] {
+            let Some(_) = input_schema.index_of_column_by_name(None, column)? else {
+                return Err(datafusion::common::field_not_found(None, column, input_schema.as_ref()))
+            };
+        }
+        let columns = input_schema
+            .fields()
+            .iter()
+            .filter(|field| field.name() != le_column)
+            .cloned()
+            .collect();
+
+        Ok(Arc::new(DFSchema::new_with_metadata(
+            columns,
+            HashMap::new(),
+        )?))
+    }
+
+    pub fn to_execution_plan(
+        &self,
+        exec_input: Arc<dyn ExecutionPlan>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        let input_schema = exec_input.schema();
+        let le_index = input_schema.index_of(&self.le_column)?;
+        let field_index = input_schema.index_of(&self.field_column)?;
+        Ok(Arc::new(HistogramFoldExec {
+            le_column: self.le_column.clone(),
+            field_column: self.field_column.clone(),
+            le_index,
+            field_index,
+            quantile: self.quantile,
+            input: exec_input,
+            output_schema: SchemaRef::new(self.output_schema.as_ref().into()),
+            metric: ExecutionPlanMetricsSet::new(),
+        }))
+    }
+}
+
+impl UserDefinedLogicalNode for HistogramFold {
+    fn as_any(&self) -> &dyn Any {
+        self as _
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![&self.input]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        &self.output_schema
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        vec![]
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "PromHistogramFold: le=[{}], field=[{}], quantile=[{}]",
+            self.le_column, self.field_column, self.quantile
+        )
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[Expr],
+        inputs: &[LogicalPlan],
+    ) -> Arc<dyn UserDefinedLogicalNode> {
+        assert!(!inputs.is_empty());
+
+        Arc::new(Self {
+            le_column: self.le_column.clone(),
+            field_column: self.field_column.clone(),
+            quantile: self.quantile,
+            input: inputs[0].clone(),
+            output_schema: self.output_schema.clone(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct HistogramFoldExec {
+    le_column: String,
+    field_column: String,
+    /// Index of `le_column` in the input schema.
+    le_index: usize,
+    /// Index of `field_column` in the input schema.
+    field_index: usize,
+    quantile: f64,
+
+    input: Arc<dyn ExecutionPlan>,
+    output_schema: SchemaRef,
+    metric: ExecutionPlanMetricsSet,
+}
+
+impl ExecutionPlan for HistogramFoldExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.output_schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        self.input.output_partitioning()
+    }
+
+    /// The sort exprs of the input refer to the columns by index, which are shifted in the
+    /// output as the `le` column is removed.
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![false; self.children().len()]
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        assert!(!children.is_empty());
+        Ok(Arc::new(Self {
+            le_column: self.le_column.clone(),
+            field_column: self.field_column.clone(),
+            le_index: self.le_index,
+            field_index: self.field_index,
+            quantile: self.quantile,
+            input: children[0].clone(),
+            output_schema: self.output_schema.clone(),
+            metric: self.metric.clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        let baseline_metric = BaselineMetrics::new(&self.metric, partition);
+
+        let input = self.input.execute(partition, context)?;
+        let group_columns = (0..input.schema().fields().len())
+            .filter(|index| *index != self.le_index && *index != self.field_index)
+            .collect();
+        Ok(Box::pin(HistogramFoldStream {
+            le_column: self.le_index,
+            field_column: self.field_index,
+            group_columns,
+            quantile: self.quantile,
+            pending: None,
+            output_schema: self.output_schema.clone(),
+            input,
+            metric: baseline_metric,
+        }))
+    }
+
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(
+                    f,
+                    "PromHistogramFoldExec: le=[{}], field=[{}], quantile=[{}]",
+                    self.le_column, self.field_column, self.quantile
+                )
+            }
+        }
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metric.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics {
+            num_rows: None,
+            total_byte_size: None,
+            // TODO(ruihang): support this column statistics
+            column_statistics: None,
+            is_exact: false,
+        }
+    }
+}
+
+/// Buckets of a histogram sample whose last row hasn't been seen yet.
+struct PendingHistogram {
+    /// The first row of this sample, used to compare and fill group columns.
+    row: RecordBatch,
+    /// `(upper bound, count)` of every bucket.
+    buckets: Vec<(f64, f64)>,
+}
+
+pub struct HistogramFoldStream {
+    le_column: usize,
+    field_column: usize,
+    /// Columns other than `le` and field. Rows with the same values on them are
+    /// buckets of one histogram sample.
+    group_columns: Vec<usize>,
+    quantile: f64,
+    pending: Option<PendingHistogram>,
+
+    output_schema: SchemaRef,
+    input: SendableRecordBatchStream,
+    metric: BaselineMetrics,
+}
+
+impl RecordBatchStream for HistogramFoldStream {
+    fn schema(&self) -> SchemaRef {
+        self.output_schema.clone()
+    }
+}
+
+impl Stream for HistogramFoldStream {
+    type Item = DataFusionResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let poll = match ready!(self.input.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => {
+                    let elapsed_compute = self.metric.elapsed_compute().clone();
+                    let _timer = elapsed_compute.timer();
+                    match self.fold(batch) {
+                        // the whole batch belongs to the pending sample
+                        Ok(None) => continue,
+                        result => result.transpose(),
+                    }
+                }
+                // flush the last sample
+                None => {
+                    let elapsed_compute = self.metric.elapsed_compute().clone();
+                    let _timer = elapsed_compute.timer();
+                    self.flush().transpose()
+                }
+                error => error,
+            };
+            return self.metric.record_poll(Poll::Ready(poll));
+        }
+    }
+}
+
+impl HistogramFoldStream {
+    /// Fold the input batch. Returns samples that are finished in this batch.
+    pub fn fold(&mut self, input: RecordBatch) -> DataFusionResult<Option<RecordBatch>> {
+        let le_array = input
+            .column(self.le_column)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .ok_or_else(|| {
+                DataFusionError::Execution(format!(
+                    "expect Utf8 as le column's type, found {}",
+                    input.column(self.le_column).data_type()
+                ))
+            })?;
+        let field_array = input
+            .column(self.field_column)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .ok_or_else(|| {
+                DataFusionError::Execution(format!(
+                    "expect Float64 as field column's type, found {}",
+                    input.column(self.field_column).data_type()
+                ))
+            })?;
+
+        let mut finished = vec![];
+        for row in 0..input.num_rows() {
+            let same_sample = match &self.pending {
+                Some(pending) => self.is_same_sample(&pending.row, &input, row)?,
+                None => false,
+            };
+            if !same_sample {
+                if let Some(pending) = self.pending.take() {
+                    finished.push(pending);
+                }
+                self.pending = Some(PendingHistogram {
+                    row: input.slice(row, 1),
+                    buckets: vec![],
+                });
+            }
+
+            // null buckets are ignored
+            if le_array.is_null(row) || field_array.is_null(row) {
+                continue;
+            }
+            let le = le_array.value(row);
+            let upper_bound = le.parse::<f64>().map_err(|_| {
+                DataFusionError::Execution(format!("invalid bucket upper bound (le): {le}"))
+            })?;
+            if let Some(pending) = &mut self.pending {
+                pending.buckets.push((upper_bound, field_array.value(row)));
+            }
+        }
+
+        self.build_output(finished)
+    }
+
+    /// Fold the remaining pending sample.
+    pub fn flush(&mut self) -> DataFusionResult<Option<RecordBatch>> {
+        let finished = self.pending.take().into_iter().collect();
+        self.build_output(finished)
+    }
+
+    fn is_same_sample(
+        &self,
+        sample: &RecordBatch,
+        input: &RecordBatch,
+        row: usize,
+    ) -> DataFusionResult<bool> {
+        for index in &self.group_columns {
+            let lhs = ScalarValue::try_from_array(sample.column(*index), 0)?;
+            let rhs = ScalarValue::try_from_array(input.column(*index), row)?;
+            if lhs != rhs {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn build_output(
+        &self,
+        finished: Vec<PendingHistogram>,
+    ) -> DataFusionResult<Option<RecordBatch>> {
+        if finished.is_empty() {
+            return Ok(None);
+        }
+
+        let rows = finished
+            .iter()
+            .map(|sample| sample.row.clone())
+            .collect::<Vec<_>>();
+        let rows = compute::concat_batches(&rows[0].schema(), &rows)?;
+        let quantiles = finished
+            .into_iter()
+            .map(|mut sample| Some(bucket_quantile(self.quantile, &mut sample.buckets)))
+            .collect::<Float64Array>();
+        let quantiles = Arc::new(quantiles) as ArrayRef;
+
+        let columns = (0..rows.num_columns())
+            .filter(|index| *index != self.le_column)
+            .map(|index| {
+                if index == self.field_column {
+                    quantiles.clone()
+                } else {
+                    rows.column(index).clone()
+                }
+            })
+            .collect();
+        Ok(Some(RecordBatch::try_new(
+            self.output_schema.clone(),
+            columns,
+        )?))
+    }
+}
+
+/// Estimate the φ-quantile from the `(upper bound, count)` buckets of a classic histogram.
+///
+/// Ported from Prometheus' `bucketQuantile` in
+/// <https://github.com/prometheus/prometheus/blob/e934d0f01158a1d55fa0ebb035346b195fcc1260/promql/quantile.go#L71-L122>
+fn bucket_quantile(quantile: f64, buckets: &mut Vec<(f64, f64)>) -> f64 {
+    if quantile.is_nan() {
+        return f64::NAN;
+    }
+    if quantile < 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if quantile > 1.0 {
+        return f64::INFINITY;
+    }
+
+    buckets.sort_unstable_by(|lhs, rhs| lhs.0.total_cmp(&rhs.0));
+    match buckets.last() {
+        Some((upper_bound, _)) if *upper_bound == f64::INFINITY => {}
+        _ => return f64::NAN,
+    }
+    coalesce_buckets(buckets);
+    ensure_monotonic(buckets);
+
+    if buckets.len() < 2 {
+        return f64::NAN;
+    }
+    let observations = buckets[buckets.len() - 1].1;
+    if observations == 0.0 {
+        return f64::NAN;
+    }
+    let mut rank = quantile * observations;
+    // the first bucket whose count is not less than the rank, "+Inf" is not searched
+    let index = buckets[..buckets.len() - 1]
+        .iter()
+        .position(|(_, count)| *count >= rank)
+        .unwrap_or(buckets.len() - 1);
+
+    if index == buckets.len() - 1 {
+        return buckets[buckets.len() - 2].0;
+    }
+    if index == 0 && buckets[0].0 <= 0.0 {
+        return buckets[0].0;
+    }
+
+    let mut bucket_start = 0.0;
+    let bucket_end = buckets[index].0;
+    let mut count = buckets[index].1;
+    if index > 0 {
+        bucket_start = buckets[index - 1].0;
+        count -= buckets[index - 1].1;
+        rank -= buckets[index - 1].1;
+    }
+    bucket_start + (bucket_end - bucket_start) * (rank / count)
+}
+
+/// Merge buckets with the same upper bound by summing their counts. This may happen
+/// when the `le` label is formatted differently, like "1" and "1.0".
+fn coalesce_buckets(buckets: &mut Vec<(f64, f64)>) {
+    buckets.dedup_by(|curr, prev| {
+        if curr.0 == prev.0 {
+            prev.1 += curr.1;
+            true
+        } else {
+            false
+        }
+    });
+}
+
+/// Bucket counts are expected to be monotonically increasing. Precision issues or
+/// scraping different bucket counts at slightly different time may break this, which
+/// is fixed by raising the decreased counts.
+fn ensure_monotonic(buckets: &mut [(f64, f64)]) {
+    let mut max = f64::NEG_INFINITY;
+    for (_, count) in buckets.iter_mut() {
+        if *count > max {
+            max = *count;
+        } else if *count < max {
+            *count = max;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use datafusion::arrow::datatypes::{DataType, Field, Schema};
+    use datafusion::from_slice::FromSlice;
+    use datafusion::logical_expr::EmptyRelation;
+    use datafusion::physical_plan::memory::MemoryExec;
+    use datafusion::prelude::SessionContext;
+
+    use super::*;
+
+    fn prepare_test_data() -> MemoryExec {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("host", DataType::Utf8, true),
+            Field::new("le", DataType::Utf8, true),
+            Field::new("val", DataType::Float64, true),
+        ]));
+
+        // buckets of one sample are separated into two batches
+        let host_column_1 = Arc::new(StringArray::from_slice([
+            "host_1", "host_1", "host_1", "host_1", "host_2", "host_2",
+        ])) as _;
+        let le_column_1 = Arc::new(StringArray::from_slice([
+            "0.1", "1", "10", "+Inf", "0.1", "1",
+        ])) as _;
+        let val_column_1 =
+            Arc::new(Float64Array::from_slice([0.0, 10.0, 20.0, 20.0, 5.0, 5.0])) as _;
+
+        let host_column_2 = Arc::new(StringArray::from_slice(["host_2", "host_2"])) as _;
+        let le_column_2 = Arc::new(StringArray::from_slice(["10", "+Inf"])) as _;
+        let val_column_2 = Arc::new(Float64Array::from_slice([10.0, 10.0])) as _;
+
+        let data_1 = RecordBatch::try_new(
+            schema.clone(),
+            vec![host_column_1, le_column_1, val_column_1],
+        )
+        .unwrap();
+        let data_2 = RecordBatch::try_new(
+            schema.clone(),
+            vec![host_column_2, le_column_2, val_column_2],
+        )
+        .unwrap();
+
+        MemoryExec::try_new(&[vec![data_1, data_2]], schema, None).unwrap()
+    }
+
+    #[tokio::test]
+    async fn fold_histogram() {
+        let memory_exec = Arc::new(prepare_test_data());
+        let output_schema = Arc::new(Schema::new(vec![
+            Field::new("host", DataType::Utf8, true),
+            Field::new("val", DataType::Float64, true),
+        ]));
+        let fold_exec = Arc::new(HistogramFoldExec {
+            le_column: "le".to_string(),
+            field_column: "val".to_string(),
+            le_index: 1,
+            field_index: 2,
+            quantile: 0.5,
+            input: memory_exec,
+            output_schema,
+            metric: ExecutionPlanMetricsSet::new(),
+        });
+        let session_context = SessionContext::default();
+        let result = datafusion::physical_plan::collect(fold_exec, session_context.task_ctx())
+            .await
+            .unwrap();
+        let result_literal = datatypes::arrow::util::pretty::pretty_format_batches(&result)
+            .unwrap()
+            .to_string();
+
+        let expected = String::from(
+            "+--------+-----+\
+            \n| host   | val |\
+            \n+--------+-----+\
+            \n| host_1 | 1.0 |\
+            \n| host_2 | 0.1 |\
+            \n+--------+-----+",
+        );
+        assert_eq!(result_literal, expected);
+    }
+
+    #[test]
+    fn fold_without_columns() {
+        let schema = Schema::new(vec![
+            Field::new("host", DataType::Utf8, true),
+            Field::new("le", DataType::Utf8, true),
+        ]);
+        let input = LogicalPlan::EmptyRelation(EmptyRelation {
+            produce_one_row: false,
+            schema: Arc::new(DFSchema::try_from(schema).unwrap()),
+        });
+
+        // field column not found
+        assert!(
+            HistogramFold::new("le".to_string(), "val".to_string(), 0.5, input.clone()).is_err()
+        );
+        // le column not found
+        assert!(HistogramFold::new("foo".to_string(), "host".to_string(), 0.5, input).is_err());
+    }
+
+    #[test]
+    fn quantile_of_buckets() {
+        let mut buckets = vec![(1.0, 10.0), (f64::INFINITY, 40.0), (2.0, 20.0), (4.0, 40.0)];
+        assert_eq!(bucket_quantile(0.5, &mut buckets), 2.0);
+        assert_eq!(bucket_quantile(0.75, &mut buckets), 3.0);
+        assert_eq!(bucket_quantile(0.1, &mut buckets), 0.4);
+        // the rank is in the "+Inf" bucket
+        let mut buckets = vec![(1.0, 10.0), (f64::INFINITY, 20.0)];
+        assert_eq!(bucket_quantile(0.9, &mut buckets), 1.0);
+        // out of range quantile
+        assert_eq!(bucket_quantile(-1.0, &mut buckets), f64::NEG_INFINITY);
+        assert_eq!(bucket_quantile(2.0, &mut buckets), f64::INFINITY);
+    }
+
+    #[test]
+    fn quantile_of_invalid_buckets() {
+        // no "+Inf" bucket
+        let mut buckets = vec![(1.0, 10.0), (2.0, 20.0)];
+        assert!(bucket_quantile(0.5, &mut buckets).is_nan());
+        // no observations
+        let mut buckets = vec![(1.0, 0.0), (f64::INFINITY, 0.0)];
+        assert!(bucket_quantile(0.5, &mut buckets).is_nan());
+        // non-monotonic and duplicated buckets
+        let mut buckets = vec![
+            (1.0, 10.0),
+            (1.0, 0.0),
+            (2.0, 8.0),
+            (4.0, 20.0),
+            (f64::INFINITY, 20.0),
+        ];
+        assert_eq!(bucket_quantile(0.5, &mut buckets), 1.0);
+    }
+}
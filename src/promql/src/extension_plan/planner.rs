@@ -21,7 +21,7 @@ use datafusion::logical_expr::{LogicalPlan, UserDefinedLogicalNode};
 use datafusion::physical_plan::planner::ExtensionPlanner;
 use datafusion::physical_plan::{ExecutionPlan, PhysicalPlanner};
 
-use super::{InstantManipulate, RangeManipulate, SeriesDivide};
+use super::{EmptyMetric, HistogramFold, InstantManipulate, RangeManipulate, SeriesDivide};
 use crate::extension_plan::SeriesNormalize;
 
 pub struct PromExtensionPlanner {}
@@ -44,6 +44,10 @@ impl ExtensionPlanner for PromExtensionPlanner {
             Ok(Some(node.to_execution_plan(physical_inputs[0].clone())))
         } else if let Some(node) = node.as_any().downcast_ref::<SeriesDivide>() {
             Ok(Some(node.to_execution_plan(physical_inputs[0].clone())))
+        } else if let Some(node) = node.as_any().downcast_ref::<HistogramFold>() {
+            Ok(Some(node.to_execution_plan(physical_inputs[0].clone())?))
+        } else if let Some(node) = node.as_any().downcast_ref::<EmptyMetric>() {
+            Ok(Some(node.to_execution_plan()))
         } else {
             Ok(None)
         }
@@ -395,35 +395,57 @@ impl RangeManipulateStream {
             .downcast_ref::<TimestampMillisecondArray>()
             .unwrap();
 
-        let mut aligned_ts = vec![];
-        let mut ranges = vec![];
-
-        // calculate for every aligned timestamp (`curr_ts`), assume the ts column is ordered.
-        for curr_ts in (self.start..=self.end).step_by(self.interval as _) {
-            aligned_ts.push(curr_ts);
-            let mut range_start = ts_column.len();
-            let mut range_end = 0;
-            for (index, ts) in ts_column.values().iter().enumerate() {
-                if ts + self.range >= curr_ts {
-                    range_start = range_start.min(index);
-                }
-                if *ts <= curr_ts {
-                    range_end = range_end.max(index);
-                } else {
-                    break;
-                }
+        let (aligned_ts, ranges) = calculate_ranges(
+            ts_column.values(),
+            self.start,
+            self.end,
+            self.interval,
+            self.range,
+        );
+        let aligned_ts_array = Arc::new(TimestampMillisecondArray::from(aligned_ts)) as _;
+
+        (aligned_ts_array, ranges)
+    }
+}
+
+/// Calculates the ranges of `timestamps` for every aligned timestamp from `start` to `end`
+/// (both inclusive) by `interval`. The range of an aligned timestamp `t` covers timestamps
+/// in `[t - range, t]`, and is represented by the offset and length in `timestamps`.
+///
+/// `timestamps` must be ordered. Returns the aligned timestamps and their ranges.
+pub fn calculate_ranges(
+    timestamps: &[i64],
+    start: i64,
+    end: i64,
+    interval: i64,
+    range: i64,
+) -> (Vec<i64>, Vec<(u32, u32)>) {
+    let mut aligned_ts = vec![];
+    let mut ranges = vec![];
+
+    // calculate for every aligned timestamp (`curr_ts`), assume the ts column is ordered.
+    for curr_ts in (start..=end).step_by(interval as _) {
+        aligned_ts.push(curr_ts);
+        let mut range_start = timestamps.len();
+        let mut range_end = 0;
+        for (index, ts) in timestamps.iter().enumerate() {
+            if ts + range >= curr_ts {
+                range_start = range_start.min(index);
             }
-            if range_start > range_end {
-                ranges.push((0, 0));
+            if *ts <= curr_ts {
+                range_end = range_end.max(index);
             } else {
-                ranges.push((range_start as _, (range_end + 1 - range_start) as _));
+                break;
             }
         }
-
-        let aligned_ts_array = Arc::new(TimestampMillisecondArray::from(aligned_ts)) as _;
-
-        (aligned_ts_array, ranges)
+        if range_start > range_end {
+            ranges.push((0, 0));
+        } else {
+            ranges.push((range_start as _, (range_end + 1 - range_start) as _));
+        }
     }
+
+    (aligned_ts, ranges)
 }
 
 #[cfg(test)]
@@ -12,14 +12,38 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod aggr_over_time;
+mod changes;
+mod deriv;
+mod extrapolate_rate;
+mod format_float;
 mod idelta;
-mod increase;
+mod quantile;
+mod unique_match;
 
-use datafusion::arrow::array::ArrayRef;
+use std::sync::Arc;
+
+pub use aggr_over_time::{
+    AvgOverTime, CountOverTime, LastOverTime, MaxOverTime, MinOverTime, PresentOverTime,
+    QuantileOverTime, StddevOverTime, StdvarOverTime, SumOverTime,
+};
+pub use changes::{Changes, Resets};
+use datafusion::arrow::array::{ArrayRef, Float64Array, TimestampMillisecondArray};
+use datafusion::arrow::datatypes::TimeUnit;
 use datafusion::error::DataFusionError;
 use datafusion::physical_plan::ColumnarValue;
+use datafusion::scalar::ScalarValue;
+use datatypes::arrow::array::Array;
+use datatypes::arrow::datatypes::DataType;
+pub use deriv::{Deriv, PredictLinear};
+pub use extrapolate_rate::{Delta, ExtrapolatedRate, Increase, Rate};
+pub use format_float::FormatFloat;
 pub use idelta::IDelta;
-pub use increase::Increase;
+pub use quantile::Quantile;
+pub use unique_match::{OneToOneMatch, UniqueMatch};
+
+use crate::error;
+use crate::range_array::RangeArray;
 
 pub(crate) fn extract_array(columnar_value: &ColumnarValue) -> Result<ArrayRef, DataFusionError> {
     if let ColumnarValue::Array(array) = columnar_value {
@@ -30,3 +54,151 @@ pub(crate) fn extract_array(columnar_value: &ColumnarValue) -> Result<ArrayRef,
         ))
     }
 }
+
+/// Extract a float64 parameter (like `quantile_over_time`'s `φ`) from a scalar input.
+pub(crate) fn extract_f64_param(
+    func_name: &str,
+    columnar_value: &ColumnarValue,
+) -> Result<f64, DataFusionError> {
+    match columnar_value {
+        ColumnarValue::Scalar(ScalarValue::Float64(Some(value))) => Ok(*value),
+        _ => Err(DataFusionError::Execution(format!(
+            "{func_name}: expect a float64 scalar as parameter, found {columnar_value:?}"
+        ))),
+    }
+}
+
+/// Time index and value [RangeArray] input types of range functions.
+pub(crate) fn range_input_types() -> Vec<DataType> {
+    vec![
+        RangeArray::convert_data_type(DataType::Timestamp(TimeUnit::Millisecond, None)),
+        RangeArray::convert_data_type(DataType::Float64),
+    ]
+}
+
+/// Evaluate `fun` on every range of the given time index and value [RangeArray]s.
+/// `fun` accepts timestamps (in millisecond) and values of one range, and returns
+/// `None` if there is no result for it.
+pub(crate) fn compute_over_ranges<F>(
+    func_name: &str,
+    ts_input: &ColumnarValue,
+    value_input: &ColumnarValue,
+    mut fun: F,
+) -> Result<ColumnarValue, DataFusionError>
+where
+    F: FnMut(&[i64], &[f64]) -> Option<f64>,
+{
+    let ts_array = extract_array(ts_input)?;
+    let value_array = extract_array(value_input)?;
+
+    let ts_range: RangeArray = RangeArray::try_new(ts_array.data().clone().into())?;
+    let value_range: RangeArray = RangeArray::try_new(value_array.data().clone().into())?;
+    error::ensure(
+        ts_range.len() == value_range.len(),
+        DataFusionError::Execution(format!(
+            "{func_name}: input arrays should have the same length, found {} and {}",
+            ts_range.len(),
+            value_range.len()
+        )),
+    )?;
+    error::ensure(
+        ts_range.value_type() == DataType::Timestamp(TimeUnit::Millisecond, None),
+        DataFusionError::Execution(format!(
+            "{func_name}: expect TimestampMillisecond as time index array's type, found {}",
+            ts_range.value_type()
+        )),
+    )?;
+    error::ensure(
+        value_range.value_type() == DataType::Float64,
+        DataFusionError::Execution(format!(
+            "{func_name}: expect Float64 as value array's type, found {}",
+            value_range.value_type()
+        )),
+    )?;
+
+    let mut result_array = Vec::with_capacity(ts_range.len());
+    for index in 0..ts_range.len() {
+        let timestamps = ts_range.get(index).unwrap();
+        let timestamps = timestamps
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .unwrap()
+            .values();
+        let values = value_range.get(index).unwrap();
+        let values = values
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap()
+            .values();
+        error::ensure(
+            timestamps.len() == values.len(),
+            DataFusionError::Execution(format!(
+                "{func_name}: time index and value ranges should have the same length, found {} and {}",
+                timestamps.len(),
+                values.len()
+            )),
+        )?;
+
+        result_array.push(fun(timestamps, values));
+    }
+
+    let result = ColumnarValue::Array(Arc::new(Float64Array::from_iter(result_array)));
+    Ok(result)
+}
+
+/// Define a range function that takes time index and value ranges as input, and
+/// computes one float64 output from each range via the given function.
+macro_rules! define_range_function {
+    ($(#[$meta:meta])* $name:ident, $func_name:literal, $fun:path) => {
+        $(#[$meta])*
+        #[derive(Debug)]
+        pub struct $name {}
+
+        impl $name {
+            pub const fn name() -> &'static str {
+                $func_name
+            }
+
+            pub fn scalar_udf() -> datafusion::logical_expr::ScalarUDF {
+                datafusion::logical_expr::ScalarUDF {
+                    name: Self::name().to_string(),
+                    signature: datafusion::logical_expr::Signature::new(
+                        datafusion::logical_expr::TypeSignature::Exact(
+                            $crate::functions::range_input_types(),
+                        ),
+                        datafusion::logical_expr::Volatility::Immutable,
+                    ),
+                    return_type: std::sync::Arc::new(|_| {
+                        Ok(std::sync::Arc::new(
+                            datatypes::arrow::datatypes::DataType::Float64,
+                        ))
+                    }),
+                    fun: std::sync::Arc::new(Self::calc),
+                }
+            }
+
+            fn calc(
+                input: &[datafusion::physical_plan::ColumnarValue],
+            ) -> Result<datafusion::physical_plan::ColumnarValue, datafusion::error::DataFusionError>
+            {
+                $crate::error::ensure(
+                    input.len() == 2,
+                    datafusion::error::DataFusionError::Plan(format!(
+                        "{}: expect 2 arguments, found {}",
+                        Self::name(),
+                        input.len()
+                    )),
+                )?;
+                $crate::functions::compute_over_ranges(Self::name(), &input[0], &input[1], $fun)
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "PromQL {} Function", Self::name())
+            }
+        }
+    };
+}
+
+pub(crate) use define_range_function;
@@ -0,0 +1,392 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementations of `<aggregation>_over_time` functions,
+//! from <https://github.com/prometheus/prometheus/blob/e934d0f01158a1d55fa0ebb035346b195fcc1260/promql/functions.go#L376-L597>
+
+use std::fmt::Display;
+use std::sync::Arc;
+
+use datafusion::common::DataFusionError;
+use datafusion::logical_expr::{ScalarUDF, Signature, TypeSignature, Volatility};
+use datafusion::physical_plan::ColumnarValue;
+use datatypes::arrow::datatypes::DataType;
+
+use crate::error;
+use crate::functions::{
+    compute_over_ranges, define_range_function, extract_f64_param, range_input_types,
+};
+
+define_range_function!(
+    /// The `avg_over_time` in PromQL. The average value of all points in the range.
+    AvgOverTime,
+    "prom_avg_over_time",
+    avg_over_time
+);
+
+define_range_function!(
+    /// The `min_over_time` in PromQL. The minimum value of all points in the range.
+    MinOverTime,
+    "prom_min_over_time",
+    min_over_time
+);
+
+define_range_function!(
+    /// The `max_over_time` in PromQL. The maximum value of all points in the range.
+    MaxOverTime,
+    "prom_max_over_time",
+    max_over_time
+);
+
+define_range_function!(
+    /// The `sum_over_time` in PromQL. The sum of all values in the range.
+    SumOverTime,
+    "prom_sum_over_time",
+    sum_over_time
+);
+
+define_range_function!(
+    /// The `count_over_time` in PromQL. The count of all values in the range.
+    CountOverTime,
+    "prom_count_over_time",
+    count_over_time
+);
+
+define_range_function!(
+    /// The `last_over_time` in PromQL. The most recent point value in the range.
+    LastOverTime,
+    "prom_last_over_time",
+    last_over_time
+);
+
+define_range_function!(
+    /// The `present_over_time` in PromQL. The value 1 for any series in the range.
+    PresentOverTime,
+    "prom_present_over_time",
+    present_over_time
+);
+
+define_range_function!(
+    /// The `stddev_over_time` in PromQL. The population standard deviation of the values
+    /// in the range.
+    StddevOverTime,
+    "prom_stddev_over_time",
+    stddev_over_time
+);
+
+define_range_function!(
+    /// The `stdvar_over_time` in PromQL. The population standard variance of the values
+    /// in the range.
+    StdvarOverTime,
+    "prom_stdvar_over_time",
+    stdvar_over_time
+);
+
+fn avg_over_time(_: &[i64], values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<f64>() / values.len() as f64)
+}
+
+fn min_over_time(_: &[i64], values: &[f64]) -> Option<f64> {
+    let (first, rest) = values.split_first()?;
+    // NaN is replaced by any other value
+    Some(rest.iter().fold(
+        *first,
+        |min, v| {
+            if *v < min || min.is_nan() {
+                *v
+            } else {
+                min
+            }
+        },
+    ))
+}
+
+fn max_over_time(_: &[i64], values: &[f64]) -> Option<f64> {
+    let (first, rest) = values.split_first()?;
+    // NaN is replaced by any other value
+    Some(rest.iter().fold(
+        *first,
+        |max, v| {
+            if *v > max || max.is_nan() {
+                *v
+            } else {
+                max
+            }
+        },
+    ))
+}
+
+fn sum_over_time(_: &[i64], values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum())
+}
+
+fn count_over_time(_: &[i64], values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.len() as f64)
+}
+
+fn last_over_time(_: &[i64], values: &[f64]) -> Option<f64> {
+    values.last().copied()
+}
+
+fn present_over_time(_: &[i64], values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(1.0)
+}
+
+fn stdvar_over_time(_: &[i64], values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    // Welford's online algorithm
+    let mut count = 0.0;
+    let mut mean = 0.0;
+    let mut aux = 0.0;
+    for value in values {
+        count += 1.0;
+        let delta = value - mean;
+        mean += delta / count;
+        aux += delta * (value - mean);
+    }
+    Some(aux / count)
+}
+
+fn stddev_over_time(ts: &[i64], values: &[f64]) -> Option<f64> {
+    stdvar_over_time(ts, values).map(f64::sqrt)
+}
+
+/// Calculate the `quantile` (φ) of the given values, following Prometheus'
+/// `quantile` function in `quantile.go`.
+pub(crate) fn quantile(quantile: f64, values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    if quantile.is_nan() {
+        return Some(f64::NAN);
+    }
+    if quantile < 0.0 {
+        return Some(f64::NEG_INFINITY);
+    }
+    if quantile > 1.0 {
+        return Some(f64::INFINITY);
+    }
+
+    let mut values = values.to_vec();
+    values.sort_unstable_by(f64::total_cmp);
+
+    let n = values.len() as f64;
+    let rank = quantile * (n - 1.0);
+    let lower_index = rank.floor().max(0.0) as usize;
+    let upper_index = (lower_index + 1).min(values.len() - 1);
+    let weight = rank - rank.floor();
+    Some(values[lower_index] * (1.0 - weight) + values[upper_index] * weight)
+}
+
+/// The `quantile_over_time` in PromQL. The φ-quantile (0 ≤ φ ≤ 1) of the values in
+/// the range.
+#[derive(Debug)]
+pub struct QuantileOverTime {}
+
+impl QuantileOverTime {
+    pub const fn name() -> &'static str {
+        "prom_quantile_over_time"
+    }
+
+    pub fn scalar_udf() -> ScalarUDF {
+        ScalarUDF {
+            name: Self::name().to_string(),
+            signature: Signature::new(
+                TypeSignature::Exact(Self::input_type()),
+                Volatility::Immutable,
+            ),
+            return_type: Arc::new(|_| Ok(Arc::new(Self::return_type()))),
+            fun: Arc::new(Self::calc),
+        }
+    }
+
+    // time index column, value column and the quantile parameter
+    fn input_type() -> Vec<DataType> {
+        let mut input_type = range_input_types();
+        input_type.push(DataType::Float64);
+        input_type
+    }
+
+    fn return_type() -> DataType {
+        DataType::Float64
+    }
+
+    fn calc(input: &[ColumnarValue]) -> Result<ColumnarValue, DataFusionError> {
+        error::ensure(
+            input.len() == 3,
+            DataFusionError::Plan(format!(
+                "{}: expect 3 arguments, found {}",
+                Self::name(),
+                input.len()
+            )),
+        )?;
+        let phi = extract_f64_param(Self::name(), &input[2])?;
+        compute_over_ranges(Self::name(), &input[0], &input[1], |_, values| {
+            quantile(phi, values)
+        })
+    }
+}
+
+impl Display for QuantileOverTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PromQL QuantileOverTime Function")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use datafusion::arrow::array::{Float64Array, TimestampMillisecondArray};
+    use datafusion::scalar::ScalarValue;
+
+    use super::*;
+    use crate::functions::extract_array;
+    use crate::range_array::RangeArray;
+
+    fn prepare_input() -> Vec<ColumnarValue> {
+        let ts_array = Arc::new(TimestampMillisecondArray::from_iter(
+            [1000i64, 2000, 3000, 4000, 5000, 6000, 7000, 8000, 9000]
+                .into_iter()
+                .map(Some),
+        ));
+        let values_array = Arc::new(Float64Array::from_iter([
+            1.0, 5.0, 3.0, 2.0, 8.0, 4.0, 6.0, 7.0, 9.0,
+        ]));
+        let ranges = [(0, 2), (0, 5), (1, 1), (3, 3), (8, 1), (9, 0)];
+
+        let ts_range_array = RangeArray::from_ranges(ts_array, ranges).unwrap();
+        let value_range_array = RangeArray::from_ranges(values_array, ranges).unwrap();
+        vec![
+            ColumnarValue::Array(Arc::new(ts_range_array.into_dict())),
+            ColumnarValue::Array(Arc::new(value_range_array.into_dict())),
+        ]
+    }
+
+    fn collect_output(output: ColumnarValue) -> Vec<Option<f64>> {
+        extract_array(&output)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap()
+            .iter()
+            .collect()
+    }
+
+    #[test]
+    fn simple_aggregations() {
+        let input = prepare_input();
+        assert_eq!(
+            collect_output(AvgOverTime::calc(&input).unwrap()),
+            vec![
+                Some(3.0),
+                Some(3.8),
+                Some(5.0),
+                Some(14.0 / 3.0),
+                Some(9.0),
+                None
+            ]
+        );
+        assert_eq!(
+            collect_output(MinOverTime::calc(&input).unwrap()),
+            vec![Some(1.0), Some(1.0), Some(5.0), Some(2.0), Some(9.0), None]
+        );
+        assert_eq!(
+            collect_output(MaxOverTime::calc(&input).unwrap()),
+            vec![Some(5.0), Some(8.0), Some(5.0), Some(8.0), Some(9.0), None]
+        );
+        assert_eq!(
+            collect_output(SumOverTime::calc(&input).unwrap()),
+            vec![
+                Some(6.0),
+                Some(19.0),
+                Some(5.0),
+                Some(14.0),
+                Some(9.0),
+                None
+            ]
+        );
+        assert_eq!(
+            collect_output(CountOverTime::calc(&input).unwrap()),
+            vec![Some(2.0), Some(5.0), Some(1.0), Some(3.0), Some(1.0), None]
+        );
+        assert_eq!(
+            collect_output(LastOverTime::calc(&input).unwrap()),
+            vec![Some(5.0), Some(8.0), Some(5.0), Some(4.0), Some(9.0), None]
+        );
+        assert_eq!(
+            collect_output(PresentOverTime::calc(&input).unwrap()),
+            vec![Some(1.0), Some(1.0), Some(1.0), Some(1.0), Some(1.0), None]
+        );
+    }
+
+    #[test]
+    fn variance_and_deviation() {
+        let input = prepare_input();
+        let stdvar = collect_output(StdvarOverTime::calc(&input).unwrap());
+        let expected = [
+            Some(4.0),
+            Some(6.16),
+            Some(0.0),
+            Some(56.0 / 9.0),
+            Some(0.0),
+            None,
+        ];
+        for (result, expected) in stdvar.iter().zip(expected.iter()) {
+            match (result, expected) {
+                (Some(result), Some(expected)) => assert!((result - expected).abs() < 1e-10),
+                _ => assert_eq!(result, expected),
+            }
+        }
+
+        let stddev = collect_output(StddevOverTime::calc(&input).unwrap());
+        assert!((stddev[0].unwrap() - 2.0).abs() < 1e-10);
+        assert!((stddev[1].unwrap() - 6.16f64.sqrt()).abs() < 1e-10);
+        assert_eq!(stddev[5], None);
+    }
+
+    #[test]
+    fn quantile_over_time() {
+        let mut input = prepare_input();
+        input.push(ColumnarValue::Scalar(ScalarValue::Float64(Some(0.5))));
+        assert_eq!(
+            collect_output(QuantileOverTime::calc(&input).unwrap()),
+            vec![Some(3.0), Some(3.0), Some(5.0), Some(4.0), Some(9.0), None]
+        );
+
+        input[2] = ColumnarValue::Scalar(ScalarValue::Float64(Some(0.9)));
+        let result = collect_output(QuantileOverTime::calc(&input).unwrap());
+        assert!((result[1].unwrap() - 6.8).abs() < 1e-10);
+
+        input[2] = ColumnarValue::Scalar(ScalarValue::Float64(Some(-1.0)));
+        let result = collect_output(QuantileOverTime::calc(&input).unwrap());
+        assert_eq!(result[0], Some(f64::NEG_INFINITY));
+
+        input[2] = ColumnarValue::Scalar(ScalarValue::Float64(Some(2.0)));
+        let result = collect_output(QuantileOverTime::calc(&input).unwrap());
+        assert_eq!(result[0], Some(f64::INFINITY));
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementations of `changes` and `resets` functions,
+//! from <https://github.com/prometheus/prometheus/blob/e934d0f01158a1d55fa0ebb035346b195fcc1260/promql/functions.go#L1061-L1097>
+
+use crate::functions::define_range_function;
+
+define_range_function!(
+    /// The `changes` in PromQL. The number of times the value has changed within the
+    /// range.
+    Changes,
+    "prom_changes",
+    changes
+);
+
+define_range_function!(
+    /// The `resets` in PromQL. The number of counter resets within the range. Any decrease
+    /// in the value between two consecutive points is treated as a counter reset.
+    Resets,
+    "prom_resets",
+    resets
+);
+
+fn changes(_: &[i64], values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let count = values
+        .windows(2)
+        .filter(|window| {
+            let (prev, curr) = (window[0], window[1]);
+            // two NaNs are not treated as a change
+            curr != prev && !(curr.is_nan() && prev.is_nan())
+        })
+        .count();
+    Some(count as f64)
+}
+
+fn resets(_: &[i64], values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let count = values
+        .windows(2)
+        .filter(|window| window[1] < window[0])
+        .count();
+    Some(count as f64)
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use datafusion::arrow::array::{Float64Array, TimestampMillisecondArray};
+    use datafusion::physical_plan::ColumnarValue;
+
+    use super::*;
+    use crate::functions::extract_array;
+    use crate::range_array::RangeArray;
+
+    fn range_fn_runner(
+        values: Vec<f64>,
+        ranges: Vec<(u32, u32)>,
+        fun: fn(&[ColumnarValue]) -> Result<ColumnarValue, datafusion::error::DataFusionError>,
+        expected: Vec<Option<f64>>,
+    ) {
+        let ts_array = Arc::new(TimestampMillisecondArray::from_iter(
+            (0..values.len() as i64).map(|i| Some(i * 1000)),
+        ));
+        let values_array = Arc::new(Float64Array::from_iter(values));
+        let ts_range_array = RangeArray::from_ranges(ts_array, ranges.clone()).unwrap();
+        let value_range_array = RangeArray::from_ranges(values_array, ranges).unwrap();
+        let input = vec![
+            ColumnarValue::Array(Arc::new(ts_range_array.into_dict())),
+            ColumnarValue::Array(Arc::new(value_range_array.into_dict())),
+        ];
+        let output = extract_array(&fun(&input).unwrap())
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn changes_and_resets() {
+        let values = vec![1.0, 1.0, 2.0, 3.0, 1.0, 1.0, f64::NAN, f64::NAN, 4.0];
+        let ranges = vec![(0, 2), (0, 5), (1, 1), (4, 4), (5, 4), (9, 0)];
+        range_fn_runner(
+            values.clone(),
+            ranges.clone(),
+            Changes::calc,
+            vec![Some(0.0), Some(3.0), Some(0.0), Some(1.0), Some(2.0), None],
+        );
+        range_fn_runner(
+            values,
+            ranges,
+            Resets::calc,
+            vec![Some(0.0), Some(1.0), Some(0.0), Some(0.0), Some(0.0), None],
+        );
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementations of `deriv` and `predict_linear` functions,
+//! from <https://github.com/prometheus/prometheus/blob/e934d0f01158a1d55fa0ebb035346b195fcc1260/promql/functions.go#L893-L957>
+
+use std::fmt::Display;
+use std::sync::Arc;
+
+use datafusion::arrow::array::{Float64Array, TimestampMillisecondArray};
+use datafusion::arrow::datatypes::TimeUnit;
+use datafusion::common::DataFusionError;
+use datafusion::logical_expr::{ScalarUDF, Signature, TypeSignature, Volatility};
+use datafusion::physical_plan::ColumnarValue;
+use datatypes::arrow::array::Array;
+use datatypes::arrow::datatypes::DataType;
+
+use crate::error;
+use crate::functions::{
+    compute_over_ranges, define_range_function, extract_array, extract_f64_param, range_input_types,
+};
+
+define_range_function!(
+    /// The `deriv` in PromQL. The per-second derivative of the time series in the range,
+    /// using simple linear regression.
+    Deriv,
+    "prom_deriv",
+    deriv
+);
+
+fn deriv(timestamps: &[i64], values: &[f64]) -> Option<f64> {
+    if timestamps.len() < 2 {
+        return None;
+    }
+    // use the first timestamp as the intercept time to avoid floating point accuracy issues
+    let (slope, _) = linear_regression(timestamps, values, timestamps[0]);
+    Some(slope)
+}
+
+/// Calculate the slope and intercept of the given points via simple linear regression.
+/// Timestamps are in millisecond and will be converted to seconds relative to
+/// `intercept_time`.
+fn linear_regression(timestamps: &[i64], values: &[f64], intercept_time: i64) -> (f64, f64) {
+    let mut n = 0.0;
+    let mut sum_x = 0.0;
+    let mut sum_y = 0.0;
+    let mut sum_xy = 0.0;
+    let mut sum_x2 = 0.0;
+    let init_y = values[0];
+    let mut const_y = true;
+
+    for (ts, value) in timestamps.iter().zip(values.iter()) {
+        if const_y && *value != init_y {
+            const_y = false;
+        }
+        n += 1.0;
+        let x = (ts - intercept_time) as f64 / 1e3;
+        sum_x += x;
+        sum_y += value;
+        sum_xy += x * value;
+        sum_x2 += x * x;
+    }
+    if const_y {
+        if init_y.is_infinite() {
+            return (f64::NAN, f64::NAN);
+        }
+        return (0.0, init_y);
+    }
+
+    let cov_xy = sum_xy - sum_x * sum_y / n;
+    let var_x = sum_x2 - sum_x * sum_x / n;
+
+    let slope = cov_xy / var_x;
+    let intercept = sum_y / n - slope * sum_x / n;
+    (slope, intercept)
+}
+
+/// The `predict_linear` in PromQL. Predicts the value of time series `t` seconds from
+/// the evaluation time, using simple linear regression.
+#[derive(Debug)]
+pub struct PredictLinear {}
+
+impl PredictLinear {
+    pub const fn name() -> &'static str {
+        "prom_predict_linear"
+    }
+
+    pub fn scalar_udf() -> ScalarUDF {
+        ScalarUDF {
+            name: Self::name().to_string(),
+            signature: Signature::new(
+                TypeSignature::Exact(Self::input_type()),
+                Volatility::Immutable,
+            ),
+            return_type: Arc::new(|_| Ok(Arc::new(Self::return_type()))),
+            fun: Arc::new(Self::calc),
+        }
+    }
+
+    // time index range column, value range column, the duration parameter (in second)
+    // and the evaluation timestamp column
+    fn input_type() -> Vec<DataType> {
+        let mut input_type = range_input_types();
+        input_type.push(DataType::Float64);
+        input_type.push(DataType::Timestamp(TimeUnit::Millisecond, None));
+        input_type
+    }
+
+    fn return_type() -> DataType {
+        DataType::Float64
+    }
+
+    fn calc(input: &[ColumnarValue]) -> Result<ColumnarValue, DataFusionError> {
+        error::ensure(
+            input.len() == 4,
+            DataFusionError::Plan(format!(
+                "{}: expect 4 arguments, found {}",
+                Self::name(),
+                input.len()
+            )),
+        )?;
+        let duration = extract_f64_param(Self::name(), &input[2])?;
+        let eval_ts_array = extract_array(&input[3])?;
+        let eval_timestamps = eval_ts_array
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .ok_or_else(|| {
+                DataFusionError::Execution(format!(
+                    "{}: expect TimestampMillisecond as evaluation timestamp's type, found {}",
+                    Self::name(),
+                    eval_ts_array.data_type()
+                ))
+            })?
+            .values();
+
+        let mut index = 0;
+        let result = compute_over_ranges(Self::name(), &input[0], &input[1], |ts, values| {
+            let eval_ts = *eval_timestamps.get(index)?;
+            index += 1;
+            if ts.len() < 2 {
+                return None;
+            }
+            let (slope, intercept) = linear_regression(ts, values, eval_ts);
+            Some(slope * duration + intercept)
+        })?;
+
+        Ok(result)
+    }
+}
+
+impl Display for PredictLinear {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PromQL PredictLinear Function")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use datafusion::scalar::ScalarValue;
+
+    use super::*;
+    use crate::range_array::RangeArray;
+
+    fn prepare_input() -> Vec<ColumnarValue> {
+        let ts_array = Arc::new(TimestampMillisecondArray::from_iter(
+            [0i64, 1000, 2000, 3000, 4000, 5000, 6000, 7000, 8000]
+                .into_iter()
+                .map(Some),
+        ));
+        let values_array = Arc::new(Float64Array::from_iter([
+            0.0, 2.0, 4.0, 6.0, 6.0, 6.0, 6.0, 5.0, 4.0,
+        ]));
+        let ranges = [(0, 4), (3, 4), (6, 3), (8, 1)];
+
+        let ts_range_array = RangeArray::from_ranges(ts_array, ranges).unwrap();
+        let value_range_array = RangeArray::from_ranges(values_array, ranges).unwrap();
+        vec![
+            ColumnarValue::Array(Arc::new(ts_range_array.into_dict())),
+            ColumnarValue::Array(Arc::new(value_range_array.into_dict())),
+        ]
+    }
+
+    fn collect_output(output: ColumnarValue) -> Vec<Option<f64>> {
+        extract_array(&output)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap()
+            .iter()
+            .collect()
+    }
+
+    #[test]
+    fn deriv_slope() {
+        let input = prepare_input();
+        assert_eq!(
+            collect_output(Deriv::calc(&input).unwrap()),
+            vec![Some(2.0), Some(0.0), Some(-1.0), None]
+        );
+    }
+
+    #[test]
+    fn predict_linear_values() {
+        let mut input = prepare_input();
+        input.push(ColumnarValue::Scalar(ScalarValue::Float64(Some(10.0))));
+        input.push(ColumnarValue::Array(Arc::new(
+            TimestampMillisecondArray::from_iter([3000i64, 6000, 8000, 8000].into_iter().map(Some)),
+        )));
+        assert_eq!(
+            collect_output(PredictLinear::calc(&input).unwrap()),
+            vec![Some(26.0), Some(6.0), Some(-6.0), None]
+        );
+    }
+}
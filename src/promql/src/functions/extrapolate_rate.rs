@@ -0,0 +1,365 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementations of `rate`, `increase` and `delta` functions in PromQL.
+
+use std::fmt::Display;
+use std::sync::Arc;
+
+use datafusion::arrow::array::TimestampMillisecondArray;
+use datafusion::arrow::datatypes::TimeUnit;
+use datafusion::common::DataFusionError;
+use datafusion::logical_expr::{ScalarUDF, Signature, TypeSignature, Volatility};
+use datafusion::physical_plan::ColumnarValue;
+use datafusion::scalar::ScalarValue;
+use datatypes::arrow::array::Array;
+use datatypes::arrow::datatypes::DataType;
+
+use crate::error;
+use crate::extension_plan::Millisecond;
+use crate::functions::{compute_over_ranges, extract_array, range_input_types};
+
+pub type Delta = ExtrapolatedRate<false, false>;
+pub type Rate = ExtrapolatedRate<true, true>;
+pub type Increase = ExtrapolatedRate<true, false>;
+
+/// The `extrapolatedRate` in Promql,
+/// from https://github.com/prometheus/prometheus/blob/e934d0f01158a1d55fa0ebb035346b195fcc1260/promql/functions.go#L66
+///
+/// It accepts four inputs: the time index range, the value range, the evaluation timestamp
+/// and the range length (in millisecond) of the matrix selector.
+#[derive(Debug)]
+pub struct ExtrapolatedRate<const IS_COUNTER: bool, const IS_RATE: bool> {}
+
+impl<const IS_COUNTER: bool, const IS_RATE: bool> ExtrapolatedRate<IS_COUNTER, IS_RATE> {
+    pub const fn name() -> &'static str {
+        match (IS_COUNTER, IS_RATE) {
+            (true, true) => "prom_rate",
+            (true, false) => "prom_increase",
+            (false, _) => "prom_delta",
+        }
+    }
+
+    pub fn scalar_udf() -> ScalarUDF {
+        ScalarUDF {
+            name: Self::name().to_string(),
+            signature: Signature::new(
+                TypeSignature::Exact(Self::input_type()),
+                Volatility::Immutable,
+            ),
+            return_type: Arc::new(|_| Ok(Arc::new(Self::return_type()))),
+            fun: Arc::new(Self::calc),
+        }
+    }
+
+    // time index range column, value range column, evaluation timestamp column and the range length
+    fn input_type() -> Vec<DataType> {
+        let mut input_type = range_input_types();
+        input_type.push(DataType::Timestamp(TimeUnit::Millisecond, None));
+        input_type.push(DataType::Int64);
+        input_type
+    }
+
+    fn return_type() -> DataType {
+        DataType::Float64
+    }
+
+    fn calc(input: &[ColumnarValue]) -> Result<ColumnarValue, DataFusionError> {
+        error::ensure(
+            input.len() == 4,
+            DataFusionError::Plan(format!(
+                "{}: expect 4 arguments, found {}",
+                Self::name(),
+                input.len()
+            )),
+        )?;
+        let eval_ts_array = extract_array(&input[2])?;
+        let eval_timestamps = eval_ts_array
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .ok_or_else(|| {
+                DataFusionError::Execution(format!(
+                    "{}: expect TimestampMillisecond as evaluation timestamp's type, found {}",
+                    Self::name(),
+                    eval_ts_array.data_type()
+                ))
+            })?
+            .values();
+        let range_length = match &input[3] {
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(range))) => *range,
+            other => {
+                return Err(DataFusionError::Execution(format!(
+                    "{}: expect a int64 scalar as range length, found {other:?}",
+                    Self::name()
+                )))
+            }
+        };
+
+        let mut index = 0;
+        compute_over_ranges(Self::name(), &input[0], &input[1], |timestamps, values| {
+            let eval_ts = *eval_timestamps.get(index)?;
+            index += 1;
+            Self::extrapolate(
+                timestamps,
+                values,
+                eval_ts - range_length,
+                eval_ts,
+                range_length,
+            )
+        })
+    }
+
+    /// Calculate the (extrapolated) delta of one range. `range_start` and `range_end`
+    /// are the boundaries of the range in millisecond.
+    fn extrapolate(
+        timestamps: &[Millisecond],
+        values: &[f64],
+        range_start: Millisecond,
+        range_end: Millisecond,
+        range_length: Millisecond,
+    ) -> Option<f64> {
+        if timestamps.len() < 2 {
+            return None;
+        }
+
+        let first_value = values[0];
+        let last_value = *values.last().unwrap();
+        let mut result_value = last_value - first_value;
+        if IS_COUNTER {
+            // refer to functions.go L83-L91, handle counter reset
+            let mut prev_value = 0.0;
+            for value in values {
+                if *value < prev_value {
+                    result_value += prev_value;
+                }
+                prev_value = *value;
+            }
+        }
+
+        // duration between first/last samples and boundary of range
+        let first_ts = timestamps[0];
+        let last_ts = *timestamps.last().unwrap();
+        let mut duration_to_start = (first_ts - range_start) as f64 / 1000.0;
+        let duration_to_end = (range_end - last_ts) as f64 / 1000.0;
+        let sampled_interval = (last_ts - first_ts) as f64 / 1000.0;
+        let average_duration_between_samples = sampled_interval / (timestamps.len() - 1) as f64;
+
+        if IS_COUNTER && result_value > 0.0 && first_value >= 0.0 {
+            // counters cannot be negative. If we have any slope at all (i.e. result_value
+            // went up), we can extrapolate the zero point of the counter. If the duration
+            // to the zero point is shorter than the duration to start, we take the zero
+            // point as the start of the series, thereby avoiding extrapolation to negative
+            // counter values.
+            let duration_to_zero = sampled_interval * (first_value / result_value);
+            if duration_to_zero < duration_to_start {
+                duration_to_start = duration_to_zero;
+            }
+        }
+
+        // If the first/last samples are close to the boundaries of the range, extrapolate
+        // the result. This is as we expect that another sample will exist given the spacing
+        // between samples we've seen thus far, with an allowance for noise.
+        let extrapolation_threshold = average_duration_between_samples * 1.1;
+        let mut extrapolate_to_interval = sampled_interval;
+        if duration_to_start < extrapolation_threshold {
+            extrapolate_to_interval += duration_to_start;
+        } else {
+            extrapolate_to_interval += average_duration_between_samples / 2.0;
+        }
+        if duration_to_end < extrapolation_threshold {
+            extrapolate_to_interval += duration_to_end;
+        } else {
+            extrapolate_to_interval += average_duration_between_samples / 2.0;
+        }
+        result_value *= extrapolate_to_interval / sampled_interval;
+        if IS_RATE {
+            result_value /= range_length as f64 / 1000.0;
+        }
+
+        Some(result_value)
+    }
+}
+
+impl<const IS_COUNTER: bool, const IS_RATE: bool> Display
+    for ExtrapolatedRate<IS_COUNTER, IS_RATE>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PromQL ExtrapolatedRate Function (is_counter: {IS_COUNTER}, is_rate: {IS_RATE})"
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use datafusion::arrow::array::Float64Array;
+
+    use super::*;
+    use crate::range_array::RangeArray;
+
+    const MINUTE: Millisecond = 60_000;
+
+    /// Evaluate the given function on one range that covers all given samples, at
+    /// timestamp `eval_ts` with range length `range`.
+    fn extrapolate_runner(
+        fun: fn(&[ColumnarValue]) -> Result<ColumnarValue, DataFusionError>,
+        samples: &[(Millisecond, f64)],
+        eval_ts: Millisecond,
+        range: Millisecond,
+    ) -> Option<f64> {
+        let (timestamps, values): (Vec<_>, Vec<_>) = samples
+            .iter()
+            .filter(|(ts, _)| *ts >= eval_ts - range && *ts <= eval_ts)
+            .copied()
+            .unzip();
+        let len = timestamps.len() as u32;
+        let ts_array = Arc::new(TimestampMillisecondArray::from_iter(
+            timestamps.into_iter().map(Some),
+        ));
+        let values_array = Arc::new(Float64Array::from_iter(values));
+        let input = vec![
+            ColumnarValue::Array(Arc::new(
+                RangeArray::from_ranges(ts_array, [(0, len)])
+                    .unwrap()
+                    .into_dict(),
+            )),
+            ColumnarValue::Array(Arc::new(
+                RangeArray::from_ranges(values_array, [(0, len)])
+                    .unwrap()
+                    .into_dict(),
+            )),
+            ColumnarValue::Array(Arc::new(TimestampMillisecondArray::from_iter([Some(
+                eval_ts,
+            )]))),
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(range))),
+        ];
+        extract_array(&fun(&input).unwrap())
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap()
+            .iter()
+            .next()
+            .unwrap()
+    }
+
+    /// Build samples like `load 5m <start>+<step>x<times>` in Prometheus' test script.
+    fn load_5m(series: &[(f64, f64, usize)]) -> Vec<(Millisecond, f64)> {
+        let mut ts = 0;
+        let mut samples = vec![];
+        for (start, step, times) in series {
+            for i in 0..=*times {
+                samples.push((ts, start + step * i as f64));
+                ts += 5 * MINUTE;
+            }
+        }
+        samples
+    }
+
+    // The following cases are from Prometheus' `promql/testdata/functions.test`.
+
+    #[test]
+    fn increase_fixtures() {
+        // http_requests{path="/foo"}	0+10x10
+        // http_requests{path="/bar"}	0+10x5 0+10x5
+        let foo = load_5m(&[(0.0, 10.0, 10)]);
+        let bar = load_5m(&[(0.0, 10.0, 5), (0.0, 10.0, 5)]);
+
+        // eval instant at 50m increase(http_requests[50m])
+        assert_eq!(
+            extrapolate_runner(Increase::calc, &foo, 50 * MINUTE, 50 * MINUTE),
+            Some(100.0)
+        );
+        assert_eq!(
+            extrapolate_runner(Increase::calc, &bar, 50 * MINUTE, 50 * MINUTE),
+            Some(90.0)
+        );
+
+        // eval instant at 50m increase(http_requests[100m])
+        assert_eq!(
+            extrapolate_runner(Increase::calc, &foo, 50 * MINUTE, 100 * MINUTE),
+            Some(100.0)
+        );
+        assert_eq!(
+            extrapolate_runner(Increase::calc, &bar, 50 * MINUTE, 100 * MINUTE),
+            Some(90.0)
+        );
+    }
+
+    #[test]
+    fn rate_fixtures() {
+        // testcounter_reset_middle	0+10x4 0+10x5
+        // testcounter_reset_end    	0+10x9 0 10
+        let reset_middle = load_5m(&[(0.0, 10.0, 4), (0.0, 10.0, 5)]);
+        let reset_end = load_5m(&[(0.0, 10.0, 9), (0.0, 10.0, 1)]);
+
+        // eval instant at 50m rate(testcounter_reset_middle[50m])
+        let result = extrapolate_runner(Rate::calc, &reset_middle, 50 * MINUTE, 50 * MINUTE);
+        assert!((result.unwrap() - 0.03).abs() < 1e-10);
+
+        // eval instant at 50m rate(testcounter_reset_end[5m])
+        assert_eq!(
+            extrapolate_runner(Rate::calc, &reset_end, 50 * MINUTE, 5 * MINUTE),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn delta_fixtures() {
+        // http_requests{path="/foo"}	0 50 100 150 200
+        // http_requests{path="/bar"}	200 150 100 50 0
+        let foo = load_5m(&[(0.0, 50.0, 4)]);
+        let bar = load_5m(&[(200.0, -50.0, 4)]);
+
+        // eval instant at 20m delta(http_requests[20m])
+        assert_eq!(
+            extrapolate_runner(Delta::calc, &foo, 20 * MINUTE, 20 * MINUTE),
+            Some(200.0)
+        );
+        assert_eq!(
+            extrapolate_runner(Delta::calc, &bar, 20 * MINUTE, 20 * MINUTE),
+            Some(-200.0)
+        );
+    }
+
+    #[test]
+    fn extrapolate_to_boundaries() {
+        // samples in the middle of the range are extrapolated by half of the average
+        // interval on both sides
+        let samples = [(2 * MINUTE, 10.0), (3 * MINUTE, 20.0), (4 * MINUTE, 30.0)];
+        // sampled interval: 120s, average interval: 60s, extrapolated to 180s
+        assert_eq!(
+            extrapolate_runner(Delta::calc, &samples, 6 * MINUTE, 6 * MINUTE),
+            Some(30.0)
+        );
+        // counter doesn't extrapolate to below zero
+        let samples = [(2 * MINUTE, 5.0), (3 * MINUTE, 15.0), (4 * MINUTE, 25.0)];
+        // duration to zero: 30s, extrapolated to 120s + 30s + 30s
+        assert_eq!(
+            extrapolate_runner(Increase::calc, &samples, 6 * MINUTE, 6 * MINUTE),
+            Some(30.0)
+        );
+    }
+
+    #[test]
+    fn short_input() {
+        let samples = [(0, 1.0)];
+        assert_eq!(
+            extrapolate_runner(Increase::calc, &samples, 0, 5 * MINUTE),
+            None
+        );
+        assert_eq!(extrapolate_runner(Rate::calc, &[], 0, 5 * MINUTE), None);
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use datafusion::arrow::array::{Float64Array, StringArray};
+use datafusion::common::DataFusionError;
+use datafusion::logical_expr::{ScalarUDF, Signature, TypeSignature, Volatility};
+use datafusion::physical_plan::ColumnarValue;
+use datatypes::arrow::array::Array;
+use datatypes::arrow::datatypes::DataType;
+
+use crate::error;
+use crate::functions::extract_array;
+
+/// Format float values into label values like Prometheus, which is used by `count_values`.
+///
+/// Prometheus formats values with `strconv.FormatFloat(value, 'f', -1, 64)`, i.e., the
+/// shortest decimal representation without exponent, like `1` and `0.5`.
+#[derive(Debug)]
+pub struct FormatFloat {}
+
+impl FormatFloat {
+    pub const fn name() -> &'static str {
+        "prom_format_float"
+    }
+
+    pub fn scalar_udf() -> ScalarUDF {
+        ScalarUDF {
+            name: Self::name().to_string(),
+            signature: Signature::new(
+                TypeSignature::Exact(vec![DataType::Float64]),
+                Volatility::Immutable,
+            ),
+            return_type: Arc::new(|_| Ok(Arc::new(DataType::Utf8))),
+            fun: Arc::new(Self::calc),
+        }
+    }
+
+    fn calc(input: &[ColumnarValue]) -> Result<ColumnarValue, DataFusionError> {
+        error::ensure(
+            input.len() == 1,
+            DataFusionError::Plan(format!(
+                "{}: expect 1 argument, found {}",
+                Self::name(),
+                input.len()
+            )),
+        )?;
+        let value_array = extract_array(&input[0])?;
+        let value_array = value_array
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .ok_or_else(|| {
+                DataFusionError::Execution(format!(
+                    "{}: expect Float64 as input type, found {}",
+                    Self::name(),
+                    value_array.data_type()
+                ))
+            })?;
+
+        let result = value_array
+            .iter()
+            .map(|value| value.map(format_float))
+            .collect::<StringArray>();
+        Ok(ColumnarValue::Array(Arc::new(result)))
+    }
+}
+
+fn format_float(value: f64) -> String {
+    if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        // `Display` of f64 prints the shortest representation without exponent
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_float_like_prometheus() {
+        assert_eq!(format_float(1.0), "1");
+        assert_eq!(format_float(-2.0), "-2");
+        assert_eq!(format_float(0.5), "0.5");
+        assert_eq!(format_float(1e21), "1000000000000000000000");
+        assert_eq!(format_float(1e-7), "0.0000001");
+        assert_eq!(format_float(f64::NAN), "NaN");
+        assert_eq!(format_float(f64::INFINITY), "+Inf");
+        assert_eq!(format_float(f64::NEG_INFINITY), "-Inf");
+    }
+
+    #[test]
+    fn format_float_array() {
+        let values = ColumnarValue::Array(Arc::new(Float64Array::from(vec![
+            Some(1.0),
+            None,
+            Some(2.5),
+        ])));
+        let result = FormatFloat::calc(&[values.clone()]).unwrap();
+        let result = extract_array(&result).unwrap();
+        let result = result.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some("1"), None, Some("2.5")]
+        );
+
+        // wrong number of arguments
+        assert!(FormatFloat::calc(&[]).is_err());
+        assert!(FormatFloat::calc(&[values.clone(), values]).is_err());
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `quantile` aggregation operator.
+
+use std::fmt::Display;
+use std::sync::Arc;
+
+use datafusion::arrow::array::{ArrayRef, Float64Array, ListArray};
+use datafusion::common::DataFusionError;
+use datafusion::logical_expr::{
+    AccumulatorFunctionImplementation, AggregateUDF, ReturnTypeFunction, Signature,
+    StateTypeFunction, TypeSignature, Volatility,
+};
+use datafusion::physical_plan::Accumulator;
+use datafusion::scalar::ScalarValue;
+use datatypes::arrow::array::Array;
+use datatypes::arrow::datatypes::{DataType, Field};
+
+use crate::functions::aggr_over_time::quantile;
+
+/// The `quantile` aggregation operator in PromQL. Calculates the φ-quantile (0 ≤ φ ≤ 1)
+/// over dimensions. The first argument is the value column and the second is φ.
+#[derive(Debug)]
+pub struct Quantile {}
+
+impl Quantile {
+    pub const fn name() -> &'static str {
+        "prom_quantile"
+    }
+
+    pub fn aggregate_udf() -> AggregateUDF {
+        let return_type: ReturnTypeFunction = Arc::new(|_| Ok(Arc::new(DataType::Float64)));
+        let accumulator: AccumulatorFunctionImplementation =
+            Arc::new(|_| Ok(Box::new(QuantileAccumulator::default()) as Box<dyn Accumulator>));
+        let state_type: StateTypeFunction = Arc::new(|_| Ok(Arc::new(Self::state_type())));
+        AggregateUDF::new(
+            Self::name(),
+            &Signature::new(
+                TypeSignature::Exact(vec![DataType::Float64, DataType::Float64]),
+                Volatility::Immutable,
+            ),
+            &return_type,
+            &accumulator,
+            &state_type,
+        )
+    }
+
+    // the quantile parameter and all values seen so far
+    fn state_type() -> Vec<DataType> {
+        vec![
+            DataType::Float64,
+            DataType::List(Box::new(Field::new("item", DataType::Float64, true))),
+        ]
+    }
+}
+
+impl Display for Quantile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PromQL Quantile Aggregator")
+    }
+}
+
+#[derive(Debug, Default)]
+struct QuantileAccumulator {
+    phi: Option<f64>,
+    values: Vec<f64>,
+}
+
+impl QuantileAccumulator {
+    fn update_phi(&mut self, phi_array: &ArrayRef) -> Result<(), DataFusionError> {
+        if self.phi.is_some() {
+            return Ok(());
+        }
+        let phi_array = downcast_f64_array(phi_array)?;
+        self.phi = phi_array.iter().flatten().next();
+        Ok(())
+    }
+}
+
+impl Accumulator for QuantileAccumulator {
+    fn state(&self) -> Result<Vec<ScalarValue>, DataFusionError> {
+        let values = self
+            .values
+            .iter()
+            .map(|value| ScalarValue::Float64(Some(*value)))
+            .collect();
+        Ok(vec![
+            ScalarValue::Float64(self.phi),
+            ScalarValue::new_list(Some(values), DataType::Float64),
+        ])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<(), DataFusionError> {
+        if values.len() != 2 {
+            return Err(DataFusionError::Plan(format!(
+                "{}: expect 2 arguments, found {}",
+                Quantile::name(),
+                values.len()
+            )));
+        }
+        self.update_phi(&values[1])?;
+        self.values
+            .extend(downcast_f64_array(&values[0])?.iter().flatten());
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<(), DataFusionError> {
+        if states.len() != 2 {
+            return Err(DataFusionError::Internal(format!(
+                "{}: expect 2 states, found {}",
+                Quantile::name(),
+                states.len()
+            )));
+        }
+        self.update_phi(&states[0])?;
+        let lists = states[1]
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .ok_or_else(|| {
+                DataFusionError::Internal(format!(
+                    "{}: expect List as state's type, found {}",
+                    Quantile::name(),
+                    states[1].data_type()
+                ))
+            })?;
+        for list in lists.iter().flatten() {
+            self.values
+                .extend(downcast_f64_array(&list)?.iter().flatten());
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue, DataFusionError> {
+        let result = match self.phi {
+            Some(phi) => quantile(phi, &self.values),
+            None => None,
+        };
+        Ok(ScalarValue::Float64(result))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.values.capacity() * std::mem::size_of::<f64>()
+    }
+}
+
+fn downcast_f64_array(array: &ArrayRef) -> Result<&Float64Array, DataFusionError> {
+    array
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .ok_or_else(|| {
+            DataFusionError::Execution(format!(
+                "{}: expect Float64 as input's type, found {}",
+                Quantile::name(),
+                array.data_type()
+            ))
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn quantile_accumulator() {
+        let mut accumulator = QuantileAccumulator::default();
+        let values: ArrayRef = Arc::new(Float64Array::from(vec![1.0, 5.0, 3.0]));
+        let phi: ArrayRef = Arc::new(Float64Array::from(vec![0.5, 0.5, 0.5]));
+        accumulator.update_batch(&[values, phi]).unwrap();
+        assert_eq!(
+            accumulator.evaluate().unwrap(),
+            ScalarValue::Float64(Some(3.0))
+        );
+
+        // merge another partial state
+        let mut other = QuantileAccumulator::default();
+        let values: ArrayRef = Arc::new(Float64Array::from(vec![2.0, 4.0, 6.0]));
+        let phi: ArrayRef = Arc::new(Float64Array::from(vec![0.5, 0.5, 0.5]));
+        other.update_batch(&[values, phi]).unwrap();
+        let states = other
+            .state()
+            .unwrap()
+            .into_iter()
+            .map(|state| state.to_array())
+            .collect::<Vec<_>>();
+        accumulator.merge_batch(&states).unwrap();
+        assert_eq!(
+            accumulator.evaluate().unwrap(),
+            ScalarValue::Float64(Some(3.5))
+        );
+    }
+
+    #[test]
+    fn quantile_without_input() {
+        let accumulator = QuantileAccumulator::default();
+        assert_eq!(accumulator.evaluate().unwrap(), ScalarValue::Float64(None));
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use datafusion::arrow::array::{BooleanArray, Int64Array};
+use datafusion::common::DataFusionError;
+use datafusion::logical_expr::{ScalarUDF, Signature, TypeSignature, Volatility};
+use datafusion::physical_plan::ColumnarValue;
+use datatypes::arrow::array::Array;
+use datatypes::arrow::datatypes::DataType;
+
+use crate::error;
+use crate::functions::extract_array;
+
+/// Check the "one" side of `group_left` and `group_right` vector matching. The input is
+/// the number of series sharing the matching labels with each series at its timestamp.
+///
+/// Returns `true` for every row, or fails like Prometheus if any number is greater than
+/// one, as the series to match is ambiguous.
+#[derive(Debug)]
+pub struct UniqueMatch {}
+
+impl UniqueMatch {
+    pub const fn name() -> &'static str {
+        "prom_unique_match"
+    }
+
+    pub fn scalar_udf() -> ScalarUDF {
+        scalar_udf(Self::name(), Self::calc)
+    }
+
+    fn calc(input: &[ColumnarValue]) -> Result<ColumnarValue, DataFusionError> {
+        check_counts(
+            Self::name(),
+            input,
+            "many-to-many matching not allowed: matching labels must be unique on one side",
+        )
+    }
+}
+
+/// Check both sides of one-to-one vector matching, which is the default when neither
+/// `group_left` nor `group_right` is given. Takes the same input as [UniqueMatch].
+#[derive(Debug)]
+pub struct OneToOneMatch {}
+
+impl OneToOneMatch {
+    pub const fn name() -> &'static str {
+        "prom_one_to_one_match"
+    }
+
+    pub fn scalar_udf() -> ScalarUDF {
+        scalar_udf(Self::name(), Self::calc)
+    }
+
+    fn calc(input: &[ColumnarValue]) -> Result<ColumnarValue, DataFusionError> {
+        check_counts(
+            Self::name(),
+            input,
+            "multiple matches for labels: many-to-one matching must be explicit (group_left/group_right)",
+        )
+    }
+}
+
+fn scalar_udf(
+    name: &str,
+    calc: fn(&[ColumnarValue]) -> Result<ColumnarValue, DataFusionError>,
+) -> ScalarUDF {
+    ScalarUDF {
+        name: name.to_string(),
+        signature: Signature::new(
+            TypeSignature::Exact(vec![DataType::Int64]),
+            Volatility::Immutable,
+        ),
+        return_type: Arc::new(|_| Ok(Arc::new(DataType::Boolean))),
+        fun: Arc::new(calc),
+    }
+}
+
+fn check_counts(
+    name: &str,
+    input: &[ColumnarValue],
+    message: &str,
+) -> Result<ColumnarValue, DataFusionError> {
+    error::ensure(
+        input.len() == 1,
+        DataFusionError::Plan(format!(
+            "{}: expect 1 argument, found {}",
+            name,
+            input.len()
+        )),
+    )?;
+    let count_array = extract_array(&input[0])?;
+    let count_array = count_array
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .ok_or_else(|| {
+            DataFusionError::Execution(format!(
+                "{}: expect Int64 as input type, found {}",
+                name,
+                count_array.data_type()
+            ))
+        })?;
+    error::ensure(
+        count_array.iter().all(|count| count.unwrap_or(0) <= 1),
+        DataFusionError::Execution(message.to_string()),
+    )?;
+
+    let result = BooleanArray::from(vec![true; count_array.len()]);
+    Ok(ColumnarValue::Array(Arc::new(result)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unique_match() {
+        let counts = ColumnarValue::Array(Arc::new(Int64Array::from(vec![1, 1, 1])));
+        let result = UniqueMatch::calc(&[counts]).unwrap();
+        let result = extract_array(&result).unwrap();
+        assert_eq!(result.len(), 3);
+
+        let counts = ColumnarValue::Array(Arc::new(Int64Array::from(vec![1, 2, 1])));
+        assert!(UniqueMatch::calc(&[counts.clone()]).is_err());
+
+        // wrong number of arguments
+        assert!(UniqueMatch::calc(&[]).is_err());
+        assert!(UniqueMatch::calc(&[counts.clone(), counts]).is_err());
+    }
+
+    #[test]
+    fn one_to_one_match() {
+        let counts = ColumnarValue::Array(Arc::new(Int64Array::from(vec![1, 1])));
+        let result = OneToOneMatch::calc(&[counts]).unwrap();
+        let result = extract_array(&result).unwrap();
+        assert_eq!(result.len(), 2);
+
+        let counts = ColumnarValue::Array(Arc::new(Int64Array::from(vec![2, 2])));
+        let err = OneToOneMatch::calc(&[counts]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("many-to-one matching must be explicit"));
+        assert!(OneToOneMatch::calc(&[]).is_err());
+    }
+}
@@ -21,11 +21,12 @@ use async_recursion::async_recursion;
 use catalog::table_source::DfTableSourceProvider;
 use datafusion::common::{DFSchemaRef, OwnedTableReference, Result as DfResult};
 use datafusion::datasource::DefaultTableSource;
-use datafusion::logical_expr::expr::AggregateFunction;
+use datafusion::logical_expr::expr::{AggregateFunction, WindowFunction};
 use datafusion::logical_expr::expr_rewriter::normalize_cols;
 use datafusion::logical_expr::{
-    AggregateFunction as AggregateFunctionEnum, BinaryExpr, BuiltinScalarFunction, Cast, Extension,
-    LogicalPlan, LogicalPlanBuilder, Operator, ScalarUDF,
+    AggregateFunction as AggregateFunctionEnum, BinaryExpr, BuiltInWindowFunction,
+    BuiltinScalarFunction, Case, Cast, Extension, LogicalPlan, LogicalPlanBuilder, Operator,
+    ScalarUDF, WindowFrame, WindowFunction as WindowFunctionEnum,
 };
 use datafusion::optimizer::utils;
 use datafusion::prelude::{Column, Expr as DfExpr, JoinType};
@@ -33,24 +34,46 @@ use datafusion::scalar::ScalarValue;
 use datatypes::arrow::datatypes::DataType as ArrowDataType;
 use promql_parser::label::{MatchOp, Matchers, METRIC_NAME};
 use promql_parser::parser::{
-    token, AggModifier, AggregateExpr, BinaryExpr as PromBinaryExpr, Call, EvalStmt,
-    Expr as PromExpr, Function, MatrixSelector, NumberLiteral, Offset, ParenExpr, StringLiteral,
-    SubqueryExpr, TokenType, UnaryExpr, VectorSelector,
+    token, AggModifier, AggregateExpr, AtModifier, BinModifier, BinaryExpr as PromBinaryExpr, Call,
+    EvalStmt, Expr as PromExpr, Function, MatrixSelector, NumberLiteral, Offset, ParenExpr,
+    StringLiteral, SubqueryExpr, TokenType, UnaryExpr, VectorMatchCardinality, VectorMatchModifier,
+    VectorSelector,
 };
 use snafu::{ensure, OptionExt, ResultExt};
 use table::table::adapter::DfTableProviderAdapter;
 
 use crate::error::{
-    CatalogSnafu, DataFusionPlanningSnafu, ExpectExprSnafu, MultipleVectorSnafu, Result,
-    TableNameNotFoundSnafu, TimeIndexNotFoundSnafu, UnexpectedTokenSnafu, UnknownTableSnafu,
-    UnsupportedExprSnafu, ValueNotFoundSnafu,
+    CatalogSnafu, DataFusionPlanningSnafu, ExpectExprSnafu, LeNotFoundSnafu, MultipleVectorSnafu,
+    NotBucketSeriesSnafu, Result, TableNameNotFoundSnafu, TimeIndexNotFoundSnafu,
+    UnexpectedTokenSnafu, UnknownTableSnafu, UnsupportedExprSnafu, ValueNotFoundSnafu,
 };
 use crate::extension_plan::{
-    InstantManipulate, Millisecond, RangeManipulate, SeriesDivide, SeriesNormalize,
+    EmptyMetric, HistogramFold, InstantManipulate, Millisecond, RangeManipulate, SeriesDivide,
+    SeriesNormalize,
+};
+use crate::functions::{
+    AvgOverTime, Changes, CountOverTime, Delta, Deriv, FormatFloat, IDelta, Increase, LastOverTime,
+    MaxOverTime, MinOverTime, OneToOneMatch, PredictLinear, PresentOverTime, Quantile,
+    QuantileOverTime, Rate, Resets, StddevOverTime, StdvarOverTime, SumOverTime, UniqueMatch,
 };
-use crate::functions::{IDelta, Increase};
 
 const LEFT_PLAN_JOIN_ALIAS: &str = "lhs";
+const RIGHT_PLAN_JOIN_ALIAS: &str = "rhs";
+/// Name of the temporary row number column used by `topk` and `bottomk`.
+const TOPK_RANK_COLUMN: &str = "__prom_topk_rank";
+/// Name of the temporary column counting series with the same matching labels, used to
+/// check the "one" side of `group_left` and `group_right`.
+const MATCH_COUNT_COLUMN: &str = "__prom_match_count";
+/// The bucket upper bound label of classic histograms.
+const LE_COLUMN: &str = "le";
+/// Suffixes of the series of classic histograms.
+const BUCKET_SERIES_SUFFIX: &str = "_bucket";
+const COUNT_SERIES_SUFFIX: &str = "_count";
+const SUM_SERIES_SUFFIX: &str = "_sum";
+/// Column names of expressions that don't select any metric, like number literals
+/// and `time()`.
+const DEFAULT_TIME_INDEX_COLUMN: &str = "time";
+const DEFAULT_VALUE_COLUMN: &str = "value";
 
 #[derive(Default, Debug, Clone)]
 struct PromPlannerContext {
@@ -61,10 +84,19 @@ struct PromPlannerContext {
     lookback_delta: Millisecond,
 
     // planner states
+    /// The range length of the latest planned range vector (matrix selector or subquery)
+    range: Option<Millisecond>,
     table_name: Option<String>,
     time_index_column: Option<String>,
     value_columns: Vec<String>,
     tag_columns: Vec<String>,
+    /// Replace the `_bucket` suffix of metric names with this when set. Used to plan
+    /// `histogram_count` and `histogram_sum` of classic histograms.
+    histogram_series_suffix: Option<&'static str>,
+    /// The evaluation timestamp pinned by the `@` modifier of the latest planned range
+    /// vector. The range vector is only evaluated at this timestamp, and its result
+    /// needs to be expanded to all evaluation timestamps after a range function.
+    pinned_timestamp: Option<Millisecond>,
 }
 
 impl PromPlannerContext {
@@ -102,38 +134,64 @@ impl PromPlanner {
             PromExpr::Aggregate(AggregateExpr {
                 op,
                 expr,
-                // TODO(ruihang): support param
-                param: _param,
+                param,
                 modifier,
             }) => {
                 let input = self.prom_expr_to_plan(*expr.clone()).await?;
+                // topk and bottomk keep the original labels
+                let input_tag_columns = self.ctx.tag_columns.clone();
 
                 // calculate columns to group by
                 // Need to append time index column into group by columns
-                let group_exprs = modifier
-                    .as_ref()
-                    .map_or(Ok(vec![self.create_time_index_column_expr()?]), |m| {
-                        self.agg_modifier_to_col(input.schema(), m)
-                    })?;
+                let mut group_exprs = match modifier {
+                    Some(modifier) => self.agg_modifier_to_col(input.schema(), modifier)?,
+                    None => {
+                        // all labels are aggregated
+                        self.ctx.tag_columns = vec![];
+                        vec![self.create_time_index_column_expr()?]
+                    }
+                };
 
-                // convert op and value columns to aggregate exprs
-                let aggr_exprs = self.create_aggregate_exprs(*op, &input)?;
+                if matches!(op.id(), token::T_TOPK | token::T_BOTTOMK) {
+                    self.ctx.tag_columns = input_tag_columns;
+                    self.create_topk_plan(*op, param, input, group_exprs)?
+                } else {
+                    if op.id() == token::T_COUNT_VALUES {
+                        // the value is converted into a new label, which is also grouped by
+                        let label = Self::get_string_param(*op, param)?;
+                        let value_column = self.get_first_value_column()?;
+                        let label_expr = DfExpr::ScalarUDF {
+                            fun: Arc::new(FormatFloat::scalar_udf()),
+                            args: vec![DfExpr::Column(Column::from_name(&value_column))],
+                        }
+                        .alias(&label);
+                        // keep time index column as the last group expr
+                        group_exprs.insert(group_exprs.len() - 1, label_expr);
+                        self.ctx.tag_columns.push(label);
+                        self.ctx.value_columns = vec![value_column];
+                    }
 
-                // remove time index column from context
-                self.ctx.time_index_column = None;
+                    // convert op and value columns to aggregate exprs
+                    let aggr_exprs = self.create_aggregate_exprs(*op, param, &input)?;
 
-                // create plan
-                let group_sort_expr = group_exprs
-                    .clone()
-                    .into_iter()
-                    .map(|expr| expr.sort(true, false));
-                LogicalPlanBuilder::from(input)
-                    .aggregate(group_exprs, aggr_exprs)
-                    .context(DataFusionPlanningSnafu)?
-                    .sort(group_sort_expr)
-                    .context(DataFusionPlanningSnafu)?
-                    .build()
-                    .context(DataFusionPlanningSnafu)?
+                    // create plan
+                    let group_sort_expr = group_exprs
+                        .clone()
+                        .into_iter()
+                        .map(|expr| match expr {
+                            // sort on the aliased column instead of the expression
+                            DfExpr::Alias(_, name) => DfExpr::Column(Column::from_name(name)),
+                            expr => expr,
+                        })
+                        .map(|expr| expr.sort(true, false));
+                    LogicalPlanBuilder::from(input)
+                        .aggregate(group_exprs, aggr_exprs)
+                        .context(DataFusionPlanningSnafu)?
+                        .sort(group_sort_expr)
+                        .context(DataFusionPlanningSnafu)?
+                        .build()
+                        .context(DataFusionPlanningSnafu)?
+                }
             }
             PromExpr::Unary(UnaryExpr { expr }) => {
                 // Unary Expr in PromQL implys the `-` operator
@@ -163,20 +221,27 @@ impl PromPlanner {
                     Self::try_build_literal_expr(lhs),
                     Self::try_build_literal_expr(rhs),
                 ) {
-                    // TODO(ruihang): handle literal-only expressions
-                    (Some(_lhs), Some(_rhs)) => UnsupportedExprSnafu {
-                        name: "Literal-only expression",
+                    // both are literals, evaluate them on every timestamp
+                    (Some(lhs), Some(rhs)) => {
+                        let mut expr = Self::build_binary_expr(*op, lhs, rhs)?;
+                        if is_comparison_op {
+                            // comparisons between scalars always return 0/1
+                            expr = DfExpr::Cast(Cast {
+                                expr: Box::new(expr),
+                                data_type: ArrowDataType::Float64,
+                            });
+                        }
+                        self.create_empty_metric_plan(expr)?
                     }
-                    .fail()?,
                     // lhs is a literal, rhs is a column
                     (Some(expr), None) => {
                         let input = self.prom_expr_to_plan(*rhs.clone()).await?;
                         let bin_expr_builder = |col: &String| {
-                            let mut binary_expr = DfExpr::BinaryExpr(BinaryExpr {
-                                left: Box::new(expr.clone()),
-                                op: Self::prom_token_to_binary_op(*op)?,
-                                right: Box::new(DfExpr::Column(col.into())),
-                            });
+                            let mut binary_expr = Self::build_binary_expr(
+                                *op,
+                                expr.clone(),
+                                DfExpr::Column(col.into()),
+                            )?;
                             if is_comparison_op && should_return_bool {
                                 binary_expr = DfExpr::Cast(Cast {
                                     expr: Box::new(binary_expr),
@@ -195,11 +260,11 @@ impl PromPlanner {
                     (None, Some(expr)) => {
                         let input = self.prom_expr_to_plan(*lhs.clone()).await?;
                         let bin_expr_builder = |col: &String| {
-                            let mut binary_expr = DfExpr::BinaryExpr(BinaryExpr {
-                                left: Box::new(DfExpr::Column(col.into())),
-                                op: Self::prom_token_to_binary_op(*op)?,
-                                right: Box::new(expr.clone()),
-                            });
+                            let mut binary_expr = Self::build_binary_expr(
+                                *op,
+                                DfExpr::Column(col.into()),
+                                expr.clone(),
+                            )?;
                             if is_comparison_op && should_return_bool {
                                 binary_expr = DfExpr::Cast(Cast {
                                     expr: Box::new(binary_expr),
@@ -214,122 +279,377 @@ impl PromPlanner {
                             self.projection_for_each_value_column(input, bin_expr_builder)?
                         }
                     }
-                    // both are columns. join them on time index
+                    // both are columns. join them on time index and matching labels
                     (None, None) => {
                         let left_input = self.prom_expr_to_plan(*lhs.clone()).await?;
                         let left_value_columns = self.ctx.value_columns.clone();
-                        let left_schema = left_input.schema().clone();
+                        let left_tag_columns = self.ctx.tag_columns.clone();
+                        let left_time_index = self.ctx.time_index_column.clone();
 
                         let right_input = self.prom_expr_to_plan(*rhs.clone()).await?;
                         let right_value_columns = self.ctx.value_columns.clone();
-                        let right_schema = right_input.schema().clone();
-
-                        let mut value_columns =
-                            left_value_columns.iter().zip(right_value_columns.iter());
-                        // the new ctx.value_columns for the generated join plan
-                        let join_plan = self.join_on_non_value_columns(left_input, right_input)?;
-                        let bin_expr_builder = |_: &String| {
-                            let (left_col_name, right_col_name) = value_columns.next().unwrap();
-                            let left_col = left_schema
-                                .field_with_name(None, left_col_name)
-                                .context(DataFusionPlanningSnafu)?
-                                .qualified_column();
-                            let right_col = right_schema
-                                .field_with_name(None, right_col_name)
-                                .context(DataFusionPlanningSnafu)?
-                                .qualified_column();
-
-                            let mut binary_expr = DfExpr::BinaryExpr(BinaryExpr {
-                                left: Box::new(DfExpr::Column(left_col)),
-                                op: Self::prom_token_to_binary_op(*op)?,
-                                right: Box::new(DfExpr::Column(right_col)),
-                            });
-                            if is_comparison_op && should_return_bool {
-                                binary_expr = DfExpr::Cast(Cast {
-                                    expr: Box::new(binary_expr),
-                                    data_type: ArrowDataType::Float64,
-                                });
-                            }
-                            Ok(binary_expr)
+                        let right_tag_columns = self.ctx.tag_columns.clone();
+                        let right_time_index = self.ctx.time_index_column.clone();
+
+                        let matching = VectorMatching::try_new(
+                            modifier,
+                            &left_tag_columns,
+                            &right_tag_columns,
+                            is_comparison_op && !should_return_bool,
+                        )?;
+                        let left_time_index =
+                            left_time_index.with_context(|| TimeIndexNotFoundSnafu {
+                                table: "left side of binary expr",
+                            })?;
+                        let right_time_index =
+                            right_time_index.with_context(|| TimeIndexNotFoundSnafu {
+                                table: "right side of binary expr",
+                            })?;
+                        let (left_input, right_input) = match matching.one_side {
+                            Some(LEFT_PLAN_JOIN_ALIAS) => (
+                                Self::ensure_unique_match(
+                                    left_input,
+                                    &matching.join_labels,
+                                    &left_time_index,
+                                    UniqueMatch::scalar_udf(),
+                                )?,
+                                right_input,
+                            ),
+                            Some(_) => (
+                                left_input,
+                                Self::ensure_unique_match(
+                                    right_input,
+                                    &matching.join_labels,
+                                    &right_time_index,
+                                    UniqueMatch::scalar_udf(),
+                                )?,
+                            ),
+                            // one-to-one matching, both sides must be unique
+                            None => (
+                                Self::ensure_unique_match(
+                                    left_input,
+                                    &matching.join_labels,
+                                    &left_time_index,
+                                    OneToOneMatch::scalar_udf(),
+                                )?,
+                                Self::ensure_unique_match(
+                                    right_input,
+                                    &matching.join_labels,
+                                    &right_time_index,
+                                    OneToOneMatch::scalar_udf(),
+                                )?,
+                            ),
                         };
+                        let join_plan = Self::join_on_non_value_columns(
+                            left_input,
+                            right_input,
+                            &matching.join_labels,
+                            &left_time_index,
+                            &right_time_index,
+                        )?;
+
+                        // build the value exprs, and name them with unqualified column names
+                        let mut value_exprs = Vec::with_capacity(left_value_columns.len());
+                        for (left_col, right_col) in
+                            left_value_columns.iter().zip(right_value_columns.iter())
+                        {
+                            let build = |left: Column, right: Column| -> Result<DfExpr> {
+                                let binary_expr = Self::build_binary_expr(
+                                    *op,
+                                    DfExpr::Column(left),
+                                    DfExpr::Column(right),
+                                )?;
+                                if is_comparison_op && should_return_bool {
+                                    Ok(DfExpr::Cast(Cast {
+                                        expr: Box::new(binary_expr),
+                                        data_type: ArrowDataType::Float64,
+                                    }))
+                                } else {
+                                    Ok(binary_expr)
+                                }
+                            };
+                            let name =
+                                build(Column::from_name(left_col), Column::from_name(right_col))?
+                                    .display_name()
+                                    .context(DataFusionPlanningSnafu)?;
+                            let expr = build(
+                                Column::new(Some(LEFT_PLAN_JOIN_ALIAS), left_col),
+                                Column::new(Some(RIGHT_PLAN_JOIN_ALIAS), right_col),
+                            )?;
+                            value_exprs.push((expr, name));
+                        }
+
+                        let mut project_exprs = matching
+                            .output_labels
+                            .iter()
+                            .map(|(side, label)| DfExpr::Column(Column::new(Some(*side), label)))
+                            .collect::<Vec<_>>();
+                        project_exprs.push(DfExpr::Column(Column::new(
+                            Some(LEFT_PLAN_JOIN_ALIAS),
+                            &left_time_index,
+                        )));
+
+                        let mut builder = LogicalPlanBuilder::from(join_plan);
                         if is_comparison_op && !should_return_bool {
-                            self.filter_on_value_column(join_plan, bin_expr_builder)?
+                            // filter on the comparison result and keep the left side's values
+                            let filter =
+                                utils::conjunction(value_exprs.into_iter().map(|(expr, _)| expr))
+                                    .context(ValueNotFoundSnafu {
+                                    table: self.ctx.table_name.clone().unwrap_or_default(),
+                                })?;
+                            builder = builder.filter(filter).context(DataFusionPlanningSnafu)?;
+                            project_exprs.extend(left_value_columns.iter().map(|col| {
+                                DfExpr::Column(Column::new(Some(LEFT_PLAN_JOIN_ALIAS), col))
+                                    .alias(col)
+                            }));
+                            self.ctx.value_columns = left_value_columns;
                         } else {
-                            self.projection_for_each_value_column(join_plan, bin_expr_builder)?
+                            self.ctx.value_columns =
+                                value_exprs.iter().map(|(_, name)| name.clone()).collect();
+                            project_exprs.extend(
+                                value_exprs.into_iter().map(|(expr, name)| expr.alias(name)),
+                            );
                         }
+
+                        // update the context to describe the result of this binary expr
+                        self.ctx.tag_columns = matching
+                            .output_labels
+                            .into_iter()
+                            .map(|(_, label)| label)
+                            .collect();
+                        self.ctx.time_index_column = Some(left_time_index);
+
+                        builder
+                            .project(project_exprs)
+                            .context(DataFusionPlanningSnafu)?
+                            .build()
+                            .context(DataFusionPlanningSnafu)?
                     }
                 }
             }
             PromExpr::Paren(ParenExpr { expr }) => self.prom_expr_to_plan(*expr.clone()).await?,
-            PromExpr::Subquery(SubqueryExpr { .. }) => UnsupportedExprSnafu {
-                name: "Prom Subquery",
-            }
-            .fail()?,
-            PromExpr::NumberLiteral(NumberLiteral { .. }) => UnsupportedExprSnafu {
-                name: "Prom Number Literal",
+            PromExpr::Subquery(SubqueryExpr {
+                expr,
+                offset,
+                range,
+                step,
+                ..
+            }) => {
+                let range = range.as_millis() as Millisecond;
+                let step = step.map_or(self.ctx.interval, |s| s.as_millis() as _);
+                let offset = match offset {
+                    Some(Offset::Pos(duration)) => duration.as_millis() as Millisecond,
+                    Some(Offset::Neg(duration)) => -(duration.as_millis() as Millisecond),
+                    None => 0,
+                };
+
+                // evaluate the inner expr with subquery's step, from the first aligned
+                // timestamp after `start - offset - range` to `end - offset`
+                let (outer_start, outer_end, outer_interval) =
+                    (self.ctx.start, self.ctx.end, self.ctx.interval);
+                self.ctx.start = Self::align_subquery_start(outer_start - offset - range, step);
+                self.ctx.end = outer_end - offset;
+                self.ctx.interval = step;
+                let inner = self.prom_expr_to_plan(*expr.clone()).await;
+                self.ctx.start = outer_start;
+                self.ctx.end = outer_end;
+                self.ctx.interval = outer_interval;
+                let inner = inner?;
+                self.ctx.range = Some(range);
+                let time_index = self
+                    .ctx
+                    .time_index_column
+                    .clone()
+                    .with_context(|| TimeIndexNotFoundSnafu { table: "subquery" })?;
+
+                // inner output is still sorted by tags and time index, re-divide it to make
+                // sure every batch fed into range manipulate contains only one series
+                let mut input = LogicalPlan::Extension(Extension {
+                    node: Arc::new(SeriesDivide::new(self.ctx.tag_columns.clone(), inner)),
+                });
+                if offset != 0 {
+                    // shift the inner output forward by offset, like the offset of selectors
+                    input = LogicalPlan::Extension(Extension {
+                        node: Arc::new(SeriesNormalize::new(-offset, time_index.clone(), input)),
+                    });
+                }
+                let manipulate = RangeManipulate::new(
+                    self.ctx.start,
+                    self.ctx.end,
+                    self.ctx.interval,
+                    range,
+                    time_index,
+                    self.ctx.value_columns.clone(),
+                    input,
+                )
+                .context(DataFusionPlanningSnafu)?;
+
+                LogicalPlan::Extension(Extension {
+                    node: Arc::new(manipulate),
+                })
             }
-            .fail()?,
-            PromExpr::StringLiteral(StringLiteral { .. }) => UnsupportedExprSnafu {
-                name: "Prom String Literal",
+            PromExpr::NumberLiteral(NumberLiteral { val }) => {
+                self.create_empty_metric_plan(DfExpr::Literal(ScalarValue::Float64(Some(*val))))?
             }
-            .fail()?,
+            PromExpr::StringLiteral(StringLiteral { val }) => self
+                .create_empty_metric_plan(DfExpr::Literal(ScalarValue::Utf8(Some(val.clone()))))?,
             PromExpr::VectorSelector(VectorSelector {
                 name: _,
                 offset,
                 matchers,
-                at: _,
+                at,
             }) => {
                 let matchers = self.preprocess_label_matchers(matchers)?;
                 self.setup_context().await?;
+                self.ctx.range = None;
+                let at = self.at_modifier_to_timestamp(at);
+                let outer_range = self.pin_evaluation_time(at);
                 let normalize = self
                     .selector_to_series_normalize_plan(offset, matchers)
-                    .await?;
-                let manipulate = InstantManipulate::new(
-                    self.ctx.start,
-                    self.ctx.end,
-                    self.ctx.lookback_delta,
-                    self.ctx.interval,
-                    self.ctx
-                        .time_index_column
-                        .clone()
-                        .expect("time index should be set in `setup_context`"),
-                    normalize,
-                );
-                LogicalPlan::Extension(Extension {
-                    node: Arc::new(manipulate),
-                })
+                    .await;
+                let manipulate = normalize.map(|normalize| {
+                    InstantManipulate::new(
+                        self.ctx.start,
+                        self.ctx.end,
+                        self.ctx.lookback_delta,
+                        self.ctx.interval,
+                        self.ctx
+                            .time_index_column
+                            .clone()
+                            .expect("time index should be set in `setup_context`"),
+                        normalize,
+                    )
+                });
+                (self.ctx.start, self.ctx.end) = outer_range;
+
+                let plan = LogicalPlan::Extension(Extension {
+                    node: Arc::new(manipulate?),
+                });
+                if at.is_some() {
+                    self.create_pinned_expand_plan(plan)?
+                } else {
+                    plan
+                }
             }
             PromExpr::MatrixSelector(MatrixSelector {
                 vector_selector,
                 range,
             }) => {
                 let VectorSelector {
-                    offset, matchers, ..
+                    offset,
+                    matchers,
+                    at,
+                    ..
                 } = vector_selector;
                 let matchers = self.preprocess_label_matchers(matchers)?;
                 self.setup_context().await?;
+                // TODO(ruihang): convert via Timestamp datatypes to support different time units
+                let range = range.as_millis() as Millisecond;
+                self.ctx.range = Some(range);
+                // the pinned range vector is expanded after being consumed by a range function
+                let at = self.at_modifier_to_timestamp(at);
+                self.ctx.pinned_timestamp = at;
+                let outer_range = self.pin_evaluation_time(at);
                 let normalize = self
                     .selector_to_series_normalize_plan(offset, matchers)
-                    .await?;
-                let manipulate = RangeManipulate::new(
-                    self.ctx.start,
-                    self.ctx.end,
-                    self.ctx.interval,
-                    // TODO(ruihang): convert via Timestamp datatypes to support different time units
-                    range.as_millis() as _,
-                    self.ctx
-                        .time_index_column
-                        .clone()
-                        .expect("time index should be set in `setup_context`"),
-                    self.ctx.value_columns.clone(),
-                    normalize,
-                )
-                .context(DataFusionPlanningSnafu)?;
+                    .await;
+                let manipulate = normalize.and_then(|normalize| {
+                    RangeManipulate::new(
+                        self.ctx.start,
+                        self.ctx.end,
+                        self.ctx.interval,
+                        range,
+                        self.ctx
+                            .time_index_column
+                            .clone()
+                            .expect("time index should be set in `setup_context`"),
+                        self.ctx.value_columns.clone(),
+                        normalize,
+                    )
+                    .context(DataFusionPlanningSnafu)
+                });
+                (self.ctx.start, self.ctx.end) = outer_range;
 
                 LogicalPlan::Extension(Extension {
-                    node: Arc::new(manipulate),
+                    node: Arc::new(manipulate?),
                 })
             }
+            PromExpr::Call(Call { func, args }) if func.name == "histogram_quantile" => {
+                let args = self.create_function_args(&args.args)?;
+                let quantile = match args.literals.as_slice() {
+                    [DfExpr::Literal(ScalarValue::Float64(Some(quantile)))] => *quantile,
+                    _ => UnsupportedExprSnafu {
+                        name: "histogram_quantile without number literal quantile",
+                    }
+                    .fail()?,
+                };
+                let input = self
+                    .prom_expr_to_plan(args.input.with_context(|| ExpectExprSnafu {
+                        expr: prom_expr.clone(),
+                    })?)
+                    .await?;
+                self.create_histogram_fold_plan(quantile, input)?
+            }
+            PromExpr::Call(Call { func, args })
+                if matches!(func.name, "histogram_count" | "histogram_sum") =>
+            {
+                let args = self.create_function_args(&args.args)?;
+                let input = args.input.with_context(|| ExpectExprSnafu {
+                    expr: prom_expr.clone(),
+                })?;
+                // classic histograms store count and sum of observations in separated
+                // series, so plan the input on them instead of the bucket series.
+                let suffix = if func.name == "histogram_count" {
+                    COUNT_SERIES_SUFFIX
+                } else {
+                    SUM_SERIES_SUFFIX
+                };
+                let prev_suffix = self.ctx.histogram_series_suffix.replace(suffix);
+                let plan = self.prom_expr_to_plan(input).await;
+                self.ctx.histogram_series_suffix = prev_suffix;
+                plan?
+            }
+            PromExpr::Call(Call { func, .. }) if func.name == "time" => {
+                // seconds since the epoch of each evaluation timestamp
+                let millis = DfExpr::Cast(Cast {
+                    expr: Box::new(DfExpr::Cast(Cast {
+                        expr: Box::new(DfExpr::Column(Column::from_name(
+                            DEFAULT_TIME_INDEX_COLUMN,
+                        ))),
+                        data_type: ArrowDataType::Int64,
+                    })),
+                    data_type: ArrowDataType::Float64,
+                });
+                let seconds = DfExpr::BinaryExpr(BinaryExpr {
+                    left: Box::new(millis),
+                    op: Operator::Divide,
+                    right: Box::new(DfExpr::Literal(ScalarValue::Float64(Some(1000.0)))),
+                });
+                self.create_empty_metric_plan(seconds)?
+            }
+            PromExpr::Call(Call { func, args }) if func.name == "vector" => {
+                let args = self.create_function_args(&args.args)?;
+                match (args.input, args.literals.as_slice()) {
+                    (None, [literal]) => self.create_empty_metric_plan(literal.clone())?,
+                    // the input is already an instant vector without labels
+                    (Some(input), []) => self.prom_expr_to_plan(input).await?,
+                    _ => UnsupportedExprSnafu {
+                        name: "vector with invalid arguments",
+                    }
+                    .fail()?,
+                }
+            }
+            PromExpr::Call(Call { func, args })
+                if matches!(func.name, "label_replace" | "label_join") =>
+            {
+                let args = self.create_function_args(&args.args)?;
+                let input = self
+                    .prom_expr_to_plan(args.input.with_context(|| ExpectExprSnafu {
+                        expr: prom_expr.clone(),
+                    })?)
+                    .await?;
+                self.create_label_manipulate_plan(func.name, args.literals, input)?
+            }
             PromExpr::Call(Call { func, args }) => {
                 let args = self.create_function_args(&args.args)?;
                 let input = self
@@ -340,18 +660,135 @@ impl PromPlanner {
                 let mut func_exprs = self.create_function_expr(func, args.literals)?;
                 func_exprs.insert(0, self.create_time_index_column_expr()?);
                 func_exprs.extend_from_slice(&self.create_tag_column_exprs()?);
-                LogicalPlanBuilder::from(input)
+                let plan = LogicalPlanBuilder::from(input)
                     .project(func_exprs)
                     .context(DataFusionPlanningSnafu)?
                     .filter(self.create_empty_values_filter_expr()?)
                     .context(DataFusionPlanningSnafu)?
                     .build()
-                    .context(DataFusionPlanningSnafu)?
+                    .context(DataFusionPlanningSnafu)?;
+                if self.ctx.pinned_timestamp.take().is_some() {
+                    self.create_pinned_expand_plan(plan)?
+                } else {
+                    plan
+                }
             }
         };
         Ok(res)
     }
 
+    /// Align the subquery's evaluation start to the first multiple of `step` that
+    /// is not earlier than `start`.
+    ///
+    /// Refer to <https://github.com/prometheus/prometheus/blob/e934d0f01158a1d55fa0ebb035346b195fcc1260/promql/engine.go#L1566-L1572>
+    fn align_subquery_start(start: Millisecond, step: Millisecond) -> Millisecond {
+        let aligned = step * (start / step);
+        if aligned < start {
+            aligned + step
+        } else {
+            aligned
+        }
+    }
+
+    /// Convert the `@` modifier to the pinned evaluation timestamp.
+    fn at_modifier_to_timestamp(&self, at: &Option<AtModifier>) -> Option<Millisecond> {
+        let timestamp = match at.as_ref()? {
+            AtModifier::Start => self.ctx.start,
+            AtModifier::End => self.ctx.end,
+            AtModifier::At(time) => match time.duration_since(UNIX_EPOCH) {
+                Ok(duration) => duration.as_millis() as Millisecond,
+                // `@` accepts timestamps before the unix epoch
+                Err(e) => -(e.duration().as_millis() as Millisecond),
+            },
+        };
+        Some(timestamp)
+    }
+
+    /// Set both the start and end of the query to the pinned timestamp if exists.
+    /// Returns the original `(start, end)` to restore.
+    fn pin_evaluation_time(&mut self, pinned: Option<Millisecond>) -> (Millisecond, Millisecond) {
+        let outer_range = (self.ctx.start, self.ctx.end);
+        if let Some(timestamp) = pinned {
+            self.ctx.start = timestamp;
+            self.ctx.end = timestamp;
+        }
+        outer_range
+    }
+
+    /// Expand the result of an expression evaluated at a pinned timestamp (by `@`) to
+    /// all evaluation timestamps of the query, by replacing its time index with the one
+    /// generated by [EmptyMetric].
+    fn create_pinned_expand_plan(&self, input: LogicalPlan) -> Result<LogicalPlan> {
+        let time_index =
+            self.ctx
+                .time_index_column
+                .clone()
+                .with_context(|| TimeIndexNotFoundSnafu {
+                    table: self.ctx.table_name.clone().unwrap_or_default(),
+                })?;
+        let tag_exprs = self.create_tag_column_exprs()?;
+        let value_exprs = self
+            .ctx
+            .value_columns
+            .iter()
+            .map(|col| DfExpr::Column(Column::from_name(col)))
+            .collect::<Vec<_>>();
+        let timestamps =
+            EmptyMetric::new(self.ctx.start, self.ctx.end, self.ctx.interval, time_index)
+                .context(DataFusionPlanningSnafu)?;
+
+        let mut project_exprs = tag_exprs.clone();
+        project_exprs.push(self.create_time_index_column_expr()?);
+        project_exprs.extend(value_exprs.clone());
+        LogicalPlanBuilder::from(input)
+            .project(tag_exprs.into_iter().chain(value_exprs))
+            .context(DataFusionPlanningSnafu)?
+            .cross_join(LogicalPlan::Extension(Extension {
+                node: Arc::new(timestamps),
+            }))
+            .context(DataFusionPlanningSnafu)?
+            .project(project_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .sort(self.create_tag_and_time_index_column_sort_exprs()?)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Plan an expression that doesn't select any metric as an instant vector without
+    /// labels. `value_expr` is evaluated on every evaluation timestamp, which is in the
+    /// [DEFAULT_TIME_INDEX_COLUMN] column.
+    ///
+    /// # Side effect
+    ///
+    /// This method will reset the [PromPlannerContext] to describe the new plan.
+    fn create_empty_metric_plan(&mut self, value_expr: DfExpr) -> Result<LogicalPlan> {
+        let empty_metric = EmptyMetric::new(
+            self.ctx.start,
+            self.ctx.end,
+            self.ctx.interval,
+            DEFAULT_TIME_INDEX_COLUMN.to_string(),
+        )
+        .context(DataFusionPlanningSnafu)?;
+
+        self.ctx.table_name = None;
+        self.ctx.time_index_column = Some(DEFAULT_TIME_INDEX_COLUMN.to_string());
+        self.ctx.value_columns = vec![DEFAULT_VALUE_COLUMN.to_string()];
+        self.ctx.tag_columns = vec![];
+        self.ctx.range = None;
+
+        LogicalPlanBuilder::from(LogicalPlan::Extension(Extension {
+            node: Arc::new(empty_metric),
+        }))
+        .project(vec![
+            DfExpr::Column(Column::from_name(DEFAULT_TIME_INDEX_COLUMN)),
+            value_expr.alias(DEFAULT_VALUE_COLUMN),
+        ])
+        .context(DataFusionPlanningSnafu)?
+        .build()
+        .context(DataFusionPlanningSnafu)
+    }
+
     /// Extract metric name from `__name__` matcher and set it into [PromPlannerContext].
     /// Returns a new [Matchers] that doesn't contains metric name matcher.
     fn preprocess_label_matchers(&mut self, label_matchers: &Matchers) -> Result<Matchers> {
@@ -359,7 +796,16 @@ impl PromPlanner {
         for matcher in &label_matchers.matchers {
             // TODO(ruihang): support other metric match ops
             if matcher.name == METRIC_NAME && matches!(matcher.op, MatchOp::Equal) {
-                self.ctx.table_name = Some(matcher.value.clone());
+                let mut table_name = matcher.value.clone();
+                if let Some(suffix) = self.ctx.histogram_series_suffix {
+                    let metric = table_name.strip_suffix(BUCKET_SERIES_SUFFIX).context(
+                        NotBucketSeriesSnafu {
+                            metric: &table_name,
+                        },
+                    )?;
+                    table_name = format!("{metric}{suffix}");
+                }
+                self.ctx.table_name = Some(table_name);
             } else {
                 matchers.insert(matcher.clone());
             }
@@ -374,21 +820,24 @@ impl PromPlanner {
     ) -> Result<LogicalPlan> {
         let table_name = self.ctx.table_name.clone().unwrap();
 
-        // make filter exprs
+        // make filter exprs. `offset_duration` is the negated offset, samples are selected
+        // from `offset` earlier and then shifted forward by `SeriesNormalize`
         let offset_duration = -match offset {
             Some(Offset::Pos(duration)) => duration.as_millis() as Millisecond,
             Some(Offset::Neg(duration)) => -(duration.as_millis() as Millisecond),
             None => 0,
         };
+        // range vector selects data in `range`, and instant vector looks back `lookback_delta`
+        let lookback = self.ctx.range.unwrap_or(self.ctx.lookback_delta);
         let mut filters = self.matchers_to_expr(label_matchers)?;
         filters.push(self.create_time_index_column_expr()?.gt_eq(DfExpr::Literal(
             ScalarValue::TimestampMillisecond(
-                Some(self.ctx.start - offset_duration - self.ctx.lookback_delta),
+                Some(self.ctx.start + offset_duration - lookback),
                 None,
             ),
         )));
         filters.push(self.create_time_index_column_expr()?.lt_eq(DfExpr::Literal(
-            ScalarValue::TimestampMillisecond(Some(self.ctx.end - offset_duration), None),
+            ScalarValue::TimestampMillisecond(Some(self.ctx.end + offset_duration), None),
         )));
 
         // make table scan with filter exprs
@@ -440,15 +889,17 @@ impl PromPlanner {
         match modifier {
             AggModifier::By(labels) => {
                 let mut exprs = Vec::with_capacity(labels.len());
+                let mut tag_columns = Vec::with_capacity(labels.len());
                 for label in labels {
                     // nonexistence label will be ignored
                     if let Ok(field) = input_schema.field_with_unqualified_name(label) {
                         exprs.push(DfExpr::Column(Column::from(field.name())));
+                        tag_columns.push(label.clone());
                     }
                 }
 
                 // change the tag columns in context
-                self.ctx.tag_columns = labels.iter().cloned().collect();
+                self.ctx.tag_columns = tag_columns;
 
                 // add timestamp column
                 exprs.push(self.create_time_index_column_expr()?);
@@ -638,9 +1089,38 @@ impl PromPlanner {
         // TODO(ruihang): set this according to in-param list
         let value_column_pos = 0;
         let scalar_func = match func.name {
-            "increase" => ScalarFunc::Udf(Increase::scalar_udf()),
+            "increase" => {
+                other_input_exprs.extend(self.create_extrapolate_rate_args()?);
+                ScalarFunc::Udf(Increase::scalar_udf())
+            }
+            "rate" => {
+                other_input_exprs.extend(self.create_extrapolate_rate_args()?);
+                ScalarFunc::Udf(Rate::scalar_udf())
+            }
+            "delta" => {
+                other_input_exprs.extend(self.create_extrapolate_rate_args()?);
+                ScalarFunc::Udf(Delta::scalar_udf())
+            }
             "idelta" => ScalarFunc::Udf(IDelta::<false>::scalar_udf()),
             "irate" => ScalarFunc::Udf(IDelta::<true>::scalar_udf()),
+            "avg_over_time" => ScalarFunc::Udf(AvgOverTime::scalar_udf()),
+            "min_over_time" => ScalarFunc::Udf(MinOverTime::scalar_udf()),
+            "max_over_time" => ScalarFunc::Udf(MaxOverTime::scalar_udf()),
+            "sum_over_time" => ScalarFunc::Udf(SumOverTime::scalar_udf()),
+            "count_over_time" => ScalarFunc::Udf(CountOverTime::scalar_udf()),
+            "last_over_time" => ScalarFunc::Udf(LastOverTime::scalar_udf()),
+            "quantile_over_time" => ScalarFunc::Udf(QuantileOverTime::scalar_udf()),
+            "stddev_over_time" => ScalarFunc::Udf(StddevOverTime::scalar_udf()),
+            "stdvar_over_time" => ScalarFunc::Udf(StdvarOverTime::scalar_udf()),
+            "present_over_time" => ScalarFunc::Udf(PresentOverTime::scalar_udf()),
+            "changes" => ScalarFunc::Udf(Changes::scalar_udf()),
+            "resets" => ScalarFunc::Udf(Resets::scalar_udf()),
+            "deriv" => ScalarFunc::Udf(Deriv::scalar_udf()),
+            "predict_linear" => {
+                // predict from the evaluation timestamp, which is the time index column
+                other_input_exprs.push(self.create_time_index_column_expr()?);
+                ScalarFunc::Udf(PredictLinear::scalar_udf())
+            }
             _ => ScalarFunc::DataFusionBuiltin(
                 BuiltinScalarFunction::from_str(func.name).map_err(|_| {
                     UnsupportedExprSnafu {
@@ -701,6 +1181,18 @@ impl PromPlanner {
         Ok(exprs)
     }
 
+    /// Create the extra arguments of extrapolated rate functions (`rate`, `increase`
+    /// and `delta`), i.e., the evaluation timestamp column and the range length.
+    fn create_extrapolate_rate_args(&self) -> Result<Vec<DfExpr>> {
+        let range = self.ctx.range.context(UnsupportedExprSnafu {
+            name: "extrapolated rate function without range vector input",
+        })?;
+        Ok(vec![
+            self.create_time_index_column_expr()?,
+            DfExpr::Literal(ScalarValue::Int64(Some(range))),
+        ])
+    }
+
     fn create_time_index_column_expr(&self) -> Result<DfExpr> {
         Ok(DfExpr::Column(Column::from_name(
             self.ctx
@@ -738,7 +1230,7 @@ impl PromPlanner {
         }
 
         utils::conjunction(exprs.into_iter()).context(ValueNotFoundSnafu {
-            table: self.ctx.table_name.clone().unwrap(),
+            table: self.ctx.table_name.clone().unwrap_or_default(),
         })
     }
 
@@ -751,23 +1243,41 @@ impl PromPlanner {
     fn create_aggregate_exprs(
         &mut self,
         op: TokenType,
+        param: &Option<Box<PromExpr>>,
         input_plan: &LogicalPlan,
     ) -> Result<Vec<DfExpr>> {
         let aggr = match op.id() {
             token::T_SUM => AggregateFunctionEnum::Sum,
             token::T_AVG => AggregateFunctionEnum::Avg,
-            token::T_COUNT => AggregateFunctionEnum::Count,
+            token::T_COUNT | token::T_COUNT_VALUES => AggregateFunctionEnum::Count,
             token::T_MIN => AggregateFunctionEnum::Min,
             token::T_MAX => AggregateFunctionEnum::Max,
             token::T_GROUP => AggregateFunctionEnum::Grouping,
             token::T_STDDEV => AggregateFunctionEnum::StddevPop,
             token::T_STDVAR => AggregateFunctionEnum::VariancePop,
-            token::T_TOPK | token::T_BOTTOMK | token::T_COUNT_VALUES | token::T_QUANTILE => {
-                UnsupportedExprSnafu {
-                    name: format!("{op:?}"),
-                }
-                .fail()?
+            token::T_QUANTILE => {
+                let phi = Self::get_f64_param(op, param)?;
+                let udaf = Arc::new(Quantile::aggregate_udf());
+                let exprs = self
+                    .ctx
+                    .value_columns
+                    .iter()
+                    .map(|col| DfExpr::AggregateUDF {
+                        fun: udaf.clone(),
+                        args: vec![
+                            DfExpr::Column(Column::from_name(col)),
+                            DfExpr::Literal(ScalarValue::Float64(Some(phi))),
+                        ],
+                        filter: None,
+                    })
+                    .collect::<Vec<_>>();
+                self.update_value_columns_by_exprs(&exprs, input_plan)?;
+                return Ok(exprs);
+            }
+            token::T_TOPK | token::T_BOTTOMK => UnsupportedExprSnafu {
+                name: format!("{op:?} as aggregate function"),
             }
+            .fail()?,
             _ => UnexpectedTokenSnafu { token: op }.fail()?,
         };
 
@@ -786,16 +1296,277 @@ impl PromPlanner {
             })
             .collect();
 
-        // update value column name according to the aggregators
-        let mut new_value_columns = Vec::with_capacity(self.ctx.value_columns.len());
+        self.update_value_columns_by_exprs(&exprs, input_plan)?;
+
+        Ok(exprs)
+    }
+
+    /// Update value column names in context according to the aggregators.
+    fn update_value_columns_by_exprs(
+        &mut self,
+        exprs: &[DfExpr],
+        input_plan: &LogicalPlan,
+    ) -> Result<()> {
+        let mut new_value_columns = Vec::with_capacity(exprs.len());
         let normalized_exprs =
             normalize_cols(exprs.iter().cloned(), input_plan).context(DataFusionPlanningSnafu)?;
         for expr in normalized_exprs {
             new_value_columns.push(expr.display_name().context(DataFusionPlanningSnafu)?);
         }
         self.ctx.value_columns = new_value_columns;
+        Ok(())
+    }
 
-        Ok(exprs)
+    /// Build a projection plan for `label_replace` and `label_join`, which rewrites (or
+    /// adds) the destination label based on other labels. Other columns are unchanged.
+    ///
+    /// # Side Effects
+    ///
+    /// The destination label will be added to [PromPlannerContext]'s tag columns.
+    fn create_label_manipulate_plan(
+        &mut self,
+        func_name: &str,
+        literals: Vec<DfExpr>,
+        input: LogicalPlan,
+    ) -> Result<LogicalPlan> {
+        let params = literals
+            .into_iter()
+            .map(|expr| match expr {
+                DfExpr::Literal(ScalarValue::Utf8(Some(val))) => Ok(val),
+                _ => UnsupportedExprSnafu {
+                    name: format!("{func_name} with non-string-literal parameter"),
+                }
+                .fail(),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let (dst_label, dst_expr) = match (func_name, params.as_slice()) {
+            // label_replace(v, dst_label, replacement, src_label, regex)
+            ("label_replace", [dst, replacement, src, regex]) => {
+                // the regex is fully anchored like Prometheus does
+                let regex = DfExpr::Literal(ScalarValue::Utf8(Some(format!("^(?:{regex})$"))));
+                let src_expr = self.create_label_value_expr(src);
+                let replaced = DfExpr::ScalarFunction {
+                    fun: BuiltinScalarFunction::RegexpReplace,
+                    args: vec![
+                        src_expr.clone(),
+                        regex.clone(),
+                        DfExpr::Literal(ScalarValue::Utf8(Some(replacement.clone()))),
+                    ],
+                };
+                // keep the original value if not matched
+                let expr = DfExpr::Case(Case {
+                    expr: None,
+                    when_then_expr: vec![(
+                        Box::new(DfExpr::BinaryExpr(BinaryExpr {
+                            left: Box::new(src_expr),
+                            op: Operator::RegexMatch,
+                            right: Box::new(regex),
+                        })),
+                        Box::new(replaced),
+                    )],
+                    else_expr: Some(Box::new(self.create_label_value_expr(dst))),
+                });
+                (dst.clone(), expr)
+            }
+            // label_join(v, dst_label, separator, src_label_1, src_label_2, ...)
+            ("label_join", [dst, separator, srcs @ ..]) => {
+                let expr = if srcs.is_empty() {
+                    DfExpr::Literal(ScalarValue::Utf8(Some(String::new())))
+                } else {
+                    let mut args =
+                        vec![DfExpr::Literal(ScalarValue::Utf8(Some(separator.clone())))];
+                    args.extend(srcs.iter().map(|src| self.create_label_value_expr(src)));
+                    DfExpr::ScalarFunction {
+                        fun: BuiltinScalarFunction::ConcatWithSeparator,
+                        args,
+                    }
+                };
+                (dst.clone(), expr)
+            }
+            _ => UnsupportedExprSnafu {
+                name: format!("{func_name} with {} parameters", params.len()),
+            }
+            .fail()?,
+        };
+
+        // replace the destination label, and keep other columns
+        let mut project_exprs = self
+            .create_tag_column_exprs()?
+            .into_iter()
+            .zip(self.ctx.tag_columns.iter())
+            .filter(|(_, tag)| **tag != dst_label)
+            .map(|(expr, _)| expr)
+            .collect::<Vec<_>>();
+        project_exprs.push(dst_expr.alias(&dst_label));
+        project_exprs.push(self.create_time_index_column_expr()?);
+        project_exprs.extend(
+            self.ctx
+                .value_columns
+                .iter()
+                .map(|col| DfExpr::Column(Column::from_name(col))),
+        );
+
+        if !self.ctx.tag_columns.contains(&dst_label) {
+            self.ctx.tag_columns.push(dst_label);
+        }
+
+        LogicalPlanBuilder::from(input)
+            .project(project_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// The value of given label. Nonexistent labels are treated as empty strings.
+    fn create_label_value_expr(&self, label: &str) -> DfExpr {
+        if self.ctx.tag_columns.iter().any(|tag| tag == label) {
+            DfExpr::Column(Column::from_name(label))
+        } else {
+            DfExpr::Literal(ScalarValue::Utf8(Some(String::new())))
+        }
+    }
+
+    /// Build a [HistogramFold] plan for `histogram_quantile`. Buckets of one histogram
+    /// sample are identified by all tags except `le` and the time index.
+    fn create_histogram_fold_plan(
+        &mut self,
+        quantile: f64,
+        input: LogicalPlan,
+    ) -> Result<LogicalPlan> {
+        ensure!(
+            self.ctx.tag_columns.iter().any(|tag| tag == LE_COLUMN),
+            LeNotFoundSnafu {
+                tags: self.ctx.tag_columns.clone(),
+            }
+        );
+        let field_column = self.get_first_value_column()?;
+        let group_tags = self
+            .ctx
+            .tag_columns
+            .iter()
+            .filter(|tag| *tag != LE_COLUMN)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        // keep only the first value column, and sort to make buckets of one sample
+        // consecutive
+        let mut project_exprs = group_tags
+            .iter()
+            .map(|tag| DfExpr::Column(Column::from_name(tag)))
+            .collect::<Vec<_>>();
+        let mut sort_exprs = project_exprs
+            .iter()
+            .map(|expr| expr.clone().sort(true, false))
+            .collect::<Vec<_>>();
+        project_exprs.push(DfExpr::Column(Column::from_name(LE_COLUMN)));
+        project_exprs.push(self.create_time_index_column_expr()?);
+        project_exprs.push(DfExpr::Column(Column::from_name(&field_column)));
+        sort_exprs.push(self.create_time_index_column_expr()?.sort(true, false));
+        let sorted = LogicalPlanBuilder::from(input)
+            .project(project_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .sort(sort_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+
+        let fold = HistogramFold::new(
+            LE_COLUMN.to_string(),
+            field_column.clone(),
+            quantile,
+            sorted,
+        )
+        .context(DataFusionPlanningSnafu)?;
+        self.ctx.tag_columns = group_tags;
+        self.ctx.value_columns = vec![field_column];
+
+        Ok(LogicalPlan::Extension(Extension {
+            node: Arc::new(fold),
+        }))
+    }
+
+    /// Build a plan for `topk` and `bottomk`. Rows are ranked by the first value column
+    /// within each group (time index is always grouped), and only the top `k` rows of each
+    /// group are kept. Unlike other aggregations, labels of the input series are preserved.
+    fn create_topk_plan(
+        &mut self,
+        op: TokenType,
+        param: &Option<Box<PromExpr>>,
+        input: LogicalPlan,
+        group_exprs: Vec<DfExpr>,
+    ) -> Result<LogicalPlan> {
+        let k = Self::get_f64_param(op, param)?;
+        // non-positive k selects nothing
+        let k = if k.is_nan() || k < 1.0 { 0 } else { k as u64 };
+        let value_column = self.get_first_value_column()?;
+        let asc = op.id() == token::T_BOTTOMK;
+
+        let rank_expr = DfExpr::WindowFunction(WindowFunction {
+            fun: WindowFunctionEnum::BuiltInWindowFunction(BuiltInWindowFunction::RowNumber),
+            args: vec![],
+            partition_by: group_exprs,
+            order_by: vec![DfExpr::Column(Column::from_name(value_column)).sort(asc, false)],
+            window_frame: WindowFrame::new(true),
+        })
+        .alias(TOPK_RANK_COLUMN);
+        let rank_filter = DfExpr::Column(Column::from_name(TOPK_RANK_COLUMN))
+            .lt_eq(DfExpr::Literal(ScalarValue::UInt64(Some(k))));
+
+        // project the input columns to drop the rank column
+        let mut project_exprs = self.create_tag_column_exprs()?;
+        project_exprs.push(self.create_time_index_column_expr()?);
+        project_exprs.extend(
+            self.ctx
+                .value_columns
+                .iter()
+                .map(|col| DfExpr::Column(Column::from_name(col))),
+        );
+
+        LogicalPlanBuilder::from(input)
+            .window(vec![rank_expr])
+            .context(DataFusionPlanningSnafu)?
+            .filter(rank_filter)
+            .context(DataFusionPlanningSnafu)?
+            .project(project_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    fn get_first_value_column(&self) -> Result<String> {
+        self.ctx
+            .value_columns
+            .first()
+            .cloned()
+            .with_context(|| ValueNotFoundSnafu {
+                table: self.ctx.table_name.clone().unwrap_or_default(),
+            })
+    }
+
+    /// Get the number literal parameter of aggregation operators like `topk` and `quantile`.
+    fn get_f64_param(op: TokenType, param: &Option<Box<PromExpr>>) -> Result<f64> {
+        match param.as_deref() {
+            Some(PromExpr::NumberLiteral(NumberLiteral { val })) => Ok(*val),
+            Some(PromExpr::Paren(ParenExpr { expr })) => {
+                Self::get_f64_param(op, &Some(expr.clone()))
+            }
+            _ => UnsupportedExprSnafu {
+                name: format!("{op:?} with non-number-literal parameter"),
+            }
+            .fail(),
+        }
+    }
+
+    /// Get the string literal parameter of aggregation operators like `count_values`.
+    fn get_string_param(op: TokenType, param: &Option<Box<PromExpr>>) -> Result<String> {
+        match param.as_deref() {
+            Some(PromExpr::StringLiteral(StringLiteral { val })) => Ok(val.clone()),
+            _ => UnsupportedExprSnafu {
+                name: format!("{op:?} with non-string-literal parameter"),
+            }
+            .fail(),
+        }
     }
 
     /// Try to build a DataFusion Literal Expression from PromQL Expr, return
@@ -816,17 +1587,22 @@ impl PromPlanner {
             | PromExpr::Aggregate(_)
             | PromExpr::Subquery(_) => None,
             PromExpr::Paren(ParenExpr { expr }) => Self::try_build_literal_expr(expr),
-            // TODO(ruihang): support Unary operator
-            PromExpr::Unary(UnaryExpr { expr, .. }) => Self::try_build_literal_expr(expr),
+            PromExpr::Unary(UnaryExpr { expr, .. }) => Some(DfExpr::Negative(Box::new(
+                Self::try_build_literal_expr(expr)?,
+            ))),
             PromExpr::Binary(PromBinaryExpr { lhs, rhs, op, .. }) => {
                 let lhs = Self::try_build_literal_expr(lhs)?;
                 let rhs = Self::try_build_literal_expr(rhs)?;
-                let op = Self::prom_token_to_binary_op(*op).ok()?;
-                Some(DfExpr::BinaryExpr(BinaryExpr {
-                    left: Box::new(lhs),
-                    op,
-                    right: Box::new(rhs),
-                }))
+                let expr = Self::build_binary_expr(*op, lhs, rhs).ok()?;
+                if Self::is_token_a_comparison_op(*op) {
+                    // comparisons between scalars always return 0/1
+                    Some(DfExpr::Cast(Cast {
+                        expr: Box::new(expr),
+                        data_type: ArrowDataType::Float64,
+                    }))
+                } else {
+                    Some(expr)
+                }
             }
         }
     }
@@ -844,13 +1620,31 @@ impl PromPlanner {
             token::T_LSS => Ok(Operator::Lt),
             token::T_GTE => Ok(Operator::GtEq),
             token::T_LTE => Ok(Operator::LtEq),
-            // TODO(ruihang): support these two operators
-            // token::T_POW => Ok(Operator::Power),
-            // token::T_ATAN2 => Ok(Operator::Atan2),
             _ => UnexpectedTokenSnafu { token }.fail(),
         }
     }
 
+    /// Build the expr of a binary operator. `^` and `atan2` don't have corresponding
+    /// [Operator]s so they are built as scalar functions.
+    fn build_binary_expr(token: TokenType, left: DfExpr, right: DfExpr) -> Result<DfExpr> {
+        let expr = match token.id() {
+            token::T_POW => DfExpr::ScalarFunction {
+                fun: BuiltinScalarFunction::Power,
+                args: vec![left, right],
+            },
+            token::T_ATAN2 => DfExpr::ScalarFunction {
+                fun: BuiltinScalarFunction::Atan2,
+                args: vec![left, right],
+            },
+            _ => DfExpr::BinaryExpr(BinaryExpr {
+                left: Box::new(left),
+                op: Self::prom_token_to_binary_op(token)?,
+                right: Box::new(right),
+            }),
+        };
+        Ok(expr)
+    }
+
     /// Check if the given op is a [comparison operator](https://prometheus.io/docs/prometheus/latest/querying/operators/#comparison-binary-operators).
     fn is_token_a_comparison_op(token: TokenType) -> bool {
         matches!(
@@ -864,36 +1658,81 @@ impl PromPlanner {
         )
     }
 
-    /// Build a inner join on time index column and tag columns to concat two logical plans.
-    /// The left plan will be alised as [`LEFT_PLAN_JOIN_ALIAS`].
+    /// Build a inner join on time index column and given label columns to concat two logical
+    /// plans. The left plan will be alised as [`LEFT_PLAN_JOIN_ALIAS`], and the right plan
+    /// will be alised as [`RIGHT_PLAN_JOIN_ALIAS`].
     fn join_on_non_value_columns(
-        &self,
         left: LogicalPlan,
         right: LogicalPlan,
+        join_labels: &[String],
+        left_time_index: &str,
+        right_time_index: &str,
     ) -> Result<LogicalPlan> {
-        let mut tag_columns = self
-            .ctx
-            .tag_columns
+        let mut left_keys = join_labels
             .iter()
             .map(Column::from_name)
             .collect::<Vec<_>>();
+        let mut right_keys = left_keys.clone();
+        left_keys.push(Column::from_name(left_time_index));
+        right_keys.push(Column::from_name(right_time_index));
 
-        // push time index column if it exist
-        if let Some(time_index_column) = &self.ctx.time_index_column {
-            tag_columns.push(Column::from_name(time_index_column));
-        }
+        let right = LogicalPlanBuilder::from(right)
+            .alias(RIGHT_PLAN_JOIN_ALIAS)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
 
-        // Inner Join on time index column to concat two operator
+        // Inner Join on time index column and labels to concat two operator
         LogicalPlanBuilder::from(left)
             .alias(LEFT_PLAN_JOIN_ALIAS)
             .context(DataFusionPlanningSnafu)?
-            .join(
-                right,
-                JoinType::Inner,
-                // (vec![time_index_column.clone()], vec![time_index_column]),
-                (tag_columns.clone(), tag_columns),
-                None,
-            )
+            .join(right, JoinType::Inner, (left_keys, right_keys), None)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Make the plan fail at execution if series of `input` are not unique on `join_labels`
+    /// at any timestamp. Used on the "one" side of `group_left` and `group_right` with
+    /// [UniqueMatch], and on both sides of one-to-one matching with [OneToOneMatch].
+    fn ensure_unique_match(
+        input: LogicalPlan,
+        join_labels: &[String],
+        time_index: &str,
+        unique_udf: ScalarUDF,
+    ) -> Result<LogicalPlan> {
+        let mut partition_by = join_labels
+            .iter()
+            .map(|label| DfExpr::Column(Column::from_name(label)))
+            .collect::<Vec<_>>();
+        partition_by.push(DfExpr::Column(Column::from_name(time_index)));
+        let count_expr = DfExpr::WindowFunction(WindowFunction {
+            fun: WindowFunctionEnum::AggregateFunction(AggregateFunctionEnum::Count),
+            args: vec![DfExpr::Column(Column::from_name(time_index))],
+            partition_by,
+            order_by: vec![],
+            window_frame: WindowFrame::new(false),
+        })
+        .alias(MATCH_COUNT_COLUMN);
+        let unique_filter = DfExpr::ScalarUDF {
+            fun: Arc::new(unique_udf),
+            args: vec![DfExpr::Column(Column::from_name(MATCH_COUNT_COLUMN))],
+        };
+
+        // project the input columns to drop the count column
+        let project_exprs = input
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| DfExpr::Column(field.qualified_column()))
+            .collect::<Vec<_>>();
+
+        LogicalPlanBuilder::from(input)
+            .window(vec![count_expr])
+            .context(DataFusionPlanningSnafu)?
+            .filter(unique_filter)
+            .context(DataFusionPlanningSnafu)?
+            .project(project_exprs)
             .context(DataFusionPlanningSnafu)?
             .build()
             .context(DataFusionPlanningSnafu)
@@ -991,6 +1830,125 @@ enum ScalarFunc {
     Udf(ScalarUDF),
 }
 
+/// How to match and output labels of two instant vectors in a binary expr, according to
+/// the [vector matching](https://prometheus.io/docs/prometheus/latest/querying/operators/#vector-matching)
+/// modifiers (`on`/`ignoring` and `group_left`/`group_right`).
+#[derive(Debug)]
+struct VectorMatching {
+    /// Labels to join two sides on. Time index is not included.
+    join_labels: Vec<String>,
+    /// Labels in the result, with the alias of the side they come from.
+    output_labels: Vec<(&'static str, String)>,
+    /// Alias of the "one" side of `group_left` or `group_right`, whose series must
+    /// be unique on the join labels.
+    one_side: Option<&'static str>,
+}
+
+impl VectorMatching {
+    /// Labels that don't exist on both sides are ignored when matching.
+    ///
+    /// `is_filter` indicates a comparison without `bool`, which filters the left side
+    /// and keeps all its labels.
+    fn try_new(
+        modifier: &Option<BinModifier>,
+        left_labels: &[String],
+        right_labels: &[String],
+        is_filter: bool,
+    ) -> Result<Self> {
+        let common_labels = left_labels
+            .iter()
+            .filter(|label| right_labels.contains(label))
+            .cloned()
+            .collect::<Vec<_>>();
+        let matching = modifier.as_ref().and_then(|m| m.matching.as_ref());
+        let card = modifier
+            .as_ref()
+            .map(|m| &m.card)
+            .unwrap_or(&VectorMatchCardinality::OneToOne);
+
+        let join_labels = match matching {
+            Some(VectorMatchModifier::On(on)) => common_labels
+                .into_iter()
+                .filter(|label| on.contains(label))
+                .collect(),
+            Some(VectorMatchModifier::Ignoring(ignoring)) => common_labels
+                .into_iter()
+                .filter(|label| !ignoring.contains(label))
+                .collect(),
+            None => common_labels,
+        };
+
+        let output_labels = match card {
+            VectorMatchCardinality::OneToOne if is_filter => left_labels
+                .iter()
+                .map(|label| (LEFT_PLAN_JOIN_ALIAS, label.clone()))
+                .collect(),
+            VectorMatchCardinality::OneToOne => match matching {
+                // only matching labels are kept
+                Some(VectorMatchModifier::On(_)) => join_labels
+                    .iter()
+                    .map(|label| (LEFT_PLAN_JOIN_ALIAS, label.clone()))
+                    .collect(),
+                Some(VectorMatchModifier::Ignoring(ignoring)) => left_labels
+                    .iter()
+                    .filter(|label| !ignoring.contains(*label))
+                    .map(|label| (LEFT_PLAN_JOIN_ALIAS, label.clone()))
+                    .collect(),
+                None => join_labels
+                    .iter()
+                    .map(|label| (LEFT_PLAN_JOIN_ALIAS, label.clone()))
+                    .collect(),
+            },
+            // labels of the "many" side are kept, with extra labels from the "one" side
+            VectorMatchCardinality::ManyToOne(include) => Self::group_labels(
+                (LEFT_PLAN_JOIN_ALIAS, left_labels),
+                (RIGHT_PLAN_JOIN_ALIAS, right_labels),
+                include.iter(),
+            ),
+            VectorMatchCardinality::OneToMany(include) => Self::group_labels(
+                (RIGHT_PLAN_JOIN_ALIAS, right_labels),
+                (LEFT_PLAN_JOIN_ALIAS, left_labels),
+                include.iter(),
+            ),
+            VectorMatchCardinality::ManyToMany => UnsupportedExprSnafu {
+                name: "many-to-many vector matching",
+            }
+            .fail()?,
+        };
+
+        let one_side = match card {
+            VectorMatchCardinality::ManyToOne(_) => Some(RIGHT_PLAN_JOIN_ALIAS),
+            VectorMatchCardinality::OneToMany(_) => Some(LEFT_PLAN_JOIN_ALIAS),
+            _ => None,
+        };
+
+        Ok(Self {
+            join_labels,
+            output_labels,
+            one_side,
+        })
+    }
+
+    /// Output labels of `group_left` or `group_right`. All labels of the "many" side
+    /// are kept, and `include` labels are copied from the "one" side if exist.
+    fn group_labels<'a>(
+        (many_alias, many_labels): (&'static str, &[String]),
+        (one_alias, one_labels): (&'static str, &[String]),
+        include: impl Iterator<Item = &'a String>,
+    ) -> Vec<(&'static str, String)> {
+        let include = include
+            .filter(|label| one_labels.contains(label))
+            .collect::<Vec<_>>();
+        let mut labels = many_labels
+            .iter()
+            .filter(|label| !include.contains(label))
+            .map(|label| (many_alias, label.clone()))
+            .collect::<Vec<_>>();
+        labels.extend(include.into_iter().map(|label| (one_alias, label.clone())));
+        labels
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::time::{Duration, UNIX_EPOCH};
@@ -1006,16 +1964,27 @@ mod test {
     use table::test_util::EmptyTable;
 
     use super::*;
+    use crate::error::Error;
 
     async fn build_test_table_provider(
         table_name: String,
         num_tag: usize,
         num_field: usize,
     ) -> DfTableSourceProvider {
+        let tags = (0..num_tag).map(|i| format!("tag_{i}")).collect();
+        build_test_table_provider_with_tags(table_name, tags, num_field).await
+    }
+
+    async fn build_test_table_provider_with_tags(
+        table_name: String,
+        tags: Vec<String>,
+        num_field: usize,
+    ) -> DfTableSourceProvider {
+        let num_tag = tags.len();
         let mut columns = vec![];
-        for i in 0..num_tag {
+        for tag in tags {
             columns.push(ColumnSchema::new(
-                format!("tag_{i}"),
+                tag,
                 ConcreteDataType::string_datatype(),
                 false,
             ));
@@ -1063,6 +2032,36 @@ mod test {
         DfTableSourceProvider::new(catalog_list, false, &QueryContext::new())
     }
 
+    async fn build_histogram_table_provider(table_name: &str) -> DfTableSourceProvider {
+        let tags = vec!["tag_0".to_string(), LE_COLUMN.to_string()];
+        build_test_table_provider_with_tags(table_name.to_string(), tags, 1).await
+    }
+
+    /// Plan `query` evaluated in `[0, 100000s]`, with 5s interval and 1s lookback delta.
+    async fn plan_query(query: &str, table_provider: DfTableSourceProvider) -> Result<LogicalPlan> {
+        let prom_expr = parser::parse(query).unwrap();
+        let eval_stmt = EvalStmt {
+            expr: prom_expr,
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+        PromPlanner::stmt_to_plan(table_provider, eval_stmt).await
+    }
+
+    /// Plan `query` on `some_metric` with two tags and one field, returns the displayed plan.
+    async fn do_query_plan(query: &str) -> String {
+        let table_provider = build_test_table_provider("some_metric".to_string(), 2, 1).await;
+        plan_query(query, table_provider)
+            .await
+            .unwrap()
+            .display_indent_schema()
+            .to_string()
+    }
+
     // {
     //     input: `abs(some_metric{foo!="bar"})`,
     //     expected: &Call{
@@ -1381,30 +2380,166 @@ mod test {
     }
 
     #[tokio::test]
-    #[should_panic]
     async fn aggregate_top_k() {
-        do_aggregate_expr_plan("topk", "").await;
+        let plan = do_query_plan("topk by (tag_1) (3, some_metric)").await;
+        assert!(plan.contains("Projection: some_metric.tag_0, some_metric.tag_1, some_metric.timestamp, some_metric.field_0 [tag_0:Utf8, tag_1:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]"));
+        assert!(plan.contains("Filter: __prom_topk_rank <= UInt64(3)"));
+        assert!(plan.contains("ROW_NUMBER() PARTITION BY [some_metric.tag_1, some_metric.timestamp] ORDER BY [some_metric.field_0 DESC NULLS LAST]"));
     }
 
     #[tokio::test]
-    #[should_panic]
     async fn aggregate_bottom_k() {
-        do_aggregate_expr_plan("bottomk", "").await;
+        let plan = do_query_plan("bottomk(2, some_metric)").await;
+        assert!(plan.contains("Filter: __prom_topk_rank <= UInt64(2)"));
+        assert!(plan.contains(
+            "ROW_NUMBER() PARTITION BY [some_metric.timestamp] ORDER BY [some_metric.field_0 ASC NULLS LAST]"
+        ));
+    }
+
+    #[tokio::test]
+    async fn aggregate_top_k_of_aggregation() {
+        let plan = do_query_plan("topk(1, sum by (tag_0) (some_metric))").await;
+        assert!(plan.contains("ROW_NUMBER() PARTITION BY [some_metric.timestamp] ORDER BY [SUM(some_metric.field_0) DESC NULLS LAST]"));
+        assert!(plan.contains("Aggregate: groupBy=[[some_metric.tag_0, some_metric.timestamp]]"));
+
+        let plan = do_query_plan("bottomk(1, sum(some_metric))").await;
+        assert!(plan.contains("ROW_NUMBER() PARTITION BY [some_metric.timestamp] ORDER BY [SUM(some_metric.field_0) ASC NULLS LAST]"));
     }
 
     #[tokio::test]
-    #[should_panic]
     async fn aggregate_count_values() {
-        do_aggregate_expr_plan("count_values", "").await;
+        let plan = do_query_plan("count_values by (tag_1) (\"value\", some_metric)").await;
+        assert!(plan.contains("Aggregate: groupBy=[[some_metric.tag_1, prom_format_float(some_metric.field_0) AS value, some_metric.timestamp]], aggr=[[COUNT(some_metric.field_0)]]"));
+        assert!(plan.contains(
+            "Sort: some_metric.tag_1 ASC NULLS LAST, value ASC NULLS LAST, some_metric.timestamp ASC NULLS LAST"
+        ));
     }
 
     #[tokio::test]
-    #[should_panic]
     async fn aggregate_quantile() {
-        do_aggregate_expr_plan("quantile", "").await;
+        let plan = do_query_plan("quantile by (tag_1) (0.5, some_metric)").await;
+        assert!(plan.contains("Aggregate: groupBy=[[some_metric.tag_1, some_metric.timestamp]], aggr=[[prom_quantile(some_metric.field_0, Float64(0.5))]]"));
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn aggregate_top_k_without_param() {
+        do_aggregate_expr_plan("topk", "").await;
+    }
+
+    async fn do_range_function_call(query: &str, plan_name: &str) {
+        let table_provider = build_test_table_provider("some_metric".to_string(), 1, 1).await;
+        let plan = plan_query(query, table_provider)
+            .await
+            .unwrap()
+            .display_indent()
+            .to_string();
+
+        assert!(
+            plan.contains(&format!("Filter: {plan_name}(timestamp_range,field_0")),
+            "{plan}"
+        );
+        assert!(plan.contains("PromRangeManipulate: req range=[0..100000000], interval=[5000], eval range=[300000], time index=[timestamp], values=[\"field_0\"]"));
+        // range vector scans data from `start - range`
+        assert!(plan.contains("some_metric.timestamp >= TimestampMillisecond(-300000, None)"));
+    }
+
+    #[tokio::test]
+    async fn range_avg_over_time() {
+        do_range_function_call("avg_over_time(some_metric[5m])", "prom_avg_over_time").await;
+    }
+
+    #[tokio::test]
+    async fn range_min_over_time() {
+        do_range_function_call("min_over_time(some_metric[5m])", "prom_min_over_time").await;
+    }
+
+    #[tokio::test]
+    async fn range_max_over_time() {
+        do_range_function_call("max_over_time(some_metric[5m])", "prom_max_over_time").await;
+    }
+
+    #[tokio::test]
+    async fn range_sum_over_time() {
+        do_range_function_call("sum_over_time(some_metric[5m])", "prom_sum_over_time").await;
+    }
+
+    #[tokio::test]
+    async fn range_count_over_time() {
+        do_range_function_call("count_over_time(some_metric[5m])", "prom_count_over_time").await;
+    }
+
+    #[tokio::test]
+    async fn range_last_over_time() {
+        do_range_function_call("last_over_time(some_metric[5m])", "prom_last_over_time").await;
+    }
+
+    #[tokio::test]
+    async fn range_quantile_over_time() {
+        do_range_function_call(
+            "quantile_over_time(0.9, some_metric[5m])",
+            "prom_quantile_over_time",
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn range_stddev_over_time() {
+        do_range_function_call("stddev_over_time(some_metric[5m])", "prom_stddev_over_time").await;
+    }
+
+    #[tokio::test]
+    async fn range_stdvar_over_time() {
+        do_range_function_call("stdvar_over_time(some_metric[5m])", "prom_stdvar_over_time").await;
+    }
+
+    #[tokio::test]
+    async fn range_present_over_time() {
+        do_range_function_call(
+            "present_over_time(some_metric[5m])",
+            "prom_present_over_time",
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn range_changes() {
+        do_range_function_call("changes(some_metric[5m])", "prom_changes").await;
     }
 
-    // TODO(ruihang): add range fn tests once exprs are ready.
+    #[tokio::test]
+    async fn range_resets() {
+        do_range_function_call("resets(some_metric[5m])", "prom_resets").await;
+    }
+
+    #[tokio::test]
+    async fn range_deriv() {
+        do_range_function_call("deriv(some_metric[5m])", "prom_deriv").await;
+    }
+
+    #[tokio::test]
+    async fn range_rate() {
+        do_range_function_call("rate(some_metric[5m])", "prom_rate").await;
+    }
+
+    #[tokio::test]
+    async fn range_increase() {
+        do_range_function_call("increase(some_metric[5m])", "prom_increase").await;
+    }
+
+    #[tokio::test]
+    async fn range_delta() {
+        do_range_function_call("delta(some_metric[5m])", "prom_delta").await;
+    }
+
+    #[tokio::test]
+    async fn range_predict_linear() {
+        do_range_function_call(
+            "predict_linear(some_metric[5m], 3600)",
+            "prom_predict_linear",
+        )
+        .await;
+    }
 
     // {
     //     input: "some_metric{tag_0="foo"} + some_metric{tag_0="bar"}",
@@ -1447,26 +2582,226 @@ mod test {
             .unwrap();
 
         let  expected = String::from(
-            "Projection: lhs.tag_0, lhs.timestamp, some_metric.field_0 + some_metric.field_0 AS some_metric.field_0 + some_metric.field_0 [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), some_metric.field_0 + some_metric.field_0:Float64;N]\
-            \n  Inner Join: lhs.tag_0 = some_metric.tag_0, lhs.timestamp = some_metric.timestamp [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N, tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            "Projection: lhs.tag_0, lhs.timestamp, lhs.field_0 + rhs.field_0 AS field_0 + field_0 [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0 + field_0:Float64;N]\
+            \n  Inner Join: lhs.tag_0 = rhs.tag_0, lhs.timestamp = rhs.timestamp [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N, tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
             \n    SubqueryAlias: lhs [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n      PromInstantManipulate: range=[0..100000000], lookback=[1000], interval=[5000], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n        PromSeriesNormalize: offset=[0], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n          PromSeriesDivide: tags=[\"tag_0\"] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n            Sort: some_metric.tag_0 DESC NULLS LAST, some_metric.timestamp DESC NULLS LAST [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n              Filter: some_metric.tag_0 = Utf8(\"foo\") AND some_metric.timestamp >= TimestampMillisecond(-1000, None) AND some_metric.timestamp <= TimestampMillisecond(100000000, None) [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n                TableScan: some_metric, unsupported_filters=[tag_0 = Utf8(\"foo\"), timestamp >= TimestampMillisecond(-1000, None), timestamp <= TimestampMillisecond(100000000, None)] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n    PromInstantManipulate: range=[0..100000000], lookback=[1000], interval=[5000], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n      PromSeriesNormalize: offset=[0], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n        PromSeriesDivide: tags=[\"tag_0\"] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n          Sort: some_metric.tag_0 DESC NULLS LAST, some_metric.timestamp DESC NULLS LAST [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n            Filter: some_metric.tag_0 = Utf8(\"bar\") AND some_metric.timestamp >= TimestampMillisecond(-1000, None) AND some_metric.timestamp <= TimestampMillisecond(100000000, None) [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n              TableScan: some_metric, unsupported_filters=[tag_0 = Utf8(\"bar\"), timestamp >= TimestampMillisecond(-1000, None), timestamp <= TimestampMillisecond(100000000, None)] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]"
+            \n      Projection: some_metric.tag_0, some_metric.timestamp, some_metric.field_0 [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n        Filter: prom_one_to_one_match(__prom_match_count) [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N, __prom_match_count:Int64;N]\
+            \n          WindowAggr: windowExpr=[[COUNT(some_metric.timestamp) PARTITION BY [some_metric.tag_0, some_metric.timestamp] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS __prom_match_count]] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N, __prom_match_count:Int64;N]\
+            \n            PromInstantManipulate: range=[0..100000000], lookback=[1000], interval=[5000], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n              PromSeriesNormalize: offset=[0], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n                PromSeriesDivide: tags=[\"tag_0\"] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n                  Sort: some_metric.tag_0 DESC NULLS LAST, some_metric.timestamp DESC NULLS LAST [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n                    Filter: some_metric.tag_0 = Utf8(\"foo\") AND some_metric.timestamp >= TimestampMillisecond(-1000, None) AND some_metric.timestamp <= TimestampMillisecond(100000000, None) [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n                      TableScan: some_metric, unsupported_filters=[tag_0 = Utf8(\"foo\"), timestamp >= TimestampMillisecond(-1000, None), timestamp <= TimestampMillisecond(100000000, None)] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n    SubqueryAlias: rhs [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n      Projection: some_metric.tag_0, some_metric.timestamp, some_metric.field_0 [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n        Filter: prom_one_to_one_match(__prom_match_count) [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N, __prom_match_count:Int64;N]\
+            \n          WindowAggr: windowExpr=[[COUNT(some_metric.timestamp) PARTITION BY [some_metric.tag_0, some_metric.timestamp] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS __prom_match_count]] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N, __prom_match_count:Int64;N]\
+            \n            PromInstantManipulate: range=[0..100000000], lookback=[1000], interval=[5000], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n              PromSeriesNormalize: offset=[0], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n                PromSeriesDivide: tags=[\"tag_0\"] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n                  Sort: some_metric.tag_0 DESC NULLS LAST, some_metric.timestamp DESC NULLS LAST [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n                    Filter: some_metric.tag_0 = Utf8(\"bar\") AND some_metric.timestamp >= TimestampMillisecond(-1000, None) AND some_metric.timestamp <= TimestampMillisecond(100000000, None) [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n                      TableScan: some_metric, unsupported_filters=[tag_0 = Utf8(\"bar\"), timestamp >= TimestampMillisecond(-1000, None), timestamp <= TimestampMillisecond(100000000, None)] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]"
         );
 
         assert_eq!(plan.display_indent_schema().to_string(), expected);
     }
 
+    #[tokio::test]
+    async fn vector_matching_on() {
+        let plan = do_query_plan("some_metric + on(tag_0) some_metric").await;
+        assert!(plan.contains(
+            "Projection: lhs.tag_0, lhs.timestamp, lhs.field_0 + rhs.field_0 AS field_0 + field_0"
+        ));
+        assert!(plan.contains("Inner Join: lhs.tag_0 = rhs.tag_0, lhs.timestamp = rhs.timestamp"));
+    }
+
+    #[tokio::test]
+    async fn vector_matching_ignoring() {
+        let plan = do_query_plan("some_metric + ignoring(tag_1) some_metric").await;
+        assert!(plan.contains(
+            "Projection: lhs.tag_0, lhs.timestamp, lhs.field_0 + rhs.field_0 AS field_0 + field_0"
+        ));
+        assert!(plan.contains("Inner Join: lhs.tag_0 = rhs.tag_0, lhs.timestamp = rhs.timestamp"));
+    }
+
+    #[tokio::test]
+    async fn vector_matching_without_modifier() {
+        let plan = do_query_plan("some_metric * some_metric").await;
+        assert!(plan.contains(
+            "Projection: lhs.tag_0, lhs.tag_1, lhs.timestamp, lhs.field_0 * rhs.field_0 AS field_0 * field_0"
+        ));
+        assert!(plan.contains(
+            "Inner Join: lhs.tag_0 = rhs.tag_0, lhs.tag_1 = rhs.tag_1, lhs.timestamp = rhs.timestamp"
+        ));
+    }
+
+    #[tokio::test]
+    async fn vector_matching_group_left() {
+        let plan = do_query_plan("some_metric / on(tag_0) group_left(tag_1) some_metric").await;
+        assert!(plan.contains(
+            "Projection: lhs.tag_0, rhs.tag_1, lhs.timestamp, lhs.field_0 / rhs.field_0 AS field_0 / field_0"
+        ));
+        assert!(plan.contains("Inner Join: lhs.tag_0 = rhs.tag_0, lhs.timestamp = rhs.timestamp"));
+    }
+
+    #[tokio::test]
+    async fn vector_matching_group_right() {
+        let plan = do_query_plan("some_metric - ignoring(tag_1) group_right some_metric").await;
+        assert!(plan.contains(
+            "Projection: rhs.tag_0, rhs.tag_1, lhs.timestamp, lhs.field_0 - rhs.field_0 AS field_0 - field_0"
+        ));
+        assert!(plan.contains("Inner Join: lhs.tag_0 = rhs.tag_0, lhs.timestamp = rhs.timestamp"));
+    }
+
+    #[tokio::test]
+    async fn vector_matching_comparison_filter() {
+        let plan = do_query_plan("some_metric > on(tag_0) some_metric").await;
+        // all labels of the left side are kept
+        assert!(plan
+            .contains("Projection: lhs.tag_0, lhs.tag_1, lhs.timestamp, lhs.field_0 AS field_0"));
+        assert!(plan.contains("Filter: lhs.field_0 > rhs.field_0"));
+    }
+
+    #[tokio::test]
+    async fn vector_matching_unique_one_side() {
+        let plan = do_query_plan("some_metric / on(tag_0) group_left some_metric").await;
+        let join = plan.find("Inner Join:").unwrap();
+        let right = plan.find("SubqueryAlias: rhs").unwrap();
+        let unique_filter = plan
+            .find("Filter: prom_unique_match(__prom_match_count)")
+            .unwrap();
+        // only the right side is checked
+        assert!(join < right && right < unique_filter);
+        assert!(plan.contains(
+            "COUNT(some_metric.timestamp) PARTITION BY [some_metric.tag_0, some_metric.timestamp]"
+        ));
+
+        let plan = do_query_plan("some_metric / on(tag_0) some_metric").await;
+        assert!(!plan.contains("prom_unique_match"));
+        // one-to-one matching checks both sides
+        let join = plan.find("Inner Join:").unwrap();
+        let left = plan.find("SubqueryAlias: lhs").unwrap();
+        let right = plan.find("SubqueryAlias: rhs").unwrap();
+        let left_filter = plan
+            .find("Filter: prom_one_to_one_match(__prom_match_count)")
+            .unwrap();
+        let right_filter = plan
+            .rfind("Filter: prom_one_to_one_match(__prom_match_count)")
+            .unwrap();
+        assert!(join < left && left < left_filter && left_filter < right);
+        assert!(right < right_filter);
+    }
+
+    #[tokio::test]
+    async fn vector_matching_aggregation() {
+        let plan = do_query_plan("sum by (tag_0) (some_metric) + sum(some_metric)").await;
+        assert!(plan.contains("Inner Join: lhs.timestamp = rhs.timestamp"));
+        assert!(plan.contains("Projection: lhs.timestamp, "));
+
+        let plan =
+            do_query_plan("some_metric / on(tag_0) group_left sum by (tag_0) (some_metric)").await;
+        assert!(plan.contains("Inner Join: lhs.tag_0 = rhs.tag_0, lhs.timestamp = rhs.timestamp"));
+    }
+
+    #[tokio::test]
+    async fn histogram_quantile() {
+        let table_provider = build_histogram_table_provider("some_histogram_bucket").await;
+        let plan = plan_query(
+            "histogram_quantile(0.9, some_histogram_bucket)",
+            table_provider,
+        )
+        .await
+        .unwrap()
+        .display_indent_schema()
+        .to_string();
+        assert!(plan.starts_with("PromHistogramFold: le=[le], field=[field_0], quantile=[0.9] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]"));
+        assert!(plan.contains(
+            "Sort: some_histogram_bucket.tag_0 ASC NULLS LAST, some_histogram_bucket.timestamp ASC NULLS LAST"
+        ));
+        assert!(plan.contains("Projection: some_histogram_bucket.tag_0, some_histogram_bucket.le, some_histogram_bucket.timestamp, some_histogram_bucket.field_0"));
+    }
+
+    #[tokio::test]
+    async fn histogram_quantile_without_le() {
+        let table_provider = build_test_table_provider("some_metric".to_string(), 1, 1).await;
+        let result = plan_query("histogram_quantile(0.9, some_metric)", table_provider).await;
+        assert!(matches!(result, Err(Error::LeNotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn histogram_quantile_of_aggregation() {
+        let table_provider = build_histogram_table_provider("some_histogram_bucket").await;
+        let plan = plan_query(
+            "histogram_quantile(0.9, sum by (le) (rate(some_histogram_bucket[5m])))",
+            table_provider,
+        )
+        .await
+        .unwrap()
+        .display_indent()
+        .to_string();
+        assert!(plan.starts_with("PromHistogramFold: le=[le]"));
+        assert!(plan.contains(
+            "Aggregate: groupBy=[[some_histogram_bucket.le, some_histogram_bucket.timestamp]]"
+        ));
+    }
+
+    #[tokio::test]
+    async fn histogram_count_and_sum() {
+        let table_provider = build_histogram_table_provider("some_histogram_sum").await;
+        let plan = plan_query(
+            "histogram_sum(rate(some_histogram_bucket[5m]))",
+            table_provider,
+        )
+        .await
+        .unwrap()
+        .display_indent()
+        .to_string();
+        assert!(plan.contains("TableScan: some_histogram_sum"));
+
+        let table_provider = build_histogram_table_provider("some_histogram_count").await;
+        let plan = plan_query("histogram_count(some_histogram_bucket)", table_provider)
+            .await
+            .unwrap()
+            .display_indent()
+            .to_string();
+        assert!(plan.contains("TableScan: some_histogram_count"));
+
+        // only bucket series of classic histograms are accepted
+        let table_provider = build_histogram_table_provider("some_histogram").await;
+        let result = plan_query("histogram_count(some_histogram)", table_provider).await;
+        assert!(matches!(result, Err(Error::NotBucketSeries { .. })));
+    }
+
+    #[tokio::test]
+    async fn label_replace_existing_label() {
+        let plan =
+            do_query_plan(r#"label_replace(some_metric, "tag_1", "$1-x", "tag_0", "(.*)-.*")"#)
+                .await;
+        assert!(plan.starts_with("Projection: some_metric.tag_0, CASE WHEN some_metric.tag_0 ~ Utf8(\"^(?:(.*)-.*)$\") THEN regexp_replace(some_metric.tag_0, Utf8(\"^(?:(.*)-.*)$\"), Utf8(\"$1-x\")) ELSE some_metric.tag_1 END AS tag_1, some_metric.timestamp, some_metric.field_0 [tag_0:Utf8, tag_1:Utf8;N, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]"));
+    }
+
+    #[tokio::test]
+    async fn label_replace_new_label() {
+        let plan =
+            do_query_plan(r#"label_replace(some_metric, "foo", "$1", "tag_0", "(.*)")"#).await;
+        assert!(plan.contains("ELSE Utf8(\"\") END AS foo"));
+        assert!(plan.contains(
+            "[tag_0:Utf8, tag_1:Utf8, foo:Utf8;N, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]"
+        ));
+    }
+
+    #[tokio::test]
+    async fn label_join() {
+        let plan =
+            do_query_plan(r#"label_join(some_metric, "foo", ",", "tag_0", "tag_1", "bar")"#).await;
+        assert!(plan.contains(
+            "concat_ws(Utf8(\",\"), some_metric.tag_0, some_metric.tag_1, Utf8(\"\")) AS foo"
+        ));
+    }
+
     async fn indie_query_plan_compare(query: &str, expected: String) {
         let prom_expr = parser::parse(query).unwrap();
         let eval_stmt = EvalStmt {
@@ -1504,10 +2839,12 @@ mod test {
     }
 
     #[tokio::test]
-    #[ignore = "pure literal arithmetic is not supported yet"]
     async fn binary_op_literal_literal() {
         let query = r#"1 + 1"#;
-        let expected = String::from("");
+        let expected = String::from(
+            "Projection: time, Float64(1) + Float64(1) AS value [time:Timestamp(Millisecond, None), value:Float64]\
+            \n  PromEmptyMetric: range=[0..100000000], interval=[5000], time index=[time] [time:Timestamp(Millisecond, None)]"
+        );
 
         indie_query_plan_compare(query, expected).await;
     }
@@ -1529,12 +2866,103 @@ mod test {
     }
 
     #[tokio::test]
-    #[ignore = "pure literal arithmetic is not supported yet"]
     async fn bool_with_additional_arithmetic() {
-        let query = "some_metric + (1 == bool 2)";
-        let expected = String::from("");
+        let plan = do_query_plan("some_metric + (1 == bool 2)").await;
+        assert!(plan.contains(
+            "Projection: some_metric.tag_0, some_metric.tag_1, some_metric.timestamp, some_metric.field_0 + CAST(Float64(1) = Float64(2) AS Float64) AS field_0 + CAST(Float64(1) = Float64(2) AS Float64)"
+        ));
+    }
 
-        indie_query_plan_compare(query, expected).await;
+    #[tokio::test]
+    async fn number_literal() {
+        let plan = do_query_plan("1.5").await;
+        assert!(plan.contains("Projection: time, Float64(1.5) AS value"));
+        assert!(plan
+            .contains("PromEmptyMetric: range=[0..100000000], interval=[5000], time index=[time]"));
+    }
+
+    #[tokio::test]
+    async fn time_function() {
+        let plan = do_query_plan("time()").await;
+        assert!(plan.contains(
+            "Projection: time, CAST(CAST(time AS Int64) AS Float64) / Float64(1000) AS value"
+        ));
+        assert!(plan
+            .contains("PromEmptyMetric: range=[0..100000000], interval=[5000], time index=[time]"));
+    }
+
+    #[tokio::test]
+    async fn vector_function() {
+        let plan = do_query_plan("vector(1)").await;
+        assert!(plan.contains("Projection: time, Float64(1) AS value"));
+
+        // the literal vector can be used like other instant vectors
+        let plan = do_query_plan("abs(vector(1))").await;
+        assert!(plan.contains("abs(value)"));
+    }
+
+    #[tokio::test]
+    async fn pow_and_atan2() {
+        let plan = do_query_plan("some_metric ^ 2").await;
+        assert!(plan.contains("power(some_metric.field_0, Float64(2))"));
+
+        let plan = do_query_plan("some_metric atan2 some_metric").await;
+        assert!(plan.contains("atan2(lhs.field_0, rhs.field_0)"));
+    }
+
+    #[tokio::test]
+    async fn at_modifier_on_vector_selector() {
+        let plan = do_query_plan("some_metric @ 100").await;
+        // the selector is evaluated at the pinned timestamp only
+        assert!(plan.contains("PromInstantManipulate: range=[100000..100000], lookback=[1000], interval=[5000], time index=[timestamp]"));
+        assert!(plan.contains("some_metric.timestamp >= TimestampMillisecond(99000, None) AND some_metric.timestamp <= TimestampMillisecond(100000, None)"));
+        // then expanded to every evaluation timestamp
+        assert!(plan.contains("CrossJoin:"));
+        assert!(plan.contains(
+            "PromEmptyMetric: range=[0..100000000], interval=[5000], time index=[timestamp]"
+        ));
+
+        let plan = do_query_plan("some_metric @ start()").await;
+        assert!(plan.contains("PromInstantManipulate: range=[0..0]"));
+        let plan = do_query_plan("some_metric @ end()").await;
+        assert!(plan.contains("PromInstantManipulate: range=[100000000..100000000]"));
+    }
+
+    #[tokio::test]
+    async fn at_modifier_on_matrix_selector() {
+        let plan = do_query_plan("rate(some_metric[5m] @ 100)").await;
+        assert!(plan.contains(
+            "PromRangeManipulate: req range=[100000..100000], interval=[5000], eval range=[300000]"
+        ));
+        // expanded after the range function is applied
+        let cross_join = plan.find("CrossJoin:").unwrap();
+        let manipulate = plan.find("PromRangeManipulate:").unwrap();
+        assert!(cross_join < manipulate);
+        assert!(plan.contains(
+            "PromEmptyMetric: range=[0..100000000], interval=[5000], time index=[timestamp]"
+        ));
+    }
+
+    #[tokio::test]
+    async fn offset_modifier() {
+        let plan = do_query_plan("some_metric offset 1m").await;
+        assert!(plan.contains("PromSeriesNormalize: offset=[-60000]"));
+        assert!(plan.contains("some_metric.timestamp >= TimestampMillisecond(-61000, None) AND some_metric.timestamp <= TimestampMillisecond(99940000, None)"));
+    }
+
+    // Regression test: the scan range used to be shifted towards the opposite direction
+    // of the offset, so the shifted samples were outside the evaluation range.
+    #[tokio::test]
+    async fn offset_modifier_scan_range() {
+        // negative offset selects samples after the evaluation range
+        let plan = do_query_plan("some_metric offset -1m").await;
+        assert!(plan.contains("PromSeriesNormalize: offset=[60000]"));
+        assert!(plan.contains("some_metric.timestamp >= TimestampMillisecond(59000, None) AND some_metric.timestamp <= TimestampMillisecond(100060000, None)"));
+
+        // offset of range vector
+        let plan = do_query_plan("rate(some_metric[5m] offset 1h)").await;
+        assert!(plan.contains("PromSeriesNormalize: offset=[-3600000]"));
+        assert!(plan.contains("some_metric.timestamp >= TimestampMillisecond(-3900000, None) AND some_metric.timestamp <= TimestampMillisecond(96400000, None)"));
     }
 
     #[tokio::test]
@@ -1558,20 +2986,95 @@ mod test {
     async fn increase_aggr() {
         let query = "increase(some_metric[5m])";
         let expected = String::from(
-            "Filter: prom_increase(timestamp_range,field_0) IS NOT NULL [timestamp:Timestamp(Millisecond, None), prom_increase(timestamp_range,field_0):Float64;N, tag_0:Utf8]\
-            \n  Projection: some_metric.timestamp, prom_increase(timestamp_range, field_0) AS prom_increase(timestamp_range,field_0), some_metric.tag_0 [timestamp:Timestamp(Millisecond, None), prom_increase(timestamp_range,field_0):Float64;N, tag_0:Utf8]\
+            "Filter: prom_increase(timestamp_range,field_0,timestamp,Int64(300000)) IS NOT NULL [timestamp:Timestamp(Millisecond, None), prom_increase(timestamp_range,field_0,timestamp,Int64(300000)):Float64;N, tag_0:Utf8]\
+            \n  Projection: some_metric.timestamp, prom_increase(timestamp_range, field_0, some_metric.timestamp, Int64(300000)) AS prom_increase(timestamp_range,field_0,timestamp,Int64(300000)), some_metric.tag_0 [timestamp:Timestamp(Millisecond, None), prom_increase(timestamp_range,field_0,timestamp,Int64(300000)):Float64;N, tag_0:Utf8]\
             \n    PromRangeManipulate: req range=[0..100000000], interval=[5000], eval range=[300000], time index=[timestamp], values=[\"field_0\"] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Dictionary(Int64, Float64);N, timestamp_range:Dictionary(Int64, Timestamp(Millisecond, None))]\
             \n      PromSeriesNormalize: offset=[0], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
             \n        PromSeriesDivide: tags=[\"tag_0\"] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
             \n          Sort: some_metric.tag_0 DESC NULLS LAST, some_metric.timestamp DESC NULLS LAST [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n            Filter: some_metric.timestamp >= TimestampMillisecond(-1000, None) AND some_metric.timestamp <= TimestampMillisecond(100000000, None) [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n              TableScan: some_metric, unsupported_filters=[timestamp >= TimestampMillisecond(-1000, None), timestamp <= TimestampMillisecond(100000000, None)] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]"
+            \n            Filter: some_metric.timestamp >= TimestampMillisecond(-300000, None) AND some_metric.timestamp <= TimestampMillisecond(100000000, None) [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n              TableScan: some_metric, unsupported_filters=[timestamp >= TimestampMillisecond(-300000, None), timestamp <= TimestampMillisecond(100000000, None)] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]"
 
         );
 
         indie_query_plan_compare(query, expected).await;
     }
 
+    #[test]
+    fn subquery_start_alignment() {
+        assert_eq!(
+            PromPlanner::align_subquery_start(-300_000, 60_000),
+            -300_000
+        );
+        assert_eq!(
+            PromPlanner::align_subquery_start(-299_000, 60_000),
+            -240_000
+        );
+        assert_eq!(PromPlanner::align_subquery_start(61_000, 60_000), 120_000);
+        assert_eq!(PromPlanner::align_subquery_start(0, 60_000), 0);
+    }
+
+    #[tokio::test]
+    async fn simple_subquery() {
+        let table_provider = build_test_table_provider("some_metric".to_string(), 1, 1).await;
+        let plan = plan_query("idelta(some_metric[5m:1m])", table_provider)
+            .await
+            .unwrap()
+            .display_indent()
+            .to_string();
+
+        // outer range manipulate runs with the query's interval
+        assert!(plan.contains(
+            "PromRangeManipulate: req range=[0..100000000], interval=[5000], eval range=[300000], time index=[timestamp], values=[\"field_0\"]"
+        ));
+        // inner expr is evaluated with subquery's step, from the aligned start
+        assert!(plan.contains(
+            "PromInstantManipulate: range=[-300000..100000000], lookback=[1000], interval=[60000], time index=[timestamp]"
+        ));
+        assert!(plan.contains("some_metric.timestamp >= TimestampMillisecond(-301000, None)"));
+    }
+
+    #[tokio::test]
+    async fn subquery_with_offset() {
+        let table_provider = build_test_table_provider("some_metric".to_string(), 1, 1).await;
+        let plan = plan_query("idelta(some_metric[5m:1m] offset 10m)", table_provider)
+            .await
+            .unwrap()
+            .display_indent()
+            .to_string();
+
+        assert!(plan.contains(
+            "PromRangeManipulate: req range=[0..100000000], interval=[5000], eval range=[300000]"
+        ));
+        // the output of inner expr is shifted forward by offset
+        assert!(plan.contains("PromSeriesNormalize: offset=[-600000], time index=[timestamp]"));
+        // inner expr is evaluated `offset` earlier
+        assert!(plan.contains(
+            "PromInstantManipulate: range=[-900000..99400000], lookback=[1000], interval=[60000]"
+        ));
+        assert!(plan.contains("some_metric.timestamp >= TimestampMillisecond(-901000, None) AND some_metric.timestamp <= TimestampMillisecond(99400000, None)"));
+    }
+
+    #[tokio::test]
+    async fn nested_subquery() {
+        let table_provider = build_test_table_provider("some_metric".to_string(), 1, 1).await;
+        let plan = plan_query("idelta(idelta(some_metric[5m:1m])[1h:10m])", table_provider)
+            .await
+            .unwrap()
+            .display_indent()
+            .to_string();
+
+        assert!(plan.contains(
+            "PromRangeManipulate: req range=[0..100000000], interval=[5000], eval range=[3600000]"
+        ));
+        assert!(plan.contains(
+            "PromRangeManipulate: req range=[-3600000..100000000], interval=[600000], eval range=[300000]"
+        ));
+        assert!(plan.contains(
+            "PromInstantManipulate: range=[-3900000..100000000], lookback=[1000], interval=[60000]"
+        ));
+    }
+
     #[tokio::test]
     async fn less_filter_on_value() {
         let query = "some_metric < 1.2345";
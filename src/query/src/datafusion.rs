@@ -36,7 +36,9 @@ use common_recordbatch::{EmptyRecordBatchStream, SendableRecordBatchStream};
 use common_telemetry::timer;
 use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
 use datafusion::physical_plan::ExecutionPlan;
-use datafusion_sql::planner::{ParserOptions, SqlToRel};
+use datafusion_sql::parser::Statement as DfStatement;
+use datafusion_sql::planner::SqlToRel;
+use datafusion_sql::sqlparser::ast::Statement as SpStatement;
 use datatypes::schema::Schema;
 use promql::planner::PromPlanner;
 use promql_parser::parser::EvalStmt;
@@ -45,6 +47,7 @@ use snafu::{OptionExt, ResultExt};
 use sql::statements::statement::Statement;
 
 pub use crate::datafusion::catalog_adapter::DfCatalogListAdapter;
+use crate::datafusion::planner::parser_options;
 pub use crate::datafusion::planner::DfContextProviderAdapter;
 use crate::error::{
     DataFusionSnafu, PlanSqlSnafu, QueryExecutionSnafu, QueryPlanSnafu, Result, SqlSnafu,
@@ -56,6 +59,7 @@ use crate::physical_optimizer::PhysicalOptimizer;
 use crate::physical_planner::PhysicalPlanner;
 use crate::plan::LogicalPlan;
 use crate::query_engine::{QueryEngineContext, QueryEngineState};
+use crate::range_select::plan_range_query;
 use crate::{metric, QueryEngine};
 
 pub struct DatafusionQueryEngine {
@@ -76,14 +80,20 @@ impl DatafusionQueryEngine {
     ) -> Result<LogicalPlan> {
         let session_state = self.state.session_state();
 
-        let df_stmt = (&stmt).try_into().context(SqlSnafu)?;
-
-        let config_options = session_state.config().config_options();
-        let parser_options = ParserOptions {
-            enable_ident_normalization: config_options.sql_parser.enable_ident_normalization,
-            parse_float_as_decimal: config_options.sql_parser.parse_float_as_decimal,
+        let range_query = match &stmt {
+            Statement::Query(query) => query.range.as_ref().map(|range| (&query.inner, range)),
+            _ => None,
+        };
+        let df_stmt = match range_query {
+            // Range clauses are planned after the rest of the query.
+            Some((query, _)) => {
+                DfStatement::Statement(Box::new(SpStatement::Query(Box::new(query.clone()))))
+            }
+            None => (&stmt).try_into().context(SqlSnafu)?,
         };
 
+        let parser_options = parser_options(&session_state);
+
         let context_provider = DfContextProviderAdapter::try_new(
             self.state.clone(),
             session_state,
@@ -91,6 +101,10 @@ impl DatafusionQueryEngine {
             query_ctx,
         )
         .await?;
+        if let Some((query, range)) = range_query {
+            let plan = plan_range_query(&context_provider, parser_options, query, range)?;
+            return Ok(LogicalPlan::DfPlan(plan));
+        }
         let sql_to_rel = SqlToRel::new_with_options(&context_provider, parser_options);
 
         let result = sql_to_rel.statement_to_plan(df_stmt).with_context(|_| {
@@ -107,6 +107,13 @@ impl CatalogProvider for CatalogProviderAdapter {
             .schema(name)
             .map(|df_schema_provider| Arc::new(SchemaProviderAdapter { df_schema_provider }) as _))
     }
+
+    fn deregister_schema(&self, _name: &str) -> catalog::error::Result<Option<SchemaProviderRef>> {
+        catalog_error::UnimplementedSnafu {
+            operation: "deregister schema in DataFusion catalog provider",
+        }
+        .fail()
+    }
 }
 
 ///Greptime CatalogProvider -> datafusion's CatalogProvider
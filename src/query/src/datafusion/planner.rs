@@ -20,6 +20,8 @@ use arrow_schema::DataType;
 use catalog::table_source::DfTableSourceProvider;
 use common_query::logical_plan::create_aggregate_function;
 use datafusion::catalog::TableReference;
+use datafusion::datasource::view::ViewTable as DfViewTable;
+use datafusion::datasource::{provider_as_source, DefaultTableSource};
 use datafusion::error::Result as DfResult;
 use datafusion::execution::context::SessionState;
 use datafusion::physical_plan::udaf::AggregateUDF;
@@ -30,10 +32,22 @@ use datafusion_common::{DataFusionError, OwnedTableReference};
 use datafusion_expr::TableSource;
 use datafusion_physical_expr::var_provider::{is_system_variables, VarType};
 use datafusion_sql::parser::Statement as DfStatement;
-use session::context::QueryContextRef;
-use snafu::ResultExt;
+use datafusion_sql::planner::{ParserOptions, SqlToRel};
+use futures::future::BoxFuture;
+use session::context::{QueryContext, QueryContextRef};
+use snafu::{ensure, ResultExt};
+use sql::dialect::GenericDialect;
+use sql::parser::ParserContext;
+use sql::statements::statement::Statement;
+use table::metadata::TableInfoRef;
+use table::table::adapter::DfTableProviderAdapter;
+use table::table::view::ViewTable;
+use table::{Table, TableRef};
 
-use crate::error::{CatalogSnafu, DataFusionSnafu, Result};
+use crate::error::{
+    CatalogSnafu, DataFusionSnafu, InvalidViewSnafu, PlanSqlSnafu, RecursiveViewSnafu, Result,
+    SqlSnafu,
+};
 use crate::query_engine::QueryEngineState;
 
 pub struct DfContextProviderAdapter {
@@ -49,6 +63,18 @@ impl DfContextProviderAdapter {
         session_state: SessionState,
         df_stmt: &DfStatement,
         query_ctx: QueryContextRef,
+    ) -> Result<Self> {
+        Self::try_new_with_views(engine_state, session_state, df_stmt, query_ctx, &[]).await
+    }
+
+    /// Creates the adapter for `df_stmt`, which is the definition of the views in
+    /// `expanding_views` (the outermost one first) if it's not empty.
+    async fn try_new_with_views(
+        engine_state: QueryEngineState,
+        session_state: SessionState,
+        df_stmt: &DfStatement,
+        query_ctx: QueryContextRef,
+        expanding_views: &[String],
     ) -> Result<Self> {
         let table_names = session_state
             .resolve_table_references(df_stmt)
@@ -60,7 +86,14 @@ impl DfContextProviderAdapter {
             query_ctx.as_ref(),
         );
 
-        let tables = resolve_tables(table_names, &mut table_provider).await?;
+        let tables = resolve_tables(
+            &engine_state,
+            &session_state,
+            table_names,
+            &mut table_provider,
+            expanding_views,
+        )
+        .await?;
 
         Ok(Self {
             engine_state,
@@ -69,31 +102,138 @@ impl DfContextProviderAdapter {
             table_provider,
         })
     }
+
+    /// Returns the tables resolved for the statement, `None` for the ones not backed by
+    /// a [Table], e.g. views.
+    pub(crate) fn tables(&self) -> impl Iterator<Item = Option<TableRef>> + '_ {
+        self.tables.values().map(source_table)
+    }
+}
+
+pub(crate) fn parser_options(session_state: &SessionState) -> ParserOptions {
+    let config_options = session_state.config().config_options();
+    ParserOptions {
+        enable_ident_normalization: config_options.sql_parser.enable_ident_normalization,
+        parse_float_as_decimal: config_options.sql_parser.parse_float_as_decimal,
+    }
 }
 
 async fn resolve_tables(
+    engine_state: &QueryEngineState,
+    session_state: &SessionState,
     table_names: Vec<OwnedTableReference>,
     table_provider: &mut DfTableSourceProvider,
+    expanding_views: &[String],
 ) -> Result<HashMap<String, Arc<dyn TableSource>>> {
     let mut tables = HashMap::with_capacity(table_names.len());
 
     for table_name in table_names {
         let resolved_name = table_provider
             .resolve_table_ref(table_name.as_table_reference())
-            .context(CatalogSnafu)?;
+            .context(CatalogSnafu)?
+            .to_string();
 
-        if let Entry::Vacant(v) = tables.entry(resolved_name.to_string()) {
+        if let Entry::Vacant(v) = tables.entry(resolved_name.clone()) {
             let table = table_provider
                 .resolve_table(table_name)
                 .await
                 .context(CatalogSnafu)?;
 
+            let table = match view_definition(&table) {
+                Some((table_info, definition)) => {
+                    plan_view(
+                        engine_state,
+                        session_state,
+                        resolved_name,
+                        table_info,
+                        definition,
+                        expanding_views,
+                    )
+                    .await?
+                }
+                None => table,
+            };
+
             v.insert(table);
         }
     }
     Ok(tables)
 }
 
+/// Returns the [Table] under the table source.
+fn source_table(source: &Arc<dyn TableSource>) -> Option<TableRef> {
+    let source = source.as_any().downcast_ref::<DefaultTableSource>()?;
+    let adapter = source
+        .table_provider
+        .as_any()
+        .downcast_ref::<DfTableProviderAdapter>()?;
+    Some(adapter.table())
+}
+
+/// Returns the table info and the definition of the view if `table` is a view.
+fn view_definition(table: &Arc<dyn TableSource>) -> Option<(TableInfoRef, String)> {
+    let table = source_table(table)?;
+    let view = table.as_any().downcast_ref::<ViewTable>()?;
+    Some((view.table_info(), view.definition().to_string()))
+}
+
+/// Plans the definition of the view, and returns a table source holding the plan, which
+/// is inlined into the plan of the query by the optimizer.
+///
+/// Tables in the definition are resolved in the catalog and schema of the view.
+fn plan_view<'a>(
+    engine_state: &'a QueryEngineState,
+    session_state: &'a SessionState,
+    view_name: String,
+    table_info: TableInfoRef,
+    definition: String,
+    expanding_views: &'a [String],
+) -> BoxFuture<'a, Result<Arc<dyn TableSource>>> {
+    Box::pin(async move {
+        ensure!(
+            !expanding_views.contains(&view_name),
+            RecursiveViewSnafu { name: &view_name }
+        );
+
+        let mut stmts = ParserContext::create_with_dialect(&definition, &GenericDialect {})
+            .context(SqlSnafu)?;
+        let stmt = match stmts.pop() {
+            Some(stmt @ Statement::Query(_)) if stmts.is_empty() => stmt,
+            _ => {
+                return InvalidViewSnafu {
+                    name: &view_name,
+                    definition: &definition,
+                }
+                .fail()
+            }
+        };
+        let df_stmt = (&stmt).try_into().context(SqlSnafu)?;
+
+        let query_ctx = Arc::new(QueryContext::with(
+            &table_info.catalog_name,
+            &table_info.schema_name,
+        ));
+        let mut views = expanding_views.to_vec();
+        views.push(view_name);
+        let context_provider = DfContextProviderAdapter::try_new_with_views(
+            engine_state.clone(),
+            session_state.clone(),
+            &df_stmt,
+            query_ctx,
+            &views,
+        )
+        .await?;
+
+        let sql_to_rel =
+            SqlToRel::new_with_options(&context_provider, parser_options(session_state));
+        let plan = sql_to_rel
+            .statement_to_plan(df_stmt)
+            .context(PlanSqlSnafu { sql: &definition })?;
+        let view = DfViewTable::try_new(plan, Some(definition)).context(DataFusionSnafu)?;
+        Ok(provider_as_source(Arc::new(view)))
+    })
+}
+
 impl ContextProvider for DfContextProviderAdapter {
     fn get_table_provider(&self, name: TableReference) -> DfResult<Arc<dyn TableSource>> {
         let table_ref = self.table_provider.resolve_table_ref(name)?;
@@ -107,6 +107,19 @@ pub enum Error {
         source: DataFusionError,
         backtrace: Backtrace,
     },
+
+    #[snafu(display("Invalid definition of view {}: {}", name, definition))]
+    InvalidView {
+        name: String,
+        definition: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("View {} is defined recursively", name))]
+    RecursiveView { name: String, backtrace: Backtrace },
+
+    #[snafu(display("Invalid range query: {}", msg))]
+    InvalidRangeQuery { msg: String, backtrace: Backtrace },
 }
 
 impl ErrorExt for Error {
@@ -120,14 +133,16 @@ impl ErrorExt for Error {
             | SchemaNotFound { .. }
             | TableNotFound { .. }
             | ParseTimestamp { .. }
-            | ParseFloat { .. } => StatusCode::InvalidArguments,
+            | ParseFloat { .. }
+            | RecursiveView { .. }
+            | InvalidRangeQuery { .. } => StatusCode::InvalidArguments,
             QueryAccessDenied { .. } => StatusCode::AccessDenied,
             Catalog { source } => source.status_code(),
             VectorComputation { source } => source.status_code(),
             CreateRecordBatch { source } => source.status_code(),
             Datatype { source } => source.status_code(),
             QueryExecution { source } | QueryPlan { source } => source.status_code(),
-            DataFusion { .. } => StatusCode::Internal,
+            DataFusion { .. } | InvalidView { .. } => StatusCode::Internal,
             Sql { source } => source.status_code(),
             PlanSql { .. } => StatusCode::PlanQuery,
         }
@@ -25,6 +25,7 @@ pub mod physical_planner;
 pub mod plan;
 pub mod planner;
 pub mod query_engine;
+pub mod range_select;
 pub mod sql;
 #[cfg(test)]
 mod tests;
@@ -110,7 +110,7 @@ impl QueryLanguageParser {
         Ok(QueryStatement::Promql(eval_stmt))
     }
 
-    fn parse_promql_timestamp(timestamp: &str) -> Result<SystemTime> {
+    pub fn parse_promql_timestamp(timestamp: &str) -> Result<SystemTime> {
         // try rfc3339 format
         let rfc3339_result = DateTime::parse_from_rfc3339(timestamp)
             .context(ParseTimestampSnafu { raw: timestamp })
@@ -174,7 +174,7 @@ mod test {
             sort_by: [], \
             having: None, \
             qualify: None \
-            }), order_by: [], limit: None, offset: None, fetch: None, locks: [] }, param_types: [] }))");
+            }), order_by: [], limit: None, offset: None, fetch: None, locks: [] }, param_types: [], range: None }))");
 
         assert_eq!(format!("{stmt:?}"), expected);
     }
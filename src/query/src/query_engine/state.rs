@@ -34,6 +34,7 @@ use promql::extension_plan::PromExtensionPlanner;
 use crate::datafusion::DfCatalogListAdapter;
 use crate::optimizer::TypeConversionRule;
 use crate::query_engine::options::QueryOptions;
+use crate::range_select::RangeSelectPlanner;
 
 /// Query engine global state
 // TODO(yingwen): This QueryEngineState still relies on datafusion, maybe we can define a trait for it,
@@ -140,9 +141,10 @@ impl QueryPlanner for DfQueryPlanner {
 impl DfQueryPlanner {
     fn new() -> Self {
         Self {
-            physical_planner: DefaultPhysicalPlanner::with_extension_planners(vec![Arc::new(
-                PromExtensionPlanner {},
-            )]),
+            physical_planner: DefaultPhysicalPlanner::with_extension_planners(vec![
+                Arc::new(PromExtensionPlanner {}),
+                Arc::new(RangeSelectPlanner {}),
+            ]),
         }
     }
 }
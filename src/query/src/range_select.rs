@@ -0,0 +1,23 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Range query of SQL, e.g. `SELECT avg(cpu) RANGE '5m' FROM t ALIGN '1m' BY (host) FILL PREV`.
+
+mod plan;
+mod plan_rewrite;
+mod planner;
+
+pub use plan::{Fill, RangeFn, RangeSelect, RangeSelectExec, RangeSelectStream};
+pub(crate) use plan_rewrite::plan_range_query;
+pub use planner::RangeSelectPlanner;
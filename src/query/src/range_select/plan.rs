@@ -0,0 +1,865 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::fmt::{self, Display};
+use std::ops::Range;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use datafusion::arrow::array::{Array, ArrayRef, Float64Array, Int64Array, UInt32Array};
+use datafusion::arrow::compute::{self, SortColumn, SortOptions};
+use datafusion::arrow::datatypes::{DataType, SchemaRef};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::common::{DFField, DFSchema, DFSchemaRef, ScalarValue};
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::context::{SessionState, TaskContext};
+use datafusion::logical_expr::{Expr, LogicalPlan, UserDefinedLogicalNode};
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::planner::create_aggregate_expr;
+use datafusion::physical_plan::{
+    AggregateExpr, DisplayFormatType, Distribution, ExecutionPlan, Partitioning, PhysicalExpr,
+    PhysicalPlanner, RecordBatchStream, SendableRecordBatchStream, Statistics,
+};
+use futures::{Stream, StreamExt};
+use promql::extension_plan::calculate_ranges;
+use promql::range_array::RangeArray;
+
+/// How to fill the aligned timestamps without data in their ranges.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fill {
+    Null,
+    Prev,
+    Linear,
+    Const(ScalarValue),
+}
+
+impl Display for Fill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fill::Null => write!(f, "NULL"),
+            Fill::Prev => write!(f, "PREV"),
+            Fill::Linear => write!(f, "LINEAR"),
+            Fill::Const(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl Fill {
+    /// Fills the gaps, i.e. `None`s in `values`. `timestamps` are the aligned timestamps
+    /// of `values`.
+    fn fill(
+        &self,
+        values: &mut [Option<ScalarValue>],
+        timestamps: &[i64],
+        data_type: &DataType,
+    ) -> DataFusionResult<()> {
+        match self {
+            Fill::Null => {
+                let null = ScalarValue::try_from(data_type)?;
+                values
+                    .iter_mut()
+                    .filter(|value| value.is_none())
+                    .for_each(|value| *value = Some(null.clone()));
+            }
+            Fill::Const(constant) => values
+                .iter_mut()
+                .filter(|value| value.is_none())
+                .for_each(|value| *value = Some(constant.clone())),
+            Fill::Prev => {
+                let mut prev = None;
+                for value in values.iter_mut() {
+                    match value {
+                        Some(_) => prev = value.clone(),
+                        None => *value = prev.clone(),
+                    }
+                }
+            }
+            Fill::Linear => {
+                // Interpolates between the original values only.
+                let points = values
+                    .iter()
+                    .map(|value| value.as_ref().map(to_f64).transpose())
+                    .collect::<DataFusionResult<Vec<_>>>()?;
+                let known = points
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, point)| point.flatten().map(|v| (timestamps[i], v)))
+                    .collect::<Vec<_>>();
+                for (i, value) in values.iter_mut().enumerate() {
+                    if value.is_some() {
+                        continue;
+                    }
+                    let ts = timestamps[i];
+                    let next = known.partition_point(|(t, _)| *t < ts);
+                    if next == 0 || next == known.len() {
+                        // No value at one side.
+                        continue;
+                    }
+                    let (t0, v0) = known[next - 1];
+                    let (t1, v1) = known[next];
+                    let v = v0 + (v1 - v0) * (ts - t0) as f64 / (t1 - t0) as f64;
+                    *value = Some(from_f64(v, data_type)?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Converts a numeric value to f64, returns `None` if it's null.
+fn to_f64(value: &ScalarValue) -> DataFusionResult<Option<f64>> {
+    let array = compute::cast(&value.to_array(), &DataType::Float64)?;
+    let array = array.as_any().downcast_ref::<Float64Array>().unwrap();
+    Ok((!array.is_null(0)).then(|| array.value(0)))
+}
+
+fn from_f64(value: f64, data_type: &DataType) -> DataFusionResult<ScalarValue> {
+    let array = Arc::new(Float64Array::from(vec![value])) as ArrayRef;
+    let array = compute::cast(&array, data_type)?;
+    ScalarValue::try_from_array(&array, 0)
+}
+
+/// An aggregate expression with its range.
+#[derive(Debug, Clone)]
+pub struct RangeFn {
+    pub expr: Expr,
+    /// Range of the aggregation, in the unit of the time index.
+    pub range: i64,
+    pub fill: Option<Fill>,
+}
+
+/// Range query of SQL.
+///
+/// For every group of rows with the same values of `by`, this plan aligns the time index
+/// to every `align` interval, then evaluates the aggregate expressions over rows in the range
+/// before each aligned timestamp. Aligned timestamps whose ranges have no data are filled
+/// according to the fill options, or removed from the output if none of the expressions
+/// could be filled.
+///
+/// The output has columns of `by`, the aligned time index, and the aggregations in order.
+#[derive(Debug)]
+pub struct RangeSelect {
+    input: LogicalPlan,
+    by: Vec<Expr>,
+    time_index: Expr,
+    range_fns: Vec<RangeFn>,
+    /// Interval of the aligned timestamps, in the unit of the time index.
+    align: i64,
+    output_schema: DFSchemaRef,
+}
+
+impl RangeSelect {
+    /// Creates the plan, whose output columns take the names from `schema`.
+    pub fn try_new(
+        input: LogicalPlan,
+        by: Vec<Expr>,
+        time_index: Expr,
+        range_fns: Vec<RangeFn>,
+        align: i64,
+        schema: &DFSchema,
+    ) -> DataFusionResult<Self> {
+        // Aggregations are null at aligned timestamps filled with null.
+        let fields = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                if i > by.len() {
+                    DFField::new(
+                        field.qualifier().map(|q| q.as_str()),
+                        field.name(),
+                        field.data_type().clone(),
+                        true,
+                    )
+                } else {
+                    field.clone()
+                }
+            })
+            .collect();
+        let output_schema = Arc::new(DFSchema::new_with_metadata(
+            fields,
+            schema.metadata().clone(),
+        )?);
+
+        Ok(Self {
+            input,
+            by,
+            time_index,
+            range_fns,
+            align,
+            output_schema,
+        })
+    }
+
+    pub fn to_execution_plan(
+        &self,
+        planner: &dyn PhysicalPlanner,
+        logical_input: &LogicalPlan,
+        exec_input: Arc<dyn ExecutionPlan>,
+        session_state: &SessionState,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        let input_dfschema = logical_input.schema();
+        let input_schema = exec_input.schema();
+        let create_physical_expr = |expr: &Expr| {
+            planner.create_physical_expr(expr, input_dfschema, &input_schema, session_state)
+        };
+
+        let by = self
+            .by
+            .iter()
+            .map(create_physical_expr)
+            .collect::<DataFusionResult<Vec<_>>>()?;
+        let time_index = create_physical_expr(&self.time_index)?;
+        let required_input_ordering = by
+            .iter()
+            .chain(Some(&time_index))
+            .map(|expr| PhysicalSortExpr {
+                expr: expr.clone(),
+                options: SortOptions::default(),
+            })
+            .collect();
+        let range_fns = self
+            .range_fns
+            .iter()
+            .map(|range_fn| {
+                Ok(RangeFnExec {
+                    expr: create_aggregate_expr(
+                        &range_fn.expr,
+                        input_dfschema,
+                        &input_schema,
+                        session_state.execution_props(),
+                    )?,
+                    range: range_fn.range,
+                    fill: range_fn.fill.clone(),
+                })
+            })
+            .collect::<DataFusionResult<Vec<_>>>()?;
+
+        Ok(Arc::new(RangeSelectExec {
+            input: exec_input,
+            by,
+            time_index,
+            required_input_ordering,
+            range_fns,
+            align: self.align,
+            output_schema: SchemaRef::new(self.output_schema.as_ref().into()),
+            metric: ExecutionPlanMetricsSet::new(),
+        }))
+    }
+}
+
+impl UserDefinedLogicalNode for RangeSelect {
+    fn as_any(&self) -> &dyn Any {
+        self as _
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![&self.input]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        &self.output_schema
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        self.by
+            .iter()
+            .chain(Some(&self.time_index))
+            .cloned()
+            .chain(self.range_fns.iter().map(|range_fn| range_fn.expr.clone()))
+            .collect()
+    }
+
+    fn fmt_for_explain(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "RangeSelect: range_exprs=[{}], align={}, time_index={}, by={:?}",
+            self.range_fns
+                .iter()
+                .map(|range_fn| format_range_fn(&range_fn.expr, range_fn.range, &range_fn.fill))
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.align,
+            self.time_index,
+            self.by
+        )
+    }
+
+    fn from_template(
+        &self,
+        exprs: &[Expr],
+        inputs: &[LogicalPlan],
+    ) -> Arc<dyn UserDefinedLogicalNode> {
+        assert!(!inputs.is_empty());
+        assert_eq!(exprs.len(), self.by.len() + 1 + self.range_fns.len());
+
+        let (by, exprs) = exprs.split_at(self.by.len());
+        Arc::new(Self {
+            input: inputs[0].clone(),
+            by: by.to_vec(),
+            time_index: exprs[0].clone(),
+            range_fns: self
+                .range_fns
+                .iter()
+                .zip(&exprs[1..])
+                .map(|(range_fn, expr)| RangeFn {
+                    expr: expr.clone(),
+                    ..range_fn.clone()
+                })
+                .collect(),
+            align: self.align,
+            output_schema: self.output_schema.clone(),
+        })
+    }
+}
+
+fn format_range_fn(expr: &impl Display, range: i64, fill: &Option<Fill>) -> String {
+    match fill {
+        Some(fill) => format!("{expr} RANGE {range} FILL {fill}"),
+        None => format!("{expr} RANGE {range}"),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RangeFnExec {
+    expr: Arc<dyn AggregateExpr>,
+    range: i64,
+    fill: Option<Fill>,
+}
+
+#[derive(Debug)]
+pub struct RangeSelectExec {
+    input: Arc<dyn ExecutionPlan>,
+    by: Vec<Arc<dyn PhysicalExpr>>,
+    time_index: Arc<dyn PhysicalExpr>,
+    /// Sorts the input by `by` and `time_index`, so groups can be computed one by one.
+    required_input_ordering: Vec<PhysicalSortExpr>,
+    range_fns: Vec<RangeFnExec>,
+    align: i64,
+    output_schema: SchemaRef,
+    metric: ExecutionPlanMetricsSet,
+}
+
+impl ExecutionPlan for RangeSelectExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.output_schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition]
+    }
+
+    fn required_input_ordering(&self) -> Vec<Option<&[PhysicalSortExpr]>> {
+        vec![Some(&self.required_input_ordering)]
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        assert!(!children.is_empty());
+        Ok(Arc::new(Self {
+            input: children[0].clone(),
+            by: self.by.clone(),
+            time_index: self.time_index.clone(),
+            required_input_ordering: self.required_input_ordering.clone(),
+            range_fns: self.range_fns.clone(),
+            align: self.align,
+            output_schema: self.output_schema.clone(),
+            metric: self.metric.clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        let baseline_metric = BaselineMetrics::new(&self.metric, partition);
+        let input = self.input.execute(partition, context)?;
+        Ok(Box::pin(RangeSelectStream {
+            by: self.by.clone(),
+            time_index: self.time_index.clone(),
+            range_fns: self.range_fns.clone(),
+            align: self.align,
+            output_schema: self.output_schema.clone(),
+            input,
+            batches: vec![],
+            finished: false,
+            metric: baseline_metric,
+        }))
+    }
+
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(
+                    f,
+                    "RangeSelectExec: range_exprs=[{}], align={}, time_index={}, by={:?}",
+                    self.range_fns
+                        .iter()
+                        .map(|range_fn| format_range_fn(
+                            &range_fn.expr.name(),
+                            range_fn.range,
+                            &range_fn.fill
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    self.align,
+                    self.time_index,
+                    self.by
+                )
+            }
+        }
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metric.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+pub struct RangeSelectStream {
+    by: Vec<Arc<dyn PhysicalExpr>>,
+    time_index: Arc<dyn PhysicalExpr>,
+    range_fns: Vec<RangeFnExec>,
+    align: i64,
+    output_schema: SchemaRef,
+
+    input: SendableRecordBatchStream,
+    /// Rows read from the input whose groups may continue in the following batches.
+    batches: Vec<RecordBatch>,
+    finished: bool,
+    metric: BaselineMetrics,
+}
+
+impl RecordBatchStream for RangeSelectStream {
+    fn schema(&self) -> SchemaRef {
+        self.output_schema.clone()
+    }
+}
+
+impl Stream for RangeSelectStream {
+    type Item = DataFusionResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+
+        let elapsed_compute = self.metric.elapsed_compute().clone();
+        loop {
+            match ready!(self.input.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => {
+                    let output = {
+                        let _timer = elapsed_compute.timer();
+                        self.push_batch(batch)
+                    };
+                    match output {
+                        Ok(Some(output)) => {
+                            return self.metric.record_poll(Poll::Ready(Some(Ok(output))))
+                        }
+                        Ok(None) => {}
+                        Err(e) => return Poll::Ready(Some(Err(e))),
+                    }
+                }
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => {
+                    self.finished = true;
+                    let batches = std::mem::take(&mut self.batches);
+                    let output = {
+                        let _timer = elapsed_compute.timer();
+                        self.output(&batches)
+                    };
+                    return self.metric.record_poll(Poll::Ready(Some(output)));
+                }
+            }
+        }
+    }
+}
+
+impl RangeSelectStream {
+    /// Buffers `batch` and computes the groups it completes. As the input is sorted by `by`,
+    /// only the last group of `batch` may continue in the following batches.
+    fn push_batch(&mut self, batch: RecordBatch) -> DataFusionResult<Option<RecordBatch>> {
+        let num_rows = batch.num_rows();
+        if self.by.is_empty() || num_rows == 0 {
+            self.batches.push(batch);
+            return Ok(None);
+        }
+
+        let by = self.evaluate_by(&batch)?;
+        let last_group_start = group_ranges(&by)?
+            .last()
+            .map(|group| group.start)
+            .unwrap_or_default();
+        if last_group_start == 0 {
+            self.batches.push(batch);
+            return Ok(None);
+        }
+
+        let mut batches = std::mem::take(&mut self.batches);
+        batches.push(batch.slice(0, last_group_start));
+        self.batches
+            .push(batch.slice(last_group_start, num_rows - last_group_start));
+        let output = self.output(&batches)?;
+        Ok((output.num_rows() > 0).then_some(output))
+    }
+
+    fn evaluate_by(&self, batch: &RecordBatch) -> DataFusionResult<Vec<ArrayRef>> {
+        self.by
+            .iter()
+            .map(|expr| Ok(expr.evaluate(batch)?.into_array(batch.num_rows())))
+            .collect()
+    }
+
+    /// Computes the output of all groups in `batches`, whose rows are sorted by `by` and
+    /// the time index.
+    fn output(&self, batches: &[RecordBatch]) -> DataFusionResult<RecordBatch> {
+        let batch = compute::concat_batches(&self.input.schema(), batches)?;
+        let num_rows = batch.num_rows();
+        if num_rows == 0 {
+            return Ok(RecordBatch::new_empty(self.output_schema.clone()));
+        }
+
+        let by = self.evaluate_by(&batch)?;
+        let ts = self.time_index.evaluate(&batch)?.into_array(num_rows);
+        let ts = compute::cast(&ts, &DataType::Int64)?;
+        let ts = ts.as_any().downcast_ref::<Int64Array>().unwrap();
+        let args = self
+            .range_fns
+            .iter()
+            .map(|range_fn| {
+                range_fn
+                    .expr
+                    .expressions()
+                    .iter()
+                    .map(|expr| Ok(expr.evaluate(&batch)?.into_array(num_rows)))
+                    .collect::<DataFusionResult<Vec<_>>>()
+            })
+            .collect::<DataFusionResult<Vec<_>>>()?;
+
+        let groups: Vec<Range<usize>> = if by.is_empty() {
+            vec![0..num_rows]
+        } else {
+            group_ranges(&by)?
+        };
+
+        let mut output = RangeSelectOutput::new(self.range_fns.len());
+        for group in groups {
+            self.compute_group(group, ts, &args, &mut output)?;
+        }
+        if output.timestamps.is_empty() {
+            return Ok(RecordBatch::new_empty(self.output_schema.clone()));
+        }
+
+        let mut columns = Vec::with_capacity(self.output_schema.fields().len());
+        let by_indices = UInt32Array::from(output.by_indices);
+        for values in &by {
+            columns.push(compute::take(values.as_ref(), &by_indices, None)?);
+        }
+        let ts_type = self.output_schema.field(by.len()).data_type();
+        let timestamps = Arc::new(Int64Array::from(output.timestamps)) as ArrayRef;
+        columns.push(compute::cast(&timestamps, ts_type)?);
+        for values in output.values {
+            columns.push(ScalarValue::iter_to_array(values)?);
+        }
+
+        RecordBatch::try_new(self.output_schema.clone(), columns)
+            .map_err(DataFusionError::ArrowError)
+    }
+
+    /// Computes the range aggregations of rows in `group`, and appends them to `output`.
+    fn compute_group(
+        &self,
+        group: Range<usize>,
+        ts: &Int64Array,
+        args: &[Vec<ArrayRef>],
+        output: &mut RangeSelectOutput,
+    ) -> DataFusionResult<()> {
+        let timestamps = &ts.values()[group.clone()];
+        let max_range = self
+            .range_fns
+            .iter()
+            .map(|range_fn| range_fn.range)
+            .max()
+            .unwrap_or_default();
+        // From the first aligned timestamp covering the first row, to the last aligned timestamp
+        // covering the last row.
+        let start = align_ceil(timestamps[0], self.align);
+        let end = align_floor(timestamps[timestamps.len() - 1] + max_range - 1, self.align);
+        if start > end {
+            return Ok(());
+        }
+
+        let mut aligned_ts = vec![];
+        let mut all_values = Vec::with_capacity(self.range_fns.len());
+        for (range_fn, args) in self.range_fns.iter().zip(args) {
+            // A range covers `(t - range, t]` for aligned timestamp `t`. Timestamps are
+            // integers so it's the same as `[t - (range - 1), t]`.
+            let (window_ts, ranges) =
+                calculate_ranges(timestamps, start, end, self.align, range_fn.range - 1);
+            let args = args
+                .iter()
+                .map(|arg| {
+                    RangeArray::from_ranges(arg.slice(group.start, group.len()), ranges.clone())
+                        .map_err(|e| DataFusionError::External(Box::new(e)))
+                })
+                .collect::<DataFusionResult<Vec<_>>>()?;
+
+            let mut values = Vec::with_capacity(ranges.len());
+            for (i, (_, length)) in ranges.iter().enumerate() {
+                if *length == 0 {
+                    values.push(None);
+                    continue;
+                }
+                let mut accumulator = range_fn.expr.create_accumulator()?;
+                let batch = args
+                    .iter()
+                    .map(|arg| arg.get(i).unwrap())
+                    .collect::<Vec<_>>();
+                accumulator.update_batch(&batch)?;
+                values.push(Some(accumulator.evaluate()?));
+            }
+            if let Some(fill) = &range_fn.fill {
+                fill.fill(&mut values, &window_ts, range_fn.expr.field()?.data_type())?;
+            }
+
+            aligned_ts = window_ts;
+            all_values.push(values);
+        }
+
+        for (i, ts) in aligned_ts.into_iter().enumerate() {
+            // Skips aligned timestamps without any value.
+            if all_values.iter().all(|values| values[i].is_none()) {
+                continue;
+            }
+            output.by_indices.push(group.start as u32);
+            output.timestamps.push(ts);
+            for (range_fn, (values, column)) in self
+                .range_fns
+                .iter()
+                .zip(all_values.iter_mut().zip(&mut output.values))
+            {
+                let value = match values[i].take() {
+                    Some(value) => value,
+                    None => ScalarValue::try_from(range_fn.expr.field()?.data_type())?,
+                };
+                column.push(value);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct RangeSelectOutput {
+    /// Index of a row of the group in the sorted input, to take values of `by`.
+    by_indices: Vec<u32>,
+    timestamps: Vec<i64>,
+    values: Vec<Vec<ScalarValue>>,
+}
+
+impl RangeSelectOutput {
+    fn new(num_range_fns: usize) -> Self {
+        Self {
+            by_indices: vec![],
+            timestamps: vec![],
+            values: vec![vec![]; num_range_fns],
+        }
+    }
+}
+
+/// Returns ranges of continuous rows with the same values of `by`.
+fn group_ranges(by: &[ArrayRef]) -> DataFusionResult<Vec<Range<usize>>> {
+    let by_columns = by
+        .iter()
+        .map(|values| SortColumn {
+            values: values.clone(),
+            options: None,
+        })
+        .collect::<Vec<_>>();
+    Ok(compute::lexicographical_partition_ranges(&by_columns)?.collect())
+}
+
+fn align_floor(ts: i64, align: i64) -> i64 {
+    ts.div_euclid(align) * align
+}
+
+fn align_ceil(ts: i64, align: i64) -> i64 {
+    align_floor(ts + align - 1, align)
+}
+
+#[cfg(test)]
+mod test {
+    use datafusion::arrow::array::StringArray;
+    use datafusion::arrow::datatypes::{Field, Schema};
+    use datafusion::physical_plan::expressions::{col, Count};
+    use datafusion::physical_plan::memory::MemoryExec;
+    use datafusion::prelude::SessionContext;
+
+    use super::*;
+
+    fn fill(fill: Fill, values: Vec<Option<f64>>) -> Vec<Option<f64>> {
+        let mut values = values
+            .into_iter()
+            .map(|value| value.map(|v| ScalarValue::Float64(Some(v))))
+            .collect::<Vec<_>>();
+        let timestamps = (0..values.len() as i64).map(|i| i * 10).collect::<Vec<_>>();
+        fill.fill(&mut values, &timestamps, &DataType::Float64)
+            .unwrap();
+        values
+            .into_iter()
+            .map(|value| match value {
+                Some(ScalarValue::Float64(v)) => Some(v.unwrap_or(f64::NAN)),
+                Some(other) => unreachable!("{other:?}"),
+                None => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fill() {
+        let values = vec![None, Some(1.0), None, None, Some(4.0), None];
+
+        let filled = fill(Fill::Null, values.clone());
+        assert!(filled.iter().all(Option::is_some));
+        assert!(filled[0].unwrap().is_nan());
+        assert_eq!(Some(1.0), filled[1]);
+
+        assert_eq!(
+            vec![None, Some(1.0), Some(1.0), Some(1.0), Some(4.0), Some(4.0)],
+            fill(Fill::Prev, values.clone())
+        );
+        assert_eq!(
+            vec![None, Some(1.0), Some(2.0), Some(3.0), Some(4.0), None],
+            fill(Fill::Linear, values.clone())
+        );
+        assert_eq!(
+            vec![
+                Some(0.5),
+                Some(1.0),
+                Some(0.5),
+                Some(0.5),
+                Some(4.0),
+                Some(0.5)
+            ],
+            fill(Fill::Const(ScalarValue::Float64(Some(0.5))), values)
+        );
+    }
+
+    #[test]
+    fn test_align() {
+        assert_eq!(0, align_floor(59, 60));
+        assert_eq!(60, align_ceil(59, 60));
+        assert_eq!(60, align_ceil(60, 60));
+        assert_eq!(-60, align_floor(-1, 60));
+        assert_eq!(0, align_ceil(-1, 60));
+    }
+
+    #[tokio::test]
+    async fn test_range_select_across_batches() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("host", DataType::Utf8, false),
+            Field::new("ts", DataType::Int64, false),
+            Field::new("value", DataType::Float64, true),
+        ]));
+        let batch = |hosts: Vec<&str>, ts: Vec<i64>| {
+            let values = ts.iter().map(|ts| *ts as f64).collect::<Vec<_>>();
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(StringArray::from(hosts)),
+                    Arc::new(Int64Array::from(ts)),
+                    Arc::new(Float64Array::from(values)),
+                ],
+            )
+            .unwrap()
+        };
+        // Rows of host "a" are split into two batches.
+        let batches = vec![
+            batch(vec!["a", "a"], vec![0, 10]),
+            batch(vec!["a", "b"], vec![20, 0]),
+            batch(vec!["b"], vec![10]),
+        ];
+        let input = Arc::new(MemoryExec::try_new(&[batches], schema.clone(), None).unwrap());
+        let output_schema = Arc::new(Schema::new(vec![
+            Field::new("host", DataType::Utf8, false),
+            Field::new("ts", DataType::Int64, false),
+            Field::new("COUNT(value)", DataType::Int64, true),
+        ]));
+        let exec = Arc::new(RangeSelectExec {
+            input,
+            by: vec![col("host", &schema).unwrap()],
+            time_index: col("ts", &schema).unwrap(),
+            required_input_ordering: vec![],
+            range_fns: vec![RangeFnExec {
+                expr: Arc::new(Count::new(
+                    col("value", &schema).unwrap(),
+                    "COUNT(value)",
+                    DataType::Int64,
+                )),
+                range: 20,
+                fill: None,
+            }],
+            align: 10,
+            output_schema,
+            metric: ExecutionPlanMetricsSet::new(),
+        });
+
+        let session_context = SessionContext::default();
+        let output = datafusion::physical_plan::collect(exec, session_context.task_ctx())
+            .await
+            .unwrap();
+        // Each group is computed once all its rows are read.
+        assert_eq!(
+            vec![4, 3],
+            output.iter().map(|b| b.num_rows()).collect::<Vec<_>>()
+        );
+        let counts = output
+            .iter()
+            .flat_map(|b| {
+                b.column(2)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(vec![1, 2, 2, 1, 1, 2, 1], counts);
+    }
+}
@@ -0,0 +1,325 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use datafusion::arrow::array::{Array, ArrayRef, StringArray};
+use datafusion::arrow::compute;
+use datafusion::arrow::datatypes::{DataType, TimeUnit};
+use datafusion_common::ScalarValue;
+use datafusion_expr::utils::{expr_to_columns, from_plan};
+use datafusion_expr::{Aggregate, Extension, LogicalPlan};
+use datafusion_sql::parser::Statement as DfStatement;
+use datafusion_sql::planner::{ParserOptions, SqlToRel};
+use datafusion_sql::sqlparser::ast::Statement as SpStatement;
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::ast::{Expr as SqlExpr, Ident, Query, SetExpr, Value};
+use sql::statements::query::{Fill as SqlFill, RangeQuery};
+
+use crate::datafusion::DfContextProviderAdapter;
+use crate::error::{DataFusionSnafu, InvalidRangeQuerySnafu, PlanSqlSnafu, Result};
+use crate::range_select::plan::{Fill, RangeFn, RangeSelect};
+
+/// Plans the range query, whose clauses other than range clauses are in `query`.
+///
+/// The query is planned as an aggregation grouped by the `BY` expressions and the time
+/// index, then the aggregation is replaced by a [RangeSelect].
+pub(crate) fn plan_range_query(
+    context_provider: &DfContextProviderAdapter,
+    parser_options: ParserOptions,
+    query: &Query,
+    range: &RangeQuery,
+) -> Result<LogicalPlan> {
+    let mut tables = context_provider.tables();
+    let table = match (tables.next(), tables.next()) {
+        (Some(table), None) => table,
+        _ => None,
+    }
+    .context(InvalidRangeQuerySnafu {
+        msg: "range query must select from exactly one table",
+    })?;
+    let time_index = table
+        .schema()
+        .timestamp_column()
+        .map(|column| column.name.clone())
+        .context(InvalidRangeQuerySnafu {
+            msg: format!("table {} has no time index", table.table_info().name),
+        })?;
+
+    // Groups by the primary key by default.
+    let mut group_by = match &range.by {
+        Some(by) => by.clone(),
+        None => table
+            .table_info()
+            .meta
+            .row_key_column_names()
+            .map(|name| SqlExpr::Identifier(Ident::with_quote('"', name)))
+            .collect(),
+    };
+    let num_by = group_by.len();
+    group_by.push(SqlExpr::Identifier(Ident::with_quote('"', time_index)));
+
+    let mut query = query.clone();
+    let SetExpr::Select(select) = query.body.as_mut() else {
+        return InvalidRangeQuerySnafu {
+            msg: "range query must be a SELECT statement",
+        }
+        .fail();
+    };
+    ensure!(
+        select.group_by.is_empty(),
+        InvalidRangeQuerySnafu {
+            msg: "GROUP BY is not allowed in range query, use BY in ALIGN clause instead",
+        }
+    );
+    select.group_by = group_by;
+    let sql = query.to_string();
+    let df_stmt = DfStatement::Statement(Box::new(SpStatement::Query(Box::new(query))));
+
+    let sql_to_rel = SqlToRel::new_with_options(context_provider, parser_options);
+    let plan = sql_to_rel
+        .statement_to_plan(df_stmt)
+        .context(PlanSqlSnafu { sql })?;
+
+    rewrite_plan(&plan, range, num_by)?.context(InvalidRangeQuerySnafu {
+        msg: "range query must contain aggregations with RANGE",
+    })
+}
+
+/// Replaces the aggregation under the outermost projection by a [RangeSelect], returns `None`
+/// if there is no such aggregation.
+fn rewrite_plan(
+    plan: &LogicalPlan,
+    range: &RangeQuery,
+    num_by: usize,
+) -> Result<Option<LogicalPlan>> {
+    if let LogicalPlan::Projection(projection) = plan {
+        let input = match projection.input.as_ref() {
+            LogicalPlan::Filter(filter) => match filter.input.as_ref() {
+                LogicalPlan::Aggregate(aggregate) => {
+                    Some((Some(projection.input.as_ref()), aggregate))
+                }
+                _ => None,
+            },
+            LogicalPlan::Aggregate(aggregate) => Some((None, aggregate)),
+            _ => None,
+        };
+
+        if let Some((filter, aggregate)) = input {
+            let range_select = plan_range_select(&projection.expr, aggregate, range, num_by)?;
+            let mut input = LogicalPlan::Extension(Extension {
+                node: Arc::new(range_select),
+            });
+            // Filter of the HAVING clause.
+            if let Some(filter) = filter {
+                input =
+                    from_plan(filter, &filter.expressions(), &[input]).context(DataFusionSnafu)?;
+            }
+            return from_plan(plan, &plan.expressions(), &[input])
+                .context(DataFusionSnafu)
+                .map(Some);
+        }
+    }
+
+    let inputs = plan.inputs();
+    let mut new_inputs = Vec::with_capacity(inputs.len());
+    let mut rewritten = false;
+    for input in inputs {
+        match rewrite_plan(input, range, num_by)? {
+            Some(new_input) => {
+                rewritten = true;
+                new_inputs.push(new_input);
+            }
+            None => new_inputs.push(input.clone()),
+        }
+    }
+    if !rewritten {
+        return Ok(None);
+    }
+    from_plan(plan, &plan.expressions(), &new_inputs)
+        .context(DataFusionSnafu)
+        .map(Some)
+}
+
+/// Creates the [RangeSelect] of `aggregate`, whose group expressions are `BY` expressions
+/// followed by the time index.
+///
+/// Each item of the projection applies its range to the aggregations it refers to.
+fn plan_range_select(
+    projection: &[datafusion_expr::Expr],
+    aggregate: &Aggregate,
+    range: &RangeQuery,
+    num_by: usize,
+) -> Result<RangeSelect> {
+    ensure!(
+        projection.len() == range.items.len(),
+        InvalidRangeQuerySnafu {
+            msg: "wildcard is not allowed in range query",
+        }
+    );
+
+    let num_groups = aggregate.group_expr.len();
+    let ts_type = aggregate.schema.field(num_groups - 1).data_type();
+    let unit = match ts_type {
+        DataType::Timestamp(unit, _) => unit,
+        _ => {
+            return InvalidRangeQuerySnafu {
+                msg: format!("time index must be a timestamp, found: {ts_type}"),
+            }
+            .fail()
+        }
+    };
+
+    let mut ranges = vec![None; aggregate.aggr_expr.len()];
+    for (expr, item) in projection.iter().zip(&range.items) {
+        let mut columns = HashSet::new();
+        expr_to_columns(expr, &mut columns).context(DataFusionSnafu)?;
+        let aggr_indices = columns
+            .iter()
+            .map(|column| aggregate.schema.index_of_column(column))
+            .collect::<datafusion_common::Result<Vec<_>>>()
+            .context(DataFusionSnafu)?
+            .into_iter()
+            .filter_map(|index| index.checked_sub(num_groups))
+            .collect::<Vec<_>>();
+
+        let Some(item) = item else {
+            ensure!(
+                aggr_indices.is_empty(),
+                InvalidRangeQuerySnafu {
+                    msg: format!("missing RANGE of aggregations in {expr}"),
+                }
+            );
+            continue;
+        };
+        ensure!(
+            !aggr_indices.is_empty(),
+            InvalidRangeQuerySnafu {
+                msg: format!("{expr} with RANGE must contain aggregations"),
+            }
+        );
+
+        let range_duration = parse_duration(&item.range, unit)?;
+        for index in aggr_indices {
+            let output_type = aggregate.schema.field(num_groups + index).data_type();
+            let fill = item
+                .fill
+                .as_ref()
+                .or(range.fill.as_ref())
+                .map(|fill| convert_fill(fill, output_type))
+                .transpose()?;
+            let new_range = Some((range_duration, fill));
+            ensure!(
+                ranges[index].is_none() || ranges[index] == new_range,
+                InvalidRangeQuerySnafu {
+                    msg: format!("{} has different RANGE or FILL", aggregate.aggr_expr[index]),
+                }
+            );
+            ranges[index] = new_range;
+        }
+    }
+
+    let range_fns = aggregate
+        .aggr_expr
+        .iter()
+        .zip(ranges)
+        .map(|(expr, range)| {
+            let (range, fill) = range.context(InvalidRangeQuerySnafu {
+                msg: format!("missing RANGE of {expr}"),
+            })?;
+            Ok(RangeFn {
+                expr: expr.clone(),
+                range,
+                fill,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let align = parse_duration(&range.align, unit)?;
+    let by = aggregate.group_expr[..num_by].to_vec();
+    let time_index = aggregate.group_expr[num_by].clone();
+    RangeSelect::try_new(
+        aggregate.input.as_ref().clone(),
+        by,
+        time_index,
+        range_fns,
+        align,
+        &aggregate.schema,
+    )
+    .context(DataFusionSnafu)
+}
+
+/// Parses the duration in `RANGE` or `ALIGN`, in `unit` of the time index.
+fn parse_duration(duration: &str, unit: &TimeUnit) -> Result<i64> {
+    let parsed =
+        promql_parser::util::parse_duration(duration)
+            .ok()
+            .context(InvalidRangeQuerySnafu {
+                msg: format!("invalid duration '{duration}'"),
+            })?;
+    let unit_nanos = match unit {
+        TimeUnit::Second => Duration::from_secs(1),
+        TimeUnit::Millisecond => Duration::from_millis(1),
+        TimeUnit::Microsecond => Duration::from_micros(1),
+        TimeUnit::Nanosecond => Duration::from_nanos(1),
+    }
+    .as_nanos();
+    let nanos = parsed.as_nanos();
+    ensure!(
+        nanos > 0 && nanos % unit_nanos == 0,
+        InvalidRangeQuerySnafu {
+            msg: format!("duration '{duration}' must be a positive multiple of {unit:?}"),
+        }
+    );
+    i64::try_from(nanos / unit_nanos)
+        .ok()
+        .context(InvalidRangeQuerySnafu {
+            msg: format!("duration '{duration}' is too large"),
+        })
+}
+
+fn convert_fill(fill: &SqlFill, output_type: &DataType) -> Result<Fill> {
+    let fill = match fill {
+        SqlFill::Null => Fill::Null,
+        SqlFill::Prev => Fill::Prev,
+        SqlFill::Linear => {
+            ensure!(
+                output_type.is_numeric(),
+                InvalidRangeQuerySnafu {
+                    msg: format!("FILL LINEAR is not supported for {output_type}"),
+                }
+            );
+            Fill::Linear
+        }
+        SqlFill::Const(value) => {
+            let text = match value {
+                Value::Number(n, _) => n,
+                Value::SingleQuotedString(s) => s,
+                _ => unreachable!("the parser only accepts numbers and strings in FILL"),
+            };
+            let array = Arc::new(StringArray::from(vec![text.as_str()])) as ArrayRef;
+            let value = compute::cast(&array, output_type)
+                .ok()
+                .filter(|array| !array.is_null(0))
+                .and_then(|array| ScalarValue::try_from_array(&array, 0).ok())
+                .context(InvalidRangeQuerySnafu {
+                    msg: format!("invalid FILL {text} for {output_type}"),
+                })?;
+            Fill::Const(value)
+        }
+    };
+    Ok(fill)
+}
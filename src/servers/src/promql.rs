@@ -12,13 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use async_trait::async_trait;
 use axum::body::BoxBody;
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::{routing, Form, Json, Router};
 use common_error::prelude::ErrorExt;
 use common_error::status_code::StatusCode;
@@ -29,14 +30,16 @@ use datatypes::prelude::ConcreteDataType;
 use datatypes::scalars::ScalarVector;
 use datatypes::vectors::{Float64Vector, StringVector, TimestampMillisecondVector};
 use futures::FutureExt;
-use promql_parser::label::METRIC_NAME;
+use promql_parser::label::{MatchOp, Matcher, METRIC_NAME};
 use promql_parser::parser::{
     AggregateExpr, BinaryExpr, Call, Expr as PromqlExpr, MatrixSelector, ParenExpr, SubqueryExpr,
     UnaryExpr, VectorSelector,
 };
-use query::parser::PromQuery;
+use query::parser::{PromQuery, QueryLanguageParser};
+use regex::Regex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use session::context::{QueryContext, QueryContextRef};
 use snafu::{ensure, OptionExt, ResultExt};
 use tokio::sync::oneshot::Sender;
 use tokio::sync::{oneshot, Mutex};
@@ -47,9 +50,11 @@ use tower_http::trace::TraceLayer;
 
 use crate::auth::UserProviderRef;
 use crate::error::{
-    AlreadyStartedSnafu, CollectRecordbatchSnafu, InternalSnafu, Result, StartHttpSnafu,
+    AlreadyStartedSnafu, CollectRecordbatchSnafu, InternalSnafu, InvalidQuerySnafu, Result,
+    StartHttpSnafu,
 };
 use crate::http::authorize::HttpAuth;
+use crate::parse_catalog_and_schema_from_client_database_name;
 use crate::server::Server;
 
 pub const PROMQL_API_VERSION: &str = "v1";
@@ -58,7 +63,18 @@ pub type PromqlHandlerRef = Arc<dyn PromqlHandler + Send + Sync>;
 
 #[async_trait]
 pub trait PromqlHandler {
-    async fn do_query(&self, query: &PromQuery) -> Result<Output>;
+    async fn do_query(&self, query: &PromQuery, query_ctx: QueryContextRef) -> Result<Output>;
+
+    /// Returns the names of all metrics (tables) in the database of `query_ctx`.
+    async fn metric_names(&self, query_ctx: QueryContextRef) -> Result<Vec<String>>;
+
+    /// Returns the label (tag column) names of the given metric, or `None` if the
+    /// metric doesn't exist.
+    async fn label_names(
+        &self,
+        metric: &str,
+        query_ctx: QueryContextRef,
+    ) -> Result<Option<Vec<String>>>;
 }
 
 pub struct PromqlServer {
@@ -82,11 +98,17 @@ impl PromqlServer {
     }
 
     pub fn make_app(&self) -> Router {
-        // TODO(ruihang): implement format_query, series, labels, values, query_examplars and targets methods
+        // TODO(ruihang): implement format_query, query_examplars and targets methods
 
         let router = Router::new()
             .route("/query", routing::post(instant_query).get(instant_query))
             .route("/query_range", routing::post(range_query).get(range_query))
+            .route("/labels", routing::post(labels_query).get(labels_query))
+            .route(
+                "/label/:label_name/values",
+                routing::get(label_values_query),
+            )
+            .route("/series", routing::post(series_query).get(series_query))
             .with_state(self.query_handler.clone());
 
         Router::new()
@@ -337,6 +359,57 @@ impl PromqlJsonResponse {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum PromqlMetadata {
+    Labels(Vec<String>),
+    Series(Vec<BTreeMap<String, String>>),
+}
+
+/// Response of the metadata apis (`/labels`, `/label/<name>/values` and `/series`).
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PromqlMetadataResponse {
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<PromqlMetadata>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "errorType")]
+    error_type: Option<String>,
+}
+
+impl PromqlMetadataResponse {
+    pub fn success(data: PromqlMetadata) -> Json<Self> {
+        Json(PromqlMetadataResponse {
+            status: "success".to_string(),
+            data: Some(data),
+            error: None,
+            error_type: None,
+        })
+    }
+
+    pub fn error<S1, S2>(error_type: S1, reason: S2) -> Json<Self>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        Json(PromqlMetadataResponse {
+            status: "error".to_string(),
+            data: None,
+            error: Some(reason.into()),
+            error_type: Some(error_type.into()),
+        })
+    }
+
+    fn from_result(result: Result<PromqlMetadata>) -> Json<Self> {
+        match result {
+            Ok(data) => Self::success(data),
+            Err(err) => Self::error(err.status_code().to_string(), err.to_string()),
+        }
+    }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
 pub struct InstantQuery {
     query: String,
@@ -362,6 +435,7 @@ pub struct RangeQuery {
     end: Option<String>,
     step: Option<String>,
     timeout: Option<String>,
+    db: Option<String>,
 }
 
 #[axum_macros::debug_handler]
@@ -376,11 +450,449 @@ pub async fn range_query(
         end: params.end.or(form_params.end).unwrap_or_default(),
         step: params.step.or(form_params.step).unwrap_or_default(),
     };
-    let result = handler.do_query(&prom_query).await;
+    let query_ctx = query_context(params.db.or(form_params.db).as_deref());
+    let result = handler.do_query(&prom_query, query_ctx).await;
     let metric_name = retrieve_metric_name(&prom_query.query).unwrap_or_default();
     PromqlJsonResponse::from_query_result(result, metric_name).await
 }
 
+/// Creates the query context of the database `db`, or the default one if `db` is absent.
+fn query_context(db: Option<&str>) -> QueryContextRef {
+    match db {
+        Some(db) => {
+            let (catalog, schema) = parse_catalog_and_schema_from_client_database_name(db);
+            Arc::new(QueryContext::with(catalog, schema))
+        }
+        None => QueryContext::arc(),
+    }
+}
+
+/// Common parameters of the metadata apis. `match[]` can be repeated, so the
+/// parameters are collected from raw key-value pairs.
+#[derive(Debug, Default)]
+struct MetadataParams {
+    matches: Vec<String>,
+    start: Option<String>,
+    end: Option<String>,
+    db: Option<String>,
+}
+
+impl MetadataParams {
+    fn from_pairs(pairs: impl IntoIterator<Item = (String, String)>) -> Self {
+        let mut params = MetadataParams::default();
+        for (key, value) in pairs {
+            match key.as_str() {
+                "match[]" => {
+                    if !params.matches.contains(&value) {
+                        params.matches.push(value);
+                    }
+                }
+                "start" => {
+                    params.start.get_or_insert(value);
+                }
+                "end" => {
+                    params.end.get_or_insert(value);
+                }
+                "db" => {
+                    params.db.get_or_insert(value);
+                }
+                _ => {}
+            }
+        }
+        params
+    }
+
+    fn query_context(&self) -> QueryContextRef {
+        query_context(self.db.as_deref())
+    }
+}
+
+/// Handler of `/labels`. Label names are collected from the schema of matched
+/// metrics (or all metrics if there is no `match[]`), so time bounds are ignored.
+#[axum_macros::debug_handler]
+pub async fn labels_query(
+    State(handler): State<PromqlHandlerRef>,
+    Query(params): Query<Vec<(String, String)>>,
+    Form(form_params): Form<Vec<(String, String)>>,
+) -> Json<PromqlMetadataResponse> {
+    let params = MetadataParams::from_pairs(params.into_iter().chain(form_params));
+    let query_ctx = params.query_context();
+    let result: Result<PromqlMetadata> = try {
+        let mut metrics = BTreeSet::new();
+        if params.matches.is_empty() {
+            metrics.extend(handler.metric_names(query_ctx.clone()).await?);
+        } else {
+            for m in &params.matches {
+                let selector = SeriesSelector::parse(m)?;
+                metrics.extend(selector.metrics(&handler, query_ctx.clone()).await?);
+            }
+        }
+
+        let mut labels = BTreeSet::new();
+        let _ = labels.insert(METRIC_NAME.to_string());
+        for metric in metrics {
+            if let Some(names) = handler.label_names(&metric, query_ctx.clone()).await? {
+                labels.extend(names);
+            }
+        }
+        PromqlMetadata::Labels(labels.into_iter().collect())
+    };
+    PromqlMetadataResponse::from_result(result)
+}
+
+/// Handler of `/label/<label_name>/values`.
+#[axum_macros::debug_handler]
+pub async fn label_values_query(
+    State(handler): State<PromqlHandlerRef>,
+    Path(label_name): Path<String>,
+    Query(params): Query<Vec<(String, String)>>,
+) -> Json<PromqlMetadataResponse> {
+    let params = MetadataParams::from_pairs(params);
+    let query_ctx = params.query_context();
+    let result: Result<PromqlMetadata> = try {
+        let mut values = BTreeSet::new();
+        if label_name == METRIC_NAME {
+            if params.matches.is_empty() {
+                values.extend(handler.metric_names(query_ctx).await?);
+            } else {
+                for m in &params.matches {
+                    let selector = SeriesSelector::parse(m)?;
+                    values.extend(selector.metrics(&handler, query_ctx.clone()).await?);
+                }
+            }
+        } else {
+            let matches = if params.matches.is_empty() {
+                // find all metrics that have this label
+                let mut matches = Vec::new();
+                for metric in handler.metric_names(query_ctx.clone()).await? {
+                    if let Some(labels) = handler.label_names(&metric, query_ctx.clone()).await? {
+                        if labels.contains(&label_name) {
+                            matches.push(format!("{{{METRIC_NAME}=\"{metric}\"}}"));
+                        }
+                    }
+                }
+                matches
+            } else {
+                params.matches.clone()
+            };
+            let series = query_series(&handler, &matches, &params).await?;
+            values.extend(
+                series
+                    .into_iter()
+                    .filter_map(|mut labels| labels.remove(&label_name)),
+            );
+        }
+        PromqlMetadata::Labels(values.into_iter().collect())
+    };
+    PromqlMetadataResponse::from_result(result)
+}
+
+/// Handler of `/series`.
+#[axum_macros::debug_handler]
+pub async fn series_query(
+    State(handler): State<PromqlHandlerRef>,
+    Query(params): Query<Vec<(String, String)>>,
+    Form(form_params): Form<Vec<(String, String)>>,
+) -> Json<PromqlMetadataResponse> {
+    let params = MetadataParams::from_pairs(params.into_iter().chain(form_params));
+    let result: Result<PromqlMetadata> = if params.matches.is_empty() {
+        InvalidQuerySnafu {
+            reason: "no match[] parameter provided",
+        }
+        .fail()
+    } else {
+        query_series(&handler, &params.matches, &params)
+            .await
+            .map(PromqlMetadata::Series)
+    };
+    PromqlMetadataResponse::from_result(result)
+}
+
+/// Default time range of the metadata apis if `start` is absent, to avoid scanning the
+/// whole history.
+const DEFAULT_METADATA_RANGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Finds all series selected by `matches` within the time bounds in `params`.
+///
+/// Each selected metric is queried by a `count_over_time` query evaluated at `end` with
+/// a range covering `[start, end]`, so every series that has at least one sample in the
+/// range produces exactly one row. `end` defaults to now, and `start` defaults to
+/// [DEFAULT_METADATA_RANGE] before `end`.
+async fn query_series(
+    handler: &PromqlHandlerRef,
+    matches: &[String],
+    params: &MetadataParams,
+) -> Result<Vec<BTreeMap<String, String>>> {
+    let end_time = match &params.end {
+        Some(end) => parse_timestamp(end)?,
+        None => SystemTime::now(),
+    };
+    let start_time = match &params.start {
+        Some(start) => parse_timestamp(start)?,
+        None => end_time
+            .checked_sub(DEFAULT_METADATA_RANGE)
+            .unwrap_or(UNIX_EPOCH),
+    };
+    let to_secs = |time: SystemTime| {
+        time.duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64()
+    };
+    let end_secs = to_secs(end_time);
+    // The range is left-open, so it's extended by 1ms to include samples at `start`.
+    let range = end_time
+        .duration_since(start_time)
+        .map_err(|_| {
+            InvalidQuerySnafu {
+                reason: format!(
+                    "end timestamp {end_secs} is before start timestamp {}",
+                    to_secs(start_time)
+                ),
+            }
+            .build()
+        })?
+        .as_millis()
+        + 1;
+
+    let query_ctx = params.query_context();
+    let mut series = BTreeSet::new();
+    for m in matches {
+        let selector = SeriesSelector::parse(m)?;
+        for metric in selector.metrics(handler, query_ctx.clone()).await? {
+            let query = PromQuery {
+                query: format!(
+                    "count_over_time({}[{range}ms])",
+                    selector.selector_of(&metric)
+                ),
+                start: end_secs.to_string(),
+                end: end_secs.to_string(),
+                step: "1s".to_string(),
+            };
+            let record_batches = match handler.do_query(&query, query_ctx.clone()).await {
+                Ok(Output::RecordBatches(batches)) => batches,
+                Ok(Output::Stream(stream)) => RecordBatches::try_collect(stream)
+                    .await
+                    .context(CollectRecordbatchSnafu)?,
+                Ok(Output::AffectedRows(_)) => {
+                    return InternalSnafu {
+                        err_msg: "expected data result, but got affected rows",
+                    }
+                    .fail();
+                }
+                // Prometheus won't report error if querying nonexist metric or label
+                Err(err)
+                    if err.status_code() == StatusCode::TableNotFound
+                        || err.status_code() == StatusCode::TableColumnNotFound =>
+                {
+                    continue
+                }
+                // Tables that aren't metrics (e.g. without time index) can't be queried,
+                // skips them if they are not selected by name.
+                Err(err)
+                    if err.status_code() == StatusCode::InvalidArguments
+                        && !selector.selects_by_name() =>
+                {
+                    continue
+                }
+                Err(err) => return Err(err),
+            };
+            series.extend(record_batches_to_labels(record_batches, &metric)?);
+        }
+    }
+
+    Ok(series.into_iter().collect())
+}
+
+/// A series selector of `match[]`.
+struct SeriesSelector {
+    /// Matchers of the metric name.
+    name_matchers: Vec<Matcher>,
+    /// Matchers of other labels.
+    label_matchers: Vec<Matcher>,
+}
+
+impl SeriesSelector {
+    fn parse(selector: &str) -> Result<Self> {
+        let expr = promql_parser::parser::parse(selector).map_err(|reason| {
+            InvalidQuerySnafu {
+                reason: format!("invalid series selector {selector}: {reason}"),
+            }
+            .build()
+        })?;
+        let PromqlExpr::VectorSelector(VectorSelector { matchers, .. }) = expr else {
+            return InvalidQuerySnafu {
+                reason: format!("{selector} is not a series selector"),
+            }
+            .fail();
+        };
+        let (name_matchers, label_matchers) = matchers
+            .matchers
+            .into_iter()
+            .partition(|matcher| matcher.name == METRIC_NAME);
+        Ok(Self {
+            name_matchers,
+            label_matchers,
+        })
+    }
+
+    /// Returns the existing metrics selected by the metric name matchers. All metrics are
+    /// selected if there is no such matcher.
+    async fn metrics(
+        &self,
+        handler: &PromqlHandlerRef,
+        query_ctx: QueryContextRef,
+    ) -> Result<Vec<String>> {
+        let name_matchers = self
+            .name_matchers
+            .iter()
+            .map(NameMatcher::try_new)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(handler
+            .metric_names(query_ctx)
+            .await?
+            .into_iter()
+            .filter(|metric| name_matchers.iter().all(|matcher| matcher.matches(metric)))
+            .collect())
+    }
+
+    /// Returns whether the metric name is given by an equal matcher.
+    fn selects_by_name(&self) -> bool {
+        self.name_matchers
+            .iter()
+            .any(|matcher| matches!(matcher.op, MatchOp::Equal))
+    }
+
+    /// Returns the selector of `metric` with the label matchers.
+    fn selector_of(&self, metric: &str) -> String {
+        let mut matchers = vec![format!("{METRIC_NAME}={}", quote_label_value(metric))];
+        for matcher in &self.label_matchers {
+            let op = match matcher.op {
+                MatchOp::Equal => "=",
+                MatchOp::NotEqual => "!=",
+                MatchOp::Re(_) => "=~",
+                MatchOp::NotRe(_) => "!~",
+            };
+            matchers.push(format!(
+                "{}{op}{}",
+                matcher.name,
+                quote_label_value(&matcher.value)
+            ));
+        }
+        format!("{{{}}}", matchers.join(", "))
+    }
+}
+
+/// Quotes a label value like PromQL, which only escapes backslashes, double quotes and
+/// newlines. Other characters are kept as is, unlike `{:?}` whose escapes like `\u{..}`
+/// can't be parsed by PromQL.
+fn quote_label_value(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Matcher of the metric name. Regex matchers are fully anchored like Prometheus.
+enum NameMatcher<'a> {
+    Equal(&'a str),
+    NotEqual(&'a str),
+    Re(Regex),
+    NotRe(Regex),
+}
+
+impl<'a> NameMatcher<'a> {
+    fn try_new(matcher: &'a Matcher) -> Result<Self> {
+        let anchored = || {
+            Regex::new(&format!("^(?:{})$", matcher.value)).map_err(|e| {
+                InvalidQuerySnafu {
+                    reason: format!("invalid regex {}: {e}", matcher.value),
+                }
+                .build()
+            })
+        };
+        Ok(match matcher.op {
+            MatchOp::Equal => NameMatcher::Equal(&matcher.value),
+            MatchOp::NotEqual => NameMatcher::NotEqual(&matcher.value),
+            MatchOp::Re(_) => NameMatcher::Re(anchored()?),
+            MatchOp::NotRe(_) => NameMatcher::NotRe(anchored()?),
+        })
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            NameMatcher::Equal(value) => name == *value,
+            NameMatcher::NotEqual(value) => name != *value,
+            NameMatcher::Re(regex) => regex.is_match(name),
+            NameMatcher::NotRe(regex) => !regex.is_match(name),
+        }
+    }
+}
+
+fn parse_timestamp(timestamp: &str) -> Result<SystemTime> {
+    QueryLanguageParser::parse_promql_timestamp(timestamp).map_err(|e| {
+        InvalidQuerySnafu {
+            reason: e.to_string(),
+        }
+        .build()
+    })
+}
+
+/// Extracts the label set of each row. All string columns are treated as tags.
+fn record_batches_to_labels(
+    batches: RecordBatches,
+    metric_name: &str,
+) -> Result<BTreeSet<BTreeMap<String, String>>> {
+    let tag_column_indices = batches
+        .schema()
+        .column_schemas()
+        .iter()
+        .enumerate()
+        .filter_map(|(i, column)| {
+            matches!(column.data_type, ConcreteDataType::String(_)).then_some(i)
+        })
+        .collect::<Vec<_>>();
+
+    let mut result = BTreeSet::new();
+    for batch in batches.iter() {
+        let tag_columns = tag_column_indices
+            .iter()
+            .map(|i| {
+                let column = batch
+                    .column(*i)
+                    .as_any()
+                    .downcast_ref::<StringVector>()
+                    .context(InternalSnafu {
+                        err_msg: "tag column is not a string vector",
+                    })?;
+                Ok((
+                    batches.schema().column_name_by_index(*i).to_string(),
+                    column,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for row_index in 0..batch.num_rows() {
+            let mut labels = BTreeMap::new();
+            let _ = labels.insert(METRIC_NAME.to_string(), metric_name.to_string());
+            for (tag_name, tag_column) in &tag_columns {
+                if let Some(tag_value) = tag_column.get_data(row_index) {
+                    let _ = labels.insert(tag_name.clone(), tag_value.to_string());
+                }
+            }
+            let _ = result.insert(labels);
+        }
+    }
+    Ok(result)
+}
+
 fn retrieve_metric_name(promql: &str) -> Option<String> {
     let promql_expr = promql_parser::parser::parse(promql).ok()?;
     promql_expr_to_metric_name(promql_expr)
@@ -412,3 +924,15 @@ fn promql_expr_to_metric_name(expr: PromqlExpr) -> Option<String> {
             .find_map(|e| promql_expr_to_metric_name(*e)),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_quote_label_value() {
+        assert_eq!(r#""foo""#, quote_label_value("foo"));
+        assert_eq!(r#""a\\b\"c\nd""#, quote_label_value("a\\b\"c\nd"));
+        assert_eq!("\"\u{200b}\t\"", quote_label_value("\u{200b}\t"));
+    }
+}
@@ -19,9 +19,10 @@ use sqlparser::parser::{Parser, ParserError};
 use sqlparser::tokenizer::{Token, TokenWithLocation};
 
 use crate::error::{self, InvalidDatabaseNameSnafu, InvalidTableNameSnafu, Result, SyntaxSnafu};
-use crate::parsers::tql_parser;
+use crate::parsers::create_parser::FLOW;
+use crate::parsers::{admin_parser, tql_parser};
 use crate::statements::describe::DescribeTable;
-use crate::statements::drop::DropTable;
+use crate::statements::drop::{DropDatabase, DropFlow, DropTable, DropView};
 use crate::statements::explain::Explain;
 use crate::statements::show::{ShowCreateTable, ShowDatabases, ShowKind, ShowTables};
 use crate::statements::statement::Statement;
@@ -30,6 +31,7 @@ use crate::statements::statement::Statement;
 pub struct ParserContext<'a> {
     pub(crate) parser: Parser<'a>,
     pub(crate) sql: &'a str,
+    pub(crate) dialect: &'a dyn Dialect,
 }
 
 impl<'a> ParserContext<'a> {
@@ -40,7 +42,11 @@ impl<'a> ParserContext<'a> {
         let parser = Parser::new(dialect)
             .try_with_sql(sql)
             .context(SyntaxSnafu { sql })?;
-        let mut parser_ctx = ParserContext { sql, parser };
+        let mut parser_ctx = ParserContext {
+            sql,
+            parser,
+            dialect,
+        };
 
         let mut expecting_statement_delimiter = false;
         loop {
@@ -99,6 +105,8 @@ impl<'a> ParserContext<'a> {
 
                     Keyword::DROP => self.parse_drop(),
 
+                    Keyword::TRUNCATE => self.parse_truncate(),
+
                     Keyword::USE => {
                         self.parser.next_token();
 
@@ -121,6 +129,13 @@ impl<'a> ParserContext<'a> {
                         self.parse_tql()
                     }
 
+                    // ADMIN may or may not be a keyword, so we only check its value.
+                    _ if w.value.to_uppercase() == admin_parser::ADMIN
+                        && w.quote_style.is_none() =>
+                    {
+                        self.parse_admin()
+                    }
+
                     // todo(hl) support more statements.
                     _ => self.unsupported(self.peek_token_as_string()),
                 }
@@ -290,6 +305,15 @@ impl<'a> ParserContext<'a> {
 
     fn parse_drop(&mut self) -> Result<Statement> {
         self.parser.next_token();
+        if self.matches_keyword(Keyword::DATABASE) {
+            return self.parse_drop_database();
+        }
+        if self.matches_keyword(Keyword::VIEW) {
+            return self.parse_drop_view();
+        }
+        if self.peek_token_as_string().eq_ignore_ascii_case(FLOW) {
+            return self.parse_drop_flow();
+        }
         if !self.matches_keyword(Keyword::TABLE) {
             return self.unsupported(self.peek_token_as_string());
         }
@@ -313,6 +337,57 @@ impl<'a> ParserContext<'a> {
         Ok(Statement::DropTable(DropTable::new(table_ident)))
     }
 
+    fn parse_drop_database(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+
+        let database_name =
+            self.parser
+                .parse_object_name()
+                .with_context(|_| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a database name",
+                    actual: self.peek_token_as_string(),
+                })?;
+
+        Ok(Statement::DropDatabase(DropDatabase::new(
+            database_name,
+            if_exists,
+        )))
+    }
+
+    fn parse_drop_view(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+
+        let view_name =
+            self.parser
+                .parse_object_name()
+                .with_context(|_| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a view name",
+                    actual: self.peek_token_as_string(),
+                })?;
+
+        Ok(Statement::DropView(DropView::new(view_name, if_exists)))
+    }
+
+    fn parse_drop_flow(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+        let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+
+        let flow_name =
+            self.parser
+                .parse_object_name()
+                .with_context(|_| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a flow name",
+                    actual: self.peek_token_as_string(),
+                })?;
+
+        Ok(Statement::DropFlow(DropFlow::new(flow_name, if_exists)))
+    }
+
     // Report unexpected token
     pub(crate) fn expected<T>(&self, expected: &str, found: TokenWithLocation) -> Result<T> {
         Err(ParserError::ParserError(format!(
@@ -573,6 +648,24 @@ mod tests {
         assert_eq!(stmts[0], Statement::Explain(explain))
     }
 
+    #[test]
+    pub fn test_explain_analyze() {
+        let sql = "EXPLAIN ANALYZE select * from foo";
+        let result = ParserContext::create_with_dialect(sql, &GenericDialect {});
+        let stmts = result.unwrap();
+        assert_eq!(1, stmts.len());
+
+        match &stmts[0] {
+            Statement::Explain(explain) => {
+                assert!(matches!(
+                    explain.inner,
+                    SpStatement::Explain { analyze: true, .. }
+                ));
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     pub fn test_drop_table() {
         let sql = "DROP TABLE foo";
@@ -606,4 +699,71 @@ mod tests {
             ])))
         )
     }
+
+    #[test]
+    pub fn test_drop_database() {
+        let sql = "DROP DATABASE my_schema";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::DropDatabase(DropDatabase::new(
+                ObjectName(vec![Ident::new("my_schema")]),
+                false
+            ))
+        );
+
+        let sql = "DROP DATABASE IF EXISTS my_schema";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::DropDatabase(DropDatabase::new(
+                ObjectName(vec![Ident::new("my_schema")]),
+                true
+            ))
+        );
+
+        let sql = "DROP DATABASE";
+        let result = ParserContext::create_with_dialect(sql, &GenericDialect {});
+        assert!(result.is_err(), "result is: {result:?}");
+    }
+
+    #[test]
+    pub fn test_drop_view() {
+        let sql = "DROP VIEW my_schema.v";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::DropView(DropView::new(
+                ObjectName(vec![Ident::new("my_schema"), Ident::new("v")]),
+                false
+            ))
+        );
+
+        let sql = "DROP VIEW IF EXISTS v";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::DropView(DropView::new(ObjectName(vec![Ident::new("v")]), true))
+        );
+    }
+
+    #[test]
+    pub fn test_drop_flow() {
+        let sql = "DROP FLOW my_schema.f";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::DropFlow(DropFlow::new(
+                ObjectName(vec![Ident::new("my_schema"), Ident::new("f")]),
+                false
+            ))
+        );
+
+        let sql = "drop flow if exists f";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::DropFlow(DropFlow::new(ObjectName(vec![Ident::new("f")]), true))
+        );
+    }
 }
@@ -0,0 +1,150 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use snafu::ResultExt;
+use sqlparser::ast::ObjectName;
+use sqlparser::keywords::Keyword;
+
+use crate::error::{self, Result};
+use crate::parser::ParserContext;
+use crate::statements::admin::{CompactTable, FlushTable};
+use crate::statements::statement::Statement;
+
+pub const ADMIN: &str = "ADMIN";
+const FLUSH: &str = "FLUSH";
+const COMPACT: &str = "COMPACT";
+const REGION: &str = "REGION";
+
+/// ADMIN extension parser, including:
+/// - ADMIN FLUSH TABLE <table> [REGION <region_number>]
+/// - ADMIN COMPACT TABLE <table> [REGION <region_number>]
+impl<'a> ParserContext<'a> {
+    pub(crate) fn parse_admin(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+
+        if self.consume_token(FLUSH) {
+            let (table_name, region_number) = self.parse_admin_table()?;
+            Ok(Statement::FlushTable(FlushTable::new(
+                table_name,
+                region_number,
+            )))
+        } else if self.consume_token(COMPACT) {
+            let (table_name, region_number) = self.parse_admin_table()?;
+            Ok(Statement::CompactTable(CompactTable::new(
+                table_name,
+                region_number,
+            )))
+        } else {
+            self.unsupported(self.peek_token_as_string())
+        }
+    }
+
+    fn parse_admin_table(&mut self) -> Result<(ObjectName, Option<u32>)> {
+        self.parser
+            .expect_keyword(Keyword::TABLE)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
+        let table_name =
+            self.parser
+                .parse_object_name()
+                .with_context(|_| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a table name",
+                    actual: self.peek_token_as_string(),
+                })?;
+
+        let region_number = if self.consume_token(REGION) {
+            let region_number =
+                self.parser
+                    .parse_literal_uint()
+                    .with_context(|_| error::UnexpectedSnafu {
+                        sql: self.sql,
+                        expected: "a region number",
+                        actual: self.peek_token_as_string(),
+                    })?;
+            let region_number = u32::try_from(region_number).map_err(|_| {
+                error::InvalidSqlSnafu {
+                    msg: format!("invalid region number: {region_number}"),
+                }
+                .build()
+            })?;
+            Some(region_number)
+        } else {
+            None
+        };
+
+        Ok((table_name, region_number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::dialect::GenericDialect;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_admin_flush_table() {
+        let sql = "ADMIN FLUSH TABLE catalog0.schema0.tbl";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        match stmts.remove(0) {
+            Statement::FlushTable(flush_table) => {
+                assert_eq!("catalog0.schema0.tbl", flush_table.table_name().to_string());
+                assert_eq!(None, flush_table.region_number());
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "admin flush table tbl region 1";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        match stmts.remove(0) {
+            Statement::FlushTable(flush_table) => {
+                assert_eq!("tbl", flush_table.table_name().to_string());
+                assert_eq!(Some(1), flush_table.region_number());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_parse_admin_compact_table() {
+        let sql = "ADMIN COMPACT TABLE tbl REGION 2";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        match stmts.remove(0) {
+            Statement::CompactTable(compact_table) => {
+                assert_eq!("tbl", compact_table.table_name().to_string());
+                assert_eq!(Some(2), compact_table.region_number());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_parse_admin_error() {
+        let sqls = [
+            "ADMIN FLUSH tbl",
+            "ADMIN COMPACT TABLE",
+            "ADMIN FLUSH TABLE tbl REGION",
+            "ADMIN FLUSH TABLE tbl REGION 4294967296",
+            "ADMIN REBUILD TABLE tbl",
+        ];
+        for sql in sqls {
+            assert!(
+                ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err(),
+                "{sql}"
+            );
+        }
+    }
+}
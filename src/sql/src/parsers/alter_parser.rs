@@ -14,7 +14,7 @@
 
 use snafu::ResultExt;
 use sqlparser::keywords::Keyword;
-use sqlparser::parser::ParserError;
+use sqlparser::parser::{Parser, ParserError};
 
 use crate::error::{self, Result};
 use crate::parser::ParserContext;
@@ -54,20 +54,57 @@ impl<'a> ParserContext<'a> {
                 )));
             }
         } else if parser.parse_keyword(Keyword::RENAME) {
-            let new_table_name_obj = parser.parse_object_name()?;
-            let new_table_name = match &new_table_name_obj.0[..] {
-                [table] => table.value.clone(),
-                _ => {
-                    return Err(ParserError::ParserError(format!(
-                        "expect table name, actual: {new_table_name_obj}"
-                    )))
-                }
-            };
-            AlterTableOperation::RenameTable { new_table_name }
+            if parser.parse_keyword(Keyword::COLUMN) {
+                let name = parser.parse_identifier()?;
+                parser.expect_keyword(Keyword::TO)?;
+                let new_name = parser.parse_identifier()?;
+                AlterTableOperation::RenameColumn { name, new_name }
+            } else {
+                let new_table_name_obj = parser.parse_object_name()?;
+                let new_table_name = match &new_table_name_obj.0[..] {
+                    [table] => table.value.clone(),
+                    _ => {
+                        return Err(ParserError::ParserError(format!(
+                            "expect table name, actual: {new_table_name_obj}"
+                        )))
+                    }
+                };
+                AlterTableOperation::RenameTable { new_table_name }
+            }
+        } else if parser.parse_keyword(Keyword::ALTER) {
+            let _ = parser.parse_keyword(Keyword::COLUMN);
+            let name = parser.parse_identifier()?;
+            if parser.parse_keywords(&[Keyword::SET, Keyword::DEFAULT]) {
+                let default = parser.parse_expr()?;
+                AlterTableOperation::SetColumnDefault { name, default }
+            } else if parser.parse_keywords(&[Keyword::DROP, Keyword::DEFAULT]) {
+                AlterTableOperation::DropColumnDefault { name }
+            } else {
+                return Err(ParserError::ParserError(format!(
+                    "expect SET DEFAULT or DROP DEFAULT after ALTER TABLE ALTER COLUMN, found {}",
+                    parser.peek_token()
+                )));
+            }
+        } else if self.consume_token("MODIFY") {
+            let _ = self.parser.parse_keyword(Keyword::COLUMN);
+            let name = self.parser.parse_identifier()?;
+            let target_type = self.parser.parse_data_type()?;
+            AlterTableOperation::ModifyColumn { name, target_type }
+        } else if self.parser.parse_keyword(Keyword::SET) {
+            let options = self
+                .parser
+                .parse_comma_separated(Parser::parse_sql_option)?;
+            AlterTableOperation::SetTableOptions { options }
+        } else if self.consume_token("UNSET") {
+            let keys = self
+                .parser
+                .parse_comma_separated(Parser::parse_identifier)?;
+            AlterTableOperation::UnsetTableOptions { keys }
         } else {
             return Err(ParserError::ParserError(format!(
-                "expect keyword ADD or DROP or RENAME after ALTER TABLE, found {}",
-                parser.peek_token()
+                "expect keyword ADD, DROP, RENAME, ALTER, MODIFY, SET or UNSET after ALTER TABLE, \
+                 found {}",
+                self.parser.peek_token()
             )));
         };
         Ok(AlterTable::new(table_name, alter_operation))
@@ -148,9 +185,9 @@ mod tests {
     fn test_parse_alter_rename_table() {
         let sql = "ALTER TABLE test_table table_t";
         let result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap_err();
-        assert!(result
-            .to_string()
-            .contains("expect keyword ADD or DROP or RENAME after ALTER TABLE"));
+        assert!(result.to_string().contains(
+            "expect keyword ADD, DROP, RENAME, ALTER, MODIFY, SET or UNSET after ALTER TABLE"
+        ));
 
         let sql = "ALTER TABLE test_table RENAME table_t";
         let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
@@ -174,4 +211,122 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_parse_alter_modify_column() {
+        let sql = "ALTER TABLE my_metric_1 MODIFY COLUMN a BIGINT";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        let Statement::Alter(alter_table) = result.remove(0) else { unreachable!() };
+        assert_eq!("my_metric_1", alter_table.table_name().0[0].value);
+        match alter_table.alter_operation() {
+            AlterTableOperation::ModifyColumn { name, target_type } => {
+                assert_eq!("a", name.value);
+                assert_eq!(DataType::BigInt(None), *target_type);
+            }
+            _ => unreachable!(),
+        }
+
+        // COLUMN is optional.
+        let sql = "ALTER TABLE my_metric_1 MODIFY a DOUBLE";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        let Statement::Alter(alter_table) = result.remove(0) else { unreachable!() };
+        assert_matches!(
+            alter_table.alter_operation(),
+            AlterTableOperation::ModifyColumn {
+                target_type: DataType::Double,
+                ..
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_alter_rename_column() {
+        let sql = "ALTER TABLE my_metric_1 RENAME COLUMN a b";
+        let result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap_err();
+        assert!(result.to_string().contains("Expected TO"), "{result}");
+
+        let sql = "ALTER TABLE my_metric_1 RENAME COLUMN a TO b";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        let Statement::Alter(alter_table) = result.remove(0) else { unreachable!() };
+        assert_eq!("my_metric_1", alter_table.table_name().0[0].value);
+        match alter_table.alter_operation() {
+            AlterTableOperation::RenameColumn { name, new_name } => {
+                assert_eq!("a", name.value);
+                assert_eq!("b", new_name.value);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_parse_alter_column_default() {
+        let sql = "ALTER TABLE my_metric_1 ALTER COLUMN a SET DEFAULT 1";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        let Statement::Alter(alter_table) = result.remove(0) else { unreachable!() };
+        match alter_table.alter_operation() {
+            AlterTableOperation::SetColumnDefault { name, default } => {
+                assert_eq!("a", name.value);
+                assert_eq!("1", default.to_string());
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "ALTER TABLE my_metric_1 ALTER a DROP DEFAULT";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        let Statement::Alter(alter_table) = result.remove(0) else { unreachable!() };
+        match alter_table.alter_operation() {
+            AlterTableOperation::DropColumnDefault { name } => {
+                assert_eq!("a", name.value);
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "ALTER TABLE my_metric_1 ALTER COLUMN a SET NOT NULL";
+        let result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap_err();
+        assert!(result
+            .to_string()
+            .contains("expect SET DEFAULT or DROP DEFAULT after ALTER TABLE ALTER COLUMN"));
+    }
+
+    #[test]
+    fn test_parse_alter_table_options() {
+        let sql = "ALTER TABLE my_metric_1 SET ttl = '30d', write_buffer_size = '1MB'";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        let Statement::Alter(alter_table) = result.remove(0) else { unreachable!() };
+        match alter_table.alter_operation() {
+            AlterTableOperation::SetTableOptions { options } => {
+                assert_eq!(2, options.len());
+                assert_eq!("ttl", options[0].name.value);
+                assert_eq!("'30d'", options[0].value.to_string());
+                assert_eq!("write_buffer_size", options[1].name.value);
+                assert_eq!("'1MB'", options[1].value.to_string());
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "ALTER TABLE my_metric_1 UNSET ttl, write_buffer_size";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        let Statement::Alter(alter_table) = result.remove(0) else { unreachable!() };
+        match alter_table.alter_operation() {
+            AlterTableOperation::UnsetTableOptions { keys } => {
+                let keys = keys
+                    .iter()
+                    .map(|key| key.value.as_str())
+                    .collect::<Vec<_>>();
+                assert_eq!(vec!["ttl", "write_buffer_size"], keys);
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "ALTER TABLE my_metric_1 SET ttl";
+        assert!(ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err());
+    }
 }
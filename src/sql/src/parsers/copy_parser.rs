@@ -18,10 +18,11 @@ use sqlparser::keywords::Keyword;
 
 use crate::error::{self, Result};
 use crate::parser::ParserContext;
-use crate::statements::copy::{CopyTable, Format};
+use crate::statements::copy::{CopyDirection, CopyTable, Format};
 use crate::statements::statement::Statement;
 
 // COPY tbl TO 'output.parquet';
+// COPY tbl FROM 'input/*.csv' WITH (FORMAT = 'csv');
 impl<'a> ParserContext<'a> {
     pub(crate) fn parse_copy(&mut self) -> Result<Statement> {
         self.parser.next_token();
@@ -39,9 +40,14 @@ impl<'a> ParserContext<'a> {
                     actual: self.peek_token_as_string(),
                 })?;
 
-        self.parser
-            .expect_keyword(Keyword::TO)
-            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let direction = if self.parser.parse_keyword(Keyword::FROM) {
+            CopyDirection::From
+        } else {
+            self.parser
+                .expect_keyword(Keyword::TO)
+                .context(error::SyntaxSnafu { sql: self.sql })?;
+            CopyDirection::To
+        };
 
         let file_name =
             self.parser
@@ -67,7 +73,7 @@ impl<'a> ParserContext<'a> {
             }
         }
 
-        Ok(CopyTable::new(table_name, file_name, format))
+        Ok(CopyTable::new(table_name, file_name, format, direction))
     }
 }
 
@@ -113,6 +119,44 @@ mod tests {
 
                     let format = copy_table.format();
                     assert_eq!(Format::Parquet, *format);
+
+                    assert_eq!(CopyDirection::To, copy_table.direction());
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_copy_table_from() {
+        let cases = [
+            (
+                "COPY tbl FROM 'data/*.parquet'",
+                "data/*.parquet",
+                Format::Parquet,
+            ),
+            (
+                "COPY tbl FROM 'data.csv' WITH (FORMAT = 'csv')",
+                "data.csv",
+                Format::Csv,
+            ),
+            (
+                "COPY tbl FROM 'data/tbl_?.json' WITH (FORMAT = 'JSON')",
+                "data/tbl_?.json",
+                Format::Json,
+            ),
+        ];
+
+        for (sql, expected_file, expected_format) in cases {
+            let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+            assert_eq!(1, result.len());
+
+            match result.remove(0) {
+                Statement::Copy(copy_table) => {
+                    assert_eq!("tbl", copy_table.table_name().to_string());
+                    assert_eq!(expected_file, copy_table.file_name());
+                    assert_eq!(expected_format, *copy_table.format());
+                    assert_eq!(CopyDirection::From, copy_table.direction());
                 }
                 _ => unreachable!(),
             }
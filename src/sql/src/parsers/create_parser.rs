@@ -31,13 +31,16 @@ use crate::error::{
 };
 use crate::parser::ParserContext;
 use crate::statements::create::{
-    CreateDatabase, CreateTable, PartitionEntry, Partitions, TIME_INDEX,
+    CreateDatabase, CreateFlow, CreateTable, CreateView, PartitionEntry, Partitions, TIME_INDEX,
 };
+use crate::statements::query::Query;
 use crate::statements::statement::Statement;
 use crate::statements::{sql_data_type_to_concrete_data_type, sql_value_to_value};
 
 const ENGINE: &str = "ENGINE";
 const MAXVALUE: &str = "MAXVALUE";
+pub(crate) const FLOW: &str = "FLOW";
+const SINK: &str = "SINK";
 
 static LESS: Lazy<Token> = Lazy::new(|| Token::make_keyword("LESS"));
 static THAN: Lazy<Token> = Lazy::new(|| Token::make_keyword("THAN"));
@@ -51,12 +54,95 @@ impl<'a> ParserContext<'a> {
 
                 Keyword::SCHEMA | Keyword::DATABASE => self.parse_create_database(),
 
+                Keyword::OR | Keyword::VIEW => self.parse_create_view(),
+
+                _ if w.value.eq_ignore_ascii_case(FLOW) => self.parse_create_flow(),
+
                 _ => self.unsupported(w.to_string()),
             },
             unexpected => self.unsupported(unexpected.to_string()),
         }
     }
 
+    /// Parses `CREATE [OR REPLACE] VIEW <name> AS <query>`.
+    fn parse_create_view(&mut self) -> Result<Statement> {
+        let or_replace = self.parser.parse_keywords(&[Keyword::OR, Keyword::REPLACE]);
+        self.parser
+            .expect_keyword(Keyword::VIEW)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
+        let view_name = self
+            .parser
+            .parse_object_name()
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "a view name",
+                actual: self.peek_token_as_string(),
+            })?;
+
+        self.parser
+            .expect_keyword(Keyword::AS)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let query = self
+            .parser
+            .parse_query()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
+        Ok(Statement::CreateView(CreateView {
+            name: view_name,
+            or_replace,
+            query: Box::new(Query::try_from(query)?),
+        }))
+    }
+
+    /// Parses `CREATE FLOW [IF NOT EXISTS] <name> SINK TO <table> AS <query>`.
+    fn parse_create_flow(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+
+        let if_not_exists =
+            self.parser
+                .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+
+        let flow_name = self
+            .parser
+            .parse_object_name()
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "a flow name",
+                actual: self.peek_token_as_string(),
+            })?;
+
+        if !self.consume_token(SINK) {
+            return self.expected(SINK, self.parser.peek_token());
+        }
+        self.parser
+            .expect_keyword(Keyword::TO)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let sink_table = self
+            .parser
+            .parse_object_name()
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "a table name",
+                actual: self.peek_token_as_string(),
+            })?;
+
+        self.parser
+            .expect_keyword(Keyword::AS)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let query = self
+            .parser
+            .parse_query()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
+        Ok(Statement::CreateFlow(CreateFlow {
+            name: flow_name,
+            if_not_exists,
+            sink_table,
+            query: Box::new(Query::try_from(query)?),
+        }))
+    }
+
     fn parse_create_database(&mut self) -> Result<Statement> {
         self.parser.next_token();
 
@@ -73,9 +159,15 @@ impl<'a> ParserContext<'a> {
                 actual: self.peek_token_as_string(),
             })?;
 
+        let options = self
+            .parser
+            .parse_options(Keyword::WITH)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
         Ok(Statement::CreateDatabase(CreateDatabase {
             name: database_name,
             if_not_exists,
+            options,
         }))
     }
 
@@ -731,6 +823,76 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_parse_create_view() {
+        let sql = "create view v as select host, cpu from monitor where cpu > 0.5";
+        let stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        match &stmts[0] {
+            Statement::CreateView(c) => {
+                assert_eq!("v", c.name.to_string());
+                assert!(!c.or_replace);
+                assert_eq!(
+                    "SELECT host, cpu FROM monitor WHERE cpu > 0.5",
+                    c.query.inner.to_string()
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "create or replace view public.v as select * from monitor";
+        let stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        match &stmts[0] {
+            Statement::CreateView(c) => {
+                assert_eq!("public.v", c.name.to_string());
+                assert!(c.or_replace);
+                assert_eq!("SELECT * FROM monitor", c.query.inner.to_string());
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "create view v select * from monitor";
+        assert!(ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err());
+        let sql = "create or view v as select * from monitor";
+        assert!(ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err());
+    }
+
+    #[test]
+    fn test_parse_create_flow() {
+        let sql = r"create flow cpu_5m sink to cpu_rollup as
+select date_bin(interval '5 minutes', ts) as ts, host, avg(cpu) as cpu from monitor group by 1, 2";
+        let stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        match &stmts[0] {
+            Statement::CreateFlow(c) => {
+                assert_eq!("cpu_5m", c.name.to_string());
+                assert!(!c.if_not_exists);
+                assert_eq!("cpu_rollup", c.sink_table.to_string());
+                assert_eq!(
+                    "SELECT date_bin(INTERVAL '5 minutes', ts) AS ts, host, avg(cpu) AS cpu FROM monitor GROUP BY 1, 2",
+                    c.query.inner.to_string()
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "CREATE FLOW IF NOT EXISTS public.f SINK TO public.t AS SELECT * FROM monitor";
+        let stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        match &stmts[0] {
+            Statement::CreateFlow(c) => {
+                assert_eq!("public.f", c.name.to_string());
+                assert!(c.if_not_exists);
+                assert_eq!("public.t", c.sink_table.to_string());
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "create flow f as select * from monitor";
+        assert!(ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err());
+        let sql = "create flow f sink t as select * from monitor";
+        assert!(ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err());
+    }
+
     #[test]
     fn test_parse_create_database() {
         let sql = "create database";
@@ -760,6 +922,24 @@ mod tests {
             Statement::CreateDatabase(c) => {
                 assert_eq!(c.name.to_string(), "prometheus");
                 assert!(c.if_not_exists);
+                assert!(c.options.is_empty());
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "create database prometheus with (ttl='7d', write_buffer_size='1MB')";
+        let stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+
+        assert_eq!(1, stmts.len());
+        match &stmts[0] {
+            Statement::CreateDatabase(c) => {
+                assert_eq!(c.name.to_string(), "prometheus");
+                let options = &c.options;
+                assert_eq!(2, options.len());
+                assert_eq!("ttl", &options[0].name.to_string());
+                assert_eq!("'7d'", &options[0].value.to_string());
+                assert_eq!("write_buffer_size", &options[1].name.to_string());
+                assert_eq!("'1MB'", &options[1].value.to_string());
             }
             _ => unreachable!(),
         }
@@ -58,6 +58,19 @@ mod tests {
         assert_matches!(result[0], Statement::Delete { .. })
     }
 
+    #[test]
+    pub fn test_parse_delete_with_predicates() {
+        let sql = r"delete from my_table where ts < '2023-01-01' and host like 'old-%';";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+        let Statement::Delete(delete) = result.remove(0) else { unreachable!() };
+        assert_eq!("my_table", delete.table_name().to_string());
+        assert_eq!(
+            "ts < '2023-01-01' AND host LIKE 'old-%'",
+            delete.selection().as_ref().unwrap().to_string()
+        );
+    }
+
     #[test]
     pub fn test_parse_invalid_insert() {
         let sql = r"delete my_table where "; // intentionally a bad sql
@@ -12,16 +12,35 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::iter::Peekable;
+use std::vec::IntoIter;
+
 use snafu::prelude::*;
+use sqlparser::ast::{Expr, Value};
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::tokenizer::{Token, TokenWithLocation, Word};
 
 use crate::error::{self, Result};
 use crate::parser::ParserContext;
-use crate::statements::query::Query;
+use crate::statements::query::{Fill, Query, RangeItem, RangeQuery};
 use crate::statements::statement::Statement;
 
+const RANGE: &str = "RANGE";
+const ALIGN: &str = "ALIGN";
+const FILL: &str = "FILL";
+const PREV: &str = "PREV";
+const LINEAR: &str = "LINEAR";
+
+type Tokens = Peekable<IntoIter<TokenWithLocation>>;
+
 impl<'a> ParserContext<'a> {
     /// Parses select and it's variants.
     pub(crate) fn parse_query(&mut self) -> Result<Statement> {
+        if self.is_range_query() {
+            return self.parse_range_query();
+        }
+
         let spquery = self
             .parser
             .parse_query()
@@ -29,13 +48,263 @@ impl<'a> ParserContext<'a> {
 
         Ok(Statement::Query(Box::new(Query::try_from(spquery)?)))
     }
+
+    /// Returns whether the next statement is a range query, i.e. it contains `RANGE '...'`
+    /// or `ALIGN '...'` outside of parentheses.
+    fn is_range_query(&self) -> bool {
+        // Avoids looking ahead the tokens of most queries.
+        let sql = self.sql.to_uppercase();
+        if !sql.contains(RANGE) && !sql.contains(ALIGN) {
+            return false;
+        }
+
+        let mut depth = 0usize;
+        let mut n = 0;
+        loop {
+            match self.parser.peek_nth_token(n).token {
+                Token::EOF => return false,
+                Token::SemiColon if depth == 0 => return false,
+                Token::LParen => depth += 1,
+                Token::RParen => depth = depth.saturating_sub(1),
+                Token::Word(w) if depth == 0 && (is_word(&w, RANGE) || is_word(&w, ALIGN)) => {
+                    if matches!(
+                        self.parser.peek_nth_token(n + 1).token,
+                        Token::SingleQuotedString(_)
+                    ) {
+                        return true;
+                    }
+                }
+                _ => {}
+            }
+            n += 1;
+        }
+    }
+
+    /// Parses a range query like:
+    ///
+    /// ```sql
+    /// SELECT <expr> [RANGE '<duration>' [FILL <fill>]], ... FROM <table> [WHERE ...]
+    /// ALIGN '<duration>' [BY (<expr>, ...)] [FILL <fill>] [ORDER BY ...] [LIMIT ...]
+    /// ```
+    ///
+    /// where `<fill>` is one of `NULL`, `PREV`, `LINEAR` or a constant.
+    fn parse_range_query(&mut self) -> Result<Statement> {
+        // Takes the tokens of the statement, then parses the query without range clauses.
+        let mut tokens = Vec::new();
+        let mut depth = 0usize;
+        loop {
+            match self.parser.peek_token().token {
+                Token::EOF => break,
+                Token::SemiColon if depth == 0 => break,
+                Token::LParen => depth += 1,
+                Token::RParen => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            tokens.push(self.parser.next_token());
+        }
+
+        let (tokens, range) = self
+            .split_range_clauses(tokens)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let mut parser = Parser::new(self.dialect).with_tokens_with_locations(tokens);
+        let spquery = parser
+            .parse_query()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        if parser.peek_token() != Token::EOF {
+            return self.expected("end of range query", parser.peek_token());
+        }
+
+        let mut query = Query::try_from(spquery)?;
+        query.range = Some(range);
+        Ok(Statement::Query(Box::new(query)))
+    }
+
+    /// Removes range clauses from `tokens` of a range query, returns the remaining tokens
+    /// and the range clauses.
+    fn split_range_clauses(
+        &self,
+        tokens: Vec<TokenWithLocation>,
+    ) -> std::result::Result<(Vec<TokenWithLocation>, RangeQuery), ParserError> {
+        let mut tokens = tokens.into_iter().peekable();
+        let mut remaining = Vec::with_capacity(tokens.len());
+        if !peek_keyword(&mut tokens, Keyword::SELECT) {
+            return Err(ParserError::ParserError(format!(
+                "Expected SELECT in range query, found: {}",
+                tokens.peek().map(|t| t.to_string()).unwrap_or_default()
+            )));
+        }
+        remaining.extend(tokens.next());
+
+        let mut items = vec![None];
+        let mut in_projection = true;
+        let mut align = None;
+        let mut by = None;
+        let mut fill = None;
+        let mut depth = 0usize;
+        while let Some(token) = tokens.next() {
+            match &token.token {
+                Token::LParen => depth += 1,
+                Token::RParen => depth = depth.saturating_sub(1),
+                Token::Comma if depth == 0 && in_projection => items.push(None),
+                Token::Word(w) if depth == 0 && in_projection && w.keyword == Keyword::FROM => {
+                    in_projection = false
+                }
+                Token::Word(w) if depth == 0 && in_projection && is_word(w, RANGE) => {
+                    if let Some(range) = next_string(&mut tokens) {
+                        let item = items.last_mut().unwrap();
+                        if item.is_some() {
+                            return Err(ParserError::ParserError(
+                                "Duplicate RANGE of an expression".to_string(),
+                            ));
+                        }
+                        let fill = parse_fill(&mut tokens)?;
+                        *item = Some(RangeItem { range, fill });
+                        continue;
+                    }
+                }
+                Token::Word(w) if depth == 0 && !in_projection && is_word(w, ALIGN) => {
+                    if let Some(interval) = next_string(&mut tokens) {
+                        if align.is_some() {
+                            return Err(ParserError::ParserError(
+                                "Duplicate ALIGN clause".to_string(),
+                            ));
+                        }
+                        align = Some(interval);
+                        if peek_keyword(&mut tokens, Keyword::BY) {
+                            tokens.next();
+                            by = Some(self.parse_by_exprs(&mut tokens)?);
+                        }
+                        fill = parse_fill(&mut tokens)?;
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+            remaining.push(token);
+        }
+
+        let align = align.ok_or_else(|| {
+            ParserError::ParserError("Expected ALIGN clause in range query".to_string())
+        })?;
+        if items.iter().all(Option::is_none) {
+            return Err(ParserError::ParserError(
+                "Expected at least one expression with RANGE in range query".to_string(),
+            ));
+        }
+
+        Ok((
+            remaining,
+            RangeQuery {
+                items,
+                align,
+                by,
+                fill,
+            },
+        ))
+    }
+
+    /// Parses `(<expr>, ...)` after `BY`.
+    fn parse_by_exprs(&self, tokens: &mut Tokens) -> std::result::Result<Vec<Expr>, ParserError> {
+        match tokens.next() {
+            Some(token) if token.token == Token::LParen => {}
+            token => {
+                return Err(ParserError::ParserError(format!(
+                    "Expected ( after BY, found: {}",
+                    token.map(|t| t.to_string()).unwrap_or_default()
+                )))
+            }
+        }
+
+        let mut by_tokens = Vec::new();
+        let mut depth = 0usize;
+        loop {
+            let Some(token) = tokens.next() else {
+                return Err(ParserError::ParserError("Expected ) after BY".to_string()));
+            };
+            match token.token {
+                Token::LParen => depth += 1,
+                Token::RParen if depth == 0 => break,
+                Token::RParen => depth -= 1,
+                _ => {}
+            }
+            by_tokens.push(token);
+        }
+        if by_tokens.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut parser = Parser::new(self.dialect).with_tokens_with_locations(by_tokens);
+        let exprs = parser.parse_comma_separated(|p| p.parse_expr())?;
+        if parser.peek_token() != Token::EOF {
+            return Err(ParserError::ParserError(format!(
+                "Expected , or ) in BY, found: {}",
+                parser.peek_token()
+            )));
+        }
+        Ok(exprs)
+    }
+}
+
+fn is_word(word: &Word, expected: &str) -> bool {
+    word.quote_style.is_none() && word.value.eq_ignore_ascii_case(expected)
+}
+
+fn peek_keyword(tokens: &mut Tokens, keyword: Keyword) -> bool {
+    matches!(tokens.peek().map(|t| &t.token), Some(Token::Word(w)) if w.keyword == keyword)
+}
+
+/// Consumes and returns the next token if it's a single quoted string.
+fn next_string(tokens: &mut Tokens) -> Option<String> {
+    match tokens.peek().map(|t| &t.token) {
+        Some(Token::SingleQuotedString(s)) => {
+            let s = s.clone();
+            tokens.next();
+            Some(s)
+        }
+        _ => None,
+    }
+}
+
+/// Parses the optional `FILL <fill>`.
+fn parse_fill(tokens: &mut Tokens) -> std::result::Result<Option<Fill>, ParserError> {
+    match tokens.peek().map(|t| &t.token) {
+        Some(Token::Word(w)) if is_word(w, FILL) => {
+            tokens.next();
+        }
+        _ => return Ok(None),
+    }
+
+    let token = tokens.next().map(|t| t.token).unwrap_or(Token::EOF);
+    let fill = match token {
+        Token::Word(w) if w.keyword == Keyword::NULL => Fill::Null,
+        Token::Word(w) if is_word(&w, PREV) => Fill::Prev,
+        Token::Word(w) if is_word(&w, LINEAR) => Fill::Linear,
+        Token::Number(n, l) => Fill::Const(Value::Number(n, l)),
+        Token::Minus => match tokens.next().map(|t| t.token) {
+            Some(Token::Number(n, l)) => Fill::Const(Value::Number(format!("-{n}"), l)),
+            token => {
+                return Err(ParserError::ParserError(format!(
+                    "Expected a number after -, found: {}",
+                    token.unwrap_or(Token::EOF)
+                )))
+            }
+        },
+        Token::SingleQuotedString(s) => Fill::Const(Value::SingleQuotedString(s)),
+        token => {
+            return Err(ParserError::ParserError(format!(
+                "Expected NULL, PREV, LINEAR or a constant after FILL, found: {token}"
+            )))
+        }
+    };
+    Ok(Some(fill))
 }
 
 #[cfg(test)]
 mod tests {
+    use sqlparser::ast::Ident;
     use sqlparser::dialect::GenericDialect;
 
-    use crate::parser::ParserContext;
+    use super::*;
 
     #[test]
     pub fn test_parse_query() {
@@ -57,4 +326,101 @@ mod tests {
             .to_string()
             .contains("Expected an expression"));
     }
+
+    fn parse_range_query(sql: &str) -> (String, RangeQuery) {
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        match stmts.remove(0) {
+            Statement::Query(query) => (query.inner.to_string(), query.range.unwrap()),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    pub fn test_parse_range_query() {
+        let (query, range) = parse_range_query(
+            "SELECT ts, host, avg(cpu) RANGE '5m', max(cpu) RANGE '10m' FILL LINEAR \
+            FROM t WHERE host <> 'a' ALIGN '1m' BY (host) FILL PREV ORDER BY ts LIMIT 10",
+        );
+        assert_eq!(
+            "SELECT ts, host, avg(cpu), max(cpu) FROM t WHERE host <> 'a' ORDER BY ts LIMIT 10",
+            query
+        );
+        assert_eq!(
+            RangeQuery {
+                items: vec![
+                    None,
+                    None,
+                    Some(RangeItem {
+                        range: "5m".to_string(),
+                        fill: None,
+                    }),
+                    Some(RangeItem {
+                        range: "10m".to_string(),
+                        fill: Some(Fill::Linear),
+                    }),
+                ],
+                align: "1m".to_string(),
+                by: Some(vec![Expr::Identifier(Ident::new("host"))]),
+                fill: Some(Fill::Prev),
+            },
+            range
+        );
+
+        let (query, range) = parse_range_query(
+            "select count(*) range '1h' fill -1, \"range\" from t align '1h' by ()",
+        );
+        assert_eq!("SELECT count(*), \"range\" FROM t", query);
+        assert_eq!(
+            Some(Fill::Const(Value::Number("-1".to_string(), false))),
+            range.items[0].as_ref().unwrap().fill
+        );
+        assert_eq!(None, range.items[1]);
+        assert_eq!(Some(vec![]), range.by);
+        assert_eq!(None, range.fill);
+
+        // Multiple statements.
+        let stmts = ParserContext::create_with_dialect(
+            "SELECT min(cpu) RANGE '1m' FROM t ALIGN '1m' FILL NULL; SELECT 1",
+            &GenericDialect {},
+        )
+        .unwrap();
+        assert_eq!(2, stmts.len());
+
+        // Not a range query.
+        let (stmt, range) =
+            match ParserContext::create_with_dialect("SELECT range FROM t", &GenericDialect {})
+                .unwrap()
+                .remove(0)
+            {
+                Statement::Query(query) => (query.inner.to_string(), query.range),
+                _ => unreachable!(),
+            };
+        assert_eq!("SELECT range FROM t", stmt);
+        assert!(range.is_none());
+    }
+
+    #[test]
+    pub fn test_parse_invalid_range_query() {
+        let cases = [
+            ("SELECT avg(cpu) RANGE '5m' FROM t", "Expected ALIGN clause"),
+            ("SELECT avg(cpu) FROM t ALIGN '1m'", "Expected at least one"),
+            (
+                "SELECT avg(cpu) RANGE '5m' FROM t ALIGN '1m' FILL",
+                "after FILL",
+            ),
+            (
+                "SELECT avg(cpu) RANGE '5m' FROM t ALIGN '1m' BY (host",
+                "Expected ) after BY",
+            ),
+            (
+                "WITH a AS (SELECT 1) SELECT avg(cpu) RANGE '5m' FROM a ALIGN '1m'",
+                "Expected SELECT",
+            ),
+        ];
+        for (sql, expected) in cases {
+            let err = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap_err();
+            assert!(err.to_string().contains(expected), "{sql}: {err}");
+        }
+    }
 }
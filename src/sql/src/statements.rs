@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod admin;
 pub mod alter;
 pub mod copy;
 pub mod create;
@@ -24,6 +25,7 @@ pub mod query;
 pub mod show;
 pub mod statement;
 pub mod tql;
+pub mod truncate;
 
 use std::str::FromStr;
 
@@ -205,31 +207,39 @@ fn parse_column_default_constraint(
     data_type: &ConcreteDataType,
     opts: &[ColumnOptionDef],
 ) -> Result<Option<ColumnDefaultConstraint>> {
-    if let Some(opt) = opts
-        .iter()
-        .find(|o| matches!(o.option, ColumnOption::Default(_)))
-    {
-        let default_constraint = match &opt.option {
-            ColumnOption::Default(Expr::Value(v)) => {
-                ColumnDefaultConstraint::Value(sql_value_to_value(column_name, data_type, v)?)
-            }
-            ColumnOption::Default(Expr::Function(func)) => {
-                // Always use lowercase for function expression
-                ColumnDefaultConstraint::Function(format!("{func}").to_lowercase())
-            }
-            ColumnOption::Default(expr) => {
-                return UnsupportedDefaultValueSnafu {
-                    column_name,
-                    expr: expr.clone(),
-                }
-                .fail();
-            }
-            _ => unreachable!(),
-        };
+    opts.iter()
+        .find_map(|o| match &o.option {
+            ColumnOption::Default(expr) => Some(expr),
+            _ => None,
+        })
+        .map(|expr| sql_default_to_constraint(column_name, data_type, expr))
+        .transpose()
+}
 
-        Ok(Some(default_constraint))
-    } else {
-        Ok(None)
+/// Converts the default value expression `expr` of a column into a
+/// [ColumnDefaultConstraint].
+pub fn sql_default_to_constraint(
+    column_name: &str,
+    data_type: &ConcreteDataType,
+    expr: &Expr,
+) -> Result<ColumnDefaultConstraint> {
+    match expr {
+        Expr::Value(v) => Ok(ColumnDefaultConstraint::Value(sql_value_to_value(
+            column_name,
+            data_type,
+            v,
+        )?)),
+        Expr::Function(func) => {
+            // Always use lowercase for function expression
+            Ok(ColumnDefaultConstraint::Function(
+                format!("{func}").to_lowercase(),
+            ))
+        }
+        _ => UnsupportedDefaultValueSnafu {
+            column_name,
+            expr: expr.clone(),
+        }
+        .fail(),
     }
 }
 
@@ -0,0 +1,65 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use sqlparser::ast::ObjectName;
+
+/// `ADMIN FLUSH TABLE <table> [REGION <region_number>]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlushTable {
+    table_name: ObjectName,
+    region_number: Option<u32>,
+}
+
+impl FlushTable {
+    pub(crate) fn new(table_name: ObjectName, region_number: Option<u32>) -> Self {
+        Self {
+            table_name,
+            region_number,
+        }
+    }
+
+    pub fn table_name(&self) -> &ObjectName {
+        &self.table_name
+    }
+
+    /// Returns the region to flush, or `None` if all regions of the table should be flushed.
+    pub fn region_number(&self) -> Option<u32> {
+        self.region_number
+    }
+}
+
+/// `ADMIN COMPACT TABLE <table> [REGION <region_number>]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactTable {
+    table_name: ObjectName,
+    region_number: Option<u32>,
+}
+
+impl CompactTable {
+    pub(crate) fn new(table_name: ObjectName, region_number: Option<u32>) -> Self {
+        Self {
+            table_name,
+            region_number,
+        }
+    }
+
+    pub fn table_name(&self) -> &ObjectName {
+        &self.table_name
+    }
+
+    /// Returns the region to compact, or `None` if all regions of the table should be
+    /// compacted.
+    pub fn region_number(&self) -> Option<u32> {
+        self.region_number
+    }
+}
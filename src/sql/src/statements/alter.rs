@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use sqlparser::ast::{ColumnDef, Ident, ObjectName, TableConstraint};
+use sqlparser::ast::{ColumnDef, DataType, Expr, Ident, ObjectName, SqlOption, TableConstraint};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AlterTable {
@@ -47,4 +47,16 @@ pub enum AlterTableOperation {
     DropColumn { name: Ident },
     /// `RENAME <new_table_name>`
     RenameTable { new_table_name: String },
+    /// `MODIFY [ COLUMN ] <name> <target_type>`
+    ModifyColumn { name: Ident, target_type: DataType },
+    /// `RENAME COLUMN <name> TO <new_name>`
+    RenameColumn { name: Ident, new_name: Ident },
+    /// `ALTER [ COLUMN ] <name> SET DEFAULT <default>`
+    SetColumnDefault { name: Ident, default: Expr },
+    /// `ALTER [ COLUMN ] <name> DROP DEFAULT`
+    DropColumnDefault { name: Ident },
+    /// `SET <key> = <value> [, ...]`
+    SetTableOptions { options: Vec<SqlOption> },
+    /// `UNSET <key> [, ...]`
+    UnsetTableOptions { keys: Vec<Ident> },
 }
@@ -21,14 +21,21 @@ pub struct CopyTable {
     table_name: ObjectName,
     file_name: String,
     format: Format,
+    direction: CopyDirection,
 }
 
 impl CopyTable {
-    pub(crate) fn new(table_name: ObjectName, file_name: String, format: Format) -> Self {
+    pub(crate) fn new(
+        table_name: ObjectName,
+        file_name: String,
+        format: Format,
+        direction: CopyDirection,
+    ) -> Self {
         Self {
             table_name,
             file_name,
             format,
+            direction,
         }
     }
 
@@ -43,11 +50,25 @@ impl CopyTable {
     pub fn format(&self) -> &Format {
         &self.format
     }
+
+    pub fn direction(&self) -> CopyDirection {
+        self.direction
+    }
+}
+
+/// Whether the table is copied to files (`COPY tbl TO`) or from files (`COPY tbl FROM`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyDirection {
+    To,
+    From,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Format {
     Parquet,
+    Csv,
+    /// Newline-delimited JSON.
+    Json,
 }
 
 impl TryFrom<String> for Format {
@@ -56,6 +77,8 @@ impl TryFrom<String> for Format {
     fn try_from(name: String) -> Result<Self> {
         match name.to_uppercase().as_str() {
             "PARQUET" => Ok(Format::Parquet),
+            "CSV" => Ok(Format::Csv),
+            "JSON" => Ok(Format::Json),
             _ => error::UnsupportedCopyFormatOptionSnafu { name }.fail(),
         }
     }
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::ast::{ColumnDef, Ident, ObjectName, SqlOption, TableConstraint, Value as SqlValue};
+use crate::statements::query::Query;
 
 /// Time index name, used in table constraints.
 pub const TIME_INDEX: &str = "__time_index";
@@ -49,4 +50,28 @@ pub struct CreateDatabase {
     pub name: ObjectName,
     /// Create if not exists
     pub if_not_exists: bool,
+    /// Default options of tables in the database, in `WITH`.
+    pub options: Vec<SqlOption>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CreateView {
+    /// View name
+    pub name: ObjectName,
+    /// Replace the view if it exists
+    pub or_replace: bool,
+    /// The query defining the view
+    pub query: Box<Query>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CreateFlow {
+    /// Flow name
+    pub name: ObjectName,
+    /// Create if not exists
+    pub if_not_exists: bool,
+    /// The table the results of the flow are written into
+    pub sink_table: ObjectName,
+    /// The query of the flow
+    pub query: Box<Query>,
 }
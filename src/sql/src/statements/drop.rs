@@ -30,3 +30,72 @@ impl DropTable {
         &self.table_name
     }
 }
+
+/// DROP DATABASE statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropDatabase {
+    name: ObjectName,
+    /// Do nothing if the database doesn't exist.
+    if_exists: bool,
+}
+
+impl DropDatabase {
+    /// Creates a statement for `DROP DATABASE`
+    pub fn new(name: ObjectName, if_exists: bool) -> Self {
+        Self { name, if_exists }
+    }
+
+    pub fn name(&self) -> &ObjectName {
+        &self.name
+    }
+
+    pub fn if_exists(&self) -> bool {
+        self.if_exists
+    }
+}
+
+/// DROP VIEW statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropView {
+    name: ObjectName,
+    /// Do nothing if the view doesn't exist.
+    if_exists: bool,
+}
+
+impl DropView {
+    /// Creates a statement for `DROP VIEW`
+    pub fn new(name: ObjectName, if_exists: bool) -> Self {
+        Self { name, if_exists }
+    }
+
+    pub fn name(&self) -> &ObjectName {
+        &self.name
+    }
+
+    pub fn if_exists(&self) -> bool {
+        self.if_exists
+    }
+}
+
+/// DROP FLOW statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropFlow {
+    name: ObjectName,
+    /// Do nothing if the flow doesn't exist.
+    if_exists: bool,
+}
+
+impl DropFlow {
+    /// Creates a statement for `DROP FLOW`
+    pub fn new(name: ObjectName, if_exists: bool) -> Self {
+        Self { name, if_exists }
+    }
+
+    pub fn name(&self) -> &ObjectName {
+        &self.name
+    }
+
+    pub fn if_exists(&self) -> bool {
+        self.if_exists
+    }
+}
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use datatypes::prelude::ConcreteDataType;
-use sqlparser::ast::Query as SpQuery;
+use sqlparser::ast::{Expr, Query as SpQuery, Value};
 
 use crate::error::Error;
 
@@ -22,6 +22,47 @@ use crate::error::Error;
 pub struct Query {
     pub inner: SpQuery,
     pub param_types: Vec<ConcreteDataType>,
+    /// Range clauses of the query if it's a range query.
+    pub range: Option<RangeQuery>,
+}
+
+/// Range clauses of a query, for example:
+///
+/// ```sql
+/// SELECT avg(cpu) RANGE '5m' FILL LINEAR, max(cpu) RANGE '10m' FROM t ALIGN '1m' BY (host) FILL PREV;
+/// ```
+///
+/// These clauses are removed from [Query::inner], which only contains the standard parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeQuery {
+    /// Range options of each item in the projection, `None` if the item has no `RANGE`.
+    pub items: Vec<Option<RangeItem>>,
+    /// Interval of the aligned timestamps, in `ALIGN` clause.
+    pub align: String,
+    /// Expressions in `BY` clause, `None` if the clause is absent.
+    pub by: Option<Vec<Expr>>,
+    /// Default fill option of items, in `FILL` clause after `ALIGN`.
+    pub fill: Option<Fill>,
+}
+
+/// Range options of an item in the projection, e.g. `RANGE '5m' FILL LINEAR`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeItem {
+    pub range: String,
+    pub fill: Option<Fill>,
+}
+
+/// How to fill the aligned timestamps without data in their ranges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fill {
+    /// Fills with `NULL`.
+    Null,
+    /// Fills with the value of the previous timestamp with data.
+    Prev,
+    /// Fills with the linear interpolation of the values around.
+    Linear,
+    /// Fills with a constant.
+    Const(Value),
 }
 
 /// Automatically converts from sqlparser Query instance to SqlQuery.
@@ -32,6 +73,7 @@ impl TryFrom<SpQuery> for Query {
         Ok(Query {
             inner: q,
             param_types: vec![],
+            range: None,
         })
     }
 }
@@ -16,17 +16,19 @@ use datafusion_sql::parser::Statement as DfStatement;
 use sqlparser::ast::Statement as SpStatement;
 
 use crate::error::{ConvertToDfStatementSnafu, Error};
+use crate::statements::admin::{CompactTable, FlushTable};
 use crate::statements::alter::AlterTable;
 use crate::statements::copy::CopyTable;
-use crate::statements::create::{CreateDatabase, CreateTable};
+use crate::statements::create::{CreateDatabase, CreateFlow, CreateTable, CreateView};
 use crate::statements::delete::Delete;
 use crate::statements::describe::DescribeTable;
-use crate::statements::drop::DropTable;
+use crate::statements::drop::{DropDatabase, DropFlow, DropTable, DropView};
 use crate::statements::explain::Explain;
 use crate::statements::insert::Insert;
 use crate::statements::query::Query;
 use crate::statements::show::{ShowCreateTable, ShowDatabases, ShowTables};
 use crate::statements::tql::Tql;
+use crate::statements::truncate::TruncateTable;
 
 /// Tokens parsed by `DFParser` are converted into these values.
 #[allow(clippy::large_enum_variant)]
@@ -44,6 +46,16 @@ pub enum Statement {
     DropTable(DropTable),
     // CREATE DATABASE
     CreateDatabase(CreateDatabase),
+    // DROP DATABASE
+    DropDatabase(DropDatabase),
+    // CREATE VIEW
+    CreateView(CreateView),
+    // DROP VIEW
+    DropView(DropView),
+    // CREATE FLOW
+    CreateFlow(CreateFlow),
+    // DROP FLOW
+    DropFlow(DropFlow),
     /// ALTER TABLE
     Alter(AlterTable),
     // Databases.
@@ -60,6 +72,12 @@ pub enum Statement {
     // COPY
     Copy(CopyTable),
     Tql(Tql),
+    // ADMIN FLUSH TABLE
+    FlushTable(FlushTable),
+    // ADMIN COMPACT TABLE
+    CompactTable(CompactTable),
+    // TRUNCATE TABLE
+    TruncateTable(TruncateTable),
 }
 
 /// Comment hints from SQL.
@@ -77,7 +95,10 @@ impl TryFrom<&Statement> for DfStatement {
 
     fn try_from(s: &Statement) -> Result<Self, Self::Error> {
         let s = match s {
-            Statement::Query(query) => SpStatement::Query(Box::new(query.inner.clone())),
+            // Range clauses can't be expressed by the DataFusion statement.
+            Statement::Query(query) if query.range.is_none() => {
+                SpStatement::Query(Box::new(query.inner.clone()))
+            }
             Statement::Explain(explain) => explain.inner.clone(),
             _ => {
                 return ConvertToDfStatementSnafu {
@@ -25,6 +25,9 @@ fn bench_memtable_read(c: &mut Criterion) {
     let mut group = c.benchmark_group("memtable_read");
     group.throughput(Throughput::Elements(10 * 10000));
     group.bench_function("read", |b| b.iter(|| ctx.read(100)));
+    let series_ctx = BenchContext::new_series();
+    kvs.iter().for_each(|kv| series_ctx.write(kv));
+    group.bench_function("read_series", |b| b.iter(|| series_ctx.read(100)));
     group.finish();
 }
 
@@ -26,6 +26,10 @@ pub fn bench_memtable_write(c: &mut Criterion) {
         let ctx = BenchContext::new();
         b.iter(|| kvs.iter().for_each(|kv| ctx.write(kv)))
     });
+    group.bench_function("write_series", |b| {
+        let ctx = BenchContext::new_series();
+        b.iter(|| kvs.iter().for_each(|kv| ctx.write(kv)))
+    });
     group.finish();
 }
 
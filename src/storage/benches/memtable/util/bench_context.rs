@@ -14,7 +14,7 @@
 
 use storage::memtable::{IterContext, KeyValues, MemtableRef};
 
-use crate::memtable::util::new_memtable;
+use crate::memtable::util::{new_memtable, new_series_memtable};
 
 pub struct BenchContext {
     memtable: MemtableRef,
@@ -31,6 +31,12 @@ impl BenchContext {
         }
     }
 
+    pub fn new_series() -> BenchContext {
+        BenchContext {
+            memtable: new_series_memtable(),
+        }
+    }
+
     pub fn write(&self, kvs: &KeyValues) {
         self.memtable.write(kvs).unwrap();
     }
@@ -17,7 +17,9 @@ pub mod regiondesc_util;
 pub mod schema_util;
 
 use datatypes::type_id::LogicalTypeId;
-use storage::memtable::{DefaultMemtableBuilder, MemtableBuilder, MemtableRef};
+use storage::memtable::{
+    DefaultMemtableBuilder, MemtableBuilder, MemtableRef, SeriesMemtableBuilder,
+};
 use storage::metadata::RegionMetadata;
 use storage::schema::RegionSchemaRef;
 
@@ -39,3 +41,7 @@ pub fn schema_for_test() -> RegionSchemaRef {
 pub fn new_memtable() -> MemtableRef {
     DefaultMemtableBuilder::default().build(schema_for_test())
 }
+
+pub fn new_series_memtable() -> MemtableRef {
+    SeriesMemtableBuilder::default().build(schema_for_test())
+}
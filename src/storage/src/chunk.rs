@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -19,7 +20,7 @@ use common_query::logical_plan::Expr;
 use common_telemetry::debug;
 use common_time::range::TimestampRange;
 use snafu::ResultExt;
-use store_api::storage::{Chunk, ChunkReader, SchemaRef, SequenceNumber};
+use store_api::storage::{Chunk, ChunkReader, ScanMetricsRef, SchemaRef, SequenceNumber};
 use table::predicate::{Predicate, TimeRangePredicateBuilder};
 
 use crate::error::{self, Error, Result};
@@ -35,6 +36,9 @@ use crate::sst::{AccessLayerRef, FileHandle, LevelMetas, ReadOptions};
 pub struct ChunkReaderImpl {
     schema: ProjectedSchemaRef,
     batch_reader: BoxedBatchReader,
+    /// Number of deleted rows removed by the reader.
+    num_deleted: Arc<AtomicUsize>,
+    metrics: ScanMetricsRef,
 }
 
 #[async_trait]
@@ -66,6 +70,8 @@ impl ChunkReaderImpl {
         ChunkReaderImpl {
             schema,
             batch_reader,
+            num_deleted: Arc::new(AtomicUsize::new(0)),
+            metrics: ScanMetricsRef::default(),
         }
     }
 
@@ -73,6 +79,17 @@ impl ChunkReaderImpl {
     pub fn projected_schema(&self) -> &ProjectedSchemaRef {
         &self.schema
     }
+
+    /// Returns the counter of deleted rows removed by this reader, which is updated while
+    /// the reader is being read.
+    pub fn num_deleted(&self) -> Arc<AtomicUsize> {
+        self.num_deleted.clone()
+    }
+
+    /// Returns the metrics of this reader, which are updated while the reader is being read.
+    pub fn metrics(&self) -> ScanMetricsRef {
+        self.metrics.clone()
+    }
 }
 
 /// Builder to create a new [ChunkReaderImpl] from scan request.
@@ -84,6 +101,7 @@ pub struct ChunkReaderBuilder {
     iter_ctx: IterContext,
     memtables: Vec<MemtableRef>,
     files_to_read: Vec<FileHandle>,
+    filter_deleted: bool,
 }
 
 impl ChunkReaderBuilder {
@@ -96,6 +114,7 @@ impl ChunkReaderBuilder {
             iter_ctx: IterContext::default(),
             memtables: Vec::new(),
             files_to_read: Vec::new(),
+            filter_deleted: true,
         }
     }
 
@@ -125,6 +144,12 @@ impl ChunkReaderBuilder {
         self
     }
 
+    /// Sets whether to remove deleted rows from the output, default is true.
+    pub fn filter_deleted(mut self, filter_deleted: bool) -> Self {
+        self.filter_deleted = filter_deleted;
+        self
+    }
+
     pub fn pick_memtables(mut self, memtables: MemtableRef) -> Self {
         self.memtables.push(memtables);
         self
@@ -158,6 +183,7 @@ impl ChunkReaderBuilder {
             time_range_predicate
         );
 
+        let user_schema = self.schema.user_schema().clone();
         let schema = Arc::new(
             ProjectedSchema::new(self.schema, self.projection)
                 .context(error::InvalidProjectionSnafu)?,
@@ -167,6 +193,10 @@ impl ChunkReaderBuilder {
         let mut reader_builder = MergeReaderBuilder::with_capacity(schema.clone(), num_sources)
             .batch_size(self.iter_ctx.batch_size);
 
+        let metrics = ScanMetricsRef::default();
+        metrics
+            .memtables_read
+            .store(self.memtables.len(), Ordering::Relaxed);
         self.iter_ctx.projected_schema = Some(schema.clone());
         for mem in self.memtables {
             let iter = mem.iter(&self.iter_ctx)?;
@@ -178,27 +208,55 @@ impl ChunkReaderBuilder {
             projected_schema: schema.clone(),
             predicate: Predicate::new(self.filters),
             time_range: time_range_predicate,
+            metrics: metrics.clone(),
         };
-        for file in &self.files_to_read {
-            if !Self::file_in_range(file, time_range_predicate) {
-                debug!(
-                    "Skip file {:?}, predicate: {:?}",
-                    file, time_range_predicate
-                );
+        let files_in_range = self
+            .files_to_read
+            .iter()
+            .filter(|file| {
+                let in_range = Self::file_in_range(file, time_range_predicate);
+                if !in_range {
+                    debug!(
+                        "Skip file {:?}, predicate: {:?}",
+                        file, time_range_predicate
+                    );
+                }
+                in_range
+            })
+            .collect::<Vec<_>>();
+        // Prunes files by their column statistics so we don't need to open them.
+        let files_stats = files_in_range
+            .iter()
+            .map(|file| file.column_stats())
+            .collect::<Vec<_>>();
+        let files_to_keep = read_opts.predicate.prune_files(&user_schema, &files_stats);
+        for (file, keep) in files_in_range.into_iter().zip(files_to_keep) {
+            if !keep {
+                debug!("Skip file {:?} by column statistics", file);
                 continue;
             }
-            let reader = self
-                .sst_layer
-                .read_sst(file.file_name(), &read_opts)
-                .await?;
+            let reader = self.sst_layer.read_sst(file, &read_opts).await?;
+            metrics.ssts_opened.fetch_add(1, Ordering::Relaxed);
 
             reader_builder = reader_builder.push_batch_reader(reader);
         }
+        metrics.ssts_pruned.store(
+            self.files_to_read.len() - metrics.ssts_opened.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
 
         let reader = reader_builder.build();
-        let reader = DedupReader::new(schema.clone(), reader);
+        let reader = DedupReader::new(schema.clone(), reader)
+            .with_filter_deleted(self.filter_deleted)
+            .with_metrics(metrics.clone());
+        let num_deleted = reader.num_deleted();
 
-        Ok(ChunkReaderImpl::new(schema, Box::new(reader)))
+        Ok(ChunkReaderImpl {
+            schema,
+            batch_reader: Box::new(reader),
+            num_deleted,
+            metrics,
+        })
     }
 
     /// Build time range predicate from schema and filters.
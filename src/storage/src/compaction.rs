@@ -22,7 +22,7 @@ mod writer;
 use std::sync::Arc;
 
 pub use picker::{Picker, PickerContext, SimplePicker};
-pub use scheduler::{CompactionHandler, CompactionRequestImpl};
+pub use scheduler::{CompactionHandler, CompactionRequest, CompactionRequestImpl};
 pub use task::{CompactionTask, CompactionTaskImpl};
 
 use crate::scheduler::Scheduler;
@@ -17,7 +17,7 @@ use std::marker::PhantomData;
 
 use store_api::storage::RegionId;
 
-use crate::compaction::{CompactionTask, Picker, PickerContext};
+use crate::compaction::{CompactionRequest, CompactionTask, Picker, PickerContext};
 use crate::scheduler::{Request, Scheduler};
 
 pub struct NoopCompactionScheduler<R> {
@@ -75,14 +75,19 @@ impl Request for NoopCompactionRequest {
     }
 }
 
+impl CompactionRequest for NoopCompactionRequest {
+    fn complete(self, _result: crate::error::Result<()>) {}
+}
+
 #[async_trait::async_trait]
 impl<R> Scheduler for NoopCompactionScheduler<R>
 where
-    R: Request<Key = RegionId>,
+    R: CompactionRequest<Key = RegionId>,
 {
     type Request = R;
 
-    fn schedule(&self, _request: Self::Request) -> crate::error::Result<bool> {
+    fn schedule(&self, request: Self::Request) -> crate::error::Result<bool> {
+        request.complete(Ok(()));
         Ok(true)
     }
 
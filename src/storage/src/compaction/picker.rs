@@ -13,26 +13,26 @@
 // limitations under the License.
 
 use std::marker::PhantomData;
-use std::sync::Arc;
 use std::time::Duration;
 
 use common_telemetry::{debug, error, info};
+use common_time::range::TimestampRange;
+use common_time::timestamp::TimeUnit;
 use common_time::Timestamp;
 use snafu::ResultExt;
 use store_api::logstore::LogStore;
 
-use crate::compaction::scheduler::CompactionRequestImpl;
-use crate::compaction::strategy::{SimpleTimeWindowStrategy, StrategyRef};
-use crate::compaction::task::{CompactionTask, CompactionTaskImpl};
+use crate::compaction::scheduler::{CompactionRequest, CompactionRequestImpl};
+use crate::compaction::strategy::new_strategy;
+use crate::compaction::task::{CompactionOutput, CompactionTask, CompactionTaskImpl};
 use crate::error::TtlCalculationSnafu;
-use crate::scheduler::Request;
-use crate::sst::{FileHandle, Level};
+use crate::sst::{FileHandle, Level, WriteOptions};
 use crate::version::LevelMetasRef;
 
 /// Picker picks input SST files and builds the compaction task.
 /// Different compaction strategy may implement different pickers.
 pub trait Picker: Send + 'static {
-    type Request: Request;
+    type Request: CompactionRequest;
     type Task: CompactionTask;
 
     fn pick(
@@ -45,25 +45,21 @@ pub trait Picker: Send + 'static {
 pub struct PickerContext {}
 
 /// L0 -> L1 compaction based on time windows.
+/// SimplePicker picks SSTs to compact in all levels with the strategy specified by
+/// the compaction options of the region.
 pub struct SimplePicker<S> {
-    strategy: StrategyRef,
     _phantom_data: PhantomData<S>,
 }
 
 impl<S> Default for SimplePicker<S> {
     fn default() -> Self {
-        Self::new(Arc::new(SimpleTimeWindowStrategy {}))
-    }
-}
-
-impl<S> SimplePicker<S> {
-    pub fn new(strategy: StrategyRef) -> Self {
         Self {
-            strategy,
             _phantom_data: Default::default(),
         }
     }
+}
 
+impl<S> SimplePicker<S> {
     fn get_expired_ssts(
         &self,
         levels: &LevelMetasRef,
@@ -110,31 +106,152 @@ impl<S: LogStore> Picker for SimplePicker<S> {
             expired_ssts.iter().for_each(|f| f.mark_compacting(true));
         }
 
+        let strategy = new_strategy(&req.compaction_options);
+        let mut outputs = Vec::new();
         for level_num in 0..levels.level_num() {
-            let level = levels.level(level_num as u8);
-            let outputs = self.strategy.pick(ctx, level);
+            let level_outputs = strategy.pick(ctx, levels, level_num as Level);
 
-            if outputs.is_empty() {
+            if level_outputs.is_empty() {
                 debug!("No SST file can be compacted at level {}", level_num);
                 continue;
             }
 
             debug!(
                 "Found SST files to compact {:?} on level: {}",
-                outputs, level_num
+                level_outputs, level_num
             );
-            return Ok(Some(CompactionTaskImpl {
-                schema: req.schema(),
-                sst_layer: req.sst_layer.clone(),
-                outputs,
-                writer: req.writer.clone(),
-                shared_data: req.shared.clone(),
-                wal: req.wal.clone(),
-                manifest: req.manifest.clone(),
-                expired_ssts,
-            }));
+            // marks inputs as compacting so that they won't be picked again in following levels.
+            level_outputs
+                .iter()
+                .flat_map(|output| output.inputs.iter())
+                .for_each(|f| f.mark_compacting(true));
+            outputs.extend(level_outputs);
+        }
+
+        if outputs.is_empty() {
+            return Ok(None);
+        }
+
+        for output in &mut outputs {
+            output.purge_deleted = !overlaps_other_files(levels, output);
         }
 
-        Ok(None)
+        Ok(Some(CompactionTaskImpl {
+            schema: req.schema(),
+            sst_layer: req.sst_layer.clone(),
+            outputs,
+            writer: req.writer.clone(),
+            shared_data: req.shared.clone(),
+            wal: req.wal.clone(),
+            manifest: req.manifest.clone(),
+            expired_ssts,
+            write_opts: WriteOptions {
+                index_options: req.index_options.clone(),
+            },
+        }))
+    }
+}
+
+/// Returns true if any SST file other than the inputs of `output` overlaps its time bucket.
+/// Files without time range are considered overlapping.
+fn overlaps_other_files(levels: &LevelMetasRef, output: &CompactionOutput) -> bool {
+    let Some(bucket_range) = TimestampRange::with_unit(
+        output.bucket_bound,
+        output.bucket_bound.saturating_add(output.bucket),
+        TimeUnit::Second,
+    ) else {
+        return true;
+    };
+
+    levels
+        .levels()
+        .iter()
+        .flat_map(|level| level.files())
+        .filter(|file| {
+            !output
+                .inputs
+                .iter()
+                .any(|input| input.file_name() == file.file_name())
+        })
+        .any(|file| match *file.time_range() {
+            Some((start, end)) => {
+                TimestampRange::new_inclusive(Some(start), Some(end)).intersects(&bucket_range)
+            }
+            None => true,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::file_purger::noop::new_noop_file_purger;
+    use crate::sst::{FileMeta, LevelMetas};
+    use crate::test_util::access_layer_util::MockAccessLayer;
+
+    fn new_level_metas(input: &[(&str, i64, i64, Level)]) -> LevelMetasRef {
+        let files = input.iter().map(|(name, start, end, level)| FileMeta {
+            region_id: 0,
+            file_name: name.to_string(),
+            time_range: Some((
+                Timestamp::new_millisecond(*start),
+                Timestamp::new_millisecond(*end),
+            )),
+            level: *level,
+            column_stats: Default::default(),
+            index_columns: Default::default(),
+            num_rows: 0,
+        });
+        Arc::new(
+            LevelMetas::new(Arc::new(MockAccessLayer {}), new_noop_file_purger())
+                .merge(files, std::iter::empty()),
+        )
+    }
+
+    fn new_output(
+        levels: &LevelMetasRef,
+        inputs: &[(&str, Level)],
+        bucket_bound: i64,
+    ) -> CompactionOutput {
+        let inputs = inputs
+            .iter()
+            .map(|(name, level)| {
+                levels
+                    .level(*level)
+                    .files()
+                    .find(|file| file.file_name() == *name)
+                    .unwrap()
+                    .clone()
+            })
+            .collect();
+        CompactionOutput {
+            output_level: 1,
+            bucket_bound,
+            bucket: 10,
+            inputs,
+            purge_deleted: false,
+        }
+    }
+
+    #[test]
+    fn test_overlaps_other_files() {
+        let levels = new_level_metas(&[
+            ("a", 0, 9_000, 0),
+            ("b", 5_000, 15_000, 0),
+            ("c", 10_000, 19_000, 1),
+            ("d", 20_000, 29_000, 1),
+        ]);
+
+        // "b" overlaps bucket [0, 10).
+        let output = new_output(&levels, &[("a", 0)], 0);
+        assert!(overlaps_other_files(&levels, &output));
+        let output = new_output(&levels, &[("a", 0), ("b", 0)], 0);
+        assert!(!overlaps_other_files(&levels, &output));
+        // "c" in level 1 overlaps bucket [10, 20).
+        let output = new_output(&levels, &[("b", 0)], 10);
+        assert!(overlaps_other_files(&levels, &output));
+        let output = new_output(&levels, &[("d", 1)], 20);
+        assert!(!overlaps_other_files(&levels, &output));
     }
 }
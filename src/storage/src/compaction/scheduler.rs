@@ -17,8 +17,8 @@ use std::time::Duration;
 
 use common_telemetry::{debug, error, info};
 use store_api::logstore::LogStore;
-use store_api::storage::RegionId;
-use tokio::sync::Notify;
+use store_api::storage::{CompactionOptions, IndexOptions, RegionId};
+use tokio::sync::{oneshot, Notify};
 
 use crate::compaction::picker::{Picker, PickerContext};
 use crate::compaction::task::CompactionTask;
@@ -32,6 +32,12 @@ use crate::sst::AccessLayerRef;
 use crate::version::LevelMetasRef;
 use crate::wal::Wal;
 
+/// Request to compact a region.
+pub trait CompactionRequest: Request {
+    /// Notifies the issuer of the request that the compaction is finished with `result`.
+    fn complete(self, result: Result<()>);
+}
+
 impl<S: LogStore> Request for CompactionRequestImpl<S> {
     type Key = RegionId;
 
@@ -41,6 +47,15 @@ impl<S: LogStore> Request for CompactionRequestImpl<S> {
     }
 }
 
+impl<S: LogStore> CompactionRequest for CompactionRequestImpl<S> {
+    fn complete(self, result: Result<()>) {
+        if let Some(sender) = self.sender {
+            // The issuer may have given up waiting, so we ignore the error.
+            let _ = sender.send(result);
+        }
+    }
+}
+
 /// Region compaction request.
 pub struct CompactionRequestImpl<S: LogStore> {
     pub region_id: RegionId,
@@ -50,6 +65,10 @@ pub struct CompactionRequestImpl<S: LogStore> {
     pub manifest: RegionManifest,
     pub wal: Wal<S>,
     pub ttl: Option<Duration>,
+    pub compaction_options: CompactionOptions,
+    pub index_options: IndexOptions,
+    /// Sender to notify the compaction result, used by manually triggered compaction.
+    pub sender: Option<oneshot::Sender<Result<()>>>,
 }
 
 impl<S: LogStore> CompactionRequestImpl<S> {
@@ -90,18 +109,21 @@ where
         let region_id = req.key();
         let Some(task) = self.picker.pick(&PickerContext {}, &req)? else {
             info!("No file needs compaction in region: {:?}", region_id);
+            req.complete(Ok(()));
             return Ok(());
         };
 
         debug!("Compaction task, region: {:?}, task: {:?}", region_id, task);
         // TODO(hl): we need to keep a track of task handle here to allow task cancellation.
         common_runtime::spawn_bg(async move {
-            if let Err(e) = task.run().await {
+            let result = task.run().await;
+            if let Err(e) = &result {
                 // TODO(hl): maybe resubmit compaction task on failure?
                 error!(e; "Failed to compact region: {:?}", region_id);
             } else {
                 info!("Successfully compacted region: {:?}", region_id);
             }
+            req.complete(result);
             // releases rate limit token
             token.try_release();
             // notify scheduler to schedule next task when current task finishes.
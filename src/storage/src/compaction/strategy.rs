@@ -12,57 +12,185 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use common_telemetry::{debug, warn};
 use common_time::timestamp::TimeUnit;
 use common_time::timestamp_millis::BucketAligned;
 use common_time::Timestamp;
+use store_api::storage::{CompactionOptions, CompactionStrategy};
 
 use crate::compaction::picker::PickerContext;
 use crate::compaction::task::CompactionOutput;
-use crate::sst::{FileHandle, LevelMeta};
+use crate::sst::{FileHandle, Level, LevelMeta, LevelMetas};
+
+/// Default max number of SSTs in a time window of a level.
+pub const DEFAULT_MAX_FILES_IN_WINDOW: usize = 4;
 
 /// Compaction strategy that defines which SSTs need to be compacted at given level.
 pub trait Strategy {
-    fn pick(&self, ctx: &PickerContext, level: &LevelMeta) -> Vec<CompactionOutput>;
+    fn pick(&self, ctx: &PickerContext, levels: &LevelMetas, level: Level)
+        -> Vec<CompactionOutput>;
 }
 
 pub type StrategyRef = Arc<dyn Strategy + Send + Sync>;
 
-/// SimpleTimeWindowStrategy only handles level 0 to level 1 compaction in a time-window tiered
-/// manner. It picks all SSTs in level 0 and writes rows in these SSTs to a new file partitioned
-/// by a inferred time bucket in level 1.
-pub struct SimpleTimeWindowStrategy {}
+/// Creates the compaction strategy specified by region's compaction options.
+pub fn new_strategy(options: &CompactionOptions) -> StrategyRef {
+    let time_window = options.time_window.map(|window| window.as_secs() as i64);
+    let max_files_in_window = options
+        .max_files_in_window
+        .unwrap_or(DEFAULT_MAX_FILES_IN_WINDOW);
+    match options.strategy {
+        CompactionStrategy::Tiered => Arc::new(TieredTimeWindowStrategy {
+            time_window,
+            max_files_in_window,
+        }),
+        CompactionStrategy::Leveled => Arc::new(LeveledTimeWindowStrategy {
+            time_window,
+            max_files_in_window,
+        }),
+    }
+}
+
+/// TieredTimeWindowStrategy compacts SSTs in a time-window tiered manner. It picks all SSTs in
+/// level 0, and SSTs in time windows that hold more than `max_files_in_window` files in other
+/// levels, then writes rows in these SSTs to new files partitioned by time window in the next
+/// level. Files in the last level are merged in place.
+pub struct TieredTimeWindowStrategy {
+    /// Time window in seconds, inferred from files if absent.
+    time_window: Option<i64>,
+    max_files_in_window: usize,
+}
 
-impl Strategy for SimpleTimeWindowStrategy {
-    fn pick(&self, _ctx: &PickerContext, level: &LevelMeta) -> Vec<CompactionOutput> {
-        // SimpleTimeWindowStrategy only handles level 0 to level 1 compaction.
-        if level.level() != 0 {
+impl Strategy for TieredTimeWindowStrategy {
+    fn pick(
+        &self,
+        _ctx: &PickerContext,
+        levels: &LevelMetas,
+        level: Level,
+    ) -> Vec<CompactionOutput> {
+        let Some((time_bucket, files)) =
+            pick_files(levels.level(level), self.time_window, self.max_files_in_window) else {
             return vec![];
-        }
-        let files = find_compactable_files(level);
-        debug!("Compactable files found: {:?}", files);
-        if files.is_empty() {
+        };
+
+        build_outputs(time_bucket, files, output_level(levels, level))
+    }
+}
+
+/// LeveledTimeWindowStrategy picks SSTs in the same way as [TieredTimeWindowStrategy], but
+/// also merges the SSTs in the next level that overlap with the time windows of picked files,
+/// so that each time window holds as few files as possible in levels other than level 0.
+pub struct LeveledTimeWindowStrategy {
+    /// Time window in seconds, inferred from files if absent.
+    time_window: Option<i64>,
+    max_files_in_window: usize,
+}
+
+impl Strategy for LeveledTimeWindowStrategy {
+    fn pick(
+        &self,
+        _ctx: &PickerContext,
+        levels: &LevelMetas,
+        level: Level,
+    ) -> Vec<CompactionOutput> {
+        let Some((time_bucket, mut files)) =
+            pick_files(levels.level(level), self.time_window, self.max_files_in_window) else {
             return vec![];
+        };
+
+        let output_level = output_level(levels, level);
+        if output_level != level {
+            let windows = calculate_time_buckets(time_bucket, &files)
+                .into_keys()
+                .collect::<HashSet<_>>();
+            let overlapping = find_compactable_files(levels.level(output_level))
+                .into_iter()
+                .filter(|f| {
+                    file_time_buckets(f, time_bucket)
+                        .iter()
+                        .any(|bound| windows.contains(bound))
+                });
+            files.extend(overlapping);
         }
 
-        let time_bucket = infer_time_bucket(&files);
-        let buckets = calculate_time_buckets(time_bucket, &files);
-        debug!("File bucket:{}, file groups: {:?}", time_bucket, buckets);
-        buckets
-            .into_iter()
-            .map(|(bound, files)| CompactionOutput {
-                output_level: 1,
-                bucket_bound: bound,
-                bucket: time_bucket,
-                inputs: files,
-            })
-            .collect()
+        build_outputs(time_bucket, files, output_level)
+    }
+}
+
+/// Returns the level that files compacted from `level` are written to, which is the next
+/// level, or the last level itself.
+#[inline]
+fn output_level(levels: &LevelMetas, level: Level) -> Level {
+    (level + 1).min(levels.level_num() as Level - 1)
+}
+
+/// Picks files to compact in given level and returns them with the time bucket to partition
+/// them. All files in level 0 are picked, while in other levels only files in time windows
+/// holding more than `max_files_in_window` files are picked.
+fn pick_files(
+    level: &LevelMeta,
+    time_window: Option<i64>,
+    max_files_in_window: usize,
+) -> Option<(i64, Vec<FileHandle>)> {
+    let files = find_compactable_files(level);
+    debug!(
+        "Compactable files found in level {}: {:?}",
+        level.level(),
+        files
+    );
+    if files.is_empty() {
+        return None;
+    }
+
+    let time_bucket = time_window.unwrap_or_else(|| infer_time_bucket(&files));
+    if level.level() == 0 {
+        return Some((time_bucket, files));
+    }
+
+    let mut picked_names = HashSet::new();
+    let mut picked = Vec::new();
+    for files in calculate_time_buckets(time_bucket, &files).values() {
+        if files.len() <= max_files_in_window {
+            continue;
+        }
+        for file in files {
+            if picked_names.insert(file.file_name().to_string()) {
+                picked.push(file.clone());
+            }
+        }
+    }
+
+    if picked.is_empty() {
+        None
+    } else {
+        Some((time_bucket, picked))
     }
 }
 
+/// Builds compaction outputs that write `files` into `output_level`, one for each time window
+/// spanned by the files.
+fn build_outputs(
+    time_bucket: i64,
+    files: Vec<FileHandle>,
+    output_level: Level,
+) -> Vec<CompactionOutput> {
+    let buckets = calculate_time_buckets(time_bucket, &files);
+    debug!("File bucket:{}, file groups: {:?}", time_bucket, buckets);
+    buckets
+        .into_iter()
+        .map(|(bound, files)| CompactionOutput {
+            output_level,
+            bucket_bound: bound,
+            bucket: time_bucket,
+            inputs: files,
+            purge_deleted: false,
+        })
+        .collect()
+}
+
 /// Finds files that can be compacted in given level.
 /// Currently they're files that is not currently under compaction.
 #[inline]
@@ -77,25 +205,31 @@ fn calculate_time_buckets(bucket_sec: i64, files: &[FileHandle]) -> HashMap<i64,
     let mut buckets = HashMap::new();
 
     for file in files {
-        if let Some((start, end)) = file.time_range() {
-            let bounds = file_time_bucket_span(
-                start.convert_to(TimeUnit::Second).unwrap().value(),
-                end.convert_to(TimeUnit::Second).unwrap().value(),
-                bucket_sec,
-            );
-            for bound in bounds {
-                buckets
-                    .entry(bound)
-                    .or_insert_with(Vec::new)
-                    .push(file.clone());
-            }
-        } else {
-            warn!("Found corrupted SST without timestamp bounds: {:?}", file);
+        for bound in file_time_buckets(file, bucket_sec) {
+            buckets
+                .entry(bound)
+                .or_insert_with(Vec::new)
+                .push(file.clone());
         }
     }
     buckets
 }
 
+/// Returns the left bounds of time buckets spanned by the file, or an empty vector if
+/// the file does not contain a time range in metadata.
+fn file_time_buckets(file: &FileHandle, bucket_sec: i64) -> Vec<i64> {
+    if let Some((start, end)) = file.time_range() {
+        file_time_bucket_span(
+            start.convert_to(TimeUnit::Second).unwrap().value(),
+            end.convert_to(TimeUnit::Second).unwrap().value(),
+            bucket_sec,
+        )
+    } else {
+        warn!("Found corrupted SST without timestamp bounds: {:?}", file);
+        vec![]
+    }
+}
+
 /// Calculates timestamp span between start and end timestamp.
 fn file_time_bucket_span(start_sec: i64, end_sec: i64, bucket_sec: i64) -> Vec<i64> {
     assert!(start_sec <= end_sec);
@@ -239,6 +373,9 @@ mod tests {
                     Timestamp::new_millisecond(end_ts_millis),
                 )),
                 level: 0,
+                column_stats: Default::default(),
+                index_columns: Default::default(),
+                num_rows: 0,
             },
             layer,
             file_purger,
@@ -317,4 +454,151 @@ mod tests {
             &expected,
         );
     }
+
+    fn new_level_metas(input: &[(&str, i64, i64, Level)]) -> LevelMetas {
+        let layer = Arc::new(crate::test_util::access_layer_util::MockAccessLayer {});
+        let files = input.iter().map(|(name, start, end, level)| FileMeta {
+            region_id: 0,
+            file_name: name.to_string(),
+            time_range: Some((
+                Timestamp::new_millisecond(*start),
+                Timestamp::new_millisecond(*end),
+            )),
+            level: *level,
+            column_stats: Default::default(),
+            index_columns: Default::default(),
+            num_rows: 0,
+        });
+        LevelMetas::new(layer, new_noop_file_purger()).merge(files, std::iter::empty())
+    }
+
+    fn new_options(strategy: CompactionStrategy, max_files_in_window: usize) -> CompactionOptions {
+        CompactionOptions {
+            strategy,
+            time_window: Some(std::time::Duration::from_secs(10)),
+            max_files_in_window: Some(max_files_in_window),
+        }
+    }
+
+    /// Picks level `level` and returns `(output level, bucket bound, sorted input files)` of
+    /// each output, sorted by bucket bound.
+    fn check_pick(
+        options: &CompactionOptions,
+        levels: &LevelMetas,
+        level: Level,
+        expected: &[(Level, i64, &[&str])],
+    ) {
+        let outputs = new_strategy(options).pick(&PickerContext {}, levels, level);
+        let mut actual = outputs
+            .iter()
+            .map(|output| {
+                assert_eq!(10, output.bucket);
+                let mut inputs = output
+                    .inputs
+                    .iter()
+                    .map(|f| f.file_name().to_string())
+                    .collect::<Vec<_>>();
+                inputs.sort_unstable();
+                (output.output_level, output.bucket_bound, inputs)
+            })
+            .collect::<Vec<_>>();
+        actual.sort_unstable_by_key(|(_, bound, _)| *bound);
+
+        let expected = expected
+            .iter()
+            .map(|(level, bound, inputs)| {
+                (
+                    *level,
+                    *bound,
+                    inputs.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_tiered_strategy() {
+        let options = new_options(CompactionStrategy::Tiered, 2);
+        let levels = new_level_metas(&[
+            ("a", 0, 9000, 0),
+            ("b", 10000, 19000, 0),
+            // window 0 in level 1 holds too many files
+            ("c", 0, 1000, 1),
+            ("d", 2000, 3000, 1),
+            ("e", 4000, 10000, 1),
+            ("f", 20000, 21000, 1),
+            // files in the last level are merged in place
+            ("g", 30000, 31000, 2),
+            ("h", 32000, 33000, 2),
+            ("i", 34000, 35000, 2),
+        ]);
+
+        // all files in level 0 are compacted into level 1.
+        check_pick(&options, &levels, 0, &[(1, 0, &["a"]), (1, 10, &["b"])]);
+        // file "e" also spans window 10, which is rewritten as well.
+        check_pick(
+            &options,
+            &levels,
+            1,
+            &[(2, 0, &["c", "d", "e"]), (2, 10, &["e"])],
+        );
+        check_pick(&options, &levels, 2, &[(2, 30, &["g", "h", "i"])]);
+
+        // no window holds more than 3 files.
+        let options = new_options(CompactionStrategy::Tiered, 3);
+        check_pick(&options, &levels, 1, &[]);
+        check_pick(&options, &levels, 2, &[]);
+    }
+
+    #[test]
+    fn test_leveled_strategy() {
+        let options = new_options(CompactionStrategy::Leveled, 2);
+        let levels = new_level_metas(&[
+            ("a", 0, 9000, 0),
+            ("b", 1000, 2000, 0),
+            ("c", 0, 1000, 1),
+            ("d", 5000, 15000, 1),
+            ("e", 20000, 21000, 1),
+        ]);
+
+        // overlapping files in level 1 are merged together with files in level 0.
+        check_pick(
+            &options,
+            &levels,
+            0,
+            &[(1, 0, &["a", "b", "c", "d"]), (1, 10, &["d"])],
+        );
+        check_pick(&options, &levels, 1, &[]);
+
+        // files already under compaction are not picked.
+        levels
+            .level(1)
+            .files()
+            .for_each(|f| f.mark_compacting(true));
+        check_pick(&options, &levels, 0, &[(1, 0, &["a", "b"])]);
+    }
+
+    #[test]
+    fn test_default_strategy() {
+        let options = CompactionOptions::default();
+        let files = [
+            ("a", 0, 1000, 1),
+            ("b", 0, 1000, 1),
+            ("c", 0, 1000, 1),
+            ("d", 0, 1000, 1),
+            ("e", 0, 1000, 1),
+        ];
+
+        let levels = new_level_metas(&files[..DEFAULT_MAX_FILES_IN_WINDOW]);
+        assert!(new_strategy(&options)
+            .pick(&PickerContext {}, &levels, 1)
+            .is_empty());
+
+        let levels = new_level_metas(&files[..DEFAULT_MAX_FILES_IN_WINDOW + 1]);
+        let outputs = new_strategy(&options).pick(&PickerContext {}, &levels, 1);
+        assert_eq!(1, outputs.len());
+        assert_eq!(2, outputs[0].output_level);
+        assert_eq!(DEFAULT_MAX_FILES_IN_WINDOW + 1, outputs[0].inputs.len());
+    }
 }
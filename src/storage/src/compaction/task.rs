@@ -14,8 +14,10 @@
 
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter};
+use std::sync::atomic::Ordering;
 
 use common_telemetry::{error, info};
+use metrics::counter;
 use store_api::logstore::LogStore;
 use store_api::storage::RegionId;
 use uuid::Uuid;
@@ -24,6 +26,7 @@ use crate::compaction::writer::build_sst_reader;
 use crate::error::Result;
 use crate::manifest::action::RegionEdit;
 use crate::manifest::region::RegionManifest;
+use crate::metric::METRIC_COMPACTION_PURGED_ROWS;
 use crate::region::{RegionWriterRef, SharedDataRef};
 use crate::schema::RegionSchemaRef;
 use crate::sst::{AccessLayerRef, FileHandle, FileMeta, Level, Source, SstInfo, WriteOptions};
@@ -43,6 +46,8 @@ pub struct CompactionTaskImpl<S: LogStore> {
     pub wal: Wal<S>,
     pub manifest: RegionManifest,
     pub expired_ssts: Vec<FileHandle>,
+    /// Options to write the output SST files.
+    pub write_opts: WriteOptions,
 }
 
 impl<S: LogStore> Debug for CompactionTaskImpl<S> {
@@ -60,38 +65,44 @@ impl<S: LogStore> Drop for CompactionTaskImpl<S> {
 }
 
 impl<S: LogStore> CompactionTaskImpl<S> {
-    /// Compacts inputs SSTs, returns `(output file, compacted input file)`.
-    async fn merge_ssts(&mut self) -> Result<(HashSet<FileMeta>, HashSet<FileMeta>)> {
+    /// Compacts inputs SSTs, returns `(output file, compacted input file, purged rows)`.
+    async fn merge_ssts(&mut self) -> Result<(HashSet<FileMeta>, HashSet<FileMeta>, usize)> {
         let mut futs = Vec::with_capacity(self.outputs.len());
         let mut compacted_inputs = HashSet::new();
         let region_id = self.shared_data.id();
         for output in self.outputs.drain(..) {
             let schema = self.schema.clone();
             let sst_layer = self.sst_layer.clone();
+            let write_opts = &self.write_opts;
             compacted_inputs.extend(output.inputs.iter().map(FileHandle::meta));
 
             // TODO(hl): Maybe spawn to runtime to exploit in-job parallelism.
             futs.push(async move {
-                match output.build(region_id, schema, sst_layer).await {
+                match output.build(region_id, schema, sst_layer, write_opts).await {
                     Ok(meta) => Ok(meta),
                     Err(e) => Err(e),
                 }
             });
         }
 
-        let outputs = futures::future::join_all(futs)
-            .await
-            .into_iter()
-            .collect::<Result<_>>()?;
+        let mut outputs = HashSet::with_capacity(futs.len());
+        let mut purged_rows = 0;
+        for res in futures::future::join_all(futs).await {
+            let (meta, purged) = res?;
+            outputs.insert(meta);
+            purged_rows += purged;
+        }
         let inputs = compacted_inputs.into_iter().collect();
-        Ok((outputs, inputs))
+        Ok((outputs, inputs, purged_rows))
     }
 
-    /// Writes updated SST info into manifest.
+    /// Writes updated SST info into manifest, the output files are deleted instead if
+    /// some of `input_handles` have been removed from the region, e.g. by truncation.
     async fn write_manifest_and_apply(
         &self,
         output: HashSet<FileMeta>,
         input: HashSet<FileMeta>,
+        input_handles: &[FileHandle],
     ) -> Result<()> {
         let version = &self.shared_data.version_control;
         let region_version = version.metadata().version();
@@ -99,7 +110,7 @@ impl<S: LogStore> CompactionTaskImpl<S> {
         let edit = RegionEdit {
             region_version,
             flushed_sequence: None,
-            files_to_add: Vec::from_iter(output.into_iter()),
+            files_to_add: Vec::from_iter(output.iter().cloned()),
             files_to_remove: Vec::from_iter(input.into_iter()),
         };
         info!(
@@ -107,9 +118,26 @@ impl<S: LogStore> CompactionTaskImpl<S> {
             version.metadata().name(),
             edit
         );
-        self.writer
-            .write_edit_and_apply(&self.wal, &self.shared_data, &self.manifest, edit, None)
-            .await
+        let applied = self
+            .writer
+            .write_compaction_edit_and_apply(
+                &self.wal,
+                &self.shared_data,
+                &self.manifest,
+                edit,
+                input_handles,
+            )
+            .await?;
+        if !applied {
+            info!(
+                "Discard compaction output of region: {}, inputs have been removed",
+                version.metadata().name()
+            );
+            for file in output {
+                self.sst_layer.delete_sst(&file.file_name).await?;
+            }
+        }
+        Ok(())
     }
 
     /// Mark files are under compaction.
@@ -126,13 +154,26 @@ impl<S: LogStore> CompactionTaskImpl<S> {
 impl<S: LogStore> CompactionTask for CompactionTaskImpl<S> {
     async fn run(mut self) -> Result<()> {
         self.mark_files_compacting(true);
+        let inputs = self
+            .outputs
+            .iter()
+            .flat_map(|output| output.inputs.iter())
+            .chain(&self.expired_ssts)
+            .cloned()
+            .collect::<Vec<_>>();
 
-        let (output, mut compacted) = self.merge_ssts().await.map_err(|e| {
+        let (output, mut compacted, purged_rows) = self.merge_ssts().await.map_err(|e| {
             error!(e; "Failed to compact region: {}", self.shared_data.name());
             e
         })?;
+        info!(
+            "Purged {} deleted rows while compacting region: {}",
+            purged_rows,
+            self.shared_data.name()
+        );
+        counter!(METRIC_COMPACTION_PURGED_ROWS, purged_rows as u64);
         compacted.extend(self.expired_ssts.iter().map(FileHandle::meta));
-        self.write_manifest_and_apply(output, compacted)
+        self.write_manifest_and_apply(output, compacted, &inputs)
             .await
             .map_err(|e| {
                 error!(e; "Failed to update region manifest: {}", self.shared_data.name());
@@ -153,36 +194,53 @@ pub struct CompactionOutput {
     pub(crate) bucket: i64,
     /// Compaction input files.
     pub(crate) inputs: Vec<FileHandle>,
+    /// Whether deleted rows and their tombstones can be purged. It's only safe when no
+    /// SST file other than the inputs overlaps the time bucket, otherwise the tombstones
+    /// are kept to shadow older rows in these files.
+    pub(crate) purge_deleted: bool,
 }
 
 impl CompactionOutput {
+    /// Writes the rows of inputs to a new SST file, returns its metadata and the number
+    /// of purged deleted rows.
     async fn build(
         &self,
         region_id: RegionId,
         schema: RegionSchemaRef,
         sst_layer: AccessLayerRef,
-    ) -> Result<FileMeta> {
+        opts: &WriteOptions,
+    ) -> Result<(FileMeta, usize)> {
         let reader = build_sst_reader(
             schema,
             sst_layer.clone(),
             &self.inputs,
             self.bucket_bound,
             self.bucket_bound + self.bucket,
+            self.purge_deleted,
         )
         .await?;
+        let num_deleted = reader.num_deleted();
         let output_file_name = format!("{}.parquet", Uuid::new_v4().hyphenated());
-        let opts = WriteOptions {};
 
-        let SstInfo { time_range } = sst_layer
-            .write_sst(&output_file_name, Source::Reader(reader), &opts)
+        let SstInfo {
+            time_range,
+            column_stats,
+            index_columns,
+            num_rows,
+        } = sst_layer
+            .write_sst(&output_file_name, Source::Reader(reader), opts)
             .await?;
 
-        Ok(FileMeta {
+        let meta = FileMeta {
             region_id,
             file_name: output_file_name,
             time_range,
             level: self.output_level,
-        })
+            column_stats,
+            index_columns,
+            num_rows,
+        };
+        Ok((meta, num_deleted.load(Ordering::Relaxed)))
     }
 }
 
@@ -21,13 +21,15 @@ use crate::error;
 use crate::schema::RegionSchemaRef;
 use crate::sst::{AccessLayerRef, FileHandle};
 
-/// Builds an SST reader that only reads rows within given time range.
+/// Builds an SST reader that only reads rows within given time range. Deleted rows are
+/// removed from the output if `filter_deleted` is true, otherwise they are kept as tombstones.
 pub(crate) async fn build_sst_reader(
     schema: RegionSchemaRef,
     sst_layer: AccessLayerRef,
     files: &[FileHandle],
     lower_sec_inclusive: i64,
     upper_sec_exclusive: i64,
+    filter_deleted: bool,
 ) -> error::Result<ChunkReaderImpl> {
     // TODO(hl): Schemas in different SSTs may differ, thus we should infer
     // timestamp column name from Parquet metadata.
@@ -45,6 +47,7 @@ pub(crate) async fn build_sst_reader(
             upper_sec_exclusive,
             &ts_col_name,
         )])
+        .filter_deleted(filter_deleted)
         .build()
         .await
 }
@@ -221,7 +224,7 @@ mod tests {
         let iter = memtable.iter(&IterContext::default()).unwrap();
         let writer = ParquetWriter::new(sst_file_name, Source::Iter(iter), object_store.clone());
 
-        let SstInfo { time_range } = writer
+        let SstInfo { time_range, .. } = writer
             .write_sst(&sst::WriteOptions::default())
             .await
             .unwrap();
@@ -231,6 +234,9 @@ mod tests {
                 file_name: sst_file_name.to_string(),
                 time_range,
                 level: 0,
+                column_stats: Default::default(),
+                index_columns: Default::default(),
+                num_rows: 0,
             },
             Arc::new(crate::test_util::access_layer_util::MockAccessLayer {}),
             new_noop_file_purger(),
@@ -253,6 +259,7 @@ mod tests {
             files,
             lower_sec_inclusive,
             upper_sec_exclusive,
+            true,
         )
         .await
         .unwrap();
@@ -330,7 +337,7 @@ mod tests {
         sst_layer: AccessLayerRef,
     ) -> Vec<i64> {
         let mut timestamps = vec![];
-        let mut reader = build_sst_reader(schema, sst_layer, files, i64::MIN, i64::MAX)
+        let mut reader = build_sst_reader(schema, sst_layer, files, i64::MIN, i64::MAX, true)
             .await
             .unwrap();
         while let Some(chunk) = reader.next_chunk().await.unwrap() {
@@ -389,17 +396,18 @@ mod tests {
         let sst_layer = Arc::new(FsAccessLayer::new("./", object_store.clone()));
         let input_files = vec![file2, file1];
 
-        let reader1 = build_sst_reader(schema.clone(), sst_layer.clone(), &input_files, 0, 3)
+        let reader1 = build_sst_reader(schema.clone(), sst_layer.clone(), &input_files, 0, 3, true)
             .await
             .unwrap();
-        let reader2 = build_sst_reader(schema.clone(), sst_layer.clone(), &input_files, 3, 6)
-            .await
-            .unwrap();
-        let reader3 = build_sst_reader(schema.clone(), sst_layer.clone(), &input_files, 6, 10)
+        let reader2 = build_sst_reader(schema.clone(), sst_layer.clone(), &input_files, 3, 6, true)
             .await
             .unwrap();
+        let reader3 =
+            build_sst_reader(schema.clone(), sst_layer.clone(), &input_files, 6, 10, true)
+                .await
+                .unwrap();
 
-        let opts = WriteOptions {};
+        let opts = WriteOptions::default();
         let s1 = ParquetWriter::new(
             "./o1.parquet",
             Source::Reader(reader1),
@@ -409,13 +417,11 @@ mod tests {
         .await
         .unwrap();
         assert_eq!(
-            SstInfo {
-                time_range: Some((
-                    Timestamp::new_millisecond(2000),
-                    Timestamp::new_millisecond(2000)
-                )),
-            },
-            s1
+            Some((
+                Timestamp::new_millisecond(2000),
+                Timestamp::new_millisecond(2000)
+            )),
+            s1.time_range
         );
 
         let s2 = ParquetWriter::new(
@@ -427,13 +433,11 @@ mod tests {
         .await
         .unwrap();
         assert_eq!(
-            SstInfo {
-                time_range: Some((
-                    Timestamp::new_millisecond(3000),
-                    Timestamp::new_millisecond(5002)
-                )),
-            },
-            s2
+            Some((
+                Timestamp::new_millisecond(3000),
+                Timestamp::new_millisecond(5002)
+            )),
+            s2.time_range
         );
 
         let s3 = ParquetWriter::new(
@@ -446,13 +450,11 @@ mod tests {
         .unwrap();
 
         assert_eq!(
-            SstInfo {
-                time_range: Some((
-                    Timestamp::new_millisecond(6000),
-                    Timestamp::new_millisecond(8000)
-                )),
-            },
-            s3
+            Some((
+                Timestamp::new_millisecond(6000),
+                Timestamp::new_millisecond(8000)
+            )),
+            s3.time_range
         );
 
         let output_files = ["o1.parquet", "o2.parquet", "o3.parquet"]
@@ -464,6 +466,9 @@ mod tests {
                         file_name: f.to_string(),
                         level: 1,
                         time_range: None,
+                        column_stats: Default::default(),
+                        index_columns: Default::default(),
+                        num_rows: 0,
                     },
                     Arc::new(crate::test_util::access_layer_util::MockAccessLayer {}),
                     new_noop_file_purger(),
@@ -477,4 +482,84 @@ mod tests {
 
         assert_eq!(timestamps_in_outputs, timestamps_in_inputs);
     }
+
+    #[tokio::test]
+    async fn test_sst_keep_deleted() {
+        let dir = TempDir::new("write_parquet").unwrap();
+        let path = dir.path().to_str().unwrap();
+        let backend = Fs::default().root(path).build().unwrap();
+        let object_store = ObjectStore::new(backend).finish();
+
+        let schema = schema_for_test();
+        let seq = AtomicU64::new(0);
+        let file1 = write_sst(
+            "i1.parquet",
+            schema.clone(),
+            &seq,
+            object_store.clone(),
+            &[1000, 2000],
+            &[OpType::Put, OpType::Put],
+        )
+        .await;
+        // file2 deletes the row with timestamp 1000 in file1.
+        let file2 = write_sst(
+            "i2.parquet",
+            schema.clone(),
+            &seq,
+            object_store.clone(),
+            &[1000, 3000],
+            &[OpType::Delete, OpType::Put],
+        )
+        .await;
+        let sst_layer = Arc::new(FsAccessLayer::new("./", object_store.clone()));
+
+        let reader = build_sst_reader(
+            schema.clone(),
+            sst_layer.clone(),
+            &[file2.clone()],
+            0,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+        let num_deleted = reader.num_deleted();
+        let sst_info = ParquetWriter::new("./o1.parquet", Source::Reader(reader), object_store)
+            .write_sst(&WriteOptions::default())
+            .await
+            .unwrap();
+        // The tombstone is kept in the output.
+        assert_eq!(
+            Some((
+                Timestamp::new_millisecond(1000),
+                Timestamp::new_millisecond(3000)
+            )),
+            sst_info.time_range
+        );
+        assert_eq!(0, num_deleted.load(Ordering::Relaxed));
+
+        let output = FileHandle::new(
+            FileMeta {
+                region_id: 0,
+                file_name: "o1.parquet".to_string(),
+                level: 1,
+                time_range: sst_info.time_range,
+                column_stats: Default::default(),
+                index_columns: Default::default(),
+                num_rows: 0,
+            },
+            Arc::new(crate::test_util::access_layer_util::MockAccessLayer {}),
+            new_noop_file_purger(),
+        );
+        // So it still shadows the row in the older file.
+        let timestamps = read_file(&[output, file1], schema.clone(), sst_layer.clone()).await;
+        assert_eq!(vec![2000, 3000], timestamps);
+
+        let mut reader = build_sst_reader(schema, sst_layer, &[file2], 0, 10, true)
+            .await
+            .unwrap();
+        let num_deleted = reader.num_deleted();
+        while reader.next_chunk().await.unwrap().is_some() {}
+        assert_eq!(1, num_deleted.load(Ordering::Relaxed));
+    }
 }
@@ -22,7 +22,8 @@ use object_store::{util, ObjectStore};
 use snafu::ResultExt;
 use store_api::logstore::LogStore;
 use store_api::storage::{
-    CreateOptions, EngineContext, OpenOptions, Region, RegionDescriptor, StorageEngine,
+    CompactionOptions, CreateOptions, EngineContext, IndexOptions, MemtableType, OpenOptions,
+    Region, RegionDescriptor, StorageEngine,
 };
 
 use crate::background::JobPoolImpl;
@@ -32,7 +33,7 @@ use crate::error::{self, Error, Result};
 use crate::file_purger::{FilePurgeHandler, FilePurgerRef};
 use crate::flush::{FlushSchedulerImpl, FlushSchedulerRef, FlushStrategyRef, SizeBasedStrategy};
 use crate::manifest::region::RegionManifest;
-use crate::memtable::{DefaultMemtableBuilder, MemtableBuilderRef};
+use crate::memtable::{DefaultMemtableBuilder, MemtableBuilderRef, SeriesMemtableBuilder};
 use crate::metadata::RegionMetadata;
 use crate::region::{RegionImpl, StoreConfig};
 use crate::scheduler::{LocalScheduler, SchedulerConfig};
@@ -78,8 +79,8 @@ impl<S: LogStore> StorageEngine for EngineImpl<S> {
         self.inner.create_region(descriptor, opts).await
     }
 
-    async fn drop_region(&self, _ctx: &EngineContext, _region: Self::Region) -> Result<()> {
-        unimplemented!()
+    async fn drop_region(&self, _ctx: &EngineContext, region: Self::Region) -> Result<()> {
+        self.inner.drop_region(region).await
     }
 
     fn get_region(&self, _ctx: &EngineContext, name: &str) -> Result<Option<Self::Region>> {
@@ -222,6 +223,7 @@ struct EngineInner<S: LogStore> {
     log_store: Arc<S>,
     regions: RwLock<RegionMap<S>>,
     memtable_builder: MemtableBuilderRef,
+    series_memtable_builder: MemtableBuilderRef,
     flush_scheduler: FlushSchedulerRef,
     flush_strategy: FlushStrategyRef,
     compaction_scheduler: CompactionSchedulerRef<S>,
@@ -250,6 +252,7 @@ impl<S: LogStore> EngineInner<S> {
             log_store,
             regions: RwLock::new(Default::default()),
             memtable_builder: Arc::new(DefaultMemtableBuilder::default()),
+            series_memtable_builder: Arc::new(SeriesMemtableBuilder::default()),
             flush_scheduler,
             flush_strategy: Arc::new(SizeBasedStrategy::default()),
             compaction_scheduler,
@@ -290,8 +293,15 @@ impl<S: LogStore> EngineInner<S> {
 
         let mut guard = SlotGuard::new(name, &self.regions);
 
-        let store_config =
-            self.region_store_config(&opts.parent_dir, opts.write_buffer_size, name, opts.ttl);
+        let store_config = self.region_store_config(
+            &opts.parent_dir,
+            opts.write_buffer_size,
+            name,
+            opts.ttl,
+            opts.compaction_options.clone(),
+            opts.index_options.clone(),
+            opts.memtable_type,
+        );
 
         let region = match RegionImpl::open(name.to_string(), store_config, opts).await? {
             None => return Ok(None),
@@ -326,6 +336,9 @@ impl<S: LogStore> EngineInner<S> {
             opts.write_buffer_size,
             &region_name,
             opts.ttl,
+            opts.compaction_options.clone(),
+            opts.index_options.clone(),
+            opts.memtable_type,
         );
 
         let region = RegionImpl::create(metadata, store_config).await?;
@@ -337,6 +350,15 @@ impl<S: LogStore> EngineInner<S> {
         Ok(region)
     }
 
+    async fn drop_region(&self, region: RegionImpl<S>) -> Result<()> {
+        region.close().await?;
+        self.regions.write().unwrap().remove(region.name());
+        region.delete_files().await?;
+
+        info!("Storage engine drop region {}", region.id());
+        Ok(())
+    }
+
     fn get_region(&self, name: &str) -> Option<RegionImpl<S>> {
         let slot = self.regions.read().unwrap().get(name).cloned()?;
         slot.get_ready_region()
@@ -348,6 +370,9 @@ impl<S: LogStore> EngineInner<S> {
         write_buffer_size: Option<usize>,
         region_name: &str,
         ttl: Option<Duration>,
+        compaction_options: CompactionOptions,
+        index_options: IndexOptions,
+        memtable_type: MemtableType,
     ) -> StoreConfig<S> {
         let parent_dir = util::normalize_dir(parent_dir);
 
@@ -359,18 +384,24 @@ impl<S: LogStore> EngineInner<S> {
         let flush_strategy = write_buffer_size
             .map(|size| Arc::new(SizeBasedStrategy::new(size)) as Arc<_>)
             .unwrap_or_else(|| self.flush_strategy.clone());
+        let memtable_builder = match memtable_type {
+            MemtableType::BTree => self.memtable_builder.clone(),
+            MemtableType::Series => self.series_memtable_builder.clone(),
+        };
 
         StoreConfig {
             log_store: self.log_store.clone(),
             sst_layer,
             manifest,
-            memtable_builder: self.memtable_builder.clone(),
+            memtable_builder,
             flush_scheduler: self.flush_scheduler.clone(),
             flush_strategy,
             compaction_scheduler: self.compaction_scheduler.clone(),
             engine_config: self.config.clone(),
             file_purger: self.file_purger.clone(),
             ttl,
+            compaction_options,
+            index_options,
         }
     }
 }
@@ -427,4 +458,48 @@ mod tests {
 
         assert!(engine.get_region(&ctx, "no such region").unwrap().is_none());
     }
+
+    #[tokio::test]
+    async fn test_drop_region() {
+        let (log_store, _tmp) =
+            log_store_util::create_tmp_local_file_log_store("test_engine_wal").await;
+        let dir = TempDir::new("test_drop_region").unwrap();
+        let store_dir = dir.path().to_string_lossy();
+
+        let accessor = Fs::default().root(&store_dir).build().unwrap();
+        let object_store = ObjectStore::new(accessor).finish();
+
+        let compaction_scheduler = Arc::new(NoopCompactionScheduler::default());
+
+        let engine = EngineImpl::new(
+            EngineConfig::default(),
+            Arc::new(log_store),
+            object_store,
+            compaction_scheduler,
+        );
+
+        let region_name = "region-0";
+        let desc = RegionDescBuilder::new(region_name)
+            .push_key_column(("k1", LogicalTypeId::Int32, false))
+            .push_value_column(("v1", LogicalTypeId::Float32, true))
+            .build();
+        let ctx = EngineContext::default();
+        let region = engine
+            .create_region(&ctx, desc, &CreateOptions::default())
+            .await
+            .unwrap();
+        let region_dir = dir.path().join(region_name);
+        assert!(region_dir.exists());
+
+        engine.drop_region(&ctx, region).await.unwrap();
+        assert!(engine.get_region(&ctx, region_name).unwrap().is_none());
+        assert!(!region_dir.exists());
+
+        let opts = OpenOptions::default();
+        assert!(engine
+            .open_region(&ctx, region_name, &opts)
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
@@ -328,6 +328,19 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
+    #[snafu(display(
+        "Failed to cast column {} to {:?}, source: {}",
+        column,
+        data_type,
+        source
+    ))]
+    CastColumn {
+        column: String,
+        data_type: ConcreteDataType,
+        #[snafu(backtrace)]
+        source: datatypes::error::Error,
+    },
+
     #[snafu(display(
         "Failed to convert arrow chunk to batch, name: {}, source: {}",
         name,
@@ -417,6 +430,9 @@ pub enum Error {
     #[snafu(display("Failed to decode parquet file time range, msg: {}", msg))]
     DecodeParquetTimeRange { msg: String, backtrace: Backtrace },
 
+    #[snafu(display("Failed to decode SST index, msg: {}", msg))]
+    DecodeSstIndex { msg: String, backtrace: Backtrace },
+
     #[snafu(display("Scheduler rate limited, msg: {}", msg))]
     RateLimited { msg: String, backtrace: Backtrace },
 
@@ -440,6 +456,23 @@ pub enum Error {
         #[snafu(backtrace)]
         source: common_time::error::Error,
     },
+
+    #[snafu(display("Compaction of region {} is already scheduled", region_id))]
+    CompactionScheduled {
+        region_id: RegionId,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display(
+        "Failed to wait for compaction of region {}, source: {}",
+        region_id,
+        source
+    ))]
+    WaitCompaction {
+        region_id: RegionId,
+        source: tokio::sync::oneshot::error::RecvError,
+        backtrace: Backtrace,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -480,6 +513,7 @@ impl ErrorExt for Error {
             | CompatRead { .. }
             | CreateDefaultToRead { .. }
             | NoDefaultToRead { .. }
+            | CastColumn { .. }
             | NewRecordBatch { .. }
             | BatchCorrupted { .. }
             | DecodeArrow { .. }
@@ -509,12 +543,13 @@ impl ErrorExt for Error {
             CreateDefault { source, .. } => source.status_code(),
             ConvertChunk { source, .. } => source.status_code(),
             MarkWalObsolete { source, .. } => source.status_code(),
-            DecodeParquetTimeRange { .. } => StatusCode::Unexpected,
+            DecodeParquetTimeRange { .. } | DecodeSstIndex { .. } => StatusCode::Unexpected,
             RateLimited { .. } => StatusCode::Internal,
             StopScheduler { .. } => StatusCode::Internal,
             DeleteSst { .. } => StatusCode::StorageUnavailable,
             IllegalSchedulerState { .. } => StatusCode::Unexpected,
             TtlCalculation { source, .. } => source.status_code(),
+            CompactionScheduled { .. } | WaitCompaction { .. } => StatusCode::Internal,
         }
     }
 
@@ -144,7 +144,7 @@ mod tests {
         let sst_path = "table1";
         let layer = Arc::new(FsAccessLayer::new(sst_path, os.clone()));
         let _sst_info = layer
-            .write_sst(sst_file_name, Source::Iter(iter), &WriteOptions {})
+            .write_sst(sst_file_name, Source::Iter(iter), &WriteOptions::default())
             .await
             .unwrap();
 
@@ -155,6 +155,9 @@ mod tests {
                     file_name: sst_file_name.to_string(),
                     time_range: None,
                     level: 0,
+                    column_stats: Default::default(),
+                    index_columns: Default::default(),
+                    num_rows: 0,
                 },
                 layer.clone(),
                 file_purger,
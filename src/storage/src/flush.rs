@@ -20,7 +20,7 @@ use async_trait::async_trait;
 use common_telemetry::logging;
 use store_api::logstore::LogStore;
 use store_api::storage::consts::WRITE_ROW_GROUP_SIZE;
-use store_api::storage::SequenceNumber;
+use store_api::storage::{IndexOptions, SequenceNumber};
 use uuid::Uuid;
 
 use crate::background::{Context, Job, JobHandle, JobPoolRef};
@@ -173,6 +173,8 @@ pub struct FlushJob<S: LogStore> {
     pub wal: Wal<S>,
     /// Region manifest service, used to persist metadata.
     pub manifest: RegionManifest,
+    /// Options to build indexes of the SST files.
+    pub index_options: IndexOptions,
     /// Callbacks that get invoked on flush success.
     pub on_success: Option<FlushCallback>,
 }
@@ -201,10 +203,18 @@ impl<S: LogStore> FlushJob<S> {
             // TODO(hl): Check if random file name already exists in meta.
             let iter = m.iter(&iter_ctx)?;
             let sst_layer = self.sst_layer.clone();
+            let write_opts = WriteOptions {
+                index_options: self.index_options.clone(),
+            };
 
             futures.push(async move {
-                let SstInfo { time_range } = sst_layer
-                    .write_sst(&file_name, Source::Iter(iter), &WriteOptions::default())
+                let SstInfo {
+                    time_range,
+                    column_stats,
+                    index_columns,
+                    num_rows,
+                } = sst_layer
+                    .write_sst(&file_name, Source::Iter(iter), &write_opts)
                     .await?;
 
                 Ok(FileMeta {
@@ -212,6 +222,9 @@ impl<S: LogStore> FlushJob<S> {
                     file_name,
                     time_range,
                     level: 0,
+                    column_stats,
+                    index_columns,
+                    num_rows,
                 })
             });
         }
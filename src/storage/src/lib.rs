@@ -25,6 +25,7 @@ mod flush;
 pub mod manifest;
 pub mod memtable;
 pub mod metadata;
+pub mod metric;
 pub mod proto;
 pub mod read;
 pub mod region;
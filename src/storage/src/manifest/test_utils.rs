@@ -45,6 +45,9 @@ pub fn build_region_edit(
                 file_name: f.to_string(),
                 time_range: None,
                 level: 0,
+                column_stats: Default::default(),
+                index_columns: Default::default(),
+                num_rows: 0,
             })
             .collect(),
         files_to_remove: files_to_remove
@@ -54,6 +57,9 @@ pub fn build_region_edit(
                 file_name: f.to_string(),
                 time_range: None,
                 level: 0,
+                column_stats: Default::default(),
+                index_columns: Default::default(),
+                num_rows: 0,
             })
             .collect(),
     }
@@ -14,6 +14,7 @@
 
 mod btree;
 mod inserter;
+mod series;
 #[cfg(test)]
 pub mod tests;
 mod version;
@@ -27,6 +28,7 @@ use store_api::storage::{consts, OpType, SequenceNumber};
 use crate::error::Result;
 use crate::memtable::btree::BTreeMemtable;
 pub use crate::memtable::inserter::Inserter;
+pub use crate::memtable::series::SeriesMemtable;
 pub use crate::memtable::version::MemtableVersion;
 use crate::read::Batch;
 use crate::schema::{ProjectedSchemaRef, RegionSchemaRef};
@@ -171,3 +173,16 @@ impl MemtableBuilder for DefaultMemtableBuilder {
         Arc::new(BTreeMemtable::new(id, schema))
     }
 }
+
+/// Builder to build [SeriesMemtable].
+#[derive(Debug, Default)]
+pub struct SeriesMemtableBuilder {
+    memtable_id: AtomicU32,
+}
+
+impl MemtableBuilder for SeriesMemtableBuilder {
+    fn build(&self, schema: RegionSchemaRef) -> MemtableRef {
+        let id = self.memtable_id.fetch_add(1, Ordering::Relaxed);
+        Arc::new(SeriesMemtable::new(id, schema))
+    }
+}
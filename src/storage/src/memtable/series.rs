@@ -0,0 +1,606 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Bound;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, RwLock};
+
+use datatypes::data_type::DataType;
+use datatypes::prelude::*;
+use datatypes::value::Value;
+use datatypes::vectors::{MutableVector, UInt64VectorBuilder, UInt8VectorBuilder};
+use store_api::storage::{OpType, SequenceNumber};
+
+use crate::error::Result;
+use crate::memtable::{
+    BatchIterator, BoxedBatchIterator, IterContext, KeyValues, Memtable, MemtableId, RowOrdering,
+};
+use crate::read::Batch;
+use crate::schema::compat::ReadAdapter;
+use crate::schema::{ProjectedSchema, ProjectedSchemaRef, RegionSchemaRef};
+
+/// Maps the key of a series, i.e. values of the row key columns before the timestamp
+/// column, to its rows.
+type RwLockSeriesMap = RwLock<BTreeMap<Vec<Value>, Series>>;
+
+/// A memtable optimized for time series data.
+///
+/// Rows are grouped by series, so the key of a series is stored only once, and the
+/// remaining columns of each series are stored in typed columnar buffers. The sorted order
+/// of the rows in a series is cached, and only rows written since the last read are sorted
+/// and merged into it when reading.
+pub struct SeriesMemtable {
+    id: MemtableId,
+    schema: RegionSchemaRef,
+    map: Arc<RwLockSeriesMap>,
+    estimated_bytes: AtomicUsize,
+    num_rows: AtomicUsize,
+}
+
+impl SeriesMemtable {
+    pub fn new(id: MemtableId, schema: RegionSchemaRef) -> SeriesMemtable {
+        SeriesMemtable {
+            id,
+            schema,
+            map: Arc::new(RwLock::new(BTreeMap::new())),
+            estimated_bytes: AtomicUsize::new(0),
+            num_rows: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl fmt::Debug for SeriesMemtable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let series = self.map.read().unwrap().len();
+
+        f.debug_struct("SeriesMemtable")
+            .field("id", &self.id)
+            .field("schema", &self.schema)
+            .field("series", &series)
+            .field("rows", &self.num_rows)
+            .field("estimated_bytes", &self.estimated_bytes)
+            .finish()
+    }
+}
+
+impl Memtable for SeriesMemtable {
+    fn id(&self) -> MemtableId {
+        self.id
+    }
+
+    fn schema(&self) -> RegionSchemaRef {
+        self.schema.clone()
+    }
+
+    fn write(&self, kvs: &KeyValues) -> Result<()> {
+        self.estimated_bytes
+            .fetch_add(kvs.estimated_memory_size(), AtomicOrdering::Relaxed);
+
+        let series_key_end = self.schema.timestamp_key_index();
+        let mut map = self.map.write().unwrap();
+        for row in 0..kvs.len() {
+            let key: Vec<Value> = kvs.keys[..series_key_end]
+                .iter()
+                .map(|vector| vector.get(row))
+                .collect();
+            let series = map
+                .entry(key)
+                .or_insert_with(|| Series::new(kvs, series_key_end));
+            series.push(kvs, row, series_key_end);
+        }
+        self.num_rows.fetch_add(kvs.len(), AtomicOrdering::Relaxed);
+
+        Ok(())
+    }
+
+    fn iter(&self, ctx: &IterContext) -> Result<BoxedBatchIterator> {
+        assert!(ctx.batch_size > 0);
+
+        // The iterator only reads frozen rows. Rows written after freezing are invisible to
+        // it as their sequences are greater than its visible sequence, and memtables to flush
+        // are immutable.
+        for series in self.map.write().unwrap().values_mut() {
+            series.freeze();
+        }
+        let iter = SeriesIterator::new(ctx.clone(), self.schema.clone(), self.map.clone())?;
+
+        Ok(Box::new(iter))
+    }
+
+    fn bytes_allocated(&self) -> usize {
+        self.estimated_bytes.load(AtomicOrdering::Relaxed)
+    }
+
+    fn num_rows(&self) -> usize {
+        self.num_rows.load(AtomicOrdering::Relaxed)
+    }
+}
+
+/// Position of a frozen row in a series, i.e. the index of its chunk and its index in
+/// the chunk.
+type RowPosition = (usize, usize);
+
+#[derive(Clone, Copy)]
+struct RowMeta {
+    sequence: SequenceNumber,
+    index_in_batch: usize,
+    op_type: OpType,
+}
+
+/// Immutable rows of a series.
+struct Chunk {
+    /// Row key columns after the series key, that is the timestamp column and the
+    /// optional version column, followed by the value columns.
+    columns: Vec<VectorRef>,
+    metas: Vec<RowMeta>,
+}
+
+/// Rows of a series in columnar format.
+///
+/// Rows are appended to the builders on write, and frozen into an immutable chunk before
+/// being read.
+struct Series {
+    /// Number of row key columns after the series key.
+    num_time_keys: usize,
+    /// Builders of the columns of rows written since the last freeze, in the same order as
+    /// the columns of chunks.
+    builders: Vec<Box<dyn MutableVector>>,
+    metas: Vec<RowMeta>,
+    chunks: Vec<Chunk>,
+    /// Positions of all frozen rows in the same order as the btree memtable: by (time key
+    /// asc, sequence desc, index_in_batch desc, op_type desc).
+    sorted: Vec<RowPosition>,
+}
+
+impl Series {
+    fn new(kvs: &KeyValues, series_key_end: usize) -> Series {
+        let builders = kvs.keys[series_key_end..]
+            .iter()
+            .chain(&kvs.values)
+            .map(|vector| vector.data_type().create_mutable_vector(1))
+            .collect();
+        Series {
+            num_time_keys: kvs.keys.len() - series_key_end,
+            builders,
+            metas: Vec::new(),
+            chunks: Vec::new(),
+            sorted: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, kvs: &KeyValues, row: usize, series_key_end: usize) {
+        let vectors = kvs.keys[series_key_end..].iter().chain(&kvs.values);
+        for (builder, vector) in self.builders.iter_mut().zip(vectors) {
+            builder.push_value_ref(vector.get_ref(row));
+        }
+        self.metas.push(RowMeta {
+            sequence: kvs.sequence,
+            index_in_batch: kvs.start_index_in_batch + row,
+            op_type: kvs.op_type,
+        });
+    }
+
+    /// Freezes rows written since the last freeze into a chunk, and merges them into the
+    /// sorted order.
+    fn freeze(&mut self) {
+        if self.metas.is_empty() {
+            return;
+        }
+
+        let chunk_index = self.chunks.len();
+        self.chunks.push(Chunk {
+            columns: self
+                .builders
+                .iter_mut()
+                .map(|builder| builder.to_vector())
+                .collect(),
+            metas: std::mem::take(&mut self.metas),
+        });
+        let mut new_rows = (0..self.chunks[chunk_index].metas.len())
+            .map(|row| (chunk_index, row))
+            .collect::<Vec<_>>();
+        new_rows.sort_unstable_by(|left, right| self.cmp_row(*left, *right));
+
+        // Both are sorted, so they are merged instead of sorting all rows again.
+        let old_rows = std::mem::take(&mut self.sorted);
+        let mut sorted = Vec::with_capacity(old_rows.len() + new_rows.len());
+        let mut old_rows = old_rows.into_iter().peekable();
+        for new_row in new_rows {
+            while let Some(old_row) =
+                old_rows.next_if(|old_row| self.cmp_row(*old_row, new_row).is_le())
+            {
+                sorted.push(old_row);
+            }
+            sorted.push(new_row);
+        }
+        sorted.extend(old_rows);
+        self.sorted = sorted;
+    }
+
+    fn meta(&self, (chunk, row): RowPosition) -> &RowMeta {
+        &self.chunks[chunk].metas[row]
+    }
+
+    /// Returns the value of the `column`th column of the chunk at the frozen row `position`.
+    #[inline]
+    fn value_at(&self, (chunk, row): RowPosition, column: usize) -> ValueRef {
+        self.chunks[chunk].columns[column].get_ref(row)
+    }
+
+    #[inline]
+    fn cmp_time_key(&self, left: RowPosition, right: RowPosition) -> Ordering {
+        (0..self.num_time_keys)
+            .map(|column| {
+                self.value_at(left, column)
+                    .cmp(&self.value_at(right, column))
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    fn cmp_row(&self, left: RowPosition, right: RowPosition) -> Ordering {
+        let (left_meta, right_meta) = (self.meta(left), self.meta(right));
+        self.cmp_time_key(left, right)
+            .then_with(|| right_meta.sequence.cmp(&left_meta.sequence))
+            .then_with(|| right_meta.index_in_batch.cmp(&left_meta.index_in_batch))
+            .then_with(|| right_meta.op_type.cmp(&left_meta.op_type))
+    }
+
+    /// Returns the positions of frozen rows to read, in the sorted order.
+    ///
+    /// If not `for_flush`, invisible rows are skipped and only the first row of each time
+    /// key is returned.
+    fn rows_to_read(&self, ctx: &IterContext) -> Vec<RowPosition> {
+        if ctx.for_flush {
+            return self.sorted.clone();
+        }
+
+        let mut rows = self
+            .sorted
+            .iter()
+            .copied()
+            .filter(|row| self.meta(*row).sequence <= ctx.visible_sequence)
+            .collect::<Vec<_>>();
+        rows.dedup_by(|current, prev| self.cmp_time_key(*prev, *current).is_eq());
+        rows
+    }
+}
+
+/// Position of the iterator in a series.
+struct SeriesCursor {
+    key: Vec<Value>,
+    /// Rows to read, computed when the iterator reaches the series.
+    rows: Vec<RowPosition>,
+    /// Offset of the next row to read in `rows`.
+    offset: usize,
+}
+
+struct SeriesIterator {
+    ctx: IterContext,
+    /// Schema of this memtable.
+    schema: RegionSchemaRef,
+    /// Projected schema that user expect to read.
+    projected_schema: ProjectedSchemaRef,
+    adapter: ReadAdapter,
+    map: Arc<RwLockSeriesMap>,
+    cursor: Option<SeriesCursor>,
+}
+
+impl BatchIterator for SeriesIterator {
+    fn schema(&self) -> ProjectedSchemaRef {
+        self.projected_schema.clone()
+    }
+
+    fn ordering(&self) -> RowOrdering {
+        RowOrdering::Key
+    }
+}
+
+impl Iterator for SeriesIterator {
+    type Item = Result<Batch>;
+
+    fn next(&mut self) -> Option<Result<Batch>> {
+        self.next_batch().transpose()
+    }
+}
+
+impl SeriesIterator {
+    fn new(
+        ctx: IterContext,
+        schema: RegionSchemaRef,
+        map: Arc<RwLockSeriesMap>,
+    ) -> Result<SeriesIterator> {
+        let projected_schema = ctx
+            .projected_schema
+            .clone()
+            .unwrap_or_else(|| Arc::new(ProjectedSchema::no_projection(schema.clone())));
+        let adapter = ReadAdapter::new(schema.store_schema().clone(), projected_schema.clone())?;
+
+        Ok(SeriesIterator {
+            ctx,
+            schema,
+            projected_schema,
+            adapter,
+            map,
+            cursor: None,
+        })
+    }
+
+    fn next_batch(&mut self) -> Result<Option<Batch>> {
+        let batch_size = self.ctx.batch_size;
+        let series_key_end = self.schema.timestamp_key_index();
+        let mut key_builders = new_builders(
+            self.schema
+                .row_key_columns()
+                .map(|column_meta| &column_meta.desc.data_type),
+            self.adapter.source_key_needed(),
+            batch_size,
+        );
+        let mut value_builders = new_builders(
+            self.schema
+                .value_columns()
+                .map(|column_meta| &column_meta.desc.data_type),
+            self.adapter.source_value_needed(),
+            batch_size,
+        );
+        let mut sequences = UInt64VectorBuilder::with_capacity(batch_size);
+        let mut op_types = UInt8VectorBuilder::with_capacity(batch_size);
+
+        let map = self.map.clone();
+        let map = map.read().unwrap();
+        let mut num_rows = 0;
+        while num_rows < batch_size {
+            let exhausted = self
+                .cursor
+                .as_ref()
+                .map(|cursor| cursor.offset >= cursor.rows.len())
+                .unwrap_or(true);
+            if exhausted && !self.seek_next_series(&map) {
+                break;
+            }
+
+            let cursor = self.cursor.as_mut().unwrap();
+            let series = &map[&cursor.key];
+            let end = cursor.rows.len().min(cursor.offset + batch_size - num_rows);
+            for row in &cursor.rows[cursor.offset..end] {
+                let row = *row;
+                for (idx, builder) in key_builders.iter_mut().enumerate() {
+                    if let Some(builder) = builder {
+                        let value = if idx < series_key_end {
+                            cursor.key[idx].as_value_ref()
+                        } else {
+                            series.value_at(row, idx - series_key_end)
+                        };
+                        builder.push_value_ref(value);
+                    }
+                }
+                for (idx, builder) in value_builders.iter_mut().enumerate() {
+                    if let Some(builder) = builder {
+                        builder.push_value_ref(series.value_at(row, series.num_time_keys + idx));
+                    }
+                }
+                let meta = series.meta(row);
+                sequences.push(Some(meta.sequence));
+                op_types.push(Some(meta.op_type.as_u8()));
+            }
+            num_rows += end - cursor.offset;
+            cursor.offset = end;
+        }
+
+        if num_rows == 0 {
+            return Ok(None);
+        }
+
+        let batch = self.adapter.batch_from_parts(
+            finish_builders(key_builders),
+            finish_builders(value_builders),
+            Arc::new(sequences.finish()),
+            Arc::new(op_types.finish()),
+        )?;
+
+        Ok(Some(batch))
+    }
+
+    /// Moves the cursor to the next series that has rows to read, returns false if there
+    /// is no more series.
+    fn seek_next_series(&mut self, map: &BTreeMap<Vec<Value>, Series>) -> bool {
+        loop {
+            let next = match &self.cursor {
+                Some(cursor) => map
+                    .range::<Vec<Value>, _>((Bound::Excluded(&cursor.key), Bound::Unbounded))
+                    .next(),
+                None => map.iter().next(),
+            };
+            let Some((key, series)) = next else {
+                return false;
+            };
+
+            let rows = series.rows_to_read(&self.ctx);
+            let has_rows = !rows.is_empty();
+            self.cursor = Some(SeriesCursor {
+                key: key.clone(),
+                rows,
+                offset: 0,
+            });
+            if has_rows {
+                return true;
+            }
+        }
+    }
+}
+
+fn new_builders<'a, I: Iterator<Item = &'a ConcreteDataType>>(
+    data_types: I,
+    column_needed: &[bool],
+    capacity: usize,
+) -> Vec<Option<Box<dyn MutableVector>>> {
+    data_types
+        .zip(column_needed)
+        .map(|(data_type, needed)| {
+            if *needed {
+                Some(data_type.create_mutable_vector(capacity))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn finish_builders(builders: Vec<Option<Box<dyn MutableVector>>>) -> Vec<VectorRef> {
+    builders
+        .into_iter()
+        .flatten()
+        .map(|mut builder| builder.to_vector())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::type_id::LogicalTypeId;
+    use datatypes::vectors::{
+        StringVector, TimestampMillisecondVector, UInt64Vector, UInt8Vector, VectorRef,
+    };
+
+    use super::*;
+    use crate::metadata::RegionMetadata;
+    use crate::test_util::descriptor_util::RegionDescBuilder;
+
+    // Schema: key: String(host), Timestamp(timestamp), value: UInt64(v0)
+    fn schema_with_tag() -> RegionSchemaRef {
+        let desc = RegionDescBuilder::new("test")
+            .push_key_column(("host", LogicalTypeId::String, false))
+            .push_value_column(("v0", LogicalTypeId::UInt64, true))
+            .build();
+        let metadata: RegionMetadata = desc.try_into().unwrap();
+
+        metadata.schema().clone()
+    }
+
+    fn write_rows(memtable: &SeriesMemtable, sequence: SequenceNumber, rows: &[(&str, i64, u64)]) {
+        let hosts = rows.iter().map(|row| row.0).collect::<Vec<_>>();
+        let timestamps = rows.iter().map(|row| row.1).collect::<Vec<_>>();
+        let values = rows.iter().map(|row| row.2).collect::<Vec<_>>();
+        let kvs = KeyValues {
+            sequence,
+            op_type: OpType::Put,
+            start_index_in_batch: 0,
+            keys: vec![
+                Arc::new(StringVector::from(hosts)) as _,
+                Arc::new(TimestampMillisecondVector::from_vec(timestamps)) as _,
+            ],
+            values: vec![Arc::new(UInt64Vector::from_vec(values)) as _],
+        };
+
+        memtable.write(&kvs).unwrap();
+    }
+
+    #[test]
+    fn test_series_memtable_group_by_series() {
+        let memtable = SeriesMemtable::new(0, schema_with_tag());
+        write_rows(
+            &memtable,
+            1,
+            &[("b", 2, 20), ("a", 3, 30), ("b", 1, 10), ("a", 1, 11)],
+        );
+        write_rows(&memtable, 2, &[("a", 3, 31), ("c", 1, 1)]);
+        assert_eq!(6, memtable.num_rows());
+        assert_eq!(3, memtable.map.read().unwrap().len());
+
+        let iter_ctx = IterContext {
+            batch_size: 2,
+            ..Default::default()
+        };
+        let num_rows = memtable
+            .iter(&iter_ctx)
+            .unwrap()
+            .map(|batch| batch.unwrap().num_rows())
+            .collect::<Vec<_>>();
+        assert_eq!(vec![2, 2, 1], num_rows);
+
+        let mut iter = memtable.iter(&IterContext::default()).unwrap();
+        let batch = iter.next().unwrap().unwrap();
+        assert!(iter.next().is_none());
+        let expect: VectorRef = Arc::new(StringVector::from(vec!["a", "a", "b", "b", "c"]));
+        assert_eq!(expect, *batch.column(0));
+        let expect: VectorRef = Arc::new(TimestampMillisecondVector::from_vec(vec![1, 3, 1, 2, 1]));
+        assert_eq!(expect, *batch.column(1));
+        // The newer value of ("a", 3) is returned.
+        let expect: VectorRef = Arc::new(UInt64Vector::from_vec(vec![11, 31, 10, 20, 1]));
+        assert_eq!(expect, *batch.column(2));
+        let expect: VectorRef = Arc::new(UInt64Vector::from_vec(vec![1, 2, 1, 1, 2]));
+        assert_eq!(expect, *batch.column(3));
+        let expect: VectorRef = Arc::new(UInt8Vector::from_vec(vec![1; 5]));
+        assert_eq!(expect, *batch.column(4));
+    }
+
+    #[test]
+    fn test_series_memtable_write_after_read() {
+        let memtable = SeriesMemtable::new(0, schema_with_tag());
+        write_rows(&memtable, 1, &[("a", 2, 20), ("a", 4, 40)]);
+        let iter = memtable.iter(&IterContext::default()).unwrap();
+        let num_rows: usize = iter.map(|batch| batch.unwrap().num_rows()).sum();
+        assert_eq!(2, num_rows);
+
+        // Rows written after reading are merged into the sorted rows.
+        write_rows(&memtable, 2, &[("a", 3, 30), ("a", 1, 10), ("a", 4, 41)]);
+        let mut iter = memtable.iter(&IterContext::default()).unwrap();
+        let batch = iter.next().unwrap().unwrap();
+        assert!(iter.next().is_none());
+        let expect: VectorRef = Arc::new(TimestampMillisecondVector::from_vec(vec![1, 2, 3, 4]));
+        assert_eq!(expect, *batch.column(1));
+        let expect: VectorRef = Arc::new(UInt64Vector::from_vec(vec![10, 20, 30, 41]));
+        assert_eq!(expect, *batch.column(2));
+
+        let iter_ctx = IterContext {
+            for_flush: true,
+            ..Default::default()
+        };
+        let mut iter = memtable.iter(&iter_ctx).unwrap();
+        let batch = iter.next().unwrap().unwrap();
+        let expect: VectorRef = Arc::new(UInt64Vector::from_vec(vec![10, 20, 30, 41, 40]));
+        assert_eq!(expect, *batch.column(2));
+    }
+
+    #[test]
+    fn test_series_memtable_for_flush() {
+        let memtable = SeriesMemtable::new(0, schema_with_tag());
+        write_rows(&memtable, 1, &[("a", 1, 10)]);
+        write_rows(&memtable, 2, &[("a", 1, 11), ("b", 1, 1)]);
+
+        let iter_ctx = IterContext {
+            for_flush: true,
+            ..Default::default()
+        };
+        let mut iter = memtable.iter(&iter_ctx).unwrap();
+        let batch = iter.next().unwrap().unwrap();
+        assert!(iter.next().is_none());
+        // Duplicate keys are kept and ordered by sequence desc.
+        let expect: VectorRef = Arc::new(UInt64Vector::from_vec(vec![11, 10, 1]));
+        assert_eq!(expect, *batch.column(2));
+
+        // Only the row of sequence 1 is visible.
+        let iter_ctx = IterContext {
+            visible_sequence: 1,
+            ..Default::default()
+        };
+        let mut iter = memtable.iter(&iter_ctx).unwrap();
+        let batch = iter.next().unwrap().unwrap();
+        assert!(iter.next().is_none());
+        let expect: VectorRef = Arc::new(UInt64Vector::from_vec(vec![10]));
+        assert_eq!(expect, *batch.column(2));
+    }
+}
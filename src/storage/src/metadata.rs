@@ -83,6 +83,34 @@ pub enum Error {
     #[snafu(display("Failed to drop column {} as it is an internal column", name))]
     DropInternalColumn { name: String },
 
+    #[snafu(display("Failed to modify column as there is no column named {}", name))]
+    ModifyAbsentColumn { name: String },
+
+    #[snafu(display("Failed to modify column {} as it is part of key", name))]
+    ModifyKeyColumn { name: String },
+
+    #[snafu(display(
+        "Failed to change the type of column {} from {:?} to {:?}",
+        name,
+        from,
+        to
+    ))]
+    IncompatibleColumnType {
+        name: String,
+        from: ConcreteDataType,
+        to: ConcreteDataType,
+    },
+
+    #[snafu(display("Failed to rename column as there is already a column named {}", name))]
+    RenameToExistColumn { name: String },
+
+    #[snafu(display("Invalid default value of column {}, source: {}", name, source))]
+    InvalidColumnDefault {
+        name: String,
+        #[snafu(backtrace)]
+        source: datatypes::error::Error,
+    },
+
     // End of variants for validating `AlterRequest`.
     #[snafu(display("Failed to convert to column schema, source: {}", source))]
     ToColumnSchema {
@@ -247,6 +275,23 @@ impl RegionMetadata {
                     self.validate_drop_column(name)?;
                 }
             }
+            AlterOperation::ModifyColumnType { name, data_type } => {
+                self.validate_modify_column_type(name, data_type)?;
+            }
+            AlterOperation::RenameColumn { name, new_name } => {
+                self.validate_rename_column(name, new_name)?;
+            }
+            AlterOperation::SetColumnDefault {
+                name,
+                default_constraint,
+            } => {
+                let column = self.user_column_by_name(name)?;
+                if let Some(constraint) = default_constraint {
+                    constraint
+                        .validate(&column.desc.data_type, column.desc.is_nullable())
+                        .context(InvalidColumnDefaultSnafu { name })?;
+                }
+            }
         }
 
         Ok(())
@@ -312,6 +357,58 @@ impl RegionMetadata {
         Ok(())
     }
 
+    fn validate_modify_column_type(&self, name: &str, data_type: &ConcreteDataType) -> Result<()> {
+        let column = self.value_column_by_name(name)?;
+        ensure!(
+            column.desc.data_type.can_widen_to(data_type),
+            IncompatibleColumnTypeSnafu {
+                name,
+                from: column.desc.data_type.clone(),
+                to: data_type.clone(),
+            }
+        );
+        // The default value is converted to the new type on alteration.
+        if let Some(constraint) = column.desc.default_constraint() {
+            constraint
+                .cast_to(data_type)
+                .and_then(|constraint| constraint.validate(data_type, column.desc.is_nullable()))
+                .context(InvalidColumnDefaultSnafu { name })?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_rename_column(&self, name: &str, new_name: &str) -> Result<()> {
+        let _ = self.value_column_by_name(name)?;
+        // Use the store schema to check the column as it contains all internal columns.
+        ensure!(
+            !self.schema.store_schema().contains_column(new_name),
+            RenameToExistColumnSnafu { name: new_name }
+        );
+
+        Ok(())
+    }
+
+    /// Returns the user column named `name`.
+    fn user_column_by_name(&self, name: &str) -> Result<&ColumnMetadata> {
+        self.columns
+            .iter_user_columns()
+            .find(|column| column.name() == name)
+            .context(ModifyAbsentColumnSnafu { name })
+    }
+
+    /// Returns the value column named `name`, returns error if the column is absent or
+    /// is a key column.
+    fn value_column_by_name(&self, name: &str) -> Result<&ColumnMetadata> {
+        let column = self.user_column_by_name(name)?;
+        ensure!(
+            !self.schema.store_schema().is_key_column(name),
+            ModifyKeyColumnSnafu { name }
+        );
+
+        Ok(column)
+    }
+
     fn to_descriptor(&self) -> RegionDescriptor {
         let row_key = self.columns.to_row_key_descriptor();
         let mut builder = RegionDescriptorBuilder::default()
@@ -1379,6 +1476,140 @@ mod tests {
         metadata.validate_alter(&req).unwrap();
     }
 
+    #[test]
+    fn test_validate_alter_modify_columns() {
+        let metadata: RegionMetadata = RegionDescBuilder::new("region-alter")
+            .enable_version_column(false)
+            .timestamp(("ts", LogicalTypeId::TimestampMillisecond, false))
+            .push_key_column(("k0", LogicalTypeId::Int32, false))
+            .push_value_column(("v0", LogicalTypeId::Int32, true))
+            .push_value_column(("v1", LogicalTypeId::Float32, true))
+            .build()
+            .try_into()
+            .unwrap();
+
+        let mut req = AlterRequest {
+            operation: AlterOperation::ModifyColumnType {
+                name: String::from("v2"),
+                data_type: ConcreteDataType::int64_datatype(),
+            },
+            version: 0,
+        };
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::ModifyAbsentColumn { .. }
+        ));
+        req.operation = AlterOperation::ModifyColumnType {
+            name: String::from("k0"),
+            data_type: ConcreteDataType::int64_datatype(),
+        };
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::ModifyKeyColumn { .. }
+        ));
+        req.operation = AlterOperation::ModifyColumnType {
+            name: String::from("v1"),
+            data_type: ConcreteDataType::int64_datatype(),
+        };
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::IncompatibleColumnType { .. }
+        ));
+        req.operation = AlterOperation::ModifyColumnType {
+            name: String::from("v0"),
+            data_type: ConcreteDataType::int64_datatype(),
+        };
+        metadata.validate_alter(&req).unwrap();
+
+        req.operation = AlterOperation::RenameColumn {
+            name: String::from("ts"),
+            new_name: String::from("ts2"),
+        };
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::ModifyKeyColumn { .. }
+        ));
+        req.operation = AlterOperation::RenameColumn {
+            name: String::from("v0"),
+            new_name: String::from(consts::SEQUENCE_COLUMN_NAME),
+        };
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::RenameToExistColumn { .. }
+        ));
+        req.operation = AlterOperation::RenameColumn {
+            name: String::from("v0"),
+            new_name: String::from("v2"),
+        };
+        metadata.validate_alter(&req).unwrap();
+
+        req.operation = AlterOperation::SetColumnDefault {
+            name: String::from("k0"),
+            default_constraint: Some(ColumnDefaultConstraint::Value(Value::Float32(
+                1.0f32.into(),
+            ))),
+        };
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::InvalidColumnDefault { .. }
+        ));
+        req.operation = AlterOperation::SetColumnDefault {
+            name: String::from("k0"),
+            default_constraint: Some(ColumnDefaultConstraint::Value(Value::Int32(1))),
+        };
+        metadata.validate_alter(&req).unwrap();
+    }
+
+    #[test]
+    fn test_alter_metadata_modify_columns() {
+        let metadata: RegionMetadata = RegionDescBuilder::new("region-alter")
+            .enable_version_column(false)
+            .push_key_column(("k0", LogicalTypeId::Int32, false))
+            .push_value_column(("v0", LogicalTypeId::Int32, true))
+            .build()
+            .try_into()
+            .unwrap();
+        let v0_id = metadata.columns.iter_value_columns().next().unwrap().id();
+
+        let req = AlterRequest {
+            operation: AlterOperation::SetColumnDefault {
+                name: String::from("v0"),
+                default_constraint: Some(ColumnDefaultConstraint::Value(Value::Int32(1))),
+            },
+            version: 0,
+        };
+        let metadata = metadata.alter(&req).unwrap();
+        let req = AlterRequest {
+            operation: AlterOperation::ModifyColumnType {
+                name: String::from("v0"),
+                data_type: ConcreteDataType::int64_datatype(),
+            },
+            version: 1,
+        };
+        metadata.validate_alter(&req).unwrap();
+        let metadata = metadata.alter(&req).unwrap();
+        let req = AlterRequest {
+            operation: AlterOperation::RenameColumn {
+                name: String::from("v0"),
+                new_name: String::from("v1"),
+            },
+            version: 2,
+        };
+        let metadata = metadata.alter(&req).unwrap();
+
+        assert_eq!(3, metadata.version());
+        let column = metadata.columns.iter_value_columns().next().unwrap();
+        assert_eq!(v0_id, column.id());
+        assert_eq!("v1", column.name());
+        assert_eq!(ConcreteDataType::int64_datatype(), column.desc.data_type);
+        assert_eq!(
+            Some(&ColumnDefaultConstraint::Value(Value::Int64(1))),
+            column.desc.default_constraint()
+        );
+        let column_schema = metadata.user_schema().column_schema_by_name("v1").unwrap();
+        assert_eq!(ConcreteDataType::int64_datatype(), column_schema.data_type);
+    }
+
     #[test]
     fn test_column_metadata_conversion() {
         let desc = ColumnDescriptorBuilder::new(123, "test", ConcreteDataType::int32_datatype())
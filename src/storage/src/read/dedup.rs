@@ -12,10 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
 use async_trait::async_trait;
 use common_base::BitVec;
 use datatypes::prelude::ScalarVector;
 use datatypes::vectors::BooleanVector;
+use store_api::storage::ScanMetricsRef;
 
 use crate::error::Result;
 use crate::read::{Batch, BatchOp, BatchReader};
@@ -31,6 +36,12 @@ pub struct DedupReader<R> {
     prev_batch: Option<Batch>,
     /// Reused bitmap buffer.
     selected: BitVec,
+    /// Whether to remove rows with `OpType::Delete` from the output.
+    filter_deleted: bool,
+    /// Number of deleted rows removed from the output.
+    num_deleted: Arc<AtomicUsize>,
+    /// Metrics to record the time spent in the inner reader and deduplication.
+    metrics: Option<ScanMetricsRef>,
 }
 
 impl<R> DedupReader<R> {
@@ -40,9 +51,31 @@ impl<R> DedupReader<R> {
             reader,
             prev_batch: None,
             selected: BitVec::default(),
+            filter_deleted: true,
+            num_deleted: Arc::new(AtomicUsize::new(0)),
+            metrics: None,
         }
     }
 
+    /// Sets whether to remove deleted rows, they are removed by default. If deleted rows are
+    /// kept, they still shadow the older rows with the same key.
+    pub fn with_filter_deleted(mut self, filter_deleted: bool) -> DedupReader<R> {
+        self.filter_deleted = filter_deleted;
+        self
+    }
+
+    /// Sets the metrics to record the time spent in reading the inner reader, which merges
+    /// rows of a scan, and the time spent in deduplication.
+    pub fn with_metrics(mut self, metrics: ScanMetricsRef) -> DedupReader<R> {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Returns the counter of deleted rows removed by this reader.
+    pub fn num_deleted(&self) -> Arc<AtomicUsize> {
+        self.num_deleted.clone()
+    }
+
     /// Take `batch` and then returns a new batch with no duplicated rows.
     ///
     /// This method may returns empty `Batch`.
@@ -66,8 +99,13 @@ impl<R> DedupReader<R> {
             .get_or_insert_with(Batch::default)
             .clone_from(&batch); // Use `clone_from` to reuse allocated memory if possible.
 
-        // Find all rows whose op_types are `OpType::Delete`, mark their `selected` to false.
-        self.schema.unselect_deleted(&batch, &mut self.selected);
+        if self.filter_deleted {
+            // Find all rows whose op_types are `OpType::Delete`, mark their `selected` to false.
+            let num_selected = self.selected.count_ones();
+            self.schema.unselect_deleted(&batch, &mut self.selected);
+            self.num_deleted
+                .fetch_add(num_selected - self.selected.count_ones(), Ordering::Relaxed);
+        }
 
         let filter = BooleanVector::from_iterator(self.selected.iter().by_vals());
         // Filter duplicate rows.
@@ -78,18 +116,31 @@ impl<R> DedupReader<R> {
 #[async_trait]
 impl<R: BatchReader> BatchReader for DedupReader<R> {
     async fn next_batch(&mut self) -> Result<Option<Batch>> {
-        while let Some(batch) = self.reader.next_batch().await? {
+        loop {
+            let start = Instant::now();
+            let batch = self.reader.next_batch().await?;
+            let merged = Instant::now();
+            if let Some(metrics) = &self.metrics {
+                add_elapsed(&metrics.merge_nanos, start, merged);
+            }
+            let Some(batch) = batch else { return Ok(None); };
+
             let filtered = self.dedup_batch(batch)?;
+            if let Some(metrics) = &self.metrics {
+                add_elapsed(&metrics.dedup_nanos, merged, Instant::now());
+            }
             // Skip empty batch.
             if !filtered.is_empty() {
                 return Ok(Some(filtered));
             }
         }
-
-        Ok(None)
     }
 }
 
+fn add_elapsed(nanos: &AtomicU64, start: Instant, end: Instant) {
+    nanos.fetch_add((end - start).as_nanos() as u64, Ordering::Relaxed);
+}
+
 #[cfg(test)]
 mod tests {
     use store_api::storage::OpType;
@@ -178,4 +229,37 @@ mod tests {
         let expect = [(100, Some(1)), (101, Some(1)), (102, Some(12))];
         assert_eq!(&expect, &result[..]);
     }
+
+    #[tokio::test]
+    async fn test_dedup_deleted() {
+        let batches: &[&[(i64, i64, u64, OpType)]] = &[
+            // key, value, sequence, op_type
+            &[
+                (100, 1, 1000, OpType::Delete),
+                (100, 2, 999, OpType::Put),
+                (101, 1, 1000, OpType::Put),
+            ],
+            &[(102, 12, 1000, OpType::Delete)],
+        ];
+
+        let schema = read_util::new_projected_schema();
+        let reader = read_util::build_full_vec_reader(batches);
+        let mut reader = DedupReader::new(schema, reader);
+        let num_deleted = reader.num_deleted();
+
+        let result = read_util::collect_kv_batch(&mut reader).await;
+        assert_eq!(&[(101, Some(1))], &result[..]);
+        assert_eq!(2, num_deleted.load(Ordering::Relaxed));
+
+        // Keeps the deleted rows, which still shadow the older rows.
+        let schema = read_util::new_projected_schema();
+        let reader = read_util::build_full_vec_reader(batches);
+        let mut reader = DedupReader::new(schema, reader).with_filter_deleted(false);
+        let num_deleted = reader.num_deleted();
+
+        let result = read_util::collect_kv_batch(&mut reader).await;
+        let expect = [(100, Some(1)), (101, Some(1)), (102, Some(12))];
+        assert_eq!(&expect, &result[..]);
+        assert_eq!(0, num_deleted.load(Ordering::Relaxed));
+    }
 }
@@ -23,12 +23,13 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use common_telemetry::logging;
+use common_time::range::TimestampRange;
 use snafu::ResultExt;
 use store_api::logstore::LogStore;
 use store_api::manifest::{self, Manifest, ManifestVersion, MetaActionIterator};
 use store_api::storage::{
-    AlterRequest, OpenOptions, ReadContext, Region, RegionId, SequenceNumber, WriteContext,
-    WriteResponse,
+    AlterOptions, AlterRequest, CompactContext, CompactionOptions, FlushContext, IndexOptions,
+    OpenOptions, ReadContext, Region, RegionId, SequenceNumber, WriteContext, WriteResponse,
 };
 
 use crate::compaction::CompactionSchedulerRef;
@@ -125,6 +126,26 @@ impl<S: LogStore> Region for RegionImpl<S> {
     async fn close(&self) -> Result<()> {
         self.inner.close().await
     }
+
+    async fn flush(&self, ctx: &FlushContext) -> Result<()> {
+        self.inner.flush(ctx).await
+    }
+
+    async fn compact(&self, ctx: &CompactContext) -> Result<()> {
+        self.inner.compact(ctx).await
+    }
+
+    async fn drop_files_in_range(&self, time_range: TimestampRange) -> Result<usize> {
+        self.inner.drop_files_in_range(time_range).await
+    }
+
+    async fn alter_options(&self, opts: &AlterOptions) -> Result<()> {
+        self.inner.alter_options(opts).await
+    }
+
+    async fn truncate(&self) -> Result<()> {
+        self.inner.truncate().await
+    }
 }
 
 /// Storage related config for region.
@@ -142,6 +163,8 @@ pub struct StoreConfig<S: LogStore> {
     pub engine_config: Arc<EngineConfig>,
     pub file_purger: FilePurgerRef,
     pub ttl: Option<Duration>,
+    pub compaction_options: CompactionOptions,
+    pub index_options: IndexOptions,
 }
 
 pub type RecoverdMetadata = (SequenceNumber, (ManifestVersion, RawRegionMetadata));
@@ -201,6 +224,8 @@ impl<S: LogStore> RegionImpl<S> {
                 store_config.memtable_builder,
                 store_config.engine_config.clone(),
                 store_config.ttl,
+                store_config.compaction_options,
+                store_config.index_options,
             )),
             wal,
             flush_strategy: store_config.flush_strategy,
@@ -281,6 +306,8 @@ impl<S: LogStore> RegionImpl<S> {
             store_config.memtable_builder,
             store_config.engine_config.clone(),
             store_config.ttl,
+            store_config.compaction_options,
+            store_config.index_options,
         ));
         let writer_ctx = WriterContext {
             shared: &shared,
@@ -316,6 +343,14 @@ impl<S: LogStore> RegionImpl<S> {
         self.inner.shared.id()
     }
 
+    /// Deletes all files of the closed region. The manifest is also deleted as it's
+    /// under the same directory as SST files.
+    pub(crate) async fn delete_files(&self) -> Result<()> {
+        let committed_sequence = self.inner.version_control().committed_sequence();
+        self.inner.wal.obsolete(committed_sequence).await?;
+        self.inner.sst_layer.delete_dir().await
+    }
+
     async fn recover_from_manifest(
         manifest: &RegionManifest,
         memtable_builder: &MemtableBuilderRef,
@@ -513,9 +548,8 @@ impl<S: LogStore> RegionInner<S> {
         request.compat_write(schema.user_schema())
     }
 
-    /// Write to writer directly.
-    async fn write(&self, ctx: &WriteContext, request: WriteBatch) -> Result<WriteResponse> {
-        let writer_ctx = WriterContext {
+    fn writer_ctx(&self) -> WriterContext<S> {
+        WriterContext {
             shared: &self.shared,
             flush_strategy: &self.flush_strategy,
             flush_scheduler: &self.flush_scheduler,
@@ -524,7 +558,12 @@ impl<S: LogStore> RegionInner<S> {
             wal: &self.wal,
             writer: &self.writer,
             manifest: &self.manifest,
-        };
+        }
+    }
+
+    /// Write to writer directly.
+    async fn write(&self, ctx: &WriteContext, request: WriteBatch) -> Result<WriteResponse> {
+        let writer_ctx = self.writer_ctx();
         // The writer would also try to compat the schema of write batch if it finds out the
         // schema version of request is less than current schema version.
         self.writer.write(ctx, request, writer_ctx).await
@@ -550,4 +589,60 @@ impl<S: LogStore> RegionInner<S> {
     async fn close(&self) -> Result<()> {
         self.writer.close().await
     }
+
+    async fn flush(&self, ctx: &FlushContext) -> Result<()> {
+        logging::info!(
+            "Flush region {}, name: {}, wait: {}",
+            self.shared.id,
+            self.shared.name,
+            ctx.wait
+        );
+
+        self.writer.flush(self.writer_ctx(), ctx).await
+    }
+
+    async fn compact(&self, ctx: &CompactContext) -> Result<()> {
+        logging::info!(
+            "Compact region {}, name: {}, wait: {}",
+            self.shared.id,
+            self.shared.name,
+            ctx.wait
+        );
+
+        self.writer.compact(self.writer_ctx(), ctx).await
+    }
+
+    async fn drop_files_in_range(&self, time_range: TimestampRange) -> Result<usize> {
+        logging::info!(
+            "Drop files in range {:?} from region {}, name: {}",
+            time_range,
+            self.shared.id,
+            self.shared.name,
+        );
+
+        self.writer
+            .drop_files_in_range(self.writer_ctx(), time_range)
+            .await
+    }
+
+    async fn alter_options(&self, opts: &AlterOptions) -> Result<()> {
+        logging::info!(
+            "Alter options of region {}, name: {}, options: {:?}",
+            self.shared.id,
+            self.shared.name,
+            opts
+        );
+
+        self.writer.alter_options(opts).await
+    }
+
+    async fn truncate(&self) -> Result<()> {
+        logging::info!(
+            "Truncate region {}, name: {}",
+            self.shared.id,
+            self.shared.name,
+        );
+
+        self.writer.truncate(self.writer_ctx()).await
+    }
 }
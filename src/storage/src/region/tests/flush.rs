@@ -17,13 +17,14 @@
 use std::sync::Arc;
 
 use log_store::raft_engine::log_store::RaftEngineLogStore;
-use store_api::storage::{OpenOptions, WriteResponse};
+use store_api::storage::{AlterOptions, FlushContext, OpenOptions, Region, WriteResponse};
 use tempdir::TempDir;
 
 use crate::engine;
 use crate::flush::FlushStrategyRef;
 use crate::region::tests::{self, FileTesterBase};
 use crate::region::RegionImpl;
+use crate::sst::FileHandle;
 use crate::test_util::config_util;
 use crate::test_util::flush_switch::{has_parquet_file, FlushSwitch};
 
@@ -94,6 +95,14 @@ impl FlushTester {
     async fn wait_flush_done(&self) {
         self.base().region.wait_flush_done().await.unwrap();
     }
+
+    async fn flush(&self) {
+        self.base()
+            .region
+            .flush(&FlushContext::default())
+            .await
+            .unwrap();
+    }
 }
 
 #[tokio::test]
@@ -234,3 +243,109 @@ async fn test_merge_read_after_flush() {
     let output = tester.full_scan().await;
     assert_eq!(expect, output);
 }
+
+#[tokio::test]
+async fn test_manual_flush() {
+    common_telemetry::init_default_ut_logging();
+
+    let dir = TempDir::new("manual-flush").unwrap();
+    let store_dir = dir.path().to_str().unwrap();
+
+    let flush_switch = Arc::new(FlushSwitch::default());
+    let tester = FlushTester::new(store_dir, flush_switch).await;
+
+    // Flush an empty region does nothing.
+    tester.flush().await;
+
+    tester.put(&[(1000, Some(100))]).await;
+    tester.put(&[(2000, Some(200))]).await;
+
+    // The flush strategy never triggers flush, but we could flush the region manually.
+    tester.flush().await;
+    let sst_dir = format!("{}/{}", store_dir, engine::region_sst_dir("", REGION_NAME));
+    assert!(has_parquet_file(&sst_dir));
+
+    let expect = vec![(1000, Some(100)), (2000, Some(200))];
+    let output = tester.full_scan().await;
+    assert_eq!(expect, output);
+
+    // Reopen
+    let mut tester = tester;
+    tester.reopen().await;
+
+    // Scan after reopen.
+    let output = tester.full_scan().await;
+    assert_eq!(expect, output);
+}
+
+#[tokio::test]
+async fn test_alter_write_buffer_size() {
+    common_telemetry::init_default_ut_logging();
+
+    let dir = TempDir::new("alter-write-buffer-size").unwrap();
+    let store_dir = dir.path().to_str().unwrap();
+
+    let flush_switch = Arc::new(FlushSwitch::default());
+    let tester = FlushTester::new(store_dir, flush_switch).await;
+
+    tester.put(&[(1000, Some(100))]).await;
+    let sst_dir = format!("{}/{}", store_dir, engine::region_sst_dir("", REGION_NAME));
+    assert!(!has_parquet_file(&sst_dir));
+
+    // Shrinks the write buffer, so the next write triggers a flush.
+    let opts = AlterOptions {
+        write_buffer_size: Some(1),
+        ttl: None,
+    };
+    tester.base().region.alter_options(&opts).await.unwrap();
+    tester.put(&[(2000, Some(200))]).await;
+    tester.wait_flush_done().await;
+    assert!(has_parquet_file(&sst_dir));
+
+    let expect = vec![(1000, Some(100)), (2000, Some(200))];
+    let output = tester.full_scan().await;
+    assert_eq!(expect, output);
+}
+
+#[tokio::test]
+async fn test_truncate() {
+    common_telemetry::init_default_ut_logging();
+
+    let dir = TempDir::new("truncate").unwrap();
+    let store_dir = dir.path().to_str().unwrap();
+
+    let flush_switch = Arc::new(FlushSwitch::default());
+    let mut tester = FlushTester::new(store_dir, flush_switch).await;
+
+    // Puts rows into both SST files and memtables.
+    tester.put(&[(1000, Some(100))]).await;
+    tester.flush().await;
+    tester.put(&[(2000, Some(200))]).await;
+    let sst_dir = format!("{}/{}", store_dir, engine::region_sst_dir("", REGION_NAME));
+    assert!(has_parquet_file(&sst_dir));
+
+    // Files under compaction are also removed, so the output of the compaction is discarded.
+    let version = tester.base().region.inner.version_control().current();
+    let files = version
+        .ssts()
+        .levels()
+        .iter()
+        .flat_map(|level| level.files())
+        .cloned()
+        .collect::<Vec<_>>();
+    assert!(!files.is_empty());
+    files.iter().for_each(|file| file.mark_compacting(true));
+
+    tester.base().region.truncate().await.unwrap();
+    assert!(tester.full_scan().await.is_empty());
+    assert!(files.iter().all(FileHandle::deleted));
+
+    // The region is still writable after truncation.
+    tester.put(&[(3000, Some(300))]).await;
+    let expect = vec![(3000, Some(300))];
+    assert_eq!(expect, tester.full_scan().await);
+
+    // Truncated rows are not replayed from the WAL.
+    tester.reopen().await;
+    assert_eq!(expect, tester.full_scan().await);
+}
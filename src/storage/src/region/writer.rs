@@ -18,18 +18,24 @@ use std::time::Duration;
 use common_error::prelude::BoxedError;
 use common_telemetry::tracing::log::info;
 use common_telemetry::{error, logging};
+use common_time::range::TimestampRange;
 use futures::TryStreamExt;
 use snafu::{ensure, ResultExt};
 use store_api::logstore::LogStore;
 use store_api::manifest::{Manifest, ManifestVersion, MetaAction};
-use store_api::storage::{AlterRequest, SequenceNumber, WriteContext, WriteResponse};
-use tokio::sync::Mutex;
+use store_api::storage::{
+    AlterOptions, AlterRequest, CompactContext, CompactionOptions, FlushContext, IndexOptions,
+    SequenceNumber, WriteContext, WriteResponse,
+};
+use tokio::sync::{oneshot, Mutex};
 
 use crate::background::JobHandle;
 use crate::compaction::{CompactionRequestImpl, CompactionSchedulerRef};
 use crate::config::EngineConfig;
 use crate::error::{self, Result};
-use crate::flush::{FlushCallback, FlushJob, FlushSchedulerRef, FlushStrategyRef};
+use crate::flush::{
+    FlushCallback, FlushJob, FlushSchedulerRef, FlushStrategyRef, SizeBasedStrategy,
+};
 use crate::manifest::action::{
     RawRegionMetadata, RegionChange, RegionEdit, RegionMetaAction, RegionMetaActionList,
 };
@@ -38,7 +44,7 @@ use crate::metadata::RegionMetadataRef;
 use crate::proto::wal::WalHeader;
 use crate::region::{RecoverdMetadata, RecoveredMetadataMap, RegionManifest, SharedDataRef};
 use crate::schema::compat::CompatWrite;
-use crate::sst::AccessLayerRef;
+use crate::sst::{AccessLayerRef, FileHandle};
 use crate::version::{VersionControl, VersionControlRef, VersionEdit, VersionRef};
 use crate::wal::Wal;
 use crate::write_batch::WriteBatch;
@@ -65,9 +71,17 @@ impl RegionWriter {
         memtable_builder: MemtableBuilderRef,
         config: Arc<EngineConfig>,
         ttl: Option<Duration>,
+        compaction_options: CompactionOptions,
+        index_options: IndexOptions,
     ) -> RegionWriter {
         RegionWriter {
-            inner: Mutex::new(WriterInner::new(memtable_builder, config, ttl)),
+            inner: Mutex::new(WriterInner::new(
+                memtable_builder,
+                config,
+                ttl,
+                compaction_options,
+                index_options,
+            )),
             version_mutex: Mutex::new(()),
         }
     }
@@ -109,11 +123,45 @@ impl RegionWriter {
         edit: RegionEdit,
         max_memtable_id: Option<MemtableId>,
     ) -> Result<()> {
-        let _lock = self.version_mutex.lock().await;
         // HACK: We won't acquire the write lock here because write stall would hold
         // write lock thus we have no chance to get the lock and apply the version edit.
         // So we add a version lock to ensure modification to `VersionControl` is
         // serialized.
+        let _lock = self.version_mutex.lock().await;
+        self.write_edit_and_apply_locked(wal, shared, manifest, edit, max_memtable_id)
+            .await
+    }
+
+    /// Writes the edit of a compaction to the manifest and applies it, unless some of the
+    /// `inputs` have been removed from the region during the compaction, e.g. by truncation,
+    /// as the output would bring their rows back. Returns whether the edit is applied.
+    pub(crate) async fn write_compaction_edit_and_apply<S: LogStore>(
+        &self,
+        wal: &Wal<S>,
+        shared: &SharedDataRef,
+        manifest: &RegionManifest,
+        edit: RegionEdit,
+        inputs: &[FileHandle],
+    ) -> Result<bool> {
+        let _lock = self.version_mutex.lock().await;
+        if inputs.iter().any(FileHandle::deleted) {
+            return Ok(false);
+        }
+
+        self.write_edit_and_apply_locked(wal, shared, manifest, edit, None)
+            .await
+            .map(|_| true)
+    }
+
+    /// Writes the edit to the manifest and applies it, the caller must hold the version lock.
+    async fn write_edit_and_apply_locked<S: LogStore>(
+        &self,
+        wal: &Wal<S>,
+        shared: &SharedDataRef,
+        manifest: &RegionManifest,
+        edit: RegionEdit,
+        max_memtable_id: Option<MemtableId>,
+    ) -> Result<()> {
         let version_control = &shared.version_control;
         let prev_version = version_control.current_manifest_version();
 
@@ -260,6 +308,197 @@ impl RegionWriter {
         Ok(())
     }
 
+    /// Flush memtables of the region manually.
+    pub async fn flush<S: LogStore>(
+        &self,
+        writer_ctx: WriterContext<'_, S>,
+        ctx: &FlushContext,
+    ) -> Result<()> {
+        let mut inner = self.inner.lock().await;
+
+        ensure!(!inner.is_closed(), error::ClosedRegionSnafu);
+
+        inner.trigger_flush(&writer_ctx).await?;
+
+        if ctx.wait {
+            // We hold the write lock while waiting, so no other flush job could be scheduled
+            // before the current one is finished.
+            if let Some(handle) = inner.flush_handle.take() {
+                handle.join().await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compact SST files of the region manually.
+    pub async fn compact<S: LogStore>(
+        &self,
+        writer_ctx: WriterContext<'_, S>,
+        ctx: &CompactContext,
+    ) -> Result<()> {
+        let (sender, receiver) = oneshot::channel();
+        let region_id = writer_ctx.shared.id;
+        {
+            let inner = self.inner.lock().await;
+
+            ensure!(!inner.is_closed(), error::ClosedRegionSnafu);
+
+            let request = CompactionRequestImpl {
+                region_id,
+                sst_layer: writer_ctx.sst_layer.clone(),
+                writer: writer_ctx.writer.clone(),
+                shared: writer_ctx.shared.clone(),
+                manifest: writer_ctx.manifest.clone(),
+                wal: writer_ctx.wal.clone(),
+                ttl: inner.ttl,
+                compaction_options: inner.compaction_options.clone(),
+                index_options: inner.index_options.clone(),
+                sender: Some(sender),
+            };
+            let scheduled = writer_ctx.compaction_scheduler.schedule(request)?;
+            ensure!(scheduled, error::CompactionScheduledSnafu { region_id });
+        }
+        // Release the write lock before waiting, as the compaction task needs to apply
+        // its edit to the region.
+
+        if ctx.wait {
+            receiver
+                .await
+                .context(error::WaitCompactionSnafu { region_id })??;
+        }
+
+        Ok(())
+    }
+
+    /// Removes SST files whose rows are all inside `time_range` from the region, files under
+    /// compaction are skipped. Returns the number of rows in the removed files.
+    pub async fn drop_files_in_range<S: LogStore>(
+        &self,
+        writer_ctx: WriterContext<'_, S>,
+        time_range: TimestampRange,
+    ) -> Result<usize> {
+        // Holds the write lock so the region can't be closed while we are removing files.
+        let inner = self.inner.lock().await;
+
+        ensure!(!inner.is_closed(), error::ClosedRegionSnafu);
+
+        let version_control = &writer_ctx.shared.version_control;
+        let version = version_control.current();
+        let files = version
+            .ssts()
+            .levels()
+            .iter()
+            .flat_map(|level| level.files())
+            .filter(|file| match file.time_range() {
+                Some((start, end)) => time_range.contains(start) && time_range.contains(end),
+                None => false,
+            })
+            // Marks the files as compacting so they won't be picked by a compaction task
+            // while we are removing them.
+            .filter(|file| file.try_mark_compacting())
+            .cloned()
+            .collect::<Vec<_>>();
+        if files.is_empty() {
+            return Ok(0);
+        }
+
+        let num_rows = files.iter().map(|file| file.num_rows()).sum();
+        let edit = RegionEdit {
+            region_version: version.metadata().version(),
+            flushed_sequence: None,
+            files_to_add: Vec::new(),
+            files_to_remove: files.iter().map(|file| file.meta()).collect(),
+        };
+        let res = self
+            .write_edit_and_apply(
+                writer_ctx.wal,
+                writer_ctx.shared,
+                writer_ctx.manifest,
+                edit,
+                None,
+            )
+            .await;
+        if res.is_err() {
+            for file in &files {
+                file.mark_compacting(false);
+            }
+        }
+
+        res.map(|_| num_rows)
+    }
+
+    /// Discards all SST files and memtables of the region. Compactions running on the
+    /// discarded files are cancelled, their outputs won't be added to the region.
+    pub async fn truncate<S: LogStore>(&self, writer_ctx: WriterContext<'_, S>) -> Result<()> {
+        // Holds the write lock so no data could be written during truncation.
+        let mut inner = self.inner.lock().await;
+
+        ensure!(!inner.is_closed(), error::ClosedRegionSnafu);
+
+        // Waits for the pending flush job, otherwise its output files would be added to the
+        // region after truncation.
+        if let Some(handle) = inner.flush_handle.take() {
+            handle.join().await?;
+        }
+
+        // Holds the version lock while collecting and removing the files, so a compaction
+        // can't add its output in the meantime. Compactions finishing later find their
+        // inputs removed and discard the output.
+        let _version_lock = self.version_mutex.lock().await;
+        let version_control = writer_ctx.version_control();
+        let files = version_control
+            .current()
+            .ssts()
+            .levels()
+            .iter()
+            .flat_map(|level| level.files())
+            .map(|file| file.meta())
+            .collect::<Vec<_>>();
+
+        // Freezes the mutable memtable so all memtables could be removed by the edit.
+        let new_mutable = inner.alloc_memtable(version_control);
+        version_control.freeze_mutable(new_mutable);
+        let version = version_control.current();
+        let (max_memtable_id, _) = version.memtables().memtables_to_flush();
+        // All data before the committed sequence is discarded, so it won't be replayed
+        // from the WAL.
+        let flushed_sequence = version_control.committed_sequence();
+        let edit = RegionEdit {
+            region_version: version.metadata().version(),
+            flushed_sequence: Some(flushed_sequence),
+            files_to_add: Vec::new(),
+            files_to_remove: files,
+        };
+        self.write_edit_and_apply_locked(
+            writer_ctx.wal,
+            writer_ctx.shared,
+            writer_ctx.manifest,
+            edit,
+            max_memtable_id,
+        )
+        .await?;
+
+        writer_ctx.wal.obsolete(flushed_sequence).await
+    }
+
+    /// Alters the options of the region, the new options take effect on the next write,
+    /// flush or compaction.
+    pub async fn alter_options(&self, opts: &AlterOptions) -> Result<()> {
+        let mut inner = self.inner.lock().await;
+
+        ensure!(!inner.is_closed(), error::ClosedRegionSnafu);
+
+        let flush_strategy = match opts.write_buffer_size {
+            Some(size) => SizeBasedStrategy::new(size),
+            None => SizeBasedStrategy::default(),
+        };
+        inner.flush_strategy = Some(Arc::new(flush_strategy));
+        inner.ttl = opts.ttl;
+
+        Ok(())
+    }
+
     /// Cancel flush task if any
     async fn cancel_flush(&self) -> Result<()> {
         let mut inner = self.inner.lock().await;
@@ -329,7 +568,12 @@ struct WriterInner {
     /// It should protected by upper mutex
     closed: bool,
     engine_config: Arc<EngineConfig>,
+    /// Flush strategy set by altering the options of the region, overrides the strategy
+    /// in [WriterContext] if present.
+    flush_strategy: Option<FlushStrategyRef>,
     ttl: Option<Duration>,
+    compaction_options: CompactionOptions,
+    index_options: IndexOptions,
 }
 
 impl WriterInner {
@@ -337,13 +581,18 @@ impl WriterInner {
         memtable_builder: MemtableBuilderRef,
         engine_config: Arc<EngineConfig>,
         ttl: Option<Duration>,
+        compaction_options: CompactionOptions,
+        index_options: IndexOptions,
     ) -> WriterInner {
         WriterInner {
             memtable_builder,
             flush_handle: None,
             engine_config,
             closed: false,
+            flush_strategy: None,
             ttl,
+            compaction_options,
+            index_options,
         }
     }
 
@@ -548,11 +797,11 @@ impl WriterInner {
         let version_control = writer_ctx.version_control();
         // Check whether memtable is full or flush should be triggered. We need to do this first since
         // switching memtables will clear all mutable memtables.
-        if self.should_flush(
-            writer_ctx.shared,
-            version_control,
-            writer_ctx.flush_strategy,
-        ) {
+        let flush_strategy = self
+            .flush_strategy
+            .as_ref()
+            .unwrap_or(writer_ctx.flush_strategy);
+        if self.should_flush(writer_ctx.shared, version_control, flush_strategy) {
             self.trigger_flush(writer_ctx).await?;
         }
 
@@ -607,7 +856,14 @@ impl WriterInner {
             return Ok(());
         }
 
-        let cb = Self::build_flush_callback(&current_version, ctx, &self.engine_config, self.ttl);
+        let cb = Self::build_flush_callback(
+            &current_version,
+            ctx,
+            &self.engine_config,
+            self.ttl,
+            &self.compaction_options,
+            &self.index_options,
+        );
 
         let flush_req = FlushJob {
             max_memtable_id: max_memtable_id.unwrap(),
@@ -619,6 +875,7 @@ impl WriterInner {
             writer: ctx.writer.clone(),
             wal: ctx.wal.clone(),
             manifest: ctx.manifest.clone(),
+            index_options: self.index_options.clone(),
             on_success: cb,
         };
 
@@ -636,6 +893,8 @@ impl WriterInner {
         ctx: &WriterContext<S>,
         config: &Arc<EngineConfig>,
         ttl: Option<Duration>,
+        compaction_options: &CompactionOptions,
+        index_options: &IndexOptions,
     ) -> Option<FlushCallback> {
         let region_id = version.metadata().id();
         let compaction_request = CompactionRequestImpl {
@@ -646,6 +905,9 @@ impl WriterInner {
             manifest: ctx.manifest.clone(),
             wal: ctx.wal.clone(),
             ttl,
+            compaction_options: compaction_options.clone(),
+            index_options: index_options.clone(),
+            sender: None,
         };
         let compaction_scheduler = ctx.compaction_scheduler.clone();
         let shared_data = ctx.shared.clone();
@@ -14,6 +14,8 @@
 
 //! Utilities for resolving schema compatibility problems.
 
+use std::collections::HashMap;
+
 use datatypes::arrow::record_batch::RecordBatch;
 use datatypes::schema::SchemaRef;
 use datatypes::vectors::{Helper, VectorRef};
@@ -37,6 +39,9 @@ pub trait CompatWrite {
 
 /// Checks whether column with `source_column` could be read as a column with `dest_column`.
 ///
+/// Columns are identified by their ids, so a renamed column is still compatible, and
+/// the source column could be read as a dest column with a wider type.
+///
 /// Returns
 /// - `Ok(true)` if `source_column` is compatible to read using `dest_column` as schema.
 /// - `Ok(false)` if they are considered different columns.
@@ -45,23 +50,15 @@ fn is_source_column_compatible(
     source_column: &ColumnMetadata,
     dest_column: &ColumnMetadata,
 ) -> Result<bool> {
-    ensure!(
-        source_column.name() == dest_column.name(),
-        error::CompatReadSnafu {
-            reason: format!(
-                "try to use column in {} for column {}",
-                source_column.name(),
-                dest_column.name()
-            ),
-        }
-    );
-
     if source_column.id() != dest_column.id() {
         return Ok(false);
     }
 
     ensure!(
-        source_column.desc.data_type == dest_column.desc.data_type,
+        source_column
+            .desc
+            .data_type
+            .can_widen_to(&dest_column.desc.data_type),
         error::CompatReadSnafu {
             reason: format!(
                 "could not read column {} from {:?} type as {:?} type",
@@ -100,6 +97,9 @@ pub struct ReadAdapter {
     /// For each column in source schema, stores whether we need to read that column. All
     /// columns are needed by default.
     is_source_needed: Vec<bool>,
+    /// Whether the statistics of source data could be used to evaluate predicates on the
+    /// dest schema, which is false if any column has a different name or type in source.
+    is_source_stats_usable: bool,
 }
 
 impl ReadAdapter {
@@ -138,6 +138,7 @@ impl ReadAdapter {
             dest_schema,
             indices_in_result: Vec::new(),
             is_source_needed,
+            is_source_stats_usable: true,
         })
     }
 
@@ -150,21 +151,28 @@ impl ReadAdapter {
         let mut is_source_needed = vec![true; source_schema.num_columns()];
         // Number of columns in result from source data.
         let mut num_columns_in_result = 0;
+        let mut is_source_stats_usable = true;
+        // Columns are matched by id as they might be renamed.
+        let dest_index_by_id = schema_to_read
+            .columns()
+            .iter()
+            .enumerate()
+            .map(|(idx, column)| (column.id(), idx))
+            .collect::<HashMap<_, _>>();
 
         for (idx, source_column) in source_schema.columns().iter().enumerate() {
             // For each column in source schema, check whether we need to read it.
-            if let Some(dest_idx) = schema_to_read
-                .schema()
-                .column_index_by_name(source_column.name())
-            {
-                let dest_column = &schema_to_read.columns()[dest_idx];
+            if let Some(dest_idx) = dest_index_by_id.get(&source_column.id()) {
+                let dest_column = &schema_to_read.columns()[*dest_idx];
                 // Check whether we could read this column.
                 if is_source_column_compatible(source_column, dest_column)? {
                     // Mark that this column could be read from source data, since some
                     // columns in source schema would be skipped, we should not use
                     // the source column's index directly.
-                    indices_in_result[dest_idx] = Some(num_columns_in_result);
+                    indices_in_result[*dest_idx] = Some(num_columns_in_result);
                     num_columns_in_result += 1;
+                    is_source_stats_usable &= source_column.name() == dest_column.name()
+                        && source_column.desc.data_type == dest_column.desc.data_type;
                 } else {
                     // This column is not the same column in dest schema, should be fill by default value
                     // instead of reading from source data.
@@ -173,6 +181,9 @@ impl ReadAdapter {
             } else {
                 // The column is not in `dest_schema`, we don't need to read it.
                 is_source_needed[idx] = false;
+                // Predicates on a dest column with the same name shouldn't use statistics
+                // of this column.
+                is_source_stats_usable &= !schema_to_read.contains_column(source_column.name());
             }
         }
 
@@ -181,6 +192,7 @@ impl ReadAdapter {
             dest_schema,
             indices_in_result,
             is_source_needed,
+            is_source_stats_usable,
         })
     }
 
@@ -226,6 +238,13 @@ impl ReadAdapter {
         self.source_columns_to_batch(source, num_rows)
     }
 
+    /// Returns whether the statistics of source data, e.g. statistics of row groups in the
+    /// parquet file, could be used to prune data by predicates on the dest schema.
+    #[inline]
+    pub fn is_source_stats_usable(&self) -> bool {
+        self.is_source_stats_usable
+    }
+
     /// Returns list of fields indices need to read from the parquet file.
     pub fn fields_to_read(&self) -> Vec<usize> {
         self.is_source_needed
@@ -282,7 +301,18 @@ impl ReadAdapter {
             .zip(column_schemas)
             .map(|(index_opt, column_schema)| {
                 if let Some(idx) = index_opt {
-                    Ok(source[*idx].clone())
+                    let vector = &source[*idx];
+                    if vector.data_type() == column_schema.data_type {
+                        Ok(vector.clone())
+                    } else {
+                        // The type of this column has been changed.
+                        vector
+                            .cast(&column_schema.data_type)
+                            .context(error::CastColumnSnafu {
+                                column: &column_schema.name,
+                                data_type: column_schema.data_type.clone(),
+                            })
+                    }
                 } else {
                     let vector = column_schema
                         .create_default_vector(num_rows)
@@ -307,6 +337,7 @@ mod tests {
 
     use datatypes::data_type::ConcreteDataType;
     use datatypes::schema::Schema;
+    use datatypes::vectors::Float64Vector;
     use store_api::storage::ColumnDescriptorBuilder;
 
     use super::*;
@@ -596,16 +627,69 @@ mod tests {
         let desc = new_column_desc_builder().build().unwrap();
         let source = ColumnMetadata { cf_id: 1, desc };
 
+        // The column is renamed.
         let desc = new_column_desc_builder()
             .name(format!("{}_other", source.desc.name))
             .build()
             .unwrap();
         let dest = ColumnMetadata { cf_id: 1, desc };
+        assert!(is_source_column_compatible(&source, &dest).unwrap());
+    }
 
-        let err = is_source_column_compatible(&source, &dest).unwrap_err();
+    #[test]
+    fn test_read_column_with_different_type() {
+        let desc = new_column_desc_builder().build().unwrap();
+        let source = ColumnMetadata { cf_id: 1, desc };
+
+        let desc = new_column_desc_builder()
+            .data_type(ConcreteDataType::int64_datatype())
+            .build()
+            .unwrap();
+        let wider_dest = ColumnMetadata { cf_id: 1, desc };
+        assert!(is_source_column_compatible(&source, &wider_dest).unwrap());
+
+        let desc = new_column_desc_builder()
+            .data_type(ConcreteDataType::int16_datatype())
+            .build()
+            .unwrap();
+        let narrower_dest = ColumnMetadata { cf_id: 1, desc };
+        let err = is_source_column_compatible(&source, &narrower_dest).unwrap_err();
         assert!(
             matches!(err, Error::CompatRead { .. }),
             "{err:?} is not CompatRead",
         );
     }
+
+    #[test]
+    fn test_compat_modified_column() {
+        // (k0, timestamp, v0, v1) with version 0.
+        let region_schema_old = Arc::new(schema_util::new_region_schema(0, 2));
+
+        let mut descriptor = descriptor_util::desc_with_value_columns(tests::REGION_NAME, 2);
+        // Rename v0 and change its type to float64.
+        descriptor.default_cf.columns[0].name = "v0_new".to_string();
+        descriptor.default_cf.columns[0].data_type = ConcreteDataType::float64_datatype();
+        let metadata: RegionMetadata = descriptor.try_into().unwrap();
+        let columns = metadata.columns;
+        // (k0, timestamp, v0_new, v1) with version 2.
+        let region_schema_new = Arc::new(RegionSchema::new(columns, 2).unwrap());
+
+        let projected_schema = Arc::new(ProjectedSchema::no_projection(region_schema_new));
+        let source_schema = region_schema_old.store_schema().clone();
+        let adapter = ReadAdapter::new(source_schema, projected_schema).unwrap();
+
+        assert_eq!(&[true, true], adapter.source_key_needed());
+        assert_eq!(&[true, true], adapter.source_value_needed());
+        assert!(!adapter.is_source_stats_usable());
+
+        let batch = tests::new_batch_with_num_values(2);
+        let new_batch = call_batch_from_parts(&adapter, &batch, 2);
+        assert_eq!(batch.num_columns(), new_batch.num_columns());
+        let expect: VectorRef = Arc::new(Float64Vector::from_slice([0.0, 0.0, 0.0]));
+        assert_eq!(expect, *new_batch.column(2));
+        assert_eq!(batch.column(3), new_batch.column(3));
+
+        let new_batch = call_arrow_chunk_to_batch(&adapter, &batch);
+        assert_eq!(expect, *new_batch.column(2));
+    }
 }
@@ -67,8 +67,9 @@ impl Snapshot for SnapshotImpl {
         }
 
         let reader = builder.pick_all_ssts(self.version.ssts())?.build().await?;
+        let metrics = reader.metrics();
 
-        Ok(ScanResponse { reader })
+        Ok(ScanResponse { reader, metrics })
     }
 
     async fn get(&self, _ctx: &ReadContext, _request: GetRequest) -> Result<GetResponse> {
@@ -12,9 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub(crate) mod index;
 pub(crate) mod parquet;
 
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
@@ -25,8 +27,8 @@ use common_time::Timestamp;
 use object_store::{util, ObjectStore};
 use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
-use store_api::storage::{ChunkReader, RegionId};
-use table::predicate::Predicate;
+use store_api::storage::{ChunkReader, IndexOptions, RegionId, ScanMetricsRef};
+use table::predicate::{ColumnStatistics, Predicate};
 
 use crate::chunk::ChunkReaderImpl;
 use crate::error::{DeleteSstSnafu, Result};
@@ -38,7 +40,7 @@ use crate::schema::ProjectedSchemaRef;
 use crate::sst::parquet::{ParquetReader, ParquetWriter};
 
 /// Maximum level of SSTs.
-pub const MAX_LEVEL: u8 = 2;
+pub const MAX_LEVEL: u8 = 3;
 
 pub type Level = u8;
 
@@ -206,6 +208,21 @@ impl FileHandle {
         &self.inner.meta.time_range
     }
 
+    #[inline]
+    pub fn column_stats(&self) -> &HashMap<String, ColumnStatistics> {
+        &self.inner.meta.column_stats
+    }
+
+    #[inline]
+    pub fn index_columns(&self) -> &[String] {
+        &self.inner.meta.index_columns
+    }
+
+    #[inline]
+    pub fn num_rows(&self) -> usize {
+        self.inner.meta.num_rows
+    }
+
     /// Returns true if current file is under compaction.
     #[inline]
     pub fn compacting(&self) -> bool {
@@ -218,6 +235,16 @@ impl FileHandle {
         self.inner.compacting.store(compacting, Ordering::Relaxed);
     }
 
+    /// Sets the compacting flag if the file isn't under compaction, returns false
+    /// if the flag is already set.
+    #[inline]
+    pub fn try_mark_compacting(&self) -> bool {
+        self.inner
+            .compacting
+            .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+    }
+
     #[inline]
     pub fn deleted(&self) -> bool {
         self.inner.deleted.load(Ordering::Relaxed)
@@ -286,7 +313,7 @@ impl FileHandleInner {
 }
 
 /// Immutable metadata of a sst file.
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FileMeta {
     /// Region of file.
     pub region_id: RegionId,
@@ -296,11 +323,32 @@ pub struct FileMeta {
     pub time_range: Option<(Timestamp, Timestamp)>,
     /// SST level of the file.
     pub level: Level,
+    /// Statistics of tag columns in the file, keyed by column name.
+    ///
+    /// Files written by older versions have no statistics.
+    #[serde(default)]
+    pub column_stats: HashMap<String, ColumnStatistics>,
+    /// Columns indexed in the index file of the SST. The SST has no index file if it's empty.
+    #[serde(default)]
+    pub index_columns: Vec<String>,
+    /// Number of rows in the file, zero for files written by older versions.
+    #[serde(default)]
+    pub num_rows: usize,
+}
+
+impl Hash for FileMeta {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // The file name is unique in a region, so the other fields are not hashed.
+        self.region_id.hash(state);
+        self.file_name.hash(state);
+    }
 }
 
 #[derive(Debug, Default)]
 pub struct WriteOptions {
     // TODO(yingwen): [flush] row group size.
+    /// Options to build the index file of the SST.
+    pub index_options: IndexOptions,
 }
 
 pub struct ReadOptions {
@@ -312,11 +360,19 @@ pub struct ReadOptions {
 
     pub predicate: Predicate,
     pub time_range: TimestampRange,
+    /// Metrics of the scan reading the SST.
+    pub metrics: ScanMetricsRef,
 }
 
 #[derive(Debug, PartialEq)]
 pub struct SstInfo {
     pub time_range: Option<(Timestamp, Timestamp)>,
+    /// Statistics of tag columns in the file.
+    pub column_stats: HashMap<String, ColumnStatistics>,
+    /// Columns indexed in the index file, empty if no index file is written.
+    pub index_columns: Vec<String>,
+    /// Number of rows written to the file.
+    pub num_rows: usize,
 }
 
 /// SST access layer.
@@ -330,11 +386,14 @@ pub trait AccessLayer: Send + Sync + std::fmt::Debug {
         opts: &WriteOptions,
     ) -> Result<SstInfo>;
 
-    /// Read SST file with given `file_name` and schema.
-    async fn read_sst(&self, file_name: &str, opts: &ReadOptions) -> Result<BoxedBatchReader>;
+    /// Read SST `file` with given schema.
+    async fn read_sst(&self, file: &FileHandle, opts: &ReadOptions) -> Result<BoxedBatchReader>;
 
-    /// Deletes a SST file with given name.
+    /// Deletes a SST file and its index file with given name.
     async fn delete_sst(&self, file_name: &str) -> Result<()>;
+
+    /// Deletes the directory of SST files and everything under it.
+    async fn delete_dir(&self) -> Result<()>;
 }
 
 pub type AccessLayerRef = Arc<dyn AccessLayer>;
@@ -402,15 +461,17 @@ impl AccessLayer for FsAccessLayer {
         writer.write_sst(opts).await
     }
 
-    async fn read_sst(&self, file_name: &str, opts: &ReadOptions) -> Result<BoxedBatchReader> {
-        let file_path = self.sst_file_path(file_name);
+    async fn read_sst(&self, file: &FileHandle, opts: &ReadOptions) -> Result<BoxedBatchReader> {
+        let file_path = self.sst_file_path(file.file_name());
         let reader = ParquetReader::new(
             &file_path,
             self.object_store.clone(),
             opts.projected_schema.clone(),
             opts.predicate.clone(),
             opts.time_range,
-        );
+        )
+        .with_index_columns(file.index_columns().to_vec())
+        .with_metrics(opts.metrics.clone());
 
         let stream = reader.chunk_stream().await?;
         Ok(Box::new(stream))
@@ -418,9 +479,28 @@ impl AccessLayer for FsAccessLayer {
 
     async fn delete_sst(&self, file_name: &str) -> Result<()> {
         let path = self.sst_file_path(file_name);
+        // Deleting an index file that doesn't exist is fine.
+        let index_object = self.object_store.object(&index::index_file_path(&path));
+        index_object.delete().await.context(DeleteSstSnafu)?;
+
         let object = self.object_store.object(&path);
         object.delete().await.context(DeleteSstSnafu)
     }
+
+    async fn delete_dir(&self) -> Result<()> {
+        let dir = self.object_store.object(&self.sst_dir);
+        if !dir.is_exist().await.context(DeleteSstSnafu)? {
+            return Ok(());
+        }
+
+        self.object_store
+            .batch()
+            .remove_all(&self.sst_dir)
+            .await
+            .context(DeleteSstSnafu)?;
+        // The directory itself may be left after removing everything under it.
+        dir.delete().await.context(DeleteSstSnafu)
+    }
 }
 
 #[cfg(test)]
@@ -437,6 +517,9 @@ mod tests {
             file_name: name.to_string(),
             time_range: None,
             level,
+            column_stats: HashMap::new(),
+            index_columns: vec![],
+            num_rows: 0,
         }
     }
 
@@ -0,0 +1,583 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Secondary indexes of SST files.
+//!
+//! An SST file may have an index file besides it, which holds a bloom filter for each row
+//! group of the SST and each indexed column. Readers use the bloom filters to skip row
+//! groups that can't match equality predicates like `host = 'web-123'`, which min/max
+//! statistics can't help with for high-cardinality columns.
+
+use std::collections::{HashMap, HashSet};
+
+use bytes::{Buf, BufMut};
+use common_query::logical_plan::DfExpr;
+use datafusion_common::ScalarValue;
+use datafusion_expr::{BinaryExpr, Operator};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::SchemaRef;
+use datatypes::value::{Value, ValueRef};
+use snafu::ensure;
+use table::predicate::Predicate;
+
+use crate::error::{DecodeSstIndexSnafu, Result};
+use crate::read::Batch;
+
+/// Magic bytes at the beginning of the index file.
+const INDEX_MAGIC: &[u8; 4] = b"GTIX";
+/// Version of the index file format.
+const INDEX_VERSION: u8 = 1;
+/// Number of bits for each distinct value in a bloom filter, which gives a false
+/// positive rate of about 1% with [NUM_HASHES] hash functions.
+const BITS_PER_VALUE: usize = 10;
+const NUM_HASHES: u32 = 7;
+
+/// Returns the path of the index file of the SST in `sst_file_path`.
+pub(crate) fn index_file_path(sst_file_path: &str) -> String {
+    format!("{sst_file_path}.index")
+}
+
+/// Bloom filters of an SST file.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SstIndex {
+    /// Number of rows in each row group.
+    row_group_rows: Vec<u64>,
+    /// Bloom filters of each row group, keyed by column name.
+    bloom_filters: HashMap<String, Vec<BloomFilter>>,
+}
+
+impl SstIndex {
+    /// Returns names of the indexed columns.
+    pub fn columns(&self) -> Vec<String> {
+        let mut columns = self.bloom_filters.keys().cloned().collect::<Vec<_>>();
+        columns.sort_unstable();
+        columns
+    }
+
+    /// Returns whether the index is built on the row groups with `row_group_rows` rows.
+    pub fn matches_row_groups(&self, row_group_rows: &[u64]) -> bool {
+        self.row_group_rows == row_group_rows
+    }
+
+    /// Returns whether each row group may contain rows matching the `predicate`. `schema`
+    /// is the schema of the SST file.
+    pub fn prune_row_groups(&self, schema: &SchemaRef, predicate: &Predicate) -> Vec<bool> {
+        (0..self.row_group_rows.len())
+            .map(|row_group| {
+                predicate
+                    .exprs()
+                    .iter()
+                    .all(|expr| self.may_match(schema, row_group, expr.df_expr()))
+            })
+            .collect()
+    }
+
+    fn may_match(&self, schema: &SchemaRef, row_group: usize, expr: &DfExpr) -> bool {
+        match expr {
+            DfExpr::BinaryExpr(BinaryExpr { left, op, right }) => match op {
+                Operator::And => {
+                    self.may_match(schema, row_group, left)
+                        && self.may_match(schema, row_group, right)
+                }
+                Operator::Or => {
+                    self.may_match(schema, row_group, left)
+                        || self.may_match(schema, row_group, right)
+                }
+                Operator::Eq => match (left.as_ref(), right.as_ref()) {
+                    (DfExpr::Column(column), DfExpr::Literal(scalar))
+                    | (DfExpr::Literal(scalar), DfExpr::Column(column)) => {
+                        self.may_contain(schema, row_group, &column.name, scalar)
+                    }
+                    _ => true,
+                },
+                _ => true,
+            },
+            DfExpr::InList {
+                expr,
+                list,
+                negated: false,
+            } => {
+                let DfExpr::Column(column) = expr.as_ref() else { return true; };
+                list.iter().any(|item| match item {
+                    DfExpr::Literal(scalar) => {
+                        self.may_contain(schema, row_group, &column.name, scalar)
+                    }
+                    _ => true,
+                })
+            }
+            _ => true,
+        }
+    }
+
+    /// Returns whether the column may contain `scalar` in the row group. Always returns
+    /// true if the column isn't indexed or the value can't be looked up in the index.
+    fn may_contain(
+        &self,
+        schema: &SchemaRef,
+        row_group: usize,
+        column: &str,
+        scalar: &ScalarValue,
+    ) -> bool {
+        let Some(filters) = self.bloom_filters.get(column) else { return true; };
+        let Some(column_schema) = schema.column_schema_by_name(column) else { return true; };
+        let Ok(value) = Value::try_from(scalar.clone()) else { return true; };
+        // Values of different types have different hashes even if they are equal.
+        if value.data_type() != column_schema.data_type {
+            return true;
+        }
+
+        match hash_value(value.as_value_ref()) {
+            Some(hash) => filters[row_group].contains(hash),
+            None => true,
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.put_slice(INDEX_MAGIC);
+        buf.put_u8(INDEX_VERSION);
+        buf.put_u32_le(self.row_group_rows.len() as u32);
+        for rows in &self.row_group_rows {
+            buf.put_u64_le(*rows);
+        }
+        buf.put_u32_le(self.bloom_filters.len() as u32);
+        for (column, filters) in &self.bloom_filters {
+            buf.put_u32_le(column.len() as u32);
+            buf.put_slice(column.as_bytes());
+            for filter in filters {
+                buf.put_u32_le(filter.num_hashes);
+                buf.put_u32_le(filter.bits.len() as u32);
+                for word in &filter.bits {
+                    buf.put_u64_le(*word);
+                }
+            }
+        }
+        buf
+    }
+
+    pub fn decode(mut buf: &[u8]) -> Result<SstIndex> {
+        ensure_remaining(buf, INDEX_MAGIC.len() + 1)?;
+        ensure!(
+            &buf[..INDEX_MAGIC.len()] == INDEX_MAGIC,
+            DecodeSstIndexSnafu {
+                msg: "invalid magic bytes",
+            }
+        );
+        buf.advance(INDEX_MAGIC.len());
+        let version = buf.get_u8();
+        ensure!(
+            version == INDEX_VERSION,
+            DecodeSstIndexSnafu {
+                msg: format!("unsupported version {version}"),
+            }
+        );
+
+        ensure_remaining(buf, 4)?;
+        let num_row_groups = buf.get_u32_le() as usize;
+        ensure_remaining(buf, num_row_groups * 8)?;
+        let row_group_rows = (0..num_row_groups).map(|_| buf.get_u64_le()).collect();
+
+        ensure_remaining(buf, 4)?;
+        let num_columns = buf.get_u32_le() as usize;
+        let mut bloom_filters = HashMap::with_capacity(num_columns);
+        for _ in 0..num_columns {
+            ensure_remaining(buf, 4)?;
+            let len = buf.get_u32_le() as usize;
+            ensure_remaining(buf, len)?;
+            let column = String::from_utf8(buf[..len].to_vec()).map_err(|_| {
+                DecodeSstIndexSnafu {
+                    msg: "invalid column name",
+                }
+                .build()
+            })?;
+            buf.advance(len);
+
+            let mut filters = Vec::with_capacity(num_row_groups);
+            for _ in 0..num_row_groups {
+                ensure_remaining(buf, 8)?;
+                let num_hashes = buf.get_u32_le();
+                let num_words = buf.get_u32_le() as usize;
+                ensure!(
+                    num_words > 0,
+                    DecodeSstIndexSnafu {
+                        msg: "empty bloom filter",
+                    }
+                );
+                ensure_remaining(buf, num_words * 8)?;
+                let bits = (0..num_words).map(|_| buf.get_u64_le()).collect();
+                filters.push(BloomFilter { num_hashes, bits });
+            }
+            bloom_filters.insert(column, filters);
+        }
+
+        Ok(SstIndex {
+            row_group_rows,
+            bloom_filters,
+        })
+    }
+}
+
+fn ensure_remaining(buf: &[u8], len: usize) -> Result<()> {
+    ensure!(
+        buf.remaining() >= len,
+        DecodeSstIndexSnafu {
+            msg: "unexpected end of index",
+        }
+    );
+    Ok(())
+}
+
+/// Builds the [SstIndex] of batches written to an SST file.
+pub(crate) struct SstIndexBuilder {
+    /// Index and name of the columns to index.
+    columns: Vec<(usize, String)>,
+    /// Max number of rows in a row group, must be the same as the SST writer's.
+    max_row_group_size: usize,
+    /// Number of rows in the current row group.
+    current_rows: usize,
+    /// Hashes of distinct values of each column in the current row group.
+    current_hashes: Vec<HashSet<u64>>,
+    row_group_rows: Vec<u64>,
+    bloom_filters: Vec<Vec<BloomFilter>>,
+}
+
+impl SstIndexBuilder {
+    /// Creates a builder to index `columns` in `schema`. Columns not in the schema or of
+    /// types that can't be indexed are ignored.
+    pub fn new(schema: &SchemaRef, columns: &[String], max_row_group_size: usize) -> Self {
+        let columns = columns
+            .iter()
+            .filter_map(|name| {
+                let idx = schema.column_index_by_name(name)?;
+                let data_type = &schema.column_schemas()[idx].data_type;
+                is_indexable(data_type).then(|| (idx, name.clone()))
+            })
+            .collect::<Vec<_>>();
+        let num_columns = columns.len();
+
+        SstIndexBuilder {
+            columns,
+            max_row_group_size,
+            current_rows: 0,
+            current_hashes: vec![HashSet::new(); num_columns],
+            row_group_rows: Vec::new(),
+            bloom_filters: vec![Vec::new(); num_columns],
+        }
+    }
+
+    pub fn update(&mut self, batch: &Batch) {
+        if self.columns.is_empty() {
+            return;
+        }
+
+        for row in 0..batch.num_rows() {
+            for ((idx, _), hashes) in self.columns.iter().zip(self.current_hashes.iter_mut()) {
+                if let Some(hash) = hash_value(batch.column(*idx).get_ref(row)) {
+                    hashes.insert(hash);
+                }
+            }
+            self.current_rows += 1;
+            if self.current_rows == self.max_row_group_size {
+                self.finish_row_group();
+            }
+        }
+    }
+
+    /// Finishes building the index, returns None if there is nothing to index.
+    pub fn finish(mut self) -> Option<SstIndex> {
+        if self.current_rows > 0 {
+            self.finish_row_group();
+        }
+        if self.columns.is_empty() || self.row_group_rows.is_empty() {
+            return None;
+        }
+
+        let bloom_filters = self
+            .columns
+            .into_iter()
+            .map(|(_, name)| name)
+            .zip(self.bloom_filters)
+            .collect();
+        Some(SstIndex {
+            row_group_rows: self.row_group_rows,
+            bloom_filters,
+        })
+    }
+
+    fn finish_row_group(&mut self) {
+        for (hashes, filters) in self
+            .current_hashes
+            .iter_mut()
+            .zip(self.bloom_filters.iter_mut())
+        {
+            filters.push(BloomFilter::from_hashes(hashes));
+            hashes.clear();
+        }
+        self.row_group_rows.push(self.current_rows as u64);
+        self.current_rows = 0;
+    }
+}
+
+fn is_indexable(data_type: &ConcreteDataType) -> bool {
+    !matches!(
+        data_type,
+        ConcreteDataType::Null(_) | ConcreteDataType::List(_) | ConcreteDataType::Dictionary(_)
+    )
+}
+
+/// Hashes the value to look up in bloom filters, returns None for null or values that
+/// can't be indexed.
+///
+/// The hash is persisted in index files so it must be stable, we don't use the
+/// [std::hash::Hash] trait as its results may change between releases.
+fn hash_value(value: ValueRef) -> Option<u64> {
+    let hash = match value {
+        ValueRef::Null | ValueRef::List(_) => return None,
+        ValueRef::Boolean(v) => fnv1a(&[v as u8]),
+        ValueRef::UInt8(v) => fnv1a(&v.to_le_bytes()),
+        ValueRef::UInt16(v) => fnv1a(&v.to_le_bytes()),
+        ValueRef::UInt32(v) => fnv1a(&v.to_le_bytes()),
+        ValueRef::UInt64(v) => fnv1a(&v.to_le_bytes()),
+        ValueRef::Int8(v) => fnv1a(&v.to_le_bytes()),
+        ValueRef::Int16(v) => fnv1a(&v.to_le_bytes()),
+        ValueRef::Int32(v) => fnv1a(&v.to_le_bytes()),
+        ValueRef::Int64(v) => fnv1a(&v.to_le_bytes()),
+        ValueRef::Float32(v) => fnv1a(&v.0.to_bits().to_le_bytes()),
+        ValueRef::Float64(v) => fnv1a(&v.0.to_bits().to_le_bytes()),
+        ValueRef::String(v) => fnv1a(v.as_bytes()),
+        ValueRef::Binary(v) => fnv1a(v),
+        ValueRef::Date(v) => fnv1a(&v.val().to_le_bytes()),
+        ValueRef::DateTime(v) => fnv1a(&v.val().to_le_bytes()),
+        ValueRef::Timestamp(v) => fnv1a(&v.value().to_le_bytes()),
+    };
+    Some(hash)
+}
+
+/// FNV-1a hash followed by the finalizer of MurmurHash3 to improve its distribution.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+    hash ^ (hash >> 33)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BloomFilter {
+    num_hashes: u32,
+    bits: Vec<u64>,
+}
+
+impl BloomFilter {
+    fn from_hashes(hashes: &HashSet<u64>) -> BloomFilter {
+        let num_words = (hashes.len() * BITS_PER_VALUE + 63) / 64;
+        let mut filter = BloomFilter {
+            num_hashes: NUM_HASHES,
+            bits: vec![0; num_words.max(1)],
+        };
+        for hash in hashes {
+            for pos in filter.bit_positions(*hash) {
+                filter.bits[pos / 64] |= 1 << (pos % 64);
+            }
+        }
+        filter
+    }
+
+    fn contains(&self, hash: u64) -> bool {
+        self.bit_positions(hash)
+            .all(|pos| self.bits[pos / 64] & (1 << (pos % 64)) != 0)
+    }
+
+    /// Derives positions of bits from `hash` by double hashing.
+    fn bit_positions(&self, hash: u64) -> impl Iterator<Item = usize> {
+        let num_bits = self.bits.len() as u64 * 64;
+        let h1 = hash;
+        let h2 = hash.rotate_left(32) | 1;
+        (0..self.num_hashes as u64)
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % num_bits) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common_query::logical_plan::Expr;
+    use datafusion_common::Column;
+    use datafusion_expr::{col, lit};
+    use datatypes::prelude::ScalarVector;
+    use datatypes::schema::{ColumnSchema, Schema};
+    use datatypes::vectors::{StringVector, UInt32Vector, VectorRef};
+
+    use super::*;
+
+    fn new_schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new("cpu", ConcreteDataType::uint32_datatype(), true),
+        ]))
+    }
+
+    fn new_batch(hosts: &[&str], cpus: &[u32]) -> Batch {
+        Batch::new(vec![
+            Arc::new(StringVector::from_slice(hosts)) as VectorRef,
+            Arc::new(UInt32Vector::from_slice(cpus)) as VectorRef,
+        ])
+    }
+
+    fn build_index(columns: &[&str]) -> SstIndex {
+        let schema = new_schema();
+        let columns = columns.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        let mut builder = SstIndexBuilder::new(&schema, &columns, 3);
+        builder.update(&new_batch(&["a", "b"], &[1, 2]));
+        builder.update(&new_batch(&["c", "d", "a", "e"], &[3, 4, 5, 6]));
+        builder.finish().unwrap()
+    }
+
+    fn prune(index: &SstIndex, exprs: Vec<DfExpr>) -> Vec<bool> {
+        let predicate = Predicate::new(exprs.into_iter().map(Expr::from).collect());
+        index.prune_row_groups(&new_schema(), &predicate)
+    }
+
+    #[test]
+    fn test_bloom_filter() {
+        let hashes = (0..1000u64)
+            .map(|v| hash_value(ValueRef::UInt64(v)).unwrap())
+            .collect::<HashSet<_>>();
+        let filter = BloomFilter::from_hashes(&hashes);
+        assert!(hashes.iter().all(|hash| filter.contains(*hash)));
+
+        let false_positives = (1000..11000u64)
+            .filter(|v| filter.contains(hash_value(ValueRef::UInt64(*v)).unwrap()))
+            .count();
+        assert!(false_positives < 500, "false positives: {false_positives}");
+
+        let filter = BloomFilter::from_hashes(&HashSet::new());
+        assert!(!filter.contains(hash_value(ValueRef::String("a")).unwrap()));
+    }
+
+    #[test]
+    fn test_build_index() {
+        let index = build_index(&["host", "cpu", "unknown"]);
+        assert_eq!(vec![3, 3], index.row_group_rows);
+        assert_eq!(vec!["cpu".to_string(), "host".to_string()], index.columns());
+        assert!(index.matches_row_groups(&[3, 3]));
+        assert!(!index.matches_row_groups(&[6]));
+
+        let schema = new_schema();
+        let builder = SstIndexBuilder::new(&schema, &[], 3);
+        assert!(builder.finish().is_none());
+    }
+
+    #[test]
+    fn test_encode_decode_index() {
+        let index = build_index(&["host", "cpu"]);
+        let buf = index.encode();
+        assert_eq!(index, SstIndex::decode(&buf).unwrap());
+
+        assert!(SstIndex::decode(&buf[..buf.len() - 1]).is_err());
+        assert!(SstIndex::decode(b"GTIX").is_err());
+        assert!(SstIndex::decode(b"invalid").is_err());
+    }
+
+    #[test]
+    fn test_prune_row_groups() {
+        let index = build_index(&["host"]);
+        // row group 0: host a, b, c; row group 1: host d, a, e
+
+        assert_eq!(vec![true, true], prune(&index, vec![]));
+        assert_eq!(
+            vec![true, true],
+            prune(&index, vec![col("host").eq(lit("a"))])
+        );
+        assert_eq!(
+            vec![true, false],
+            prune(&index, vec![col("host").eq(lit("b"))])
+        );
+        assert_eq!(
+            vec![false, true],
+            prune(&index, vec![lit("e").eq(col("host"))])
+        );
+        assert_eq!(
+            vec![false, false],
+            prune(&index, vec![col("host").eq(lit("x"))])
+        );
+        assert_eq!(
+            vec![true, true],
+            prune(
+                &index,
+                vec![col("host").in_list(vec![lit("b"), lit("d")], false)]
+            )
+        );
+        assert_eq!(
+            vec![true, true],
+            prune(&index, vec![col("host").in_list(vec![lit("x")], true)])
+        );
+        assert_eq!(
+            vec![false, true],
+            prune(
+                &index,
+                vec![col("host").eq(lit("x")).or(col("host").eq(lit("d")))]
+            )
+        );
+        assert_eq!(
+            vec![true, false],
+            prune(
+                &index,
+                vec![col("host").eq(lit("b")), col("host").eq(lit("a"))]
+            )
+        );
+        assert_eq!(
+            vec![false, false],
+            prune(
+                &index,
+                vec![col("host").eq(lit("b")).and(col("host").eq(lit("d")))]
+            )
+        );
+        // Column isn't indexed.
+        assert_eq!(
+            vec![true, true],
+            prune(&index, vec![col("cpu").eq(lit(100u32))])
+        );
+        // Not equality predicate.
+        assert_eq!(
+            vec![true, true],
+            prune(&index, vec![col("host").gt(lit("x"))])
+        );
+        // Type mismatch.
+        assert_eq!(
+            vec![true, true],
+            prune(
+                &index,
+                vec![DfExpr::Column(Column::from_name("host")).eq(lit(1))]
+            )
+        );
+
+        let index = build_index(&["cpu"]);
+        assert_eq!(
+            vec![false, true],
+            prune(&index, vec![col("cpu").eq(lit(5u32))])
+        );
+        assert_eq!(
+            vec![true, true],
+            prune(&index, vec![col("cpu").eq(lit(5i64))])
+        );
+    }
+}
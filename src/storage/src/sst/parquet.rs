@@ -14,9 +14,12 @@
 
 //! Parquet sst format.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, SeekFrom};
 use std::pin::Pin;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 
 use arrow::datatypes::DataType;
 use arrow_array::types::Int64Type;
@@ -27,25 +30,29 @@ use arrow_array::{
 use async_compat::CompatExt;
 use async_stream::try_stream;
 use async_trait::async_trait;
-use common_telemetry::error;
+use common_telemetry::{error, warn};
 use common_time::range::TimestampRange;
 use common_time::timestamp::TimeUnit;
 use common_time::Timestamp;
+use datafusion_expr::utils::expr_to_columns;
 use datatypes::arrow::array::BooleanArray;
 use datatypes::arrow::error::ArrowError;
 use datatypes::arrow::record_batch::RecordBatch;
 use datatypes::prelude::ConcreteDataType;
+use datatypes::value::Value;
+use futures::io::{AsyncRead, AsyncSeek};
 use futures_util::{Stream, StreamExt, TryStreamExt};
 use object_store::ObjectStore;
 use parquet::arrow::arrow_reader::{ArrowPredicate, RowFilter};
 use parquet::arrow::{ArrowWriter, ParquetRecordBatchStreamBuilder, ProjectionMask};
 use parquet::basic::{Compression, Encoding};
-use parquet::file::metadata::KeyValue;
+use parquet::file::metadata::{KeyValue, RowGroupMetaData};
 use parquet::file::properties::WriterProperties;
 use parquet::format::FileMetaData;
 use parquet::schema::types::SchemaDescriptor;
 use snafu::{OptionExt, ResultExt};
-use table::predicate::Predicate;
+use store_api::storage::ScanMetricsRef;
+use table::predicate::{ColumnStatistics, Predicate};
 use tokio::io::BufReader;
 
 use crate::error::{
@@ -56,6 +63,7 @@ use crate::read::{Batch, BatchReader};
 use crate::schema::compat::ReadAdapter;
 use crate::schema::{ProjectedSchemaRef, StoreSchema, StoreSchemaRef};
 use crate::sst;
+use crate::sst::index::{self, SstIndex, SstIndexBuilder};
 use crate::sst::{Source, SstInfo};
 /// Parquet sst writer.
 pub struct ParquetWriter<'a> {
@@ -75,14 +83,19 @@ impl<'a> ParquetWriter<'a> {
         }
     }
 
-    pub async fn write_sst(self, _opts: &sst::WriteOptions) -> Result<SstInfo> {
-        self.write_rows(None).await
+    pub async fn write_sst(self, opts: &sst::WriteOptions) -> Result<SstInfo> {
+        self.write_rows(None, &opts.index_options.bloom_filter_columns)
+            .await
     }
 
     /// Iterates memtable and writes rows to Parquet file.
     /// A chunk of records yielded from each iteration with a size given
     /// in config will be written to a single row group.
-    async fn write_rows(mut self, extra_meta: Option<HashMap<String, String>>) -> Result<SstInfo> {
+    async fn write_rows(
+        mut self,
+        extra_meta: Option<HashMap<String, String>>,
+        index_columns: &[String],
+    ) -> Result<SstInfo> {
         let projected_schema = self.source.projected_schema();
         let store_schema = projected_schema.schema_to_read();
         let schema = store_schema.arrow_schema().clone();
@@ -105,8 +118,18 @@ impl<'a> ParquetWriter<'a> {
         let mut buf = vec![];
         let mut arrow_writer = ArrowWriter::try_new(&mut buf, schema.clone(), Some(writer_props))
             .context(WriteParquetSnafu)?;
+        let mut stats_collector = ColumnStatsCollector::new(store_schema);
+        let mut index_builder = SstIndexBuilder::new(
+            store_schema.schema(),
+            index_columns,
+            self.max_row_group_size,
+        );
+        let mut num_rows = 0;
 
         while let Some(batch) = self.source.next_batch().await? {
+            num_rows += batch.num_rows();
+            stats_collector.update(&batch);
+            index_builder.update(&batch);
             let arrow_batch = RecordBatch::try_new(
                 schema.clone(),
                 batch
@@ -130,7 +153,101 @@ impl<'a> ParquetWriter<'a> {
         object.write(buf).await.context(WriteObjectSnafu {
             path: object.path(),
         })?;
-        Ok(SstInfo { time_range })
+
+        let index_columns = match index_builder.finish() {
+            Some(index) => {
+                let index_object = self
+                    .object_store
+                    .object(&index::index_file_path(self.file_path));
+                index_object
+                    .write(index.encode())
+                    .await
+                    .context(WriteObjectSnafu {
+                        path: index_object.path(),
+                    })?;
+                index.columns()
+            }
+            None => Vec::new(),
+        };
+
+        Ok(SstInfo {
+            time_range,
+            column_stats: stats_collector.finish(),
+            index_columns,
+            num_rows,
+        })
+    }
+}
+
+/// Collects statistics of tag columns, i.e. row key columns except the timestamp column,
+/// from batches written to the SST.
+struct ColumnStatsCollector {
+    /// Index and name of the columns to collect.
+    columns: Vec<(usize, String)>,
+    stats: Vec<ColumnStatistics>,
+}
+
+impl ColumnStatsCollector {
+    fn new(store_schema: &StoreSchemaRef) -> ColumnStatsCollector {
+        let schema = store_schema.schema();
+        let columns = schema.column_schemas()[..store_schema.row_key_end()]
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| Some(*idx) != schema.timestamp_index())
+            .map(|(idx, column_schema)| (idx, column_schema.name.clone()))
+            .collect::<Vec<_>>();
+        let stats = vec![
+            ColumnStatistics {
+                min_value: Value::Null,
+                max_value: Value::Null,
+                null_count: 0,
+            };
+            columns.len()
+        ];
+
+        ColumnStatsCollector { columns, stats }
+    }
+
+    fn update(&mut self, batch: &Batch) {
+        for ((idx, _), stats) in self.columns.iter().zip(self.stats.iter_mut()) {
+            let vector = batch.column(*idx);
+            // Finds the min/max value of the batch by reference to avoid cloning every value.
+            let (mut min_idx, mut max_idx) = (None, None);
+            for i in 0..vector.len() {
+                let value = vector.get_ref(i);
+                if value.is_null() {
+                    stats.null_count += 1;
+                    continue;
+                }
+                if min_idx.map_or(true, |min| value < vector.get_ref(min)) {
+                    min_idx = Some(i);
+                }
+                if max_idx.map_or(true, |max| value > vector.get_ref(max)) {
+                    max_idx = Some(i);
+                }
+            }
+
+            if let Some(min_idx) = min_idx {
+                let min = vector.get(min_idx);
+                if stats.min_value.is_null() || min < stats.min_value {
+                    stats.min_value = min;
+                }
+            }
+            if let Some(max_idx) = max_idx {
+                let max = vector.get(max_idx);
+                if stats.max_value.is_null() || max > stats.max_value {
+                    stats.max_value = max;
+                }
+            }
+        }
+    }
+
+    fn finish(self) -> HashMap<String, ColumnStatistics> {
+        self.columns
+            .into_iter()
+            .map(|(_, name)| name)
+            .zip(self.stats)
+            .collect()
     }
 }
 
@@ -212,6 +329,9 @@ pub struct ParquetReader<'a> {
     projected_schema: ProjectedSchemaRef,
     predicate: Predicate,
     time_range: TimestampRange,
+    /// Columns indexed in the index file of the SST.
+    index_columns: Vec<String>,
+    metrics: ScanMetricsRef,
 }
 
 impl<'a> ParquetReader<'a> {
@@ -228,9 +348,24 @@ impl<'a> ParquetReader<'a> {
             projected_schema,
             predicate,
             time_range,
+            index_columns: Vec::new(),
+            metrics: ScanMetricsRef::default(),
         }
     }
 
+    /// Sets the columns indexed in the index file, the reader uses the index file to
+    /// skip row groups if it's not empty.
+    pub fn with_index_columns(mut self, index_columns: Vec<String>) -> Self {
+        self.index_columns = index_columns;
+        self
+    }
+
+    /// Sets the metrics to record the row groups skipped and the bytes fetched by the reader.
+    pub fn with_metrics(mut self, metrics: ScanMetricsRef) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
     pub async fn chunk_stream(&self) -> Result<ChunkStream> {
         let operator = self.object_store.clone();
         let reader = operator
@@ -239,8 +374,8 @@ impl<'a> ParquetReader<'a> {
             .await
             .context(ReadObjectSnafu {
                 path: self.file_path,
-            })?
-            .compat();
+            })?;
+        let reader = CountingReader::new(reader, self.metrics.clone()).compat();
         let buf_reader = BufReader::new(reader);
         let builder = ParquetRecordBatchStreamBuilder::new(buf_reader)
             .await
@@ -257,12 +392,31 @@ impl<'a> ParquetReader<'a> {
 
         let adapter = ReadAdapter::new(store_schema.clone(), self.projected_schema.clone())?;
 
-        let pruned_row_groups = self
-            .predicate
-            .prune_row_groups(
-                store_schema.schema().clone(),
-                builder.metadata().row_groups(),
-            )
+        let row_groups = builder.metadata().row_groups();
+        let row_groups_to_read = if adapter.is_source_stats_usable() {
+            let mut row_groups_to_read = self
+                .predicate
+                .prune_row_groups(store_schema.schema().clone(), row_groups);
+            if let Some(index) = self.load_index(row_groups).await {
+                let index_result = index.prune_row_groups(store_schema.schema(), &self.predicate);
+                for (to_read, matched) in row_groups_to_read.iter_mut().zip(index_result) {
+                    *to_read &= matched;
+                }
+            }
+            row_groups_to_read
+        } else {
+            // Some columns are renamed or have different types in this file, the statistics
+            // of the file can't be used to evaluate the predicate.
+            vec![true; row_groups.len()]
+        };
+        let num_skipped = row_groups_to_read
+            .iter()
+            .filter(|to_read| !**to_read)
+            .count();
+        self.metrics
+            .row_groups_skipped
+            .fetch_add(num_skipped, Ordering::Relaxed);
+        let pruned_row_groups = row_groups_to_read
             .into_iter()
             .enumerate()
             .filter_map(|(idx, valid)| if valid { Some(idx) } else { None })
@@ -294,6 +448,64 @@ impl<'a> ParquetReader<'a> {
         ChunkStream::new(adapter, Box::pin(chunk_stream))
     }
 
+    /// Loads the index of the SST if it may help to prune row groups. Failures are only
+    /// logged as the index is not necessary to read the SST.
+    async fn load_index(&self, row_groups: &[RowGroupMetaData]) -> Option<SstIndex> {
+        if !self.predicate_uses_index() {
+            return None;
+        }
+
+        let path = index::index_file_path(self.file_path);
+        let index = match self.object_store.object(&path).read().await {
+            Ok(buf) => {
+                self.metrics
+                    .bytes_fetched
+                    .fetch_add(buf.len(), Ordering::Relaxed);
+                SstIndex::decode(&buf)
+            }
+            Err(e) => Err(e).context(ReadObjectSnafu { path: &path }),
+        };
+        let index = match index {
+            Ok(index) => index,
+            Err(e) => {
+                warn!("Failed to load SST index {}, error: {:?}", path, e);
+                return None;
+            }
+        };
+
+        let row_group_rows = row_groups
+            .iter()
+            .map(|row_group| row_group.num_rows() as u64)
+            .collect::<Vec<_>>();
+        if !index.matches_row_groups(&row_group_rows) {
+            warn!(
+                "Row groups of SST index {} mismatch, ignore the index",
+                path
+            );
+            return None;
+        }
+
+        Some(index)
+    }
+
+    /// Returns whether any expr of the predicate references an index column.
+    fn predicate_uses_index(&self) -> bool {
+        if self.index_columns.is_empty() {
+            return false;
+        }
+
+        let mut columns = HashSet::new();
+        for expr in self.predicate.exprs() {
+            if expr_to_columns(expr.df_expr(), &mut columns).is_err() {
+                // Columns of the expr are unknown, the index may still help.
+                return true;
+            }
+        }
+        columns
+            .iter()
+            .any(|column| self.index_columns.contains(&column.name))
+    }
+
     /// Builds time range row filter.
     fn build_time_range_row_filter(&self, schema_desc: &SchemaDescriptor) -> Option<RowFilter> {
         let ts_col_idx = self
@@ -348,6 +560,42 @@ impl<'a> ParquetReader<'a> {
     }
 }
 
+/// A reader that counts the bytes read from the inner reader of an object.
+struct CountingReader<R> {
+    reader: R,
+    metrics: ScanMetricsRef,
+}
+
+impl<R> CountingReader<R> {
+    fn new(reader: R, metrics: ScanMetricsRef) -> Self {
+        Self { reader, metrics }
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for CountingReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let poll = Pin::new(&mut self.reader).poll_read(cx, buf);
+        if let Poll::Ready(Ok(n)) = &poll {
+            self.metrics.bytes_fetched.fetch_add(*n, Ordering::Relaxed);
+        }
+        poll
+    }
+}
+
+impl<R: AsyncSeek + Unpin> AsyncSeek for CountingReader<R> {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        Pin::new(&mut self.reader).poll_seek(cx, pos)
+    }
+}
+
 fn time_unit_lossy(range: &TimestampRange, ts_col_unit: TimeUnit) -> bool {
     range
         .start()
@@ -519,13 +767,15 @@ impl BatchReader for ChunkStream {
 mod tests {
     use std::sync::Arc;
 
+    use common_query::logical_plan::{DfExpr, Expr};
+    use datafusion_expr::{col, lit};
     use datatypes::arrow::array::{Array, ArrayRef, UInt64Array, UInt8Array};
     use datatypes::prelude::{ScalarVector, Vector};
     use datatypes::types::{TimestampMillisecondType, TimestampType};
     use datatypes::vectors::TimestampMillisecondVector;
     use object_store::services::Fs;
     use object_store::ObjectStoreBuilder;
-    use store_api::storage::OpType;
+    use store_api::storage::{IndexOptions, OpType};
     use tempdir::TempDir;
 
     use super::*;
@@ -569,11 +819,22 @@ mod tests {
         let iter = memtable.iter(&IterContext::default()).unwrap();
         let writer = ParquetWriter::new(sst_file_name, Source::Iter(iter), object_store.clone());
 
-        writer
+        let SstInfo { column_stats, .. } = writer
             .write_sst(&sst::WriteOptions::default())
             .await
             .unwrap();
 
+        // Only the version column is a row key column other than the timestamp.
+        assert_eq!(1, column_stats.len());
+        assert_eq!(
+            ColumnStatistics {
+                min_value: Value::UInt64(1),
+                max_value: Value::UInt64(5),
+                null_count: 0,
+            },
+            column_stats["__version"]
+        );
+
         // verify parquet file
         let reader = BufReader::new(
             object_store
@@ -667,7 +928,7 @@ mod tests {
         let iter = memtable.iter(&IterContext::default()).unwrap();
         let writer = ParquetWriter::new(sst_file_name, Source::Iter(iter), object_store.clone());
 
-        let SstInfo { time_range } = writer
+        let SstInfo { time_range, .. } = writer
             .write_sst(&sst::WriteOptions::default())
             .await
             .unwrap();
@@ -704,6 +965,88 @@ mod tests {
         assert_eq!(rows_total, rows_fetched);
     }
 
+    #[tokio::test]
+    async fn test_parquet_read_with_index() {
+        common_telemetry::init_default_ut_logging();
+        let schema = memtable_tests::schema_for_test();
+        let memtable = DefaultMemtableBuilder::default().build(schema.clone());
+
+        let rows_total = 4096 * 4;
+        // Versions are shuffled so min/max statistics can't prune any row group.
+        let version_of = |i: usize| ((i * 7919) % rows_total) as u64;
+        let keys_vec = (0..rows_total)
+            .map(|i| (i as i64, version_of(i)))
+            .collect::<Vec<_>>();
+        let values_vec = (0..rows_total)
+            .map(|i| (Some(i as u64), Some(i as u64)))
+            .collect::<Vec<_>>();
+        memtable_tests::write_kvs(
+            &*memtable,
+            10, // sequence
+            OpType::Put,
+            &keys_vec,   // keys
+            &values_vec, // values
+        );
+
+        let dir = TempDir::new("write_parquet").unwrap();
+        let path = dir.path().to_str().unwrap();
+        let backend = Fs::default().root(path).build().unwrap();
+        let object_store = ObjectStore::new(backend).finish();
+        let sst_file_name = "test-read-index.parquet";
+        let iter = memtable.iter(&IterContext::default()).unwrap();
+        let writer = ParquetWriter::new(sst_file_name, Source::Iter(iter), object_store.clone());
+        let opts = sst::WriteOptions {
+            index_options: IndexOptions {
+                bloom_filter_columns: vec!["__version".to_string()],
+            },
+        };
+        let SstInfo { index_columns, .. } = writer.write_sst(&opts).await.unwrap();
+        assert_eq!(vec!["__version".to_string()], index_columns);
+
+        let read_rows = |filters: Vec<DfExpr>, index_columns: Vec<String>| {
+            let metrics = ScanMetricsRef::default();
+            let reader = ParquetReader::new(
+                sst_file_name,
+                object_store.clone(),
+                Arc::new(ProjectedSchema::new(schema.clone(), Some(vec![1])).unwrap()),
+                Predicate::new(filters.into_iter().map(Expr::from).collect()),
+                TimestampRange::min_to_max(),
+            )
+            .with_index_columns(index_columns)
+            .with_metrics(metrics.clone());
+            async move {
+                let mut rows_fetched = 0;
+                let mut stream = reader.chunk_stream().await.unwrap();
+                while let Some(res) = stream.next_batch().await.unwrap() {
+                    rows_fetched += res.num_rows();
+                }
+                (rows_fetched, metrics)
+            }
+        };
+
+        let versions = vec![lit(version_of(100)), lit(version_of(4096 * 2 + 100))];
+        let filter = col("__version").in_list(versions, false);
+        // Only the row groups containing the versions are read.
+        let (rows_fetched, metrics) = read_rows(vec![filter.clone()], index_columns).await;
+        assert_eq!(4096 * 2, rows_fetched);
+        assert_eq!(2, metrics.row_groups_skipped.load(Ordering::Relaxed));
+        assert!(metrics.bytes_fetched.load(Ordering::Relaxed) > 0);
+        // Reads all row groups without the index.
+        let (rows_fetched, metrics) = read_rows(vec![filter], vec![]).await;
+        assert_eq!(rows_total, rows_fetched);
+        assert_eq!(0, metrics.row_groups_skipped.load(Ordering::Relaxed));
+        let bytes_without_index = metrics.bytes_fetched.load(Ordering::Relaxed);
+        assert!(bytes_without_index > 0);
+        // The index isn't fetched if the predicate doesn't reference any index column.
+        let filter = col("v0").gt(lit(100u64));
+        let (_, metrics) = read_rows(vec![filter], vec!["__version".to_string()]).await;
+        assert_eq!(0, metrics.row_groups_skipped.load(Ordering::Relaxed));
+        assert_eq!(
+            bytes_without_index,
+            metrics.bytes_fetched.load(Ordering::Relaxed)
+        );
+    }
+
     #[tokio::test]
     async fn test_parquet_reader() {
         common_telemetry::init_default_ut_logging();
@@ -740,7 +1083,7 @@ mod tests {
         let iter = memtable.iter(&IterContext::default()).unwrap();
         let writer = ParquetWriter::new(sst_file_name, Source::Iter(iter), object_store.clone());
 
-        let SstInfo { time_range } = writer
+        let SstInfo { time_range, .. } = writer
             .write_sst(&sst::WriteOptions::default())
             .await
             .unwrap();
@@ -853,7 +1196,7 @@ mod tests {
         let iter = memtable.iter(&IterContext::default()).unwrap();
         let writer = ParquetWriter::new(sst_file_name, Source::Iter(iter), object_store.clone());
 
-        let SstInfo { time_range } = writer
+        let SstInfo { time_range, .. } = writer
             .write_sst(&sst::WriteOptions::default())
             .await
             .unwrap();
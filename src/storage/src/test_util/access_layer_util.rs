@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::read::BoxedBatchReader;
-use crate::sst::{AccessLayer, ReadOptions, Source, SstInfo, WriteOptions};
+use crate::sst::{AccessLayer, FileHandle, ReadOptions, Source, SstInfo, WriteOptions};
 
 #[derive(Debug)]
 pub struct MockAccessLayer;
@@ -31,7 +31,7 @@ impl AccessLayer for MockAccessLayer {
 
     async fn read_sst(
         &self,
-        _file_name: &str,
+        _file: &FileHandle,
         _opts: &ReadOptions,
     ) -> crate::error::Result<BoxedBatchReader> {
         unimplemented!()
@@ -40,4 +40,8 @@ impl AccessLayer for MockAccessLayer {
     async fn delete_sst(&self, _file_name: &str) -> crate::error::Result<()> {
         Ok(())
     }
+
+    async fn delete_dir(&self) -> crate::error::Result<()> {
+        Ok(())
+    }
 }
@@ -70,5 +70,7 @@ pub async fn new_store_config(
         engine_config: Default::default(),
         file_purger,
         ttl: None,
+        compaction_options: Default::default(),
+        index_options: Default::default(),
     }
 }
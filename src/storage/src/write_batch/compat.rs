@@ -71,7 +71,19 @@ impl CompatWrite for Mutation {
         let mut columns = Vec::with_capacity(dest_schema.num_columns());
         for column_schema in dest_schema.column_schemas() {
             if let Some(vector) = self.record_batch.column_by_name(&column_schema.name) {
-                columns.push(vector.clone());
+                if vector.data_type() == column_schema.data_type {
+                    columns.push(vector.clone());
+                } else {
+                    // The type of the column has been changed.
+                    let vector =
+                        vector
+                            .cast(&column_schema.data_type)
+                            .context(error::CastColumnSnafu {
+                                column: &column_schema.name,
+                                data_type: column_schema.data_type.clone(),
+                            })?;
+                    columns.push(vector);
+                }
             } else {
                 // We need to fill the column by null or its default value.
                 let vector = write_batch::new_column_with_default_value(column_schema, num_rows)?;
@@ -104,7 +116,7 @@ mod tests {
 
     use datatypes::data_type::ConcreteDataType;
     use datatypes::schema::{ColumnDefaultConstraint, SchemaBuilder};
-    use datatypes::vectors::{Int32Vector, TimestampMillisecondVector, VectorRef};
+    use datatypes::vectors::{Int32Vector, Int64Vector, TimestampMillisecondVector, VectorRef};
     use store_api::storage::WriteRequest;
 
     use super::*;
@@ -228,4 +240,36 @@ mod tests {
             "err {err} is not NotInSchemaToCompat",
         );
     }
+
+    #[test]
+    fn test_mutation_compat_write_modified_type() {
+        let schema_old = new_test_schema(Some(None));
+        let mut batch = WriteBatch::new(schema_old, TEST_ROW_KEY_END);
+        let mut put_data = new_put_data();
+        put_data.insert(
+            "v0".to_string(),
+            Arc::new(Int32Vector::from_slice([4, 5, 6])) as VectorRef,
+        );
+        batch.put(put_data).unwrap();
+
+        let mut column_schemas = new_test_schema(None).column_schemas().to_vec();
+        column_schemas.push(ColumnSchema::new(
+            "v0",
+            ConcreteDataType::int64_datatype(),
+            true,
+        ));
+        let schema = Arc::new(
+            SchemaBuilder::try_from(column_schemas)
+                .unwrap()
+                .build()
+                .unwrap(),
+        );
+
+        let mutation = &mut batch.payload.mutations[0];
+        mutation.compat_write(&schema).unwrap();
+
+        let v0 = mutation.record_batch.column_by_name("v0").unwrap();
+        let expect = Arc::new(Int64Vector::from_slice([4, 5, 6])) as VectorRef;
+        assert_eq!(expect, *v0);
+    }
 }
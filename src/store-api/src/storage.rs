@@ -32,12 +32,15 @@ pub use datatypes::schema::{
 
 pub use self::chunk::{Chunk, ChunkReader};
 pub use self::descriptors::*;
-pub use self::engine::{CreateOptions, EngineContext, OpenOptions, StorageEngine};
+pub use self::engine::{
+    AlterOptions, CompactionOptions, CompactionStrategy, CreateOptions, EngineContext,
+    IndexOptions, MemtableType, OpenOptions, StorageEngine,
+};
 pub use self::metadata::RegionMeta;
-pub use self::region::{Region, WriteContext};
+pub use self::region::{CompactContext, FlushContext, Region, WriteContext};
 pub use self::requests::{
     AddColumn, AlterOperation, AlterRequest, GetRequest, ScanRequest, WriteRequest,
 };
-pub use self::responses::{GetResponse, ScanResponse, WriteResponse};
+pub use self::responses::{GetResponse, ScanMetrics, ScanMetricsRef, ScanResponse, WriteResponse};
 pub use self::snapshot::{ReadContext, Snapshot};
 pub use self::types::{OpType, SequenceNumber};
@@ -63,6 +63,14 @@ impl ColumnDescriptor {
         self.default_constraint.as_ref()
     }
 
+    #[inline]
+    pub(crate) fn set_default_constraint(
+        &mut self,
+        default_constraint: Option<ColumnDefaultConstraint>,
+    ) {
+        self.default_constraint = default_constraint;
+    }
+
     /// Convert [ColumnDescriptor] to [ColumnSchema]. Fields not in ColumnSchema **will not**
     /// be stored as metadata.
     pub fn to_column_schema(&self) -> ColumnSchema {
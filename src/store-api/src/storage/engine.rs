@@ -88,6 +88,12 @@ pub struct CreateOptions {
     pub write_buffer_size: Option<usize>,
     /// Region SST files TTL
     pub ttl: Option<Duration>,
+    /// Region compaction options
+    pub compaction_options: CompactionOptions,
+    /// Region SST index options
+    pub index_options: IndexOptions,
+    /// Type of the region's memtables
+    pub memtable_type: MemtableType,
 }
 
 /// Options to open a region.
@@ -99,4 +105,70 @@ pub struct OpenOptions {
     pub write_buffer_size: Option<usize>,
     /// Region SST files TTL
     pub ttl: Option<Duration>,
+    /// Region compaction options
+    pub compaction_options: CompactionOptions,
+    /// Region SST index options
+    pub index_options: IndexOptions,
+    /// Type of the region's memtables
+    pub memtable_type: MemtableType,
+}
+
+/// Options of a region that could be altered after the region is opened.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AlterOptions {
+    /// Region memtable max size in bytes, `None` to use the default of the engine
+    pub write_buffer_size: Option<usize>,
+    /// Region SST files TTL, `None` to keep the files forever
+    pub ttl: Option<Duration>,
+}
+
+/// Type of the memtable of a region.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MemtableType {
+    /// Stores each row as an individual entry of a btree, ordered by the row key.
+    #[default]
+    BTree,
+    /// Groups rows by series, the key of each series is stored only once and the
+    /// other columns of its rows are stored in columnar buffers.
+    Series,
+}
+
+/// Strategy to compact SST files in a region.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompactionStrategy {
+    /// Files in each time window are merged into the next level only when there are
+    /// too many of them, and files in the last level are merged among themselves.
+    #[default]
+    Tiered,
+    /// Files in level 0 are merged together with the overlapping files in the next level,
+    /// so that each time window holds as few files as possible.
+    Leveled,
+}
+
+/// Options to compact a region.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactionOptions {
+    /// Compaction strategy.
+    pub strategy: CompactionStrategy,
+    /// Duration of the time window to partition SST files. Inferred from the time ranges
+    /// of files if absent.
+    pub time_window: Option<Duration>,
+    /// Max number of files in a time window of a level, the files are merged once it's
+    /// exceeded. Use the storage engine's default value if absent.
+    pub max_files_in_window: Option<usize>,
+}
+
+/// Options to build secondary indexes in SST files of a region.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexOptions {
+    /// Names of the row key columns to build bloom filters on. Each row group of an SST
+    /// file has a bloom filter for each of these columns.
+    pub bloom_filter_columns: Vec<String>,
+}
+
+impl IndexOptions {
+    /// Returns true if no index needs to be built.
+    pub fn is_empty(&self) -> bool {
+        self.bloom_filter_columns.is_empty()
+    }
 }
@@ -34,8 +34,9 @@
 
 use async_trait::async_trait;
 use common_error::ext::ErrorExt;
+use common_time::range::TimestampRange;
 
-use crate::storage::engine::OpenOptions;
+use crate::storage::engine::{AlterOptions, OpenOptions};
 use crate::storage::metadata::RegionMeta;
 use crate::storage::requests::{AlterRequest, WriteRequest};
 use crate::storage::responses::WriteResponse;
@@ -74,6 +75,26 @@ pub trait Region: Send + Sync + Clone + std::fmt::Debug + 'static {
     async fn alter(&self, request: AlterRequest) -> Result<(), Self::Error>;
 
     async fn close(&self) -> Result<(), Self::Error>;
+
+    /// Flush memtables of the region to SST files.
+    async fn flush(&self, ctx: &FlushContext) -> Result<(), Self::Error>;
+
+    /// Compact SST files of the region.
+    async fn compact(&self, ctx: &CompactContext) -> Result<(), Self::Error>;
+
+    /// Removes SST files whose rows are all inside `time_range`, returns the number
+    /// of rows in the removed files.
+    ///
+    /// Rows in memtables and in files partially inside the range are not affected.
+    async fn drop_files_in_range(&self, time_range: TimestampRange) -> Result<usize, Self::Error>;
+
+    /// Alters the options of the region, the new options take effect on subsequent
+    /// writes, flushes and compactions.
+    async fn alter_options(&self, opts: &AlterOptions) -> Result<(), Self::Error>;
+
+    /// Discards all data in the region, including SST files and memtables, while the
+    /// metadata of the region is kept.
+    async fn truncate(&self) -> Result<(), Self::Error>;
 }
 
 /// Context for write operations.
@@ -85,3 +106,29 @@ impl From<&OpenOptions> for WriteContext {
         WriteContext::default()
     }
 }
+
+/// Context for flush operations.
+#[derive(Debug, Clone)]
+pub struct FlushContext {
+    /// If true, the flush will wait until the flush job is finished.
+    pub wait: bool,
+}
+
+impl Default for FlushContext {
+    fn default() -> FlushContext {
+        FlushContext { wait: true }
+    }
+}
+
+/// Context for compaction operations.
+#[derive(Debug, Clone)]
+pub struct CompactContext {
+    /// If true, the compaction will wait until the compaction job is finished.
+    pub wait: bool,
+}
+
+impl Default for CompactContext {
+    fn default() -> CompactContext {
+        CompactContext { wait: true }
+    }
+}
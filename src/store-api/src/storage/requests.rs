@@ -18,7 +18,9 @@ use common_error::ext::ErrorExt;
 use common_query::logical_plan::Expr;
 use datatypes::vectors::VectorRef;
 
-use crate::storage::{ColumnDescriptor, RegionDescriptor, SequenceNumber};
+use crate::storage::{
+    ColumnDefaultConstraint, ColumnDescriptor, ConcreteDataType, RegionDescriptor, SequenceNumber,
+};
 
 /// Write request holds a collection of updates to apply to a region.
 ///
@@ -76,6 +78,29 @@ pub enum AlterOperation {
         /// Name of columns to drop.
         names: Vec<String>,
     },
+    /// Change the data type of a value column, the new type must be able to hold all
+    /// values of the old type.
+    ModifyColumnType {
+        /// Name of the column to modify.
+        name: String,
+        /// New data type of the column.
+        data_type: ConcreteDataType,
+    },
+    /// Rename a value column.
+    RenameColumn {
+        /// Name of the column to rename.
+        name: String,
+        /// New name of the column.
+        new_name: String,
+    },
+    /// Set the default constraint of a column, or remove it if `default_constraint`
+    /// is `None`.
+    SetColumnDefault {
+        /// Name of the column to modify.
+        name: String,
+        /// New default constraint of the column.
+        default_constraint: Option<ColumnDefaultConstraint>,
+    },
 }
 
 impl AlterOperation {
@@ -88,9 +113,69 @@ impl AlterOperation {
             AlterOperation::DropColumns { names } => {
                 Self::apply_drop(names, descriptor);
             }
+            AlterOperation::ModifyColumnType { name, data_type } => {
+                if let Some(column) = Self::value_column_mut(name, descriptor) {
+                    // The default value should have been checked to be castable.
+                    let default_constraint = column
+                        .default_constraint()
+                        .and_then(|constraint| constraint.cast_to(data_type).ok());
+                    column.set_default_constraint(default_constraint);
+                    column.data_type = data_type.clone();
+                }
+            }
+            AlterOperation::RenameColumn { name, new_name } => {
+                if let Some(column) = Self::value_column_mut(name, descriptor) {
+                    column.name = new_name.clone();
+                }
+            }
+            AlterOperation::SetColumnDefault {
+                name,
+                default_constraint,
+            } => {
+                if let Some(column) = Self::column_mut(name, descriptor) {
+                    column.set_default_constraint(default_constraint.clone());
+                }
+            }
         }
     }
 
+    /// Returns the column named `name` in the [RegionDescriptor].
+    fn column_mut<'a>(
+        name: &str,
+        descriptor: &'a mut RegionDescriptor,
+    ) -> Option<&'a mut ColumnDescriptor> {
+        let RegionDescriptor {
+            row_key,
+            default_cf,
+            extra_cfs,
+            ..
+        } = descriptor;
+        row_key
+            .columns
+            .iter_mut()
+            .chain(std::iter::once(&mut row_key.timestamp))
+            .chain(default_cf.columns.iter_mut())
+            .chain(extra_cfs.iter_mut().flat_map(|cf| cf.columns.iter_mut()))
+            .find(|col| col.name == name)
+    }
+
+    /// Returns the value column named `name` in the [RegionDescriptor].
+    fn value_column_mut<'a>(
+        name: &str,
+        descriptor: &'a mut RegionDescriptor,
+    ) -> Option<&'a mut ColumnDescriptor> {
+        let RegionDescriptor {
+            default_cf,
+            extra_cfs,
+            ..
+        } = descriptor;
+        default_cf
+            .columns
+            .iter_mut()
+            .chain(extra_cfs.iter_mut().flat_map(|cf| cf.columns.iter_mut()))
+            .find(|col| col.name == name)
+    }
+
     /// Add `columns` to the [RegionDescriptor].
     ///
     /// Value columns would be added to the default column family.
@@ -205,5 +290,47 @@ mod tests {
         op.apply(&mut desc);
         assert_eq!(1, desc.row_key.columns.len());
         assert_eq!(1, desc.default_cf.columns.len());
+
+        let op = AlterOperation::ModifyColumnType {
+            name: String::from("4"),
+            data_type: ConcreteDataType::float64_datatype(),
+        };
+        op.apply(&mut desc);
+        assert_eq!(
+            ConcreteDataType::float64_datatype(),
+            desc.default_cf.columns[0].data_type
+        );
+
+        let op = AlterOperation::RenameColumn {
+            name: String::from("4"),
+            new_name: String::from("5"),
+        };
+        op.apply(&mut desc);
+        assert_eq!("5", desc.default_cf.columns[0].name);
+        assert_eq!(4, desc.default_cf.columns[0].id);
+
+        // Key columns are ignored.
+        let op = AlterOperation::RenameColumn {
+            name: String::from("3"),
+            new_name: String::from("6"),
+        };
+        op.apply(&mut desc);
+        assert_eq!("3", desc.row_key.columns[0].name);
+
+        let op = AlterOperation::SetColumnDefault {
+            name: String::from("3"),
+            default_constraint: Some(ColumnDefaultConstraint::Value(Value::Int64(1))),
+        };
+        op.apply(&mut desc);
+        assert_eq!(
+            Some(&ColumnDefaultConstraint::Value(Value::Int64(1))),
+            desc.row_key.columns[0].default_constraint()
+        );
+        let op = AlterOperation::SetColumnDefault {
+            name: String::from("3"),
+            default_constraint: None,
+        };
+        op.apply(&mut desc);
+        assert!(desc.row_key.columns[0].default_constraint().is_none());
     }
 }
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::{AtomicU64, AtomicUsize};
+use std::sync::Arc;
+
 #[derive(Debug)]
 pub struct WriteResponse {}
 
@@ -19,7 +22,31 @@ pub struct WriteResponse {}
 pub struct ScanResponse<R> {
     /// Reader to read result chunks.
     pub reader: R,
+    /// Metrics of the scan, which are updated while the reader is being read.
+    pub metrics: ScanMetricsRef,
+}
+
+/// Metrics of a scan in the storage layer.
+#[derive(Debug, Default)]
+pub struct ScanMetrics {
+    /// Number of memtables read.
+    pub memtables_read: AtomicUsize,
+    /// Number of SSTs opened.
+    pub ssts_opened: AtomicUsize,
+    /// Number of SSTs pruned by their time ranges or column statistics without opening them.
+    pub ssts_pruned: AtomicUsize,
+    /// Number of row groups in opened SSTs skipped by their statistics or indexes.
+    pub row_groups_skipped: AtomicUsize,
+    /// Number of bytes fetched from the object store.
+    pub bytes_fetched: AtomicUsize,
+    /// Time spent in merging rows from memtables and SSTs, including the time to read
+    /// them, in nanoseconds.
+    pub merge_nanos: AtomicU64,
+    /// Time spent in removing duplicate and deleted rows, in nanoseconds.
+    pub dedup_nanos: AtomicU64,
 }
 
+pub type ScanMetricsRef = Arc<ScanMetrics>;
+
 #[derive(Debug)]
 pub struct GetResponse {}
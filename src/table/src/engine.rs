@@ -16,9 +16,13 @@ use std::fmt::{self, Display};
 use std::sync::Arc;
 
 use common_procedure::BoxedProcedure;
+use snafu::OptionExt;
 
-use crate::error::Result;
-use crate::requests::{AlterTableRequest, CreateTableRequest, DropTableRequest, OpenTableRequest};
+use crate::error::{Result, TableNotFoundSnafu};
+use crate::requests::{
+    AlterTableRequest, CompactTableRequest, CreateTableRequest, DropTableRequest,
+    FlushTableRequest, OpenTableRequest, TruncateTableRequest,
+};
 use crate::TableRef;
 
 /// Represents a resolved path to a table of the form “catalog.schema.table”
@@ -100,6 +104,55 @@ pub trait TableEngine: Send + Sync {
 
     /// Drops the given table. Return true if the table is dropped, or false if the table doesn't exist.
     async fn drop_table(&self, ctx: &EngineContext, request: DropTableRequest) -> Result<bool>;
+
+    /// Flushes the given table and waits until the flush is finished.
+    async fn flush_table(&self, ctx: &EngineContext, request: FlushTableRequest) -> Result<()> {
+        let table_ref = TableReference {
+            catalog: &request.catalog_name,
+            schema: &request.schema_name,
+            table: &request.table_name,
+        };
+        let table = self
+            .get_table(ctx, &table_ref)?
+            .with_context(|| TableNotFoundSnafu {
+                table_name: table_ref.to_string(),
+            })?;
+        table.flush(request.region_number).await
+    }
+
+    /// Compacts the given table and waits until the compaction is finished.
+    async fn compact_table(&self, ctx: &EngineContext, request: CompactTableRequest) -> Result<()> {
+        let table_ref = TableReference {
+            catalog: &request.catalog_name,
+            schema: &request.schema_name,
+            table: &request.table_name,
+        };
+        let table = self
+            .get_table(ctx, &table_ref)?
+            .with_context(|| TableNotFoundSnafu {
+                table_name: table_ref.to_string(),
+            })?;
+        table.compact(request.region_number).await
+    }
+
+    /// Discards all rows of the given table.
+    async fn truncate_table(
+        &self,
+        ctx: &EngineContext,
+        request: TruncateTableRequest,
+    ) -> Result<()> {
+        let table_ref = TableReference {
+            catalog: &request.catalog_name,
+            schema: &request.schema_name,
+            table: &request.table_name,
+        };
+        let table = self
+            .get_table(ctx, &table_ref)?
+            .with_context(|| TableNotFoundSnafu {
+                table_name: table_ref.to_string(),
+            })?;
+        table.truncate().await
+    }
 }
 
 pub type TableEngineRef = Arc<dyn TableEngine>;
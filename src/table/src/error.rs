@@ -18,6 +18,7 @@ use common_error::prelude::*;
 use common_recordbatch::error::Error as RecordBatchError;
 use datafusion::error::DataFusionError;
 use datatypes::arrow::error::ArrowError;
+use datatypes::prelude::ConcreteDataType;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -87,6 +88,32 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
+    #[snafu(display(
+        "Not allowed to modify index column {} of table {}",
+        column_name,
+        table_name
+    ))]
+    ModifyColumnInIndex {
+        column_name: String,
+        table_name: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display(
+        "Not allowed to change the type of column {} in table {} from {:?} to {:?}",
+        column_name,
+        table_name,
+        from,
+        to
+    ))]
+    IncompatibleColumnType {
+        column_name: String,
+        table_name: String,
+        from: ConcreteDataType,
+        to: ConcreteDataType,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display(
         "Failed to build column descriptor for table: {}, column: {}, source: {}",
         table_name,
@@ -114,6 +141,19 @@ pub enum Error {
         value: String,
         backtrace: Backtrace,
     },
+
+    #[snafu(display("Table option {} of table {} can't be altered", key, table_name))]
+    AlterTableOption {
+        key: String,
+        table_name: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Table not found: {}", table_name))]
+    TableNotFound {
+        table_name: String,
+        backtrace: Backtrace,
+    },
 }
 
 impl ErrorExt for Error {
@@ -123,9 +163,10 @@ impl ErrorExt for Error {
             | Error::PollStream { .. }
             | Error::SchemaConversion { .. }
             | Error::TableProjection { .. } => StatusCode::EngineExecuteQuery,
-            Error::RemoveColumnInIndex { .. } | Error::BuildColumnDescriptor { .. } => {
-                StatusCode::InvalidArguments
-            }
+            Error::RemoveColumnInIndex { .. }
+            | Error::ModifyColumnInIndex { .. }
+            | Error::IncompatibleColumnType { .. }
+            | Error::BuildColumnDescriptor { .. } => StatusCode::InvalidArguments,
             Error::TablesRecordBatch { .. } => StatusCode::Unexpected,
             Error::ColumnExists { .. } => StatusCode::TableColumnExists,
             Error::SchemaBuild { source, .. } => source.status_code(),
@@ -133,7 +174,10 @@ impl ErrorExt for Error {
             Error::ColumnNotExists { .. } => StatusCode::TableColumnNotFound,
             Error::RegionSchemaMismatch { .. } => StatusCode::StorageUnavailable,
             Error::Unsupported { .. } => StatusCode::Unsupported,
-            Error::ParseTableOption { .. } => StatusCode::InvalidArguments,
+            Error::ParseTableOption { .. } | Error::AlterTableOption { .. } => {
+                StatusCode::InvalidArguments
+            }
+            Error::TableNotFound { .. } => StatusCode::TableNotFound,
         }
     }
 
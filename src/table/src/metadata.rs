@@ -17,15 +17,19 @@ use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+use datatypes::data_type::ConcreteDataType;
 pub use datatypes::error::{Error as ConvertError, Result as ConvertResult};
 use datatypes::schema::{ColumnSchema, RawSchema, Schema, SchemaBuilder, SchemaRef};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
-use snafu::{ensure, ResultExt};
+use snafu::{ensure, OptionExt, ResultExt};
 use store_api::storage::{ColumnDescriptor, ColumnDescriptorBuilder, ColumnId};
 
 use crate::error::{self, Result};
-use crate::requests::{AddColumnRequest, AlterKind, TableOptions};
+use crate::requests::{
+    AddColumnRequest, AlterKind, TableOptions, ALTERABLE_TABLE_OPTION_KEYS, TTL_KEY,
+    WRITE_BUFFER_SIZE_KEY,
+};
 
 pub type TableId = u32;
 pub type TableVersion = u64;
@@ -139,6 +143,24 @@ impl TableMeta {
             AlterKind::DropColumns { names } => self.remove_columns(table_name, names),
             // No need to rebuild table meta when renaming tables.
             AlterKind::RenameTable { .. } => Ok(TableMetaBuilder::default()),
+            AlterKind::ModifyColumnType {
+                column_name,
+                data_type,
+            } => self.modify_column_type(table_name, column_name, data_type),
+            AlterKind::RenameColumn {
+                column_name,
+                new_column_name,
+            } => self.rename_column(table_name, column_name, new_column_name),
+            AlterKind::SetColumnDefault {
+                column_name,
+                default_constraint,
+            } => self.replace_column(table_name, column_name, |column_schema| {
+                column_schema
+                    .clone()
+                    .with_default_constraint(default_constraint.clone())
+            }),
+            AlterKind::SetTableOptions { options } => self.set_options(table_name, options),
+            AlterKind::UnsetTableOptions { keys } => self.unset_options(table_name, keys),
         }
     }
 
@@ -315,6 +337,182 @@ impl TableMeta {
 
         Ok(meta_builder)
     }
+
+    fn modify_column_type(
+        &self,
+        table_name: &str,
+        column_name: &str,
+        data_type: &ConcreteDataType,
+    ) -> Result<TableMetaBuilder> {
+        self.ensure_not_index_column(table_name, column_name)?;
+        // The column exists as it's checked above.
+        let column_schema = self.schema.column_schema_by_name(column_name).unwrap();
+        ensure!(
+            column_schema.data_type.can_widen_to(data_type),
+            error::IncompatibleColumnTypeSnafu {
+                column_name,
+                table_name,
+                from: column_schema.data_type.clone(),
+                to: data_type.clone(),
+            }
+        );
+
+        self.replace_column(table_name, column_name, |column_schema| {
+            let default_constraint = column_schema
+                .default_constraint()
+                .map(|constraint| constraint.cast_to(data_type))
+                .transpose()?;
+            let mut column_schema = column_schema.clone();
+            column_schema.data_type = data_type.clone();
+            column_schema.with_default_constraint(default_constraint)
+        })
+    }
+
+    fn rename_column(
+        &self,
+        table_name: &str,
+        column_name: &str,
+        new_column_name: &str,
+    ) -> Result<TableMetaBuilder> {
+        self.ensure_not_index_column(table_name, column_name)?;
+        ensure!(
+            self.schema.column_schema_by_name(new_column_name).is_none(),
+            error::ColumnExistsSnafu {
+                column_name: new_column_name,
+                table_name,
+            }
+        );
+
+        self.replace_column(table_name, column_name, |column_schema| {
+            let mut column_schema = column_schema.clone();
+            column_schema.name = new_column_name.to_string();
+            Ok(column_schema)
+        })
+    }
+
+    /// Ensures the column exists and is neither a primary key column nor the time
+    /// index column, whose type and name can't be changed.
+    fn ensure_not_index_column(&self, table_name: &str, column_name: &str) -> Result<()> {
+        let index = self
+            .schema
+            .column_index_by_name(column_name)
+            .with_context(|| error::ColumnNotExistsSnafu {
+                column_name,
+                table_name,
+            })?;
+        ensure!(
+            !self.primary_key_indices.contains(&index)
+                && Some(index) != self.schema.timestamp_index(),
+            error::ModifyColumnInIndexSnafu {
+                column_name,
+                table_name,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Returns a builder whose schema has the column named `column_name` replaced by the
+    /// column returned by `replace`.
+    fn replace_column<F>(
+        &self,
+        table_name: &str,
+        column_name: &str,
+        replace: F,
+    ) -> Result<TableMetaBuilder>
+    where
+        F: FnOnce(&ColumnSchema) -> ConvertResult<ColumnSchema>,
+    {
+        let table_schema = &self.schema;
+        let mut meta_builder = self.new_meta_builder();
+
+        let index = table_schema
+            .column_index_by_name(column_name)
+            .with_context(|| error::ColumnNotExistsSnafu {
+                column_name,
+                table_name,
+            })?;
+        let mut columns = table_schema.column_schemas().to_vec();
+        columns[index] = replace(&columns[index]).with_context(|_| error::SchemaBuildSnafu {
+            msg: format!("Table {table_name} cannot alter column {column_name}"),
+        })?;
+
+        let mut builder = SchemaBuilder::try_from_columns(columns)
+            .with_context(|_| error::SchemaBuildSnafu {
+                msg: format!("Failed to convert column schemas into schema for table {table_name}"),
+            })?
+            // Also bump the schema version.
+            .version(table_schema.version() + 1);
+        for (k, v) in table_schema.metadata().iter() {
+            builder = builder.add_metadata(k, v);
+        }
+        let new_schema = builder.build().with_context(|_| error::SchemaBuildSnafu {
+            msg: format!("Table {table_name} cannot alter column {column_name}"),
+        })?;
+
+        // Columns are not reordered, so the primary key indices are unchanged.
+        meta_builder
+            .schema(Arc::new(new_schema))
+            .primary_key_indices(self.primary_key_indices.clone());
+
+        Ok(meta_builder)
+    }
+
+    fn set_options(
+        &self,
+        table_name: &str,
+        options: &HashMap<String, String>,
+    ) -> Result<TableMetaBuilder> {
+        for key in options.keys() {
+            ensure_alterable_option(table_name, key)?;
+        }
+        let parsed = TableOptions::try_from(options)?;
+
+        let mut new_options = self.options.clone();
+        if parsed.write_buffer_size.is_some() {
+            new_options.write_buffer_size = parsed.write_buffer_size;
+        }
+        if parsed.ttl.is_some() {
+            new_options.ttl = parsed.ttl;
+        }
+
+        Ok(self.options_meta_builder(new_options))
+    }
+
+    fn unset_options(&self, table_name: &str, keys: &[String]) -> Result<TableMetaBuilder> {
+        let mut new_options = self.options.clone();
+        for key in keys {
+            ensure_alterable_option(table_name, key)?;
+            match key.as_str() {
+                WRITE_BUFFER_SIZE_KEY => new_options.write_buffer_size = None,
+                TTL_KEY => new_options.ttl = None,
+                _ => unreachable!(),
+            }
+        }
+
+        Ok(self.options_meta_builder(new_options))
+    }
+
+    /// Returns a builder of the meta with the same schema and the given `options`.
+    fn options_meta_builder(&self, options: TableOptions) -> TableMetaBuilder {
+        let mut meta_builder = self.new_meta_builder();
+        meta_builder
+            .schema(self.schema.clone())
+            .primary_key_indices(self.primary_key_indices.clone())
+            .value_indices(self.value_indices.clone())
+            .region_numbers(self.region_numbers.clone())
+            .options(options);
+
+        meta_builder
+    }
+}
+
+fn ensure_alterable_option(table_name: &str, key: &str) -> Result<()> {
+    ensure!(
+        ALTERABLE_TABLE_OPTION_KEYS.contains(&key),
+        error::AlterTableOptionSnafu { key, table_name }
+    );
+    Ok(())
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
@@ -469,9 +667,13 @@ impl TryFrom<RawTableInfo> for TableInfo {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
+    use common_base::readable_size::ReadableSize;
     use common_error::prelude::*;
     use datatypes::data_type::ConcreteDataType;
-    use datatypes::schema::{ColumnSchema, Schema, SchemaBuilder};
+    use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema, Schema, SchemaBuilder};
+    use datatypes::value::Value;
 
     use super::*;
 
@@ -752,4 +954,211 @@ mod tests {
         assert_eq!(4, meta.next_column_id);
         assert_eq!(column_schema.name, desc.name);
     }
+
+    #[test]
+    fn test_modify_columns() {
+        let schema = Arc::new(new_test_schema());
+        let meta = TableMetaBuilder::default()
+            .schema(schema)
+            .primary_key_indices(vec![0])
+            .engine("engine")
+            .next_column_id(3)
+            .build()
+            .unwrap();
+
+        let alter_kind = AlterKind::SetColumnDefault {
+            column_name: String::from("col2"),
+            default_constraint: Some(ColumnDefaultConstraint::Value(Value::Int32(1))),
+        };
+        let meta = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        let alter_kind = AlterKind::ModifyColumnType {
+            column_name: String::from("col2"),
+            data_type: ConcreteDataType::int64_datatype(),
+        };
+        let meta = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        let alter_kind = AlterKind::RenameColumn {
+            column_name: String::from("col2"),
+            new_column_name: String::from("col3"),
+        };
+        let new_meta = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(126, new_meta.schema.version());
+        assert!(new_meta.schema.column_schema_by_name("col2").is_none());
+        let column_schema = new_meta.schema.column_schema_by_name("col3").unwrap();
+        assert_eq!(ConcreteDataType::int64_datatype(), column_schema.data_type);
+        assert_eq!(
+            Some(&ColumnDefaultConstraint::Value(Value::Int64(1))),
+            column_schema.default_constraint()
+        );
+        assert_eq!(&[0], &new_meta.primary_key_indices[..]);
+        assert_eq!(&[1, 2], &new_meta.value_indices[..]);
+    }
+
+    #[test]
+    fn test_modify_columns_invalid() {
+        let schema = Arc::new(new_test_schema());
+        let meta = TableMetaBuilder::default()
+            .schema(schema)
+            .primary_key_indices(vec![0])
+            .engine("engine")
+            .next_column_id(3)
+            .build()
+            .unwrap();
+
+        let alter_kinds = [
+            // Narrow the type.
+            AlterKind::ModifyColumnType {
+                column_name: String::from("col2"),
+                data_type: ConcreteDataType::int16_datatype(),
+            },
+            // Modify primary key column.
+            AlterKind::ModifyColumnType {
+                column_name: String::from("col1"),
+                data_type: ConcreteDataType::int64_datatype(),
+            },
+            // Rename timestamp column.
+            AlterKind::RenameColumn {
+                column_name: String::from("ts"),
+                new_column_name: String::from("ts2"),
+            },
+        ];
+        for alter_kind in alter_kinds {
+            let err = meta
+                .builder_with_alter_kind("my_table", &alter_kind)
+                .err()
+                .unwrap();
+            assert_eq!(StatusCode::InvalidArguments, err.status_code());
+        }
+
+        // Default value with another type.
+        let alter_kind = AlterKind::SetColumnDefault {
+            column_name: String::from("col2"),
+            default_constraint: Some(ColumnDefaultConstraint::Value(Value::String("a".into()))),
+        };
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert!(
+            matches!(err, error::Error::SchemaBuild { .. }),
+            "{err:?} is not SchemaBuild"
+        );
+
+        let alter_kind = AlterKind::RenameColumn {
+            column_name: String::from("col2"),
+            new_column_name: String::from("col1"),
+        };
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::TableColumnExists, err.status_code());
+
+        let alter_kind = AlterKind::RenameColumn {
+            column_name: String::from("col4"),
+            new_column_name: String::from("col5"),
+        };
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::TableColumnNotFound, err.status_code());
+    }
+
+    #[test]
+    fn test_alter_table_options() {
+        let schema = Arc::new(new_test_schema());
+        let mut options = TableOptions::default();
+        let _ = options
+            .extra_options
+            .insert("compaction.strategy".to_string(), "leveled".to_string());
+        let meta = TableMetaBuilder::default()
+            .schema(schema)
+            .primary_key_indices(vec![0])
+            .engine("engine")
+            .next_column_id(3)
+            .options(options)
+            .build()
+            .unwrap();
+
+        let alter_kind = AlterKind::SetTableOptions {
+            options: HashMap::from([
+                (TTL_KEY.to_string(), "30d".to_string()),
+                (WRITE_BUFFER_SIZE_KEY.to_string(), "1MB".to_string()),
+            ]),
+        };
+        let new_meta = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            Some(Duration::from_secs(30 * 24 * 3600)),
+            new_meta.options.ttl
+        );
+        assert_eq!(
+            Some(ReadableSize::mb(1)),
+            new_meta.options.write_buffer_size
+        );
+        assert_eq!(meta.options.extra_options, new_meta.options.extra_options);
+        assert_eq!(meta.schema, new_meta.schema);
+        assert_eq!(meta.value_indices, new_meta.value_indices);
+
+        let alter_kind = AlterKind::UnsetTableOptions {
+            keys: vec![TTL_KEY.to_string()],
+        };
+        let new_meta = new_meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(None, new_meta.options.ttl);
+        assert_eq!(
+            Some(ReadableSize::mb(1)),
+            new_meta.options.write_buffer_size
+        );
+
+        // Invalid value.
+        let alter_kind = AlterKind::SetTableOptions {
+            options: HashMap::from([(TTL_KEY.to_string(), "abc".to_string())]),
+        };
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        // Options can't be altered.
+        let alter_kind = AlterKind::SetTableOptions {
+            options: HashMap::from([("compaction.strategy".to_string(), "tiered".to_string())]),
+        };
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert!(
+            matches!(err, error::Error::AlterTableOption { .. }),
+            "{err:?} is not AlterTableOption"
+        );
+        let alter_kind = AlterKind::UnsetTableOptions {
+            keys: vec!["memtable.type".to_string()],
+        };
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+    }
 }
@@ -12,17 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use common_query::logical_plan::{DfExpr, Expr};
 use common_telemetry::{error, warn};
 use common_time::range::TimestampRange;
 use common_time::Timestamp;
 use datafusion::parquet::file::metadata::RowGroupMetaData;
-use datafusion::physical_optimizer::pruning::PruningPredicate;
+use datafusion::physical_optimizer::pruning::{PruningPredicate, PruningStatistics};
 use datafusion_expr::{Between, BinaryExpr, Operator};
 use datatypes::schema::SchemaRef;
 use datatypes::value::scalar_value_to_timestamp;
 
-use crate::predicate::stats::RowGroupPruningStatistics;
+pub use crate::predicate::stats::ColumnStatistics;
+use crate::predicate::stats::{FilePruningStatistics, RowGroupPruningStatistics};
 
 mod stats;
 
@@ -40,27 +43,47 @@ impl Predicate {
         Self { exprs: vec![] }
     }
 
+    pub fn exprs(&self) -> &[Expr] {
+        &self.exprs
+    }
+
     pub fn prune_row_groups(
         &self,
         schema: SchemaRef,
         row_groups: &[RowGroupMetaData],
     ) -> Vec<bool> {
-        let mut res = vec![true; row_groups.len()];
+        let stats = RowGroupPruningStatistics::new(row_groups, &schema);
+        self.prune_with_stats(&schema, &stats)
+    }
+
+    /// Prunes files by the statistics of their columns. `files` holds the statistics of
+    /// each file, keyed by column name. Files without statistics of a column are never
+    /// pruned by predicates on that column.
+    ///
+    /// Returns whether each file may contain rows matching the predicate.
+    pub fn prune_files(
+        &self,
+        schema: &SchemaRef,
+        files: &[&HashMap<String, ColumnStatistics>],
+    ) -> Vec<bool> {
+        let stats = FilePruningStatistics::new(files, schema);
+        self.prune_with_stats(schema, &stats)
+    }
+
+    fn prune_with_stats<S: PruningStatistics>(&self, schema: &SchemaRef, stats: &S) -> Vec<bool> {
+        let mut res = vec![true; stats.num_containers()];
         for expr in &self.exprs {
             match PruningPredicate::try_new(expr.df_expr().clone(), schema.arrow_schema().clone()) {
-                Ok(p) => {
-                    let stat = RowGroupPruningStatistics::new(row_groups, &schema);
-                    match p.prune(&stat) {
-                        Ok(r) => {
-                            for (curr_val, res) in r.into_iter().zip(res.iter_mut()) {
-                                *res &= curr_val
-                            }
-                        }
-                        Err(e) => {
-                            warn!("Failed to prune row groups, error: {:?}", e);
+                Ok(p) => match p.prune(stats) {
+                    Ok(r) => {
+                        for (curr_val, res) in r.into_iter().zip(res.iter_mut()) {
+                            *res &= curr_val
                         }
                     }
-                }
+                    Err(e) => {
+                        warn!("Failed to prune containers, error: {:?}", e);
+                    }
+                },
                 Err(e) => {
                     error!("Failed to create predicate for expr, error: {:?}", e);
                 }
@@ -405,4 +428,59 @@ mod tests {
         let p = Predicate::new(vec![e.into()]);
         assert_prune(40, p, vec![true, true, false, true]).await;
     }
+
+    #[test]
+    fn test_prune_files() {
+        let schema = Arc::new(
+            datatypes::schema::Schema::try_from(Arc::new(Schema::new(vec![
+                Field::new("name", DataType::Utf8, true),
+                Field::new("cnt", DataType::Int32, true),
+            ])))
+            .unwrap(),
+        );
+        let new_stats = |min: i32, max: i32| {
+            HashMap::from([(
+                "cnt".to_string(),
+                ColumnStatistics {
+                    min_value: min.into(),
+                    max_value: max.into(),
+                    null_count: 0,
+                },
+            )])
+        };
+        let all_nulls = HashMap::from([(
+            "cnt".to_string(),
+            ColumnStatistics {
+                min_value: datatypes::value::Value::Null,
+                max_value: datatypes::value::Value::Null,
+                null_count: 10,
+            },
+        )]);
+        let no_stats = HashMap::new();
+        let file0 = new_stats(0, 9);
+        let file1 = new_stats(10, 19);
+        let files = [&file0, &file1, &all_nulls, &no_stats];
+
+        // Null min/max values are unknown to the pruning predicate, so files with all nulls
+        // are kept like files without statistics.
+        let p = gen_predicate(5, Operator::Lt);
+        assert_eq!(
+            vec![true, false, true, true],
+            p.prune_files(&schema, &files)
+        );
+
+        let p = gen_predicate(15, Operator::Eq);
+        assert_eq!(
+            vec![false, true, true, true],
+            p.prune_files(&schema, &files)
+        );
+
+        let p = Predicate::empty();
+        assert_eq!(vec![true; 4], p.prune_files(&schema, &files));
+
+        // Predicates on columns without statistics prune nothing.
+        let e = Expr::Column(Column::from_name("name")).eq("1".lit());
+        let p = Predicate::new(vec![e.into()]);
+        assert_eq!(vec![true; 4], p.prune_files(&schema, &files));
+    }
 }
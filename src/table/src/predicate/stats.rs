@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use datafusion::parquet::file::metadata::RowGroupMetaData;
@@ -20,7 +21,9 @@ use datafusion::physical_optimizer::pruning::PruningStatistics;
 use datafusion_common::{Column, ScalarValue};
 use datatypes::arrow::array::{ArrayRef, UInt64Array};
 use datatypes::arrow::datatypes::DataType;
+use datatypes::value::Value;
 use paste::paste;
+use serde::{Deserialize, Serialize};
 
 pub struct RowGroupPruningStatistics<'a> {
     pub meta_data: &'a [RowGroupMetaData],
@@ -116,3 +119,75 @@ impl<'a> PruningStatistics for RowGroupPruningStatistics<'a> {
         Some(Arc::new(UInt64Array::from(values)))
     }
 }
+
+/// Statistics of a column in a file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnStatistics {
+    /// Minimum non-null value of the column, [Value::Null] if all values are null.
+    pub min_value: Value,
+    /// Maximum non-null value of the column, [Value::Null] if all values are null.
+    pub max_value: Value,
+    /// Number of null values of the column.
+    pub null_count: u64,
+}
+
+/// [PruningStatistics] of files, in which each file is a container.
+pub struct FilePruningStatistics<'a> {
+    files: &'a [&'a HashMap<String, ColumnStatistics>],
+    schema: &'a datatypes::schema::SchemaRef,
+}
+
+impl<'a> FilePruningStatistics<'a> {
+    pub fn new(
+        files: &'a [&'a HashMap<String, ColumnStatistics>],
+        schema: &'a datatypes::schema::SchemaRef,
+    ) -> Self {
+        Self { files, schema }
+    }
+
+    /// Returns values of `column` in each file, files without statistics of `column`
+    /// yield nulls, which means unknown during pruning.
+    fn values(
+        &self,
+        column: &Column,
+        value_of: impl Fn(&ColumnStatistics) -> &Value,
+    ) -> Option<ArrayRef> {
+        let data_type = &self.schema.column_schema_by_name(&column.name)?.data_type;
+        let scalar_values = self
+            .files
+            .iter()
+            .map(|stats| {
+                stats
+                    .get(&column.name)
+                    .map(&value_of)
+                    .unwrap_or(&Value::Null)
+                    .try_to_scalar_value(data_type)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+        ScalarValue::iter_to_array(scalar_values).ok()
+    }
+}
+
+impl<'a> PruningStatistics for FilePruningStatistics<'a> {
+    fn min_values(&self, column: &Column) -> Option<ArrayRef> {
+        self.values(column, |stats| &stats.min_value)
+    }
+
+    fn max_values(&self, column: &Column) -> Option<ArrayRef> {
+        self.values(column, |stats| &stats.max_value)
+    }
+
+    fn num_containers(&self) -> usize {
+        self.files.len()
+    }
+
+    fn null_counts(&self, column: &Column) -> Option<ArrayRef> {
+        let values = self
+            .files
+            .iter()
+            .map(|stats| stats.get(&column.name).map(|stats| stats.null_count))
+            .collect::<Vec<_>>();
+        Some(Arc::new(UInt64Array::from(values)))
+    }
+}
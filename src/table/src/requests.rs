@@ -18,10 +18,13 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use common_base::readable_size::ReadableSize;
-use datatypes::prelude::VectorRef;
-use datatypes::schema::{ColumnSchema, RawSchema};
+use datatypes::prelude::{ConcreteDataType, VectorRef};
+use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema, RawSchema};
 use serde::{Deserialize, Serialize};
-use store_api::storage::RegionNumber;
+use snafu::{ensure, OptionExt};
+use store_api::storage::{
+    CompactionOptions, CompactionStrategy, IndexOptions, MemtableType, RegionNumber,
+};
 
 use crate::error;
 use crate::error::ParseTableOptionSnafu;
@@ -41,6 +44,34 @@ pub struct InsertRequest {
 pub struct CreateDatabaseRequest {
     pub db_name: String,
     pub create_if_not_exists: bool,
+    /// Default options of tables created in the database.
+    pub options: TableOptions,
+}
+
+#[derive(Debug, Clone)]
+pub struct DropDatabaseRequest {
+    pub db_name: String,
+    pub drop_if_exists: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateViewRequest {
+    pub id: TableId,
+    pub catalog_name: String,
+    pub schema_name: String,
+    pub view_name: String,
+    /// The query defining the view.
+    pub definition: String,
+    /// Replace the view if it exists.
+    pub or_replace: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct DropViewRequest {
+    pub catalog_name: String,
+    pub schema_name: String,
+    pub view_name: String,
+    pub drop_if_exists: bool,
 }
 
 /// Create table request
@@ -72,6 +103,117 @@ pub struct TableOptions {
 
 pub const WRITE_BUFFER_SIZE_KEY: &str = "write_buffer_size";
 pub const TTL_KEY: &str = "ttl";
+/// Compaction strategy of the table, `tiered` or `leveled`.
+pub const COMPACTION_STRATEGY_KEY: &str = "compaction.strategy";
+/// Duration of compaction time window, like `1h`.
+pub const COMPACTION_TIME_WINDOW_KEY: &str = "compaction.time_window";
+/// Max number of SST files in a compaction time window of a level.
+pub const COMPACTION_MAX_FILES_IN_WINDOW_KEY: &str = "compaction.max_files_in_window";
+/// Comma separated names of the primary key columns to build bloom filters on, like `host,idc`.
+pub const INDEX_BLOOM_FILTER_COLUMNS_KEY: &str = "index.bloom_filter_columns";
+/// Type of the memtable, `btree` or `series`.
+pub const MEMTABLE_TYPE_KEY: &str = "memtable.type";
+/// Options that could be altered after the table is created.
+pub const ALTERABLE_TABLE_OPTION_KEYS: [&str; 2] = [WRITE_BUFFER_SIZE_KEY, TTL_KEY];
+
+impl TableOptions {
+    /// Returns the compaction options specified in [TableOptions::extra_options].
+    pub fn compaction_options(&self) -> Result<CompactionOptions, error::Error> {
+        let mut options = CompactionOptions::default();
+        if let Some(strategy) = self.extra_options.get(COMPACTION_STRATEGY_KEY) {
+            options.strategy = match strategy.to_lowercase().as_str() {
+                "tiered" => CompactionStrategy::Tiered,
+                "leveled" => CompactionStrategy::Leveled,
+                _ => {
+                    return ParseTableOptionSnafu {
+                        key: COMPACTION_STRATEGY_KEY,
+                        value: strategy,
+                    }
+                    .fail()
+                }
+            };
+        }
+
+        if let Some(time_window) = self.extra_options.get(COMPACTION_TIME_WINDOW_KEY) {
+            let window: Duration = time_window
+                .parse::<humantime::Duration>()
+                .ok()
+                .map(Into::into)
+                // time window is aligned to seconds
+                .filter(|window: &Duration| window.as_secs() > 0)
+                .with_context(|| ParseTableOptionSnafu {
+                    key: COMPACTION_TIME_WINDOW_KEY,
+                    value: time_window,
+                })?;
+            options.time_window = Some(window);
+        }
+
+        if let Some(max_files) = self.extra_options.get(COMPACTION_MAX_FILES_IN_WINDOW_KEY) {
+            let max_files = max_files
+                .parse::<usize>()
+                .ok()
+                .filter(|max_files| *max_files > 0)
+                .with_context(|| ParseTableOptionSnafu {
+                    key: COMPACTION_MAX_FILES_IN_WINDOW_KEY,
+                    value: max_files,
+                })?;
+            options.max_files_in_window = Some(max_files);
+        }
+
+        Ok(options)
+    }
+
+    /// Returns the SST index options specified in [TableOptions::extra_options].
+    pub fn index_options(&self) -> Result<IndexOptions, error::Error> {
+        let mut options = IndexOptions::default();
+        if let Some(columns) = self.extra_options.get(INDEX_BLOOM_FILTER_COLUMNS_KEY) {
+            for column in columns.split(',').map(str::trim) {
+                ensure!(
+                    !column.is_empty() && !options.bloom_filter_columns.iter().any(|c| c == column),
+                    ParseTableOptionSnafu {
+                        key: INDEX_BLOOM_FILTER_COLUMNS_KEY,
+                        value: columns,
+                    }
+                );
+                options.bloom_filter_columns.push(column.to_string());
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// Returns the memtable type specified in [TableOptions::extra_options].
+    pub fn memtable_type(&self) -> Result<MemtableType, error::Error> {
+        let Some(memtable_type) = self.extra_options.get(MEMTABLE_TYPE_KEY) else {
+            return Ok(MemtableType::default());
+        };
+
+        match memtable_type.to_lowercase().as_str() {
+            "btree" => Ok(MemtableType::BTree),
+            "series" => Ok(MemtableType::Series),
+            _ => ParseTableOptionSnafu {
+                key: MEMTABLE_TYPE_KEY,
+                value: memtable_type,
+            }
+            .fail(),
+        }
+    }
+
+    /// Fills options absent in this [TableOptions] with the options in `defaults`.
+    pub fn fill_defaults(&mut self, defaults: &TableOptions) {
+        if self.write_buffer_size.is_none() {
+            self.write_buffer_size = defaults.write_buffer_size;
+        }
+        if self.ttl.is_none() {
+            self.ttl = defaults.ttl;
+        }
+        for (key, value) in &defaults.extra_options {
+            if !self.extra_options.contains_key(key) {
+                let _ = self.extra_options.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
 
 impl TryFrom<&HashMap<String, String>> for TableOptions {
     type Error = error::Error;
@@ -109,6 +251,10 @@ impl TryFrom<&HashMap<String, String>> for TableOptions {
                 None
             }
         }));
+        // validates the options that are kept in `extra_options`
+        let _ = options.compaction_options()?;
+        let _ = options.index_options()?;
+        let _ = options.memtable_type()?;
         Ok(options)
     }
 }
@@ -168,9 +314,37 @@ pub struct AddColumnRequest {
 
 #[derive(Debug, Clone)]
 pub enum AlterKind {
-    AddColumns { columns: Vec<AddColumnRequest> },
-    DropColumns { names: Vec<String> },
-    RenameTable { new_table_name: String },
+    AddColumns {
+        columns: Vec<AddColumnRequest>,
+    },
+    DropColumns {
+        names: Vec<String>,
+    },
+    RenameTable {
+        new_table_name: String,
+    },
+    /// Changes the type of a column to a type that can hold all its values.
+    ModifyColumnType {
+        column_name: String,
+        data_type: ConcreteDataType,
+    },
+    RenameColumn {
+        column_name: String,
+        new_column_name: String,
+    },
+    /// Sets the default constraint of a column, or drops it if `default_constraint` is `None`.
+    SetColumnDefault {
+        column_name: String,
+        default_constraint: Option<ColumnDefaultConstraint>,
+    },
+    /// Sets table options, only options in [ALTERABLE_TABLE_OPTION_KEYS] can be set.
+    SetTableOptions {
+        options: HashMap<String, String>,
+    },
+    /// Resets table options to their defaults.
+    UnsetTableOptions {
+        keys: Vec<String>,
+    },
 }
 
 /// Drop table request
@@ -181,6 +355,34 @@ pub struct DropTableRequest {
     pub table_name: String,
 }
 
+/// Flush table request
+#[derive(Debug)]
+pub struct FlushTableRequest {
+    pub catalog_name: String,
+    pub schema_name: String,
+    pub table_name: String,
+    /// Region to flush, all regions of the table are flushed if it's `None`.
+    pub region_number: Option<RegionNumber>,
+}
+
+/// Compact table request
+#[derive(Debug)]
+pub struct CompactTableRequest {
+    pub catalog_name: String,
+    pub schema_name: String,
+    pub table_name: String,
+    /// Region to compact, all regions of the table are compacted if it's `None`.
+    pub region_number: Option<RegionNumber>,
+}
+
+/// Truncate table request
+#[derive(Debug)]
+pub struct TruncateTableRequest {
+    pub catalog_name: String,
+    pub schema_name: String,
+    pub table_name: String,
+}
+
 /// Delete (by primary key) request
 #[derive(Debug)]
 pub struct DeleteRequest {
@@ -197,6 +399,26 @@ pub struct CopyTableRequest {
     pub schema_name: String,
     pub table_name: String,
     pub file_name: String,
+    pub direction: CopyDirection,
+    pub format: CopyFormat,
+}
+
+/// Direction of a copy table request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyDirection {
+    /// Export the table's data into files.
+    Export,
+    /// Import data from files into the table.
+    Import,
+}
+
+/// File format used in a copy table request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    Parquet,
+    Csv,
+    /// Newline-delimited JSON.
+    Json,
 }
 
 #[cfg(test)]
@@ -244,4 +466,117 @@ mod tests {
         let serialized = TableOptions::try_from(&serialized_map).unwrap();
         assert_eq!(options, serialized);
     }
+
+    #[test]
+    fn test_fill_default_table_options() {
+        let defaults = TableOptions {
+            write_buffer_size: Some(ReadableSize::mb(128)),
+            ttl: Some(Duration::from_secs(1000)),
+            extra_options: HashMap::from([
+                (COMPACTION_STRATEGY_KEY.to_string(), "leveled".to_string()),
+                (MEMTABLE_TYPE_KEY.to_string(), "series".to_string()),
+            ]),
+        };
+
+        let mut options = TableOptions::default();
+        options.fill_defaults(&defaults);
+        assert_eq!(defaults, options);
+
+        let mut options = TableOptions {
+            write_buffer_size: None,
+            ttl: Some(Duration::from_secs(10)),
+            extra_options: HashMap::from([(
+                COMPACTION_STRATEGY_KEY.to_string(),
+                "tiered".to_string(),
+            )]),
+        };
+        options.fill_defaults(&defaults);
+        let expect = TableOptions {
+            write_buffer_size: Some(ReadableSize::mb(128)),
+            ttl: Some(Duration::from_secs(10)),
+            extra_options: HashMap::from([
+                (COMPACTION_STRATEGY_KEY.to_string(), "tiered".to_string()),
+                (MEMTABLE_TYPE_KEY.to_string(), "series".to_string()),
+            ]),
+        };
+        assert_eq!(expect, options);
+    }
+
+    #[test]
+    fn test_compaction_options() {
+        let options = TableOptions::try_from(&HashMap::new()).unwrap();
+        assert_eq!(
+            CompactionOptions::default(),
+            options.compaction_options().unwrap()
+        );
+
+        let map = HashMap::from([
+            (COMPACTION_STRATEGY_KEY.to_string(), "Leveled".to_string()),
+            (COMPACTION_TIME_WINDOW_KEY.to_string(), "2h".to_string()),
+            (
+                COMPACTION_MAX_FILES_IN_WINDOW_KEY.to_string(),
+                "16".to_string(),
+            ),
+        ]);
+        let options = TableOptions::try_from(&map).unwrap();
+        assert_eq!(3, options.extra_options.len());
+        assert_eq!(
+            CompactionOptions {
+                strategy: CompactionStrategy::Leveled,
+                time_window: Some(Duration::from_secs(2 * 60 * 60)),
+                max_files_in_window: Some(16),
+            },
+            options.compaction_options().unwrap()
+        );
+
+        for (key, value) in [
+            (COMPACTION_STRATEGY_KEY, "unknown"),
+            (COMPACTION_TIME_WINDOW_KEY, "100ms"),
+            (COMPACTION_TIME_WINDOW_KEY, "a day"),
+            (COMPACTION_MAX_FILES_IN_WINDOW_KEY, "0"),
+            (COMPACTION_MAX_FILES_IN_WINDOW_KEY, "-1"),
+        ] {
+            let map = HashMap::from([(key.to_string(), value.to_string())]);
+            assert!(TableOptions::try_from(&map).is_err());
+        }
+    }
+
+    #[test]
+    fn test_index_options() {
+        let options = TableOptions::try_from(&HashMap::new()).unwrap();
+        assert!(options.index_options().unwrap().is_empty());
+
+        let map = HashMap::from([(
+            INDEX_BLOOM_FILTER_COLUMNS_KEY.to_string(),
+            "host, idc".to_string(),
+        )]);
+        let options = TableOptions::try_from(&map).unwrap();
+        assert_eq!(
+            IndexOptions {
+                bloom_filter_columns: vec!["host".to_string(), "idc".to_string()],
+            },
+            options.index_options().unwrap()
+        );
+
+        for value in ["", "host,", "host,host"] {
+            let map = HashMap::from([(
+                INDEX_BLOOM_FILTER_COLUMNS_KEY.to_string(),
+                value.to_string(),
+            )]);
+            assert!(TableOptions::try_from(&map).is_err());
+        }
+    }
+
+    #[test]
+    fn test_memtable_type() {
+        let options = TableOptions::try_from(&HashMap::new()).unwrap();
+        assert_eq!(MemtableType::BTree, options.memtable_type().unwrap());
+
+        let map = HashMap::from([(MEMTABLE_TYPE_KEY.to_string(), "Series".to_string())]);
+        let options = TableOptions::try_from(&map).unwrap();
+        assert_eq!(MemtableType::Series, options.memtable_type().unwrap());
+
+        let map = HashMap::from([(MEMTABLE_TYPE_KEY.to_string(), "skiplist".to_string())]);
+        assert!(TableOptions::try_from(&map).is_err());
+    }
 }
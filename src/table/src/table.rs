@@ -15,6 +15,7 @@
 pub mod adapter;
 pub mod numbers;
 pub mod scan;
+pub mod view;
 
 use std::any::Any;
 use std::sync::Arc;
@@ -22,7 +23,9 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use common_query::logical_plan::Expr;
 use common_query::physical_plan::PhysicalPlanRef;
+use common_time::range::TimestampRange;
 use datatypes::schema::SchemaRef;
+use store_api::storage::RegionNumber;
 
 use crate::error::{Result, UnsupportedSnafu};
 use crate::metadata::{FilterPushDownType, TableId, TableInfoRef, TableType};
@@ -93,6 +96,40 @@ pub trait Table: Send + Sync {
         }
         .fail()?
     }
+
+    /// Delete the files of the table whose rows are all inside `time_range`, which is
+    /// cheaper than deleting the rows one by one.
+    ///
+    /// Returns number of rows in the deleted files. Tables without files do nothing
+    /// and return 0 by default.
+    async fn delete_files_in_range(&self, _time_range: TimestampRange) -> Result<usize> {
+        Ok(0)
+    }
+
+    /// Flush the table, or only the region `region_number` of the table if it's given.
+    ///
+    /// Returns after the flush is finished.
+    async fn flush(&self, _region_number: Option<RegionNumber>) -> Result<()> {
+        UnsupportedSnafu { operation: "FLUSH" }.fail()?
+    }
+
+    /// Compact the table, or only the region `region_number` of the table if it's given.
+    ///
+    /// Returns after the compaction is finished.
+    async fn compact(&self, _region_number: Option<RegionNumber>) -> Result<()> {
+        UnsupportedSnafu {
+            operation: "COMPACT",
+        }
+        .fail()?
+    }
+
+    /// Discard all rows of the table, while the schema and ID of the table are kept.
+    async fn truncate(&self) -> Result<()> {
+        UnsupportedSnafu {
+            operation: "TRUNCATE",
+        }
+        .fail()?
+    }
 }
 
 pub type TableRef = Arc<dyn Table>;
@@ -14,19 +14,25 @@
 
 use std::any::Any;
 use std::fmt::{Debug, Formatter};
+use std::pin::Pin;
 use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
 
 use common_query::error as query_error;
 use common_query::error::Result as QueryResult;
 use common_query::physical_plan::{Partitioning, PhysicalPlan, PhysicalPlanRef};
-use common_recordbatch::SendableRecordBatchStream;
+use common_recordbatch::error::Result as RecordBatchResult;
+use common_recordbatch::{RecordBatch, RecordBatchStream, SendableRecordBatchStream};
 use datafusion::execution::context::TaskContext;
+use datafusion::physical_plan::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
 use datatypes::schema::SchemaRef;
+use futures::{Stream, StreamExt};
 use snafu::OptionExt;
 
 pub struct SimpleTableScan {
     stream: Mutex<Option<SendableRecordBatchStream>>,
     schema: SchemaRef,
+    metrics: ExecutionPlanMetricsSet,
 }
 
 impl Debug for SimpleTableScan {
@@ -44,8 +50,16 @@ impl SimpleTableScan {
         Self {
             stream: Mutex::new(Some(stream)),
             schema,
+            metrics: ExecutionPlanMetricsSet::new(),
         }
     }
+
+    /// Sets the metrics of the scan, in which the scan records its output rows and
+    /// elapsed time. The table could register its own metrics of the scan in `metrics`.
+    pub fn with_metrics(mut self, metrics: ExecutionPlanMetricsSet) -> Self {
+        self.metrics = metrics;
+        self
+    }
 }
 
 impl PhysicalPlan for SimpleTableScan {
@@ -71,11 +85,49 @@ impl PhysicalPlan for SimpleTableScan {
 
     fn execute(
         &self,
-        _partition: usize,
+        partition: usize,
         _context: Arc<TaskContext>,
     ) -> QueryResult<SendableRecordBatchStream> {
         let mut stream = self.stream.lock().unwrap();
-        stream.take().context(query_error::ExecuteRepeatedlySnafu)
+        let stream = stream.take().context(query_error::ExecuteRepeatedlySnafu)?;
+        Ok(Box::pin(StreamWithMetrics {
+            stream,
+            metrics: BaselineMetrics::new(&self.metrics, partition),
+        }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+}
+
+/// A stream that records the output rows and the time spent in polling its inner stream.
+struct StreamWithMetrics {
+    stream: SendableRecordBatchStream,
+    metrics: BaselineMetrics,
+}
+
+impl RecordBatchStream for StreamWithMetrics {
+    fn schema(&self) -> SchemaRef {
+        self.stream.schema()
+    }
+}
+
+impl Stream for StreamWithMetrics {
+    type Item = RecordBatchResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let timer = this.metrics.elapsed_compute().timer();
+        let poll = this.stream.poll_next_unpin(cx);
+        timer.done();
+
+        match &poll {
+            Poll::Ready(Some(Ok(batch))) => this.metrics.record_output(batch.num_rows()),
+            Poll::Ready(None) => this.metrics.done(),
+            _ => {}
+        }
+        poll
     }
 }
 
@@ -121,6 +173,7 @@ mod test {
         let recordbatches = util::collect(stream).await.unwrap();
         assert_eq!(recordbatches[0], batch1);
         assert_eq!(recordbatches[1], batch2);
+        assert_eq!(Some(5), scan.metrics().unwrap().output_rows());
 
         let result = scan.execute(0, ctx.task_ctx());
         assert!(result.is_err());
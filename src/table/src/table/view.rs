@@ -0,0 +1,78 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+
+use common_query::physical_plan::PhysicalPlanRef;
+use datatypes::schema::SchemaRef;
+
+use crate::error::{Result, UnsupportedSnafu};
+use crate::metadata::{TableInfoRef, TableType};
+use crate::table::{Expr, Table};
+
+/// A view, i.e. a named query. Views are expanded into their queries by the query engine
+/// while planning, so they can't be scanned directly. Views are only kept in the local
+/// catalog, so they are only supported in standalone mode.
+#[derive(Debug)]
+pub struct ViewTable {
+    table_info: TableInfoRef,
+    /// SQL of the query of the view.
+    definition: String,
+}
+
+impl ViewTable {
+    /// Creates a view from its info and the SQL of its query. Unqualified tables in the
+    /// query are resolved in the catalog and schema of the view.
+    pub fn new(table_info: TableInfoRef, definition: String) -> Self {
+        Self {
+            table_info,
+            definition,
+        }
+    }
+
+    pub fn definition(&self) -> &str {
+        &self.definition
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for ViewTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.table_info.meta.schema.clone()
+    }
+
+    fn table_info(&self) -> TableInfoRef {
+        self.table_info.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::View
+    }
+
+    async fn scan(
+        &self,
+        _projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> Result<PhysicalPlanRef> {
+        UnsupportedSnafu {
+            operation: "SCAN VIEW",
+        }
+        .fail()?
+    }
+}
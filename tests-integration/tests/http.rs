@@ -307,6 +307,82 @@ pub async fn test_promql_http_api(store_type: StorageType) {
         .await;
     assert_eq!(res.status(), StatusCode::OK);
 
+    // labels
+    let res = client.get("/api/v1/labels").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<serde_json::Value>(&res.text().await).unwrap();
+    assert_eq!(body["status"], "success");
+    assert!(body["data"]
+        .as_array()
+        .unwrap()
+        .contains(&json!("__name__")));
+    let res = client
+        .post("/api/v1/labels?match[]=demo")
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // label values
+    let res = client.get("/api/v1/label/host/values").send().await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<serde_json::Value>(&res.text().await).unwrap();
+    assert_eq!(body["status"], "success");
+    let res = client
+        .get("/api/v1/label/__name__/values?match[]=demo")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // series
+    let res = client
+        .get("/api/v1/series?match[]=demo&start=0&end=100")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<serde_json::Value>(&res.text().await).unwrap();
+    assert_eq!(body["status"], "success");
+    let res = client
+        .post("/api/v1/series?match[]=demo&start=0&end=100")
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    // series without matcher is rejected
+    let res = client.get("/api/v1/series").send().await;
+    let body = serde_json::from_str::<serde_json::Value>(&res.text().await).unwrap();
+    assert_eq!(body["status"], "error");
+    // series selector without metric name
+    let res = client
+        .get("/api/v1/series?match[]={host=\"host1\"}&start=0&end=100")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = serde_json::from_str::<serde_json::Value>(&res.text().await).unwrap();
+    assert_eq!(body["status"], "success");
+    let res = client
+        .get("/api/v1/series?match[]={__name__=~\"de.*\"}&start=0&end=100")
+        .send()
+        .await;
+    let body = serde_json::from_str::<serde_json::Value>(&res.text().await).unwrap();
+    assert_eq!(body["status"], "success");
+
+    // metadata of the given database
+    let res = client
+        .get("/api/v1/label/__name__/values?db=public")
+        .send()
+        .await;
+    let body = serde_json::from_str::<serde_json::Value>(&res.text().await).unwrap();
+    assert_eq!(body["status"], "success");
+    assert!(body["data"].as_array().unwrap().contains(&json!("demo")));
+    let res = client
+        .get("/api/v1/label/__name__/values?db=nonexistent")
+        .send()
+        .await;
+    let body = serde_json::from_str::<serde_json::Value>(&res.text().await).unwrap();
+    assert_eq!(body["status"], "success");
+    assert!(body["data"].as_array().unwrap().is_empty());
+
     guard.remove_all().await;
 }
 
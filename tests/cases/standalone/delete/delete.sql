@@ -8,4 +8,8 @@ delete from monitor where host = 'host1' and ts = 1655276557000;
 
 select * from monitor;
 
+delete from monitor where host = 'host2' or ts != 1655276559000;
+
+select * from monitor;
+
 drop table monitor;